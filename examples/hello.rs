@@ -77,9 +77,11 @@ struct MyContext {
     allowed_splits: AllowedSplits,
     show_leaf_close_all: bool,
     show_leaf_collapse: bool,
+    show_leaf_split: bool,
     show_secondary_button_hint: bool,
     secondary_button_on_modifier: bool,
     secondary_button_context_menu: bool,
+    native_windows: bool,
 }
 
 struct MyApp {
@@ -168,6 +170,7 @@ impl MyContext {
                 &mut self.show_leaf_collapse,
                 "Show collaspse button on tab bars",
             );
+            ui.checkbox(&mut self.show_leaf_split, "Show split buttons on tab bars");
             ui.checkbox(
                 &mut self.secondary_button_on_modifier,
                 "Enable secondary buttons when modifiers (Shift by default) are pressed",
@@ -180,6 +183,7 @@ impl MyContext {
                 &mut self.show_secondary_button_hint,
                 "Show tooltip hints for secondary buttons",
             );
+            ui.checkbox(&mut self.native_windows, "Show windows as native windows");
             ComboBox::new("cbox:allowed_splits", "Split direction(s)")
                 .selected_text(format!("{:?}", self.allowed_splits))
                 .show_ui(ui, |ui| {
@@ -270,6 +274,13 @@ impl MyContext {
                 ui.add(Slider::new(&mut style.tab_bar.height, 20.0..=50.0));
                 ui.label("Tab bar height");
             });
+            ui.horizontal(|ui| {
+                ui.add(Slider::new(
+                    &mut style.tab_bar.reorder_animation.duration,
+                    0.0..=1.0,
+                ));
+                ui.label("Tab reorder animation duration");
+            });
 
             ComboBox::new("add_button_align", "Add button align")
                 .selected_text(format!("{:?}", style.buttons.add_tab_align))
@@ -561,6 +572,7 @@ impl Default for MyApp {
 
             show_leaf_close_all: true,
             show_leaf_collapse: true,
+            show_leaf_split: false,
             show_secondary_button_hint: true,
             secondary_button_on_modifier: true,
             secondary_button_context_menu: true,
@@ -569,6 +581,7 @@ impl Default for MyApp {
             draggable_tabs: true,
             show_tab_name_on_hover: false,
             allowed_splits: AllowedSplits::default(),
+            native_windows: false,
         };
 
         Self {
@@ -623,9 +636,11 @@ impl eframe::App for MyApp {
                     .allowed_splits(self.context.allowed_splits)
                     .show_leaf_close_all_buttons(self.context.show_leaf_close_all)
                     .show_leaf_collapse_buttons(self.context.show_leaf_collapse)
+                    .show_leaf_split_buttons(self.context.show_leaf_split)
                     .show_secondary_button_hint(self.context.show_secondary_button_hint)
                     .secondary_button_on_modifier(self.context.secondary_button_on_modifier)
                     .secondary_button_context_menu(self.context.secondary_button_context_menu)
+                    .native_windows(self.context.native_windows)
                     .show_inside(ui, &mut self.context);
             });
     }
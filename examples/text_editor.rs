@@ -34,6 +34,23 @@ impl TabViewer for Buffers {
             .desired_width(f32::INFINITY)
             .show(ui);
     }
+
+    fn tab_tooltip(&mut self, ui: &mut egui::Ui, title: &mut Title) {
+        ui.strong(title.as_str());
+        if let Some(text) = self.buffers.get(title) {
+            ui.label(format!("{} lines", text.lines().count()));
+        }
+    }
+
+    fn on_rename(&mut self, title: &mut Title, new_title: String) -> bool {
+        if new_title.is_empty() || self.buffers.contains_key(&new_title) {
+            return false;
+        }
+        let text = self.buffers.remove(title).unwrap_or_default();
+        self.buffers.insert(new_title.clone(), text);
+        *title = new_title;
+        true
+    }
 }
 
 struct MyApp {
@@ -82,6 +99,8 @@ impl eframe::App for MyApp {
 
         DockArea::new(&mut self.tree)
             .style(Style::from_egui(ctx.style().as_ref()))
+            .tab_renaming(true)
+            .show_tab_name_on_hover(true)
             .show(ctx, &mut self.buffers);
     }
 }
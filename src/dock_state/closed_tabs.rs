@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use crate::{NodeId, Split, SurfaceIndex, TabIndex, WindowState};
+
+/// The most recently closed tabs of a [`DockState`](crate::DockState), along with where they were, so that they can
+/// be reopened with [`DockState::reopen_last_closed`](crate::DockState::reopen_last_closed).
+///
+/// Tabs are remembered when they're closed with [`DockState::close_tab`](crate::DockState::close_tab), which the
+/// [`DockArea`](crate::DockArea) uses for the tabs the user closes. Once [`ClosedTabHistory::capacity`] tabs are
+/// remembered, the oldest one is dropped to make room for the next.
+///
+/// The history is empty with a capacity of 0 by default, so closed tabs are dropped right away. Only the capacity is
+/// saved with the `serde` feature, and it's the only thing kept when mapping the tabs of the
+/// [`DockState`](crate::DockState).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ClosedTabHistory<Tab> {
+    capacity: usize,
+
+    #[cfg_attr(feature = "serde", serde(skip, default = "VecDeque::new"))]
+    closed: VecDeque<ClosedTab<Tab>>,
+}
+
+impl<Tab> Default for ClosedTabHistory<Tab> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<Tab> ClosedTabHistory<Tab> {
+    /// Creates an empty history remembering up to `capacity` tabs.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            closed: VecDeque::new(),
+        }
+    }
+
+    /// Returns how many closed tabs are remembered at most.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets how many closed tabs are remembered at most, dropping the oldest ones if there are too many.
+    pub fn set_capacity(&mut self, capacity: usize) -> &mut Self {
+        self.capacity = capacity;
+        self.closed.truncate(capacity);
+        self
+    }
+
+    /// Returns the number of remembered tabs.
+    pub fn len(&self) -> usize {
+        self.closed.len()
+    }
+
+    /// Returns `true` if no closed tab is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.closed.is_empty()
+    }
+
+    /// Returns an [`Iterator`] over the remembered tabs, from the most recently closed one to the oldest one.
+    pub fn iter(&self) -> impl Iterator<Item = &ClosedTab<Tab>> {
+        self.closed.iter()
+    }
+
+    /// Forgets all the remembered tabs.
+    pub fn clear(&mut self) {
+        self.closed.clear();
+    }
+
+    /// Remembers a closed tab, dropping the oldest one if the history is full.
+    pub(crate) fn push(&mut self, closed_tab: ClosedTab<Tab>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.closed.truncate(self.capacity - 1);
+        self.closed.push_front(closed_tab);
+    }
+
+    /// Takes out the most recently closed tab.
+    pub(crate) fn pop(&mut self) -> Option<ClosedTab<Tab>> {
+        self.closed.pop_front()
+    }
+}
+
+/// A tab remembered by a [`ClosedTabHistory`], and where it was before it was closed.
+#[derive(Clone, Debug)]
+pub struct ClosedTab<Tab> {
+    pub(crate) tab: Tab,
+
+    /// The leaf the tab was in, and its position there.
+    pub(crate) leaf: NodeId,
+    pub(crate) tab_index: TabIndex,
+
+    /// The node the leaf was split off, the side of it the leaf was on, and the fraction of their parent.
+    pub(crate) sibling: Option<(NodeId, Split, f32)>,
+
+    /// The split nodes above the leaf, from its parent to the root.
+    pub(crate) ancestors: Vec<NodeId>,
+
+    /// The surface the leaf was in, and its state if it was a window.
+    pub(crate) surface_index: SurfaceIndex,
+    pub(crate) window_state: Option<WindowState>,
+}
+
+impl<Tab> ClosedTab<Tab> {
+    /// Get an immutable borrow to the closed tab.
+    pub fn tab(&self) -> &Tab {
+        &self.tab
+    }
+
+    /// Returns the surface the tab was in when it was closed.
+    ///
+    /// If that surface was a window, it may have been closed along with the tab or been reused for another window
+    /// since then.
+    pub fn surface_index(&self) -> SurfaceIndex {
+        self.surface_index
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{DockState, Node, NodeIndex, SurfaceIndex, TabId, TabIndex};
+
+/// A tab which is present in the new layout of a [`LayoutDiff`], but not in the old one.
+#[derive(Clone, Debug)]
+struct AddedTab<Tab> {
+    tab: Tab,
+
+    /// Where the tab is located in the new layout.
+    location: (SurfaceIndex, NodeIndex, TabIndex),
+
+    /// The tab directly before the added tab in its leaf, if any.
+    previous: Option<Tab>,
+
+    /// The tab directly after the added tab in its leaf, if any.
+    next: Option<Tab>,
+}
+
+/// Describes which tabs were added to or removed from a layout.
+///
+/// Created with [`DockState::diff`] and applied to another layout with [`DockState::apply_diff`].
+///
+/// This is useful for merging a user's saved layout with an updated default layout of your application:
+/// tabs which were added to the default layout appear in their default location, while the rest of the user's
+/// customizations are preserved.
+#[derive(Clone, Debug)]
+pub struct LayoutDiff<Tab> {
+    added: Vec<AddedTab<Tab>>,
+    removed: Vec<Tab>,
+}
+
+impl<Tab> LayoutDiff<Tab> {
+    /// Returns `true` if no tabs were added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Returns an [`Iterator`] over the added tabs, along with their location in the new layout.
+    pub fn added(&self) -> impl Iterator<Item = (&Tab, (SurfaceIndex, NodeIndex, TabIndex))> {
+        self.added.iter().map(|added| (&added.tab, added.location))
+    }
+
+    /// Returns the tabs which were removed in the new layout.
+    pub fn removed(&self) -> &[Tab] {
+        &self.removed
+    }
+}
+
+impl<Tab> DockState<Tab>
+where
+    Tab: PartialEq + Clone,
+{
+    /// Computes which tabs need to be added and removed to go from this layout to `other`.
+    ///
+    /// Only the presence of tabs is compared, the arrangement of nodes is not.
+    /// The returned [`LayoutDiff`] can be applied to a different layout using [`DockState::apply_diff`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// let old_default = DockState::new(vec!["Files", "Editor"]);
+    /// let new_default = DockState::new(vec!["Files", "Search", "Editor"]);
+    ///
+    /// // The user has moved their tabs around since the layout was saved.
+    /// let mut user_layout = DockState::new(vec!["Editor", "Files"]);
+    ///
+    /// user_layout.apply_diff(old_default.diff(&new_default));
+    ///
+    /// let tabs: Vec<_> = user_layout.iter_all_tabs().map(|(_, tab)| *tab).collect();
+    /// assert_eq!(tabs, vec!["Editor", "Files", "Search"]);
+    /// ```
+    pub fn diff(&self, other: &DockState<Tab>) -> LayoutDiff<Tab> {
+        let mut added = Vec::new();
+        for (surface_index, surface) in other.iter_surfaces().enumerate() {
+            let Some(tree) = surface.node_tree() else {
+                continue;
+            };
+            for (node_index, node) in tree.iter().enumerate() {
+                let Some(leaf) = node.get_leaf() else {
+                    continue;
+                };
+                for (tab_index, tab) in leaf.tabs.iter().enumerate() {
+                    if self.find_tab(tab).is_some() {
+                        continue;
+                    }
+                    added.push(AddedTab {
+                        tab: tab.clone(),
+                        location: (
+                            SurfaceIndex(surface_index),
+                            NodeIndex(node_index),
+                            TabIndex(tab_index),
+                        ),
+                        previous: tab_index
+                            .checked_sub(1)
+                            .map(|index| leaf.tabs[index].clone()),
+                        next: leaf.tabs.get(tab_index + 1).cloned(),
+                    });
+                }
+            }
+        }
+
+        let removed = self
+            .iter_all_tabs()
+            .filter(|(_, tab)| other.find_tab(tab).is_none())
+            .map(|(_, tab)| tab.clone())
+            .collect();
+
+        LayoutDiff { added, removed }
+    }
+
+    /// Applies a [`LayoutDiff`] created with [`DockState::diff`] to this layout.
+    ///
+    /// Removed tabs are taken out of this layout, and any leaves or windows left empty are removed.
+    ///
+    /// Each added tab is placed next to the tab it neighboured in the new layout if that tab can be found here.
+    /// Otherwise it's placed in the leaf at the same location as in the new layout, and if there is no such leaf,
+    /// it's pushed to the first leaf of the main surface. Tabs which are already present are left where they are.
+    ///
+    /// Adding tabs doesn't change which tab is active in a leaf.
+    pub fn apply_diff(&mut self, diff: LayoutDiff<Tab>) {
+        for tab in diff.removed {
+            if let Some(location) = self.find_tab(&tab) {
+                self.remove_tab(location);
+            }
+        }
+
+        for added in diff.added {
+            if self.find_tab(&added.tab).is_some() {
+                continue;
+            }
+
+            let destination = added
+                .previous
+                .as_ref()
+                .and_then(|previous| self.find_tab(previous))
+                .map(|(surface, node, tab)| (surface, node, TabIndex(tab.0 + 1)))
+                .or_else(|| added.next.as_ref().and_then(|next| self.find_tab(next)))
+                .or_else(|| {
+                    let (surface, node, tab) = added.location;
+                    let leaf = self
+                        .get_surface(surface)?
+                        .node_tree()?
+                        .iter()
+                        .nth(node.0)?
+                        .get_leaf()?;
+                    Some((surface, node, TabIndex(tab.0.min(leaf.len()))))
+                });
+
+            match destination {
+                Some((surface, node, tab_index)) => {
+                    if let Node::Leaf(leaf) = &mut self[surface][node] {
+                        leaf.sync_tab_ids();
+                        leaf.tabs.insert(tab_index.0, added.tab);
+                        leaf.tab_ids.insert(tab_index.0, TabId::next());
+                        if leaf.tabs.len() > 1 && tab_index <= leaf.active {
+                            leaf.active.0 += 1;
+                        }
+                    }
+                }
+                None => self.push_to_first_leaf(added.tab),
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use egui::Rect;
+
+use crate::{DockState, Node, NodeIndex, SplitNode, Tree};
+
+/// A declarative description of the layout of a [`Tree`], which can be turned into a [`Tree`] or a
+/// [`DockState`] at once instead of splitting its nodes one by one.
+///
+/// Leaves are most easily described with the [`leaf!`](crate::leaf) macro.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{leaf, DockLayout, DockState, NodeIndex};
+/// let dock_state = DockState::from(DockLayout::horizontal(
+///     0.2,
+///     leaf!["Files"],
+///     DockLayout::vertical(0.7, leaf!["Editor", "Preview"], leaf!["Console"]),
+/// ));
+///
+/// let tree = dock_state.main_surface();
+/// assert_eq!(tree[NodeIndex(1)].tabs(), Some(["Files"].as_slice()));
+/// assert_eq!(tree[NodeIndex(5)].tabs(), Some(["Editor", "Preview"].as_slice()));
+/// assert_eq!(tree[NodeIndex(6)].tabs(), Some(["Console"].as_slice()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum DockLayout<Tab> {
+    /// A leaf holding these tabs.
+    Leaf(Vec<Tab>),
+
+    /// Two layouts side by side, `left` taking `fraction` of the width.
+    Horizontal {
+        /// Fraction of the width taken by `left`.
+        fraction: f32,
+        /// Layout on the left.
+        left: Box<DockLayout<Tab>>,
+        /// Layout on the right.
+        right: Box<DockLayout<Tab>>,
+    },
+
+    /// Two layouts on top of each other, `top` taking `fraction` of the height.
+    Vertical {
+        /// Fraction of the height taken by `top`.
+        fraction: f32,
+        /// Layout at the top.
+        top: Box<DockLayout<Tab>>,
+        /// Layout at the bottom.
+        bottom: Box<DockLayout<Tab>>,
+    },
+}
+
+impl<Tab> DockLayout<Tab> {
+    /// Creates a leaf holding `tabs`.
+    #[inline(always)]
+    pub fn leaf(tabs: Vec<Tab>) -> Self {
+        Self::Leaf(tabs)
+    }
+
+    /// Places `left` and `right` side by side, `left` taking `fraction` of the width.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` isn't between `0.0` and `1.0`.
+    pub fn horizontal(fraction: f32, left: DockLayout<Tab>, right: DockLayout<Tab>) -> Self {
+        assert!((0.0..=1.0).contains(&fraction));
+        Self::Horizontal {
+            fraction,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Places `top` above `bottom`, `top` taking `fraction` of the height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fraction` isn't between `0.0` and `1.0`.
+    pub fn vertical(fraction: f32, top: DockLayout<Tab>, bottom: DockLayout<Tab>) -> Self {
+        assert!((0.0..=1.0).contains(&fraction));
+        Self::Vertical {
+            fraction,
+            top: Box::new(top),
+            bottom: Box::new(bottom),
+        }
+    }
+
+    /// Puts the nodes of this layout into `nodes`, rooted at `index`.
+    fn place(self, nodes: &mut Vec<Node<Tab>>, index: NodeIndex) {
+        if nodes.len() <= index.0 {
+            nodes.resize_with(index.0 + 1, || Node::Empty);
+        }
+        let (node, children) = match self {
+            DockLayout::Leaf(tabs) => (Node::leaf_with(tabs), None),
+            DockLayout::Horizontal {
+                fraction,
+                left,
+                right,
+            } => (
+                Node::Horizontal(SplitNode::new(Rect::NOTHING, fraction, false, 0)),
+                Some((left, right)),
+            ),
+            DockLayout::Vertical {
+                fraction,
+                top,
+                bottom,
+            } => (
+                Node::Vertical(SplitNode::new(Rect::NOTHING, fraction, false, 0)),
+                Some((top, bottom)),
+            ),
+        };
+        nodes[index.0] = node;
+        if let Some((left, right)) = children {
+            left.place(nodes, index.left());
+            right.place(nodes, index.right());
+        }
+    }
+}
+
+impl<Tab> From<DockLayout<Tab>> for Tree<Tab> {
+    fn from(layout: DockLayout<Tab>) -> Self {
+        let mut tree = Tree::default();
+        layout.place(&mut tree.nodes, NodeIndex::root());
+        tree.fill_last_level();
+        tree
+    }
+}
+
+impl<Tab> From<DockLayout<Tab>> for DockState<Tab> {
+    fn from(layout: DockLayout<Tab>) -> Self {
+        let mut dock_state = DockState::new(Vec::new());
+        *dock_state.main_surface_mut() = Tree::from(layout);
+        dock_state
+    }
+}
+
+/// Creates a [`DockLayout::Leaf`] holding the given tabs, like [`vec!`] does for a [`Vec`].
+///
+/// ```rust
+/// # use egui_dock::{leaf, DockLayout};
+/// assert_eq!(leaf!["Editor", "Console"], DockLayout::leaf(vec!["Editor", "Console"]));
+/// ```
+#[macro_export]
+macro_rules! leaf {
+    ($($tab:expr),* $(,)?) => {
+        $crate::DockLayout::leaf(::std::vec![$($tab),*])
+    };
+}
@@ -3,6 +3,25 @@ pub mod surface_index;
 
 pub mod tree;
 
+/// Comparing and merging the tabs of different layouts.
+pub mod diff;
+
+/// Declarative descriptions of layouts.
+pub mod layout;
+
+/// Versioned snapshots of a [`DockState`] which can be migrated when loaded.
+#[cfg(feature = "serde")]
+pub mod versioned;
+
+/// Saving the tabs of a session as keys, and restoring them later.
+pub mod session;
+
+/// Remembering closed tabs so that they can be reopened.
+pub mod closed_tabs;
+
+/// Switching between several layouts.
+pub mod workspaces;
+
 /// Represents an area in which a dock tree is rendered.
 pub mod surface;
 /// Specifies text displayed in different elements of the [`DockArea`](crate::DockArea).
@@ -10,14 +29,27 @@ pub mod translations;
 /// Window states which tells floating tabs how to be displayed inside their window,
 pub mod window_state;
 
+pub use closed_tabs::{ClosedTab, ClosedTabHistory};
+pub use diff::LayoutDiff;
+pub use layout::DockLayout;
+pub use session::SessionRestore;
 pub use surface::Surface;
 pub use surface_index::SurfaceIndex;
 use tree::node::LeafNode;
-pub use window_state::WindowState;
+#[cfg(feature = "serde")]
+pub use versioned::{DockStateVersioned, LayoutMigrator};
+pub use window_state::{RestoreLocation, WindowState};
+pub use workspaces::Workspaces;
 
-use egui::Rect;
+use egui::{
+    ahash::{HashMap, HashSet},
+    Id, Pos2, Rect,
+};
 
-use crate::{Node, NodeIndex, Split, TabDestination, TabIndex, TabInsert, Translations, Tree};
+use crate::{
+    tab_viewer::SplitBehavior, AllowedSplits, FocusPolicy, Node, NodeId, NodeIndex, Split, Style,
+    TabDestination, TabId, TabIndex, TabInsert, TabRef, Translations, Tree,
+};
 
 /// The heart of `egui_dock`.
 ///
@@ -34,6 +66,47 @@ pub struct DockState<Tab> {
 
     /// Contains translations of text shown in [`DockArea`](crate::DockArea).
     pub translations: Translations,
+
+    /// Styles used instead of the [`DockArea`](crate::DockArea)'s one for individual surfaces.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    surface_styles: HashMap<SurfaceIndex, Style>,
+
+    /// Whether the [`DockArea`](crate::DockArea) shows all tabs as a grid of cards instead of the layout.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    overview_open: bool,
+
+    /// Whether the [`DockArea`](crate::DockArea) shows the tab switcher.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tab_switcher_open: bool,
+
+    /// IDs of the tabs whose closing was deferred by [`TabViewer::on_close`](crate::TabViewer::on_close).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pending_closes: Vec<Id>,
+
+    /// IDs of the tabs which the [`DockArea`](crate::DockArea) closes the next time it's shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    resolved_closes: Vec<Id>,
+
+    /// The window surfaces from the one shown at the back to the one shown at the front.
+    #[cfg_attr(feature = "serde", serde(default))]
+    window_order: Vec<SurfaceIndex>,
+
+    /// Whether the [`DockArea`](crate::DockArea) has stacked the windows in [`DockState::window_order`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    window_order_applied: bool,
+
+    /// The window brought to the front by [`DockState::bring_window_to_front`] which the
+    /// [`DockArea`](crate::DockArea) hasn't raised yet.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raised_window: Option<SurfaceIndex>,
+
+    /// The tabs closed with [`DockState::close_tab`] which can be reopened.
+    #[cfg_attr(feature = "serde", serde(default = "ClosedTabHistory::default"))]
+    closed_tabs: ClosedTabHistory<Tab>,
+
+    /// Whether removing a leaf shares its space among all the nodes of the same orientation around it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    proportional_leaf_removal: bool,
 }
 
 impl<Tab> std::ops::Index<SurfaceIndex> for DockState<Tab> {
@@ -69,6 +142,16 @@ impl<Tab> DockState<Tab> {
             surfaces: vec![Surface::Main(Tree::new(tabs))],
             focused_surface: None,
             translations: Translations::english(),
+            surface_styles: HashMap::default(),
+            overview_open: false,
+            tab_switcher_open: false,
+            pending_closes: Vec::new(),
+            resolved_closes: Vec::new(),
+            window_order: Vec::new(),
+            window_order_applied: false,
+            raised_window: None,
+            closed_tabs: ClosedTabHistory::default(),
+            proportional_leaf_removal: false,
         }
     }
 
@@ -88,6 +171,41 @@ impl<Tab> DockState<Tab> {
         &mut self[SurfaceIndex::main()]
     }
 
+    /// Computes the rects and viewports of the nodes of the main surface as if it was shown by a
+    /// [`DockArea`](crate::DockArea) filling `rect`, without showing anything.
+    ///
+    /// The [`Style`] set for the main surface with [`DockState::set_surface_style`] is used over `style`.
+    /// Windows aren't laid out, since their rects depend on their frames; use [`Tree::layout`] on their trees instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::{pos2, Rect};
+    /// # use egui_dock::{DockState, NodeIndex, Style};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [left, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_below(NodeIndex::root(), 0.5, vec!["Console"]);
+    ///
+    /// let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(400.0, 300.0));
+    /// dock_state.layout(rect, &Style::default());
+    ///
+    /// let tree = dock_state.main_surface();
+    /// let top = tree[left].get_leaf().unwrap();
+    /// let bottom = tree[right].get_leaf().unwrap();
+    /// assert!(rect.contains_rect(top.viewport) && rect.contains_rect(bottom.viewport));
+    /// assert!(top.viewport.max.y < bottom.viewport.min.y);
+    /// ```
+    pub fn layout(&mut self, rect: Rect, style: &Style) {
+        let style = self
+            .surface_styles
+            .get(&SurfaceIndex::main())
+            .cloned()
+            .unwrap_or_else(|| style.clone());
+        let rect = tree::main_surface_rect(rect, &style);
+        self.main_surface_mut().layout(rect, &style);
+    }
+
     /// Get the [`WindowState`] which corresponds to a [`SurfaceIndex`].
     ///
     /// Returns `None` if the surface is [`Empty`](Surface::Empty), [`Main`](Surface::Main), or doesn't exist.
@@ -150,6 +268,16 @@ impl<Tab> DockState<Tab> {
             .is_some_and(|surface| !surface.is_empty())
     }
 
+    /// Replaces the contents of `indices` with all valid [`SurfaceIndex`]es, reusing its allocation.
+    pub(crate) fn valid_surface_indices_into(&self, indices: &mut Vec<SurfaceIndex>) {
+        indices.clear();
+        indices.extend(
+            (0..self.surfaces.len())
+                .map(SurfaceIndex)
+                .filter(|&index| self.is_surface_valid(index)),
+        );
+    }
+
     /// Returns a list of all valid [`SurfaceIndex`]es.
     #[inline]
     pub(crate) fn valid_surface_indices(&self) -> Box<[SurfaceIndex]> {
@@ -172,6 +300,11 @@ impl<Tab> DockState<Tab> {
         assert!(!surface_index.is_main());
         (surface_index.0 < self.surfaces.len()).then(|| {
             self.focused_surface = Some(SurfaceIndex::main());
+            self.surface_styles.remove(&surface_index);
+            self.window_order.retain(|&index| index != surface_index);
+            if self.raised_window == Some(surface_index) {
+                self.raised_window = None;
+            }
             if surface_index.0 == self.surfaces.len() - 1 {
                 self.surfaces.pop().unwrap()
             } else {
@@ -181,14 +314,364 @@ impl<Tab> DockState<Tab> {
         })
     }
 
-    /// Sets which is the active tab within a specific node on a given surface.
-    #[inline]
-    pub fn set_active_tab(
+    /// Returns the window surfaces from the one shown at the back to the one shown at the front.
+    ///
+    /// New windows are put in front of the others, and so is a window the user interacts with while it's shown by
+    /// a [`DockArea`](crate::DockArea). See also [`DockState::bring_window_to_front`].
+    ///
+    /// Windows which are [always on top](WindowState::set_always_on_top) are shown above the others whatever their
+    /// place in this order.
+    pub fn window_order(&self) -> &[SurfaceIndex] {
+        &self.window_order
+    }
+
+    /// Resets the state which changes as the [`DockArea`](crate::DockArea) is used rather than as its layout is
+    /// edited: the focused surface and leaf, the active tab and tab bar scroll of each leaf, collapsed leaves and
+    /// the [`DockState::window_order`].
+    ///
+    /// All of these are saved when a [`DockState`] is serialized, so that an application comes back the way it was
+    /// left. Call this on a clone of the [`DockState`] before saving it to only keep the layout instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor", "Preview"]);
+    /// dock_state.set_active_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)));
+    /// dock_state.set_focused_node_and_surface((SurfaceIndex::main(), NodeIndex::root()));
+    ///
+    /// let mut layout = dock_state.clone();
+    /// layout.strip_runtime_state();
+    /// assert_eq!(layout.focused_leaf(), None);
+    /// assert_eq!(layout.main_surface()[NodeIndex::root()].get_leaf().unwrap().active, TabIndex(0));
+    /// ```
+    pub fn strip_runtime_state(&mut self) {
+        self.focused_surface = None;
+        self.window_order.clear();
+        self.raised_window = None;
+        for surface in &mut self.surfaces {
+            if let Some(tree) = surface.node_tree_mut() {
+                tree.strip_runtime_state();
+            }
+        }
+    }
+
+    /// Returns the front-most [modal](WindowState::set_modal) window, which blocks interaction with the other
+    /// surfaces while it's open.
+    pub fn modal_window(&self) -> Option<SurfaceIndex> {
+        self.window_order.iter().rev().copied().find(|&index| {
+            matches!(self.surfaces.get(index.0), Some(Surface::Window(_, state)) if state.is_modal())
+        })
+    }
+
+    /// Shows the window at `surface_index` in front of the other windows and focuses its focused leaf.
+    ///
+    /// Native windows are focused by the windowing system instead. Does nothing if `surface_index` isn't a window.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let inspector = dock_state.add_window(vec!["Inspector"]);
+    /// let console = dock_state.add_window(vec!["Console"]);
+    /// assert_eq!(dock_state.window_order(), [inspector, console]);
+    ///
+    /// dock_state.bring_window_to_front(inspector);
+    /// assert_eq!(dock_state.window_order(), [console, inspector]);
+    /// assert_eq!(dock_state.focused_leaf(), Some((inspector, NodeIndex::root())));
+    /// ```
+    pub fn bring_window_to_front(&mut self, surface_index: SurfaceIndex) {
+        let Some(Surface::Window(tree, _)) = self.surfaces.get(surface_index.0) else {
+            return;
+        };
+        let leaf = tree.focused_leaf().or_else(|| {
+            tree.breadth_first_index_iter()
+                .find(|&node_index| tree[node_index].is_leaf())
+        });
+        self.raise_window(surface_index);
+        self.window_order_applied = false;
+        self.raised_window = Some(surface_index);
+        if let Some(leaf) = leaf {
+            self.set_focused_node_and_surface((surface_index, leaf));
+        }
+    }
+
+    /// Moves `surface_index` to the front of [`DockState::window_order`], when it was already raised on screen.
+    pub(crate) fn raise_window(&mut self, surface_index: SurfaceIndex) {
+        if self.window_order.last() != Some(&surface_index) {
+            self.window_order.retain(|&index| index != surface_index);
+            self.window_order.push(surface_index);
+        }
+    }
+
+    /// Returns the windows from back to front if they have to be stacked in this order on screen.
+    pub(crate) fn unstacked_window_order(&self) -> Option<&[SurfaceIndex]> {
+        (!self.window_order_applied).then_some(&*self.window_order)
+    }
+
+    /// Records that the windows are stacked in the order of [`DockState::window_order`] on screen.
+    pub(crate) fn window_order_stacked(&mut self) {
+        self.window_order_applied = true;
+    }
+
+    /// Takes the window brought to the front by [`DockState::bring_window_to_front`] since it was last taken.
+    pub(crate) fn take_raised_window(&mut self) -> Option<SurfaceIndex> {
+        self.raised_window.take()
+    }
+
+    /// Forgets removed windows in [`DockState::window_order`], and puts the windows missing from it in front.
+    pub(crate) fn sync_window_order(&mut self) {
+        let surfaces = &self.surfaces;
+        self.window_order
+            .retain(|index| matches!(surfaces.get(index.0), Some(Surface::Window(..))));
+        for (index, surface) in self.surfaces.iter().enumerate() {
+            if matches!(surface, Surface::Window(..))
+                && !self.window_order.contains(&SurfaceIndex(index))
+            {
+                self.window_order.push(SurfaceIndex(index));
+            }
+        }
+    }
+
+    /// Sets the [`Style`] used to show a surface instead of the [`DockArea`](crate::DockArea)'s one.
+    ///
+    /// The style is forgotten when the surface is removed. Surface styles are not serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, Style};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let window = dock_state.add_window(vec!["Inspector"]);
+    ///
+    /// // Floating windows use a more compact tab bar.
+    /// let mut compact_style = Style::default();
+    /// compact_style.tab_bar.height = 18.0;
+    /// dock_state.set_surface_style(window, compact_style);
+    ///
+    /// assert_eq!(dock_state.surface_style(window).unwrap().tab_bar.height, 18.0);
+    /// ```
+    pub fn set_surface_style(&mut self, surface_index: SurfaceIndex, style: Style) {
+        self.surface_styles.insert(surface_index, style);
+    }
+
+    /// Sets the directions in which the leaf at `node_index` can be split by dropping a tab onto it or with its
+    /// split buttons, on top of the ones allowed by [`DockArea::allowed_splits`](crate::DockArea::allowed_splits).
+    ///
+    /// Does nothing if the node isn't a leaf. See [`LeafNode::set_allowed_splits`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{AllowedSplits, DockState, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [_, properties] =
+    ///     dock_state.main_surface_mut().split_right(NodeIndex::root(), 0.7, vec!["Properties"]);
+    ///
+    /// // The properties panel is too narrow to be split side by side.
+    /// dock_state.set_allowed_splits(SurfaceIndex::main(), properties, AllowedSplits::TopBottomOnly);
+    /// assert_eq!(
+    ///     dock_state.allowed_splits(SurfaceIndex::main(), properties),
+    ///     AllowedSplits::TopBottomOnly
+    /// );
+    /// ```
+    pub fn set_allowed_splits(
         &mut self,
-        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        allowed_splits: AllowedSplits,
     ) {
+        if let Some(leaf) = self
+            .get_surface_mut(surface_index)
+            .and_then(|surface| surface.node_tree_mut())
+            .and_then(|tree| tree.nodes.get_mut(node_index.0))
+            .and_then(Node::get_leaf_mut)
+        {
+            leaf.set_allowed_splits(allowed_splits);
+        }
+    }
+
+    /// Returns the directions in which the leaf at `node_index` can be split by dropping a tab onto it or with its
+    /// split buttons, see [`DockState::set_allowed_splits`].
+    ///
+    /// Returns [`AllowedSplits::None`] if the node isn't a leaf.
+    pub fn allowed_splits(
+        &self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+    ) -> AllowedSplits {
+        self.get_surface(surface_index)
+            .and_then(|surface| surface.node_tree())
+            .and_then(|tree| tree.nodes.get(node_index.0))
+            .and_then(Node::get_leaf)
+            .map_or(AllowedSplits::None, LeafNode::allowed_splits)
+    }
+
+    /// Returns the [`Style`] set for a surface with [`DockState::set_surface_style`], if any.
+    pub fn surface_style(&self, surface_index: SurfaceIndex) -> Option<&Style> {
+        self.surface_styles.get(&surface_index)
+    }
+
+    /// Removes the [`Style`] set for a surface, so that it's shown using the [`DockArea`](crate::DockArea)'s one.
+    ///
+    /// Returns the removed style, if there was one.
+    pub fn clear_surface_style(&mut self, surface_index: SurfaceIndex) -> Option<Style> {
+        self.surface_styles.remove(&surface_index)
+    }
+
+    /// Opens or closes the overview, in which the [`DockArea`](crate::DockArea) shows every open tab as a card
+    /// in a grid instead of the layout.
+    ///
+    /// Clicking a card focuses its tab and closes the overview, as does pressing <kbd>Escape</kbd>.
+    /// The overview is not serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// let mut dock_state = DockState::new(vec!["Editor", "Console"]);
+    /// dock_state.set_overview_open(!dock_state.is_overview_open());
+    ///
+    /// assert!(dock_state.is_overview_open());
+    /// ```
+    pub fn set_overview_open(&mut self, open: bool) {
+        self.overview_open = open;
+    }
+
+    /// Returns `true` if the overview is open, see [`DockState::set_overview_open`].
+    pub fn is_overview_open(&self) -> bool {
+        self.overview_open
+    }
+
+    /// Opens the tab switcher, a popup in the [`DockArea`](crate::DockArea) listing the titles of all tabs,
+    /// similar to the quick open of code editors.
+    ///
+    /// Typing filters the list by fuzzy matching the titles, which come from
+    /// [`TabViewer::title`](crate::TabViewer::title). Choosing a tab, either by clicking it or by pressing
+    /// <kbd>Enter</kbd>, focuses it and closes the switcher. Pressing <kbd>Escape</kbd> or clicking outside of it
+    /// closes it without focusing anything.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut dock_state = DockState::new(vec!["Editor", "Console"]);
+    /// if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::P)) {
+    ///     dock_state.open_tab_switcher();
+    /// }
+    /// # });
+    /// ```
+    pub fn open_tab_switcher(&mut self) {
+        self.tab_switcher_open = true;
+    }
+
+    /// Closes the tab switcher opened with [`DockState::open_tab_switcher`].
+    pub fn close_tab_switcher(&mut self) {
+        self.tab_switcher_open = false;
+    }
+
+    /// Returns `true` if the tab switcher is open, see [`DockState::open_tab_switcher`].
+    pub fn is_tab_switcher_open(&self) -> bool {
+        self.tab_switcher_open
+    }
+
+    /// IDs of the tabs, as given by [`TabViewer::id`](crate::TabViewer::id), for which
+    /// [`TabViewer::on_close`](crate::TabViewer::on_close) returned [`OnCloseResponse::Defer`] and
+    /// which haven't been resolved with [`DockState::resolve_close`] yet.
+    ///
+    /// [`OnCloseResponse::Defer`]: crate::tab_viewer::OnCloseResponse::Defer
+    pub fn pending_closes(&self) -> &[Id] {
+        &self.pending_closes
+    }
+
+    /// Resolves a deferred close request of the tab with the given ID.
+    ///
+    /// If `close` is `true`, the tab is closed the next time the [`DockArea`](crate::DockArea) is shown,
+    /// without calling [`TabViewer::on_close`](crate::TabViewer::on_close) again.
+    /// Otherwise it stays open. Does nothing if the close request of the tab isn't pending.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::DockState;
+    /// # let mut dock_state = DockState::new(vec!["Untitled"]);
+    /// # let save_dialog_answer: Option<(egui::Id, bool)> = None;
+    /// // Once the user answered the "Save changes?" dialog of a tab:
+    /// if let Some((tab_id, discard_changes)) = save_dialog_answer {
+    ///     dock_state.resolve_close(tab_id, discard_changes);
+    /// }
+    /// ```
+    pub fn resolve_close(&mut self, tab_id: Id, close: bool) {
+        let Some(position) = self.pending_closes.iter().position(|&id| id == tab_id) else {
+            return;
+        };
+        self.pending_closes.remove(position);
+        if close {
+            self.resolved_closes.push(tab_id);
+        }
+    }
+
+    /// Adds the tab with the given ID to the [`DockState::pending_closes`], unless it's already there.
+    pub(crate) fn defer_close(&mut self, tab_id: Id) {
+        if !self.pending_closes.contains(&tab_id) {
+            self.pending_closes.push(tab_id);
+        }
+    }
+
+    /// Takes the IDs of the tabs whose deferred close requests were resolved with closing them.
+    pub(crate) fn take_resolved_closes(&mut self) -> Vec<Id> {
+        std::mem::take(&mut self.resolved_closes)
+    }
+
+    /// Sets which is the active tab within a specific node on a given surface,
+    /// and scrolls its tab bar so that the tab is in view.
+    #[inline]
+    pub fn set_active_tab(&mut self, tab: impl Into<TabRef>) {
+        let Some((surface_index, node_index, tab_index)) = self.resolve_tab(tab) else {
+            return;
+        };
+        if let Some(Node::Leaf(leaf)) = self[surface_index].nodes.get_mut(node_index.0) {
+            leaf.set_active_tab(tab_index);
+        }
+    }
+
+    /// Returns the index of the active tab of the leaf at the specified surface and node index, or `None` if it isn't
+    /// a leaf or has no tabs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor", "Preview"]);
+    /// let root = (SurfaceIndex::main(), NodeIndex::root());
+    /// assert_eq!(dock_state.active_tab(root), Some(TabIndex(0)));
+    ///
+    /// dock_state.set_active_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)));
+    /// assert_eq!(dock_state.active_tab(root), Some(TabIndex(1)));
+    /// ```
+    pub fn active_tab(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) -> Option<TabIndex> {
+        self.get_surface(surface_index)?
+            .node_tree()?
+            .nodes
+            .get(node_index.0)?
+            .get_leaf()?
+            .active_tab()
+    }
+
+    /// Scrolls the tab bar of a specific node on a given surface so that the tab is in view
+    /// the next time the node is shown, without making it the active tab.
+    #[inline]
+    pub fn scroll_tab_into_view(&mut self, tab: impl Into<TabRef>) {
+        let Some((surface_index, node_index, tab_index)) = self.resolve_tab(tab) else {
+            return;
+        };
         if let Some(Node::Leaf(leaf)) = self[surface_index].nodes.get_mut(node_index.0) {
-            leaf.active = tab_index;
+            leaf.scroll_tab_into_view(tab_index);
         }
     }
 
@@ -211,14 +694,35 @@ impl<Tab> DockState<Tab> {
 
     /// Moves a tab from a node to another node.
     /// You need to specify with [`TabDestination`] how the tab should be moved.
-    pub fn move_tab(
-        &mut self,
-        (src_surface, src_node, src_tab): (SurfaceIndex, NodeIndex, TabIndex),
-        dst_tab: impl Into<TabDestination>,
-    ) {
+    ///
+    /// This does the same as dropping a dragged tab in a [`DockArea`](crate::DockArea): leaves and windows
+    /// left empty are removed, and the leaf the tab ends up in becomes the focused one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabDestination, TabIndex, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["Editor", "Terminal"]);
+    ///
+    /// // Implements a "Move to Right Group" command.
+    /// dock_state.move_tab(
+    ///     (SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)),
+    ///     TabDestination::Node(SurfaceIndex::main(), NodeIndex::root(), TabInsert::Split(Split::Right)),
+    /// );
+    ///
+    /// assert_eq!(dock_state.focused_leaf(), Some((SurfaceIndex::main(), NodeIndex(2))));
+    /// assert_eq!(dock_state.find_active_focused().map(|(_, tab)| *tab), Some("Terminal"));
+    /// ```
+    ///
+    /// Does nothing if the tab is given by a [`TabId`] which isn't in the [`DockState`].
+    pub fn move_tab(&mut self, src_tab: impl Into<TabRef>, dst_tab: impl Into<TabDestination>) {
+        let Some((src_surface, mut src_node, src_tab)) = self.resolve_tab(src_tab) else {
+            return;
+        };
         match dst_tab.into() {
             TabDestination::Window(position) => {
-                self.detach_tab((src_surface, src_node, src_tab), position);
+                let surface = self.detach_tab((src_surface, src_node, src_tab), position);
+                self.set_focused_node_and_surface((surface, NodeIndex::root()));
                 return;
             }
             TabDestination::Node(dst_surface, dst_node, dst_tab) => {
@@ -230,102 +734,1207 @@ impl<Tab> DockState<Tab> {
                     return;
                 }
 
+                let tab_id = self.tab_id((src_surface, src_node, src_tab));
+                let src_id = self.node_id((src_surface, src_node));
                 // Call `Node::remove_tab` to avoid auto remove of the node by `Tree::remove_tab` from Tree.
                 let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
+                // Focus the destination before removing the source leaf, so that the focus
+                // follows the destination if the tree is rearranged.
                 match dst_tab {
                     TabInsert::Split(split) => {
-                        self[dst_surface].split(dst_node, split, 0.5, Node::leaf(tab));
+                        let [_, new] =
+                            self[dst_surface].split(dst_node, split, 0.5, Node::leaf(tab));
+                        self.restore_tab_id((dst_surface, new, TabIndex(0)), tab_id);
+                        self.set_focused_node_and_surface((dst_surface, new));
+                        // Splitting a parent node moves the nodes under it, which may include the source leaf.
+                        if let Some(node) =
+                            src_id.and_then(|id| self[src_surface].find_node_by_id(id))
+                        {
+                            src_node = node;
+                        }
+                    }
+                    TabInsert::Insert(index) => {
+                        self[dst_surface][dst_node].insert_tab(index, tab);
+                        self.restore_tab_id((dst_surface, dst_node, index), tab_id);
+                        self.set_focused_node_and_surface((dst_surface, dst_node));
+                    }
+                    TabInsert::Append => {
+                        self[dst_surface][dst_node].append_tab(tab);
+                        let index = TabIndex(self[dst_surface][dst_node].tabs_count() - 1);
+                        self.restore_tab_id((dst_surface, dst_node, index), tab_id);
+                        self.set_focused_node_and_surface((dst_surface, dst_node));
                     }
-
-                    TabInsert::Insert(index) => self[dst_surface][dst_node].insert_tab(index, tab),
-                    TabInsert::Append => self[dst_surface][dst_node].append_tab(tab),
                 }
             }
             TabDestination::EmptySurface(dst_surface) => {
                 assert!(self[dst_surface].is_empty());
+                let tab_id = self.tab_id((src_surface, src_node, src_tab));
                 let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
-                self[dst_surface] = Tree::new(vec![tab])
+                self[dst_surface] = Tree::new(vec![tab]);
+                self.restore_tab_id((dst_surface, NodeIndex::root(), TabIndex(0)), tab_id);
+                self.set_focused_node_and_surface((dst_surface, NodeIndex::root()));
             }
         }
         if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
-            self[src_surface].remove_leaf(src_node);
+            self.remove_empty_leaf((src_surface, src_node));
         }
         if self[src_surface].is_empty() && !src_surface.is_main() {
+            let focused_surface = self.focused_surface;
             self.remove_surface(src_surface);
+            self.focused_surface = focused_surface;
         }
     }
 
-    /// Takes a tab out of its current surface and puts it in a new window.
-    /// Returns the surface index of the new window.
-    pub fn detach_tab(
+    /// Moves a tab like [`DockState::move_tab`], along with the other tabs selected in its leaf if the tab is
+    /// [selected](LeafNode::set_tab_selected). The moved tabs keep their order and stay selected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabDestination, TabIndex, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["a", "b", "c", "d"]);
+    /// let leaf = dock_state.main_surface_mut()[NodeIndex::root()].get_leaf_mut().unwrap();
+    /// leaf.set_tab_selected(1, true);
+    /// leaf.set_tab_selected(3, true);
+    ///
+    /// dock_state.move_tab_with_selection(
+    ///     (SurfaceIndex::main(), NodeIndex::root(), TabIndex(3)),
+    ///     TabDestination::Node(SurfaceIndex::main(), NodeIndex::root(), TabInsert::Split(Split::Right)),
+    /// );
+    /// let tree = dock_state.main_surface();
+    /// assert_eq!(tree[NodeIndex(1)].tabs(), Some(["a", "c"].as_slice()));
+    /// assert_eq!(tree[NodeIndex(2)].tabs(), Some(["b", "d"].as_slice()));
+    /// ```
+    pub fn move_tab_with_selection(
         &mut self,
-        (src_surface, src_node, src_tab): (SurfaceIndex, NodeIndex, TabIndex),
-        window_rect: Rect,
-    ) -> SurfaceIndex {
-        // Remove the tab from the tree and it add to a new window.
-        let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
-        let surface_index = self.add_window(vec![tab]);
+        src_tab: impl Into<TabRef>,
+        dst_tab: impl Into<TabDestination>,
+    ) {
+        let Some(location @ (surface_index, node_index, tab_index)) = self.resolve_tab(src_tab)
+        else {
+            return;
+        };
+        let leaf = self[surface_index][node_index]
+            .get_leaf()
+            .expect("tabs can only be in leaves");
+        let selection: Vec<_> = leaf
+            .selected_tabs()
+            .into_iter()
+            .filter_map(|index| leaf.tab_id(index))
+            .collect();
+        let moved = leaf.tab_id(tab_index);
+        self.move_tab(location, dst_tab);
 
-        // Set the window size and position to match `window_rect`.
-        let state = self.get_window_state_mut(surface_index).unwrap();
-        state.set_position(window_rect.min);
-        if src_surface.is_main() {
-            state.set_size(window_rect.size() * 0.8);
-        } else {
-            state.set_size(window_rect.size());
+        let Some(position) = moved.and_then(|id| selection.iter().position(|&s| s == id)) else {
+            return;
+        };
+        // Insert the tabs selected before the moved one right before it, from the closest to the farthest,
+        // and the ones selected after it right after it.
+        let mut anchor = selection[position];
+        for &tab_id in selection[..position].iter().rev() {
+            self.move_tab_next_to(tab_id, anchor, false);
+            anchor = tab_id;
         }
-
-        // Clean up any empty leaves and surfaces which may be left behind from the detachment.
-        if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
-            self[src_surface].remove_leaf(src_node);
+        let mut anchor = selection[position];
+        for &tab_id in &selection[position + 1..] {
+            self.move_tab_next_to(tab_id, anchor, true);
+            anchor = tab_id;
         }
-        if self[src_surface].is_empty() && !src_surface.is_main() {
-            self.remove_surface(src_surface);
+        if let Some((surface_index, node_index, _)) = self.find_by_id(selection[position]) {
+            if let Some(leaf) = self[surface_index][node_index].get_leaf_mut() {
+                leaf.selected = selection;
+            }
         }
-        surface_index
-    }
-
-    /// Currently focused leaf.
-    #[inline]
-    pub fn focused_leaf(&self) -> Option<(SurfaceIndex, NodeIndex)> {
-        let surface = self.focused_surface?;
-        self[surface].focused_leaf().map(|leaf| (surface, leaf))
     }
 
-    /// Remove a tab at the specified surface, node, and tab index.
-    /// This method will yield the removed tab, or `None` if it doesn't exist.
-    pub fn remove_tab(
+    /// Moves all the tabs of a leaf to `dst`, keeping their order, selection and which one is active.
+    ///
+    /// This is what dropping a leaf dragged by the empty space of its tab bar does in a
+    /// [`DockArea`](crate::DockArea). Does nothing if `src` isn't a leaf or `dst` is the leaf itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabDestination, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [left, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.5, vec!["Console", "Output"]);
+    ///
+    /// // The leaf of the console and output is merged into the editor's one.
+    /// dock_state.move_leaf(
+    ///     (SurfaceIndex::main(), right),
+    ///     TabDestination::Node(SurfaceIndex::main(), left, TabInsert::Append),
+    /// );
+    /// let tree = dock_state.main_surface();
+    /// assert_eq!(tree[NodeIndex::root()].tabs(), Some(["Editor", "Console", "Output"].as_slice()));
+    /// ```
+    pub fn move_leaf(
         &mut self,
-        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
-    ) -> Option<Tab> {
-        let removed_tab = self[surface_index].remove_tab((node_index, tab_index));
-        if !surface_index.is_main() && self[surface_index].is_empty() {
-            self.remove_surface(surface_index);
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        dst: impl Into<TabDestination>,
+    ) {
+        let dst = dst.into();
+        if matches!(dst, TabDestination::Node(surface, node, _) if (surface, node) == (surface_index, node_index))
+        {
+            return;
+        }
+        let Some(Node::Leaf(leaf)) = self
+            .get_surface_mut(surface_index)
+            .and_then(Surface::node_tree_mut)
+            .and_then(|tree| tree.nodes.get_mut(node_index.0))
+        else {
+            return;
+        };
+        if leaf.tabs.is_empty() {
+            return;
+        }
+        let active = leaf.tab_id(leaf.active);
+        let selection = std::mem::replace(&mut leaf.selected, leaf.tab_ids.clone());
+        self.move_tab_with_selection((surface_index, node_index, TabIndex(0)), dst);
+
+        let Some((surface_index, node_index, _)) = active.and_then(|id| self.find_by_id(id)) else {
+            return;
+        };
+        if let Some(leaf) = self[surface_index][node_index].get_leaf_mut() {
+            leaf.selected = selection;
+        }
+        if let Some(active) = active {
+            self.set_active_tab(active);
         }
-        removed_tab
     }
 
-    /// Remove a leaf at the specified surface, and node index.
-    pub fn remove_leaf(&mut self, (surface_index, node_index): (SurfaceIndex, NodeIndex)) {
-        self[surface_index].remove_leaf(node_index);
-        if !surface_index.is_main() && self[surface_index].is_empty() {
-            self.remove_surface(surface_index);
+    /// Moves the tab `tab_id` right before or after the tab `anchor`.
+    fn move_tab_next_to(&mut self, tab_id: TabId, anchor: TabId, after: bool) {
+        let Some((src_surface, src_node, src_tab)) = self.find_by_id(tab_id) else {
+            return;
+        };
+        // Call `Node::remove_tab` to avoid auto remove of the node by `Tree::remove_tab` from Tree.
+        let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
+        let (dst_surface, dst_node, dst_tab) = match self.find_by_id(anchor) {
+            Some((surface, node, TabIndex(index))) => {
+                (surface, node, TabIndex(index + usize::from(after)))
+            }
+            None => (src_surface, src_node, src_tab),
+        };
+        self[dst_surface][dst_node].insert_tab(dst_tab, tab);
+        self.restore_tab_id((dst_surface, dst_node, dst_tab), Some(tab_id));
+
+        if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
+            self.remove_empty_leaf((src_surface, src_node));
+        }
+        if self[src_surface].is_empty() && !src_surface.is_main() {
+            let focused_surface = self.focused_surface;
+            self.remove_surface(src_surface);
+            self.focused_surface = focused_surface;
         }
     }
 
-    /// Creates two new nodes by splitting a given `parent` node and assigns them as its children. The first (old) node
-    /// inherits content of the `parent` from before the split, and the second (new) has `tabs`.
+    /// Puts a new `tab` at `destination`, makes it the active tab of its leaf and focuses the leaf.
     ///
-    /// `fraction` (in range 0..=1) specifies how much of the `parent` node's area the old node will occupy after the
-    /// split.
+    /// Returns the surface and node index of the leaf the tab was put into.
     ///
-    /// The new node is placed relatively to the old node, in the direction specified by `split`.
+    /// # Examples
     ///
-    /// Returns the indices of the old node and the new node.
-    pub fn split(
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabDestination, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let location = dock_state.insert_tab(
+    ///     "b",
+    ///     TabDestination::Node(SurfaceIndex::main(), NodeIndex::root(), TabInsert::Split(Split::Below)),
+    /// );
+    ///
+    /// assert_eq!(location, (SurfaceIndex::main(), NodeIndex(2)));
+    /// assert_eq!(dock_state.find_active_focused().map(|(_, tab)| *tab), Some("b"));
+    /// ```
+    pub fn insert_tab(
         &mut self,
-        (surface, parent): (SurfaceIndex, NodeIndex),
-        split: Split,
+        tab: Tab,
+        destination: impl Into<TabDestination>,
+    ) -> (SurfaceIndex, NodeIndex) {
+        let location = match destination.into() {
+            TabDestination::Window(rect) => {
+                let surface_index = self.add_window(vec![tab]);
+                let state = self.get_window_state_mut(surface_index).unwrap();
+                state.set_position(rect.min).set_size(rect.size());
+                (surface_index, NodeIndex::root())
+            }
+            TabDestination::Node(surface_index, node_index, insert) => match insert {
+                TabInsert::Split(split) => {
+                    let [_, new] =
+                        self[surface_index].split(node_index, split, 0.5, Node::leaf(tab));
+                    (surface_index, new)
+                }
+                TabInsert::Insert(index) => {
+                    self[surface_index][node_index].insert_tab(index, tab);
+                    (surface_index, node_index)
+                }
+                TabInsert::Append => {
+                    self[surface_index][node_index].append_tab(tab);
+                    (surface_index, node_index)
+                }
+            },
+            TabDestination::EmptySurface(surface_index) => {
+                assert!(self[surface_index].is_empty());
+                self[surface_index] = Tree::new(vec![tab]);
+                (surface_index, NodeIndex::root())
+            }
+        };
+        self.set_focused_node_and_surface(location);
+        location
+    }
+
+    /// Puts all of `tabs` at `destination`, keeping their order, e.g. when restoring a session.
+    ///
+    /// Unlike calling [`DockState::insert_tab`] for each of them, only the tab picked by `focus` becomes active
+    /// and gets its leaf focused. With [`FocusPolicy::None`], the active tabs and the focused leaf stay as they were.
+    ///
+    /// Returns the surface and node index of the leaf the tabs were put into, or `None` if `tabs` is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `destination` is a node which isn't a leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, FocusPolicy, NodeIndex, SurfaceIndex, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["a"]);
+    /// let location = dock_state.add_tabs(
+    ///     (SurfaceIndex::main(), NodeIndex::root(), TabInsert::Append),
+    ///     vec!["b", "c", "d"],
+    ///     FocusPolicy::None,
+    /// );
+    ///
+    /// assert_eq!(location, Some((SurfaceIndex::main(), NodeIndex::root())));
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 4);
+    /// let leaf = dock_state.main_surface()[NodeIndex::root()].get_leaf().unwrap();
+    /// assert_eq!(leaf.tabs[leaf.active.0], "a");
+    /// ```
+    pub fn add_tabs(
+        &mut self,
+        destination: impl Into<TabDestination>,
+        tabs: Vec<Tab>,
+        focus: FocusPolicy,
+    ) -> Option<(SurfaceIndex, NodeIndex)> {
+        if tabs.is_empty() {
+            return None;
+        }
+        let count = tabs.len();
+        let destination = destination.into();
+
+        // Splitting moves nodes around, so the previous focus is remembered by node IDs.
+        let focused_surface = self.focused_surface;
+        let focused_node = self.focused_leaf().and_then(|leaf| self.node_id(leaf));
+        let surface_focused_node = match destination {
+            TabDestination::Node(surface_index, ..) => self[surface_index]
+                .focused_leaf()
+                .and_then(|node_index| self.node_id((surface_index, node_index))),
+            _ => None,
+        };
+
+        let (location, first, previous_active) = match destination {
+            TabDestination::Window(rect) => {
+                let surface_index = self.add_window(tabs);
+                let state = self.get_window_state_mut(surface_index).unwrap();
+                state.set_position(rect.min).set_size(rect.size());
+                ((surface_index, NodeIndex::root()), TabIndex(0), TabIndex(0))
+            }
+            TabDestination::Node(surface_index, node_index, insert) => {
+                let leaf = self[surface_index][node_index]
+                    .get_leaf_mut()
+                    .expect("node was not a leaf");
+                // A leaf without tabs (a central one) has nothing to keep active.
+                let previous_active = if leaf.is_empty() {
+                    None
+                } else {
+                    Some(leaf.active)
+                };
+                match insert {
+                    TabInsert::Split(split) => {
+                        let [_, new] = self[surface_index].split(
+                            node_index,
+                            split,
+                            0.5,
+                            Node::leaf_with(tabs),
+                        );
+                        ((surface_index, new), TabIndex(0), TabIndex(0))
+                    }
+                    TabInsert::Insert(index) => {
+                        for (offset, tab) in tabs.into_iter().enumerate() {
+                            leaf.insert_tab(index.0 + offset, tab);
+                        }
+                        // The tabs after the inserted ones are shifted.
+                        let previous_active = previous_active.map_or(index, |active| {
+                            if index <= active {
+                                TabIndex(active.0 + count)
+                            } else {
+                                active
+                            }
+                        });
+                        ((surface_index, node_index), index, previous_active)
+                    }
+                    TabInsert::Append => {
+                        let first = TabIndex(leaf.len());
+                        for tab in tabs {
+                            leaf.append_tab(tab);
+                        }
+                        (
+                            (surface_index, node_index),
+                            first,
+                            previous_active.unwrap_or(first),
+                        )
+                    }
+                }
+            }
+            TabDestination::EmptySurface(surface_index) => {
+                assert!(self[surface_index].is_empty());
+                self[surface_index] = Tree::new(tabs);
+                ((surface_index, NodeIndex::root()), TabIndex(0), TabIndex(0))
+            }
+        };
+
+        let (surface_index, node_index) = location;
+        let leaf = self[surface_index][node_index].get_leaf_mut().unwrap();
+        match focus {
+            FocusPolicy::First => {
+                leaf.set_active_tab(first);
+                self.set_focused_node_and_surface(location);
+            }
+            FocusPolicy::Last => {
+                leaf.set_active_tab(first.0 + count - 1);
+                self.set_focused_node_and_surface(location);
+            }
+            FocusPolicy::None => {
+                leaf.set_active_tab(previous_active);
+                if let Some(node_index) = surface_focused_node
+                    .and_then(|node_id| self[surface_index].find_node_by_id(node_id))
+                {
+                    self[surface_index].set_focused_node(node_index);
+                }
+                match focused_node.and_then(|node_id| self.find_node_by_id(node_id)) {
+                    Some(focused_leaf) => self.set_focused_node_and_surface(focused_leaf),
+                    None => self.focused_surface = focused_surface,
+                }
+            }
+        }
+        Some(location)
+    }
+
+    /// Moves a tab out of this [`DockState`] and puts it at `dst` in `other`, like [`DockState::move_tab`] does
+    /// within a single one.
+    ///
+    /// The leaves and windows left empty in this [`DockState`] are removed, while the tab becomes the active tab of
+    /// its leaf in `other`, which is focused. The tab keeps its [`TabId`]. Returns where the tab ended up in
+    /// `other`, or `None` if there is no tab at `src_tab`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabDestination, TabIndex, TabInsert};
+    /// let mut project_a = DockState::new(vec!["main.rs", "lib.rs"]);
+    /// let mut project_b = DockState::new(vec!["README.md"]);
+    ///
+    /// let location = project_a.transfer_tab(
+    ///     &mut project_b,
+    ///     (SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)),
+    ///     TabDestination::Node(SurfaceIndex::main(), NodeIndex::root(), TabInsert::Append),
+    /// );
+    /// assert_eq!(location, Some((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1))));
+    /// assert_eq!(project_a.main_surface().num_tabs(), 1);
+    /// assert_eq!(project_b.find_active_focused().map(|(_, tab)| *tab), Some("lib.rs"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` isn't a valid destination in `other`, like for [`DockState::insert_tab`].
+    pub fn transfer_tab(
+        &mut self,
+        other: &mut DockState<Tab>,
+        src_tab: impl Into<TabRef>,
+        dst: impl Into<TabDestination>,
+    ) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        // Tabs pushed directly onto a leaf get their IDs here, so that a missing ID means a missing tab.
+        self.sync_tab_ids();
+        let location = self.resolve_tab(src_tab)?;
+        let tab_id = self.tab_id(location)?;
+        let tab = self.remove_tab(location)?;
+        let (surface_index, node_index) = other.insert_tab(tab, dst);
+        let tab_index = other[surface_index][node_index]
+            .get_leaf()
+            .expect("tabs are inserted into leaves")
+            .active;
+        other.restore_tab_id((surface_index, node_index, tab_index), Some(tab_id));
+        Some((surface_index, node_index, tab_index))
+    }
+
+    /// Takes a tab out of its current surface and puts it in a new window.
+    /// Returns the surface index of the new window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tab is given by a [`TabId`] which isn't in the [`DockState`].
+    pub fn detach_tab(&mut self, src_tab: impl Into<TabRef>, window_rect: Rect) -> SurfaceIndex {
+        let (src_surface, src_node, src_tab) = self
+            .resolve_tab(src_tab)
+            .expect("there is no tab with this ID");
+        // Remove the tab from the tree and it add to a new window.
+        let tab_id = self.tab_id((src_surface, src_node, src_tab));
+        let tab = self[src_surface][src_node].remove_tab(src_tab).unwrap();
+        let surface_index = self.add_window(vec![tab]);
+        self.restore_tab_id((surface_index, NodeIndex::root(), TabIndex(0)), tab_id);
+
+        // Set the window size and position to match `window_rect`.
+        let state = self.get_window_state_mut(surface_index).unwrap();
+        state.set_position(window_rect.min);
+        if src_surface.is_main() {
+            state.set_size(window_rect.size() * 0.8);
+        } else {
+            state.set_size(window_rect.size());
+        }
+
+        // Clean up any empty leaves and surfaces which may be left behind from the detachment.
+        if self[src_surface][src_node].is_leaf() && self[src_surface][src_node].tabs_count() == 0 {
+            self.remove_empty_leaf((src_surface, src_node));
+        }
+        if self[src_surface].is_empty() && !src_surface.is_main() {
+            self.remove_surface(src_surface);
+        }
+        surface_index
+    }
+
+    /// Takes a tab out of its leaf and puts it in a new window taking up `window_rect`, e.g. the rect of the tab's
+    /// body, and focuses it. Returns the surface index of the new window.
+    ///
+    /// Unlike [`DockState::detach_tab`], the window remembers where the tab came from as its
+    /// [`RestoreLocation`], so that [`DockState::redock_window`] can put the tab back.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::{pos2, vec2, Rect};
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [_, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.7, vec!["Console"]);
+    ///
+    /// let window = dock_state.float_tab(
+    ///     (SurfaceIndex::main(), right, TabIndex(0)),
+    ///     Rect::from_min_size(pos2(100.0, 100.0), vec2(400.0, 300.0)),
+    /// );
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 1);
+    ///
+    /// dock_state.redock_window(window);
+    /// assert!(!dock_state.is_surface_valid(window));
+    /// assert_eq!(dock_state.main_surface()[right].tabs(), Some(["Console"].as_slice()));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tab is given by a [`TabId`] which isn't in the [`DockState`].
+    pub fn float_tab(&mut self, src_tab: impl Into<TabRef>, window_rect: Rect) -> SurfaceIndex {
+        let location = self
+            .resolve_tab(src_tab)
+            .expect("there is no tab with this ID");
+        let restore_location = self.restore_location(location);
+        let window = self.detach_tab(location, window_rect);
+        self.get_window_state_mut(window)
+            .unwrap()
+            .set_size(window_rect.size())
+            .set_restore_location(restore_location);
+        self.set_focused_node_and_surface((window, NodeIndex::root()));
+        window
+    }
+
+    /// Moves the tabs of a window created by [`DockState::float_tab`] back to where its tab came from, see
+    /// [`WindowState::restore_location`], and removes the window. Returns the leaf the tabs went to, which gets
+    /// focused, or `None` if the surface isn't a window with a [`RestoreLocation`].
+    ///
+    /// Tabs whose leaf is gone go into a new leaf split off its sibling, or if that's gone too, into the
+    /// main surface.
+    pub fn redock_window(
+        &mut self,
+        surface_index: SurfaceIndex,
+    ) -> Option<(SurfaceIndex, NodeIndex)> {
+        let Some(Surface::Window(_, window_state)) = self.get_surface(surface_index) else {
+            return None;
+        };
+        let restore_location = window_state.restore_location()?;
+
+        let mut destination = match restore_location {
+            RestoreLocation::Leaf(node_id, tab_index) => {
+                self.find_node_by_id(node_id).map(|(surface, node)| {
+                    let tab_index = TabIndex(tab_index.0.min(self[surface][node].tabs_count()));
+                    TabDestination::Node(surface, node, TabInsert::Insert(tab_index))
+                })
+            }
+            RestoreLocation::Split(node_id, split, _) => {
+                self.find_node_by_id(node_id).map(|(surface, node)| {
+                    TabDestination::Node(surface, node, TabInsert::Split(split))
+                })
+            }
+        }
+        .or_else(|| {
+            let tree = self.main_surface();
+            match tree
+                .breadth_first_index_iter()
+                .find(|&node| tree[node].is_leaf())
+            {
+                Some(node) => Some(TabDestination::Node(
+                    SurfaceIndex::main(),
+                    node,
+                    TabInsert::Append,
+                )),
+                None => Some(TabDestination::EmptySurface(SurfaceIndex::main())),
+            }
+        })?;
+
+        let mut redocked = None;
+        while self.is_surface_valid(surface_index) {
+            let tree = &self[surface_index];
+            let Some(node) = tree
+                .breadth_first_index_iter()
+                .find(|&node| tree[node].tabs_count() > 0)
+            else {
+                break;
+            };
+            self.move_tab((surface_index, node, TabIndex(0)), destination);
+            // The tab is focused where it ended up, the next ones go after it.
+            let (surface, node) = self.focused_leaf()?;
+            if redocked.is_none() {
+                if let (RestoreLocation::Split(_, _, fraction), Some(parent)) =
+                    (restore_location, node.parent())
+                {
+                    if let Node::Horizontal(split) | Node::Vertical(split) =
+                        &mut self[surface][parent]
+                    {
+                        split.fraction = fraction;
+                    }
+                }
+            }
+            let active = self[surface][node].get_leaf()?.active;
+            destination =
+                TabDestination::Node(surface, node, TabInsert::Insert(TabIndex(active.0 + 1)));
+            redocked = Some((surface, node));
+        }
+        if self.is_surface_valid(surface_index) {
+            self.remove_surface(surface_index);
+        }
+        if let Some(redocked) = redocked {
+            self.set_focused_node_and_surface(redocked);
+        }
+        redocked
+    }
+
+    /// Returns where the tab at the given location would go back to if it was floated into a window.
+    fn restore_location(
+        &self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Option<RestoreLocation> {
+        let tree = &self[surface_index];
+        if tree[node_index].tabs_count() > 1 {
+            let node_id = self.node_id((surface_index, node_index))?;
+            return Some(RestoreLocation::Leaf(node_id, tab_index));
+        }
+
+        // The leaf is removed along with its only tab, so the tab comes back next to its sibling.
+        let (node_id, split, fraction) = self.sibling_of((surface_index, node_index))?;
+        Some(RestoreLocation::Split(node_id, split, fraction))
+    }
+
+    /// Returns the [`NodeId`] of the sibling of a node, the side of the sibling the node is on, and the fraction of
+    /// their parent, or `None` if the node is the root.
+    fn sibling_of(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) -> Option<(NodeId, Split, f32)> {
+        let tree = &self[surface_index];
+        let parent = node_index.parent()?;
+        let (sibling, split) = match (&tree[parent], node_index.is_left()) {
+            (Node::Horizontal(_), true) => (parent.right(), Split::Left),
+            (Node::Horizontal(_), false) => (parent.left(), Split::Right),
+            (Node::Vertical(_), true) => (parent.right(), Split::Above),
+            (Node::Vertical(_), false) => (parent.left(), Split::Below),
+            _ => return None,
+        };
+        let fraction = match &tree[parent] {
+            Node::Horizontal(split) | Node::Vertical(split) => split.fraction,
+            _ => return None,
+        };
+        let node_id = self.node_id((surface_index, sibling))?;
+        Some((node_id, split, fraction))
+    }
+
+    /// Takes all the tabs of a leaf out of its current surface and puts them in a new window, like dragging the
+    /// leaf by the empty space of its tab bar out of the dock does. Returns the surface index of the new window.
+    ///
+    /// The window is placed at `window_rect`, and the tabs keep their order, selection and which one is active.
+    /// Useful for a "Float panel" command.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::{pos2, vec2, Rect};
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [_, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.5, vec!["Console", "Output"]);
+    ///
+    /// let window = dock_state.detach_node_to_window(
+    ///     (SurfaceIndex::main(), right),
+    ///     Rect::from_min_size(pos2(100.0, 100.0), vec2(400.0, 300.0)),
+    /// );
+    /// assert_eq!(dock_state[window][NodeIndex::root()].tabs(), Some(["Console", "Output"].as_slice()));
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn't a leaf with tabs.
+    pub fn detach_node_to_window(
+        &mut self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        window_rect: Rect,
+    ) -> SurfaceIndex {
+        let tab_id = self[surface_index][node_index]
+            .get_leaf()
+            .and_then(|leaf| leaf.tab_id(TabIndex(0)))
+            .expect("the node isn't a leaf with tabs");
+        self.move_leaf(
+            (surface_index, node_index),
+            TabDestination::Window(window_rect),
+        );
+        let (window, _, _) = self
+            .find_by_id(tab_id)
+            .expect("the tabs are moved to the new window");
+        window
+    }
+
+    /// Splits the leaf containing a tab in the direction of `split`, putting the tab into the new leaf
+    /// as specified by `behavior`, and focuses the new leaf.
+    ///
+    /// Returns the index of the new leaf, or `None` if nothing was split. This happens when `behavior` is
+    /// [`SplitBehavior::Veto`], when it's [`SplitBehavior::Move`] and the tab is the only one in its leaf,
+    /// when the leaf doesn't allow splitting in the direction of `split` (see [`LeafNode::set_allowed_splits`]),
+    /// or when the tab is given by a [`TabId`] which isn't in the [`DockState`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabIndex};
+    /// # use egui_dock::tab_viewer::SplitBehavior;
+    /// let mut dock_state = DockState::new(vec!["a", "b"]);
+    /// let tab = (SurfaceIndex::main(), NodeIndex::root(), TabIndex(1));
+    ///
+    /// // "Split Editor Right".
+    /// let new_leaf = dock_state.split_tab(tab, Split::Right, SplitBehavior::Move);
+    /// assert_eq!(new_leaf, Some(NodeIndex(2)));
+    /// assert_eq!(dock_state.focused_leaf(), Some((SurfaceIndex::main(), NodeIndex(2))));
+    /// ```
+    pub fn split_tab(
+        &mut self,
+        tab: impl Into<TabRef>,
+        split: Split,
+        behavior: SplitBehavior<Tab>,
+    ) -> Option<NodeIndex> {
+        let (surface_index, node_index, tab_index) = self.resolve_tab(tab)?;
+        if !self.allowed_splits(surface_index, node_index).allows(split) {
+            return None;
+        }
+        let (tab, tab_id) = match behavior {
+            SplitBehavior::Move => {
+                if self[surface_index][node_index].tabs_count() < 2 {
+                    return None;
+                }
+                let tab_id = self.tab_id((surface_index, node_index, tab_index));
+                (
+                    self[surface_index][node_index].remove_tab(tab_index)?,
+                    tab_id,
+                )
+            }
+            // The clone is a different tab, so it gets a new ID.
+            SplitBehavior::Clone(tab) => (tab, None),
+            SplitBehavior::Veto => return None,
+        };
+        let [_, new_node] = self[surface_index].split(node_index, split, 0.5, Node::leaf(tab));
+        self.restore_tab_id((surface_index, new_node, TabIndex(0)), tab_id);
+        self.set_focused_node_and_surface((surface_index, new_node));
+        Some(new_node)
+    }
+
+    /// Currently focused leaf.
+    #[inline]
+    pub fn focused_leaf(&self) -> Option<(SurfaceIndex, NodeIndex)> {
+        let surface = self.focused_surface?;
+        self[surface].focused_leaf().map(|leaf| (surface, leaf))
+    }
+
+    /// Returns the location of the active tab of the focused leaf, which is the tab the user is looking at, or `None`
+    /// if no leaf is in focus.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [_, console] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_below(NodeIndex::root(), 0.7, vec!["Console", "Terminal"]);
+    /// assert_eq!(dock_state.focused_tab(), None);
+    ///
+    /// dock_state.set_active_tab((SurfaceIndex::main(), console, TabIndex(1)));
+    /// dock_state.set_focused_node_and_surface((SurfaceIndex::main(), console));
+    /// assert_eq!(dock_state.focused_tab(), Some((SurfaceIndex::main(), console, TabIndex(1))));
+    /// ```
+    pub fn focused_tab(&self) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        let (surface_index, node_index) = self.focused_leaf()?;
+        let tab_index = self.active_tab((surface_index, node_index))?;
+        Some((surface_index, node_index, tab_index))
+    }
+
+    /// Returns the leaf which was shown at `pos` the last time the [`DockArea`](crate::DockArea) was drawn.
+    ///
+    /// `pos` is in the coordinates of the viewport the [`DockArea`](crate::DockArea) is shown in.
+    /// Windows are checked before the main surface, as they're shown on top of it, in the order of
+    /// [`DockState::window_order`] from front to back, while minimized windows and the ones left out in the taskbar of
+    /// the [`DockArea`](crate::DockArea) are skipped. Returns `None` if there is no leaf at `pos`.
+    pub fn node_at_pos(&self, pos: Pos2) -> Option<(SurfaceIndex, NodeIndex)> {
+        // Windows are checked from front to back, the ones missing from the order being stacked in front.
+        let mut windows: Vec<SurfaceIndex> = (1..self.surfaces.len())
+            .rev()
+            .map(SurfaceIndex)
+            .filter(|index| !self.window_order.contains(index))
+            .chain(self.window_order.iter().rev().copied())
+            .collect();
+        windows.sort_by_key(|&index| {
+            !matches!(&self.surfaces[index.0], Surface::Window(_, state) if state.is_always_on_top())
+        });
+        for index in windows.into_iter().chain([SurfaceIndex::main()]) {
+            let Some(surface) = self.surfaces.get(index.0) else {
+                continue;
+            };
+            let tree = match surface {
+                Surface::Main(tree) => tree,
+                Surface::Window(tree, state)
+                    if !state.is_minimized()
+                        && !state.is_hidden_in_taskbar()
+                        && state.rect().contains(pos) =>
+                {
+                    tree
+                }
+                _ => continue,
+            };
+            // Whatever is below the surface is covered by it, even if no leaf was hit.
+            return tree
+                .breadth_first_index_iter()
+                .find(|&node_index| {
+                    tree[node_index].get_leaf().is_some_and(|leaf| {
+                        leaf.rect.contains(pos) && !tree.is_auto_hidden(node_index)
+                    })
+                })
+                .map(|node_index| (index, node_index));
+        }
+        None
+    }
+
+    /// Returns the tab which was shown at `pos` the last time the [`DockArea`](crate::DockArea) was drawn.
+    ///
+    /// This is either the tab whose button in the tab bar contains `pos`, or the active tab of the leaf whose body
+    /// contains `pos`. See [`DockState::node_at_pos`] for how the leaf is found.
+    pub fn tab_at_pos(&self, pos: Pos2) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        let (surface_index, node_index) = self.node_at_pos(pos)?;
+        let leaf = self[surface_index][node_index].get_leaf()?;
+        let tab_index = if leaf.viewport.contains(pos) {
+            Some(leaf.active).filter(|active| active.0 < leaf.len())
+        } else {
+            leaf.tab_rects
+                .iter()
+                .position(|rect| rect.contains(pos))
+                .map(TabIndex)
+        }?;
+        Some((surface_index, node_index, tab_index))
+    }
+
+    /// Remove a tab at the specified surface, node, and tab index, or with the specified [`TabId`].
+    /// This method will yield the removed tab, or `None` if it doesn't exist.
+    pub fn remove_tab(&mut self, tab: impl Into<TabRef>) -> Option<Tab> {
+        let (surface_index, node_index, tab_index) = self.resolve_tab(tab)?;
+        let node = &mut self[surface_index][node_index];
+        let removed_tab = node.remove_tab(tab_index);
+        if node.tabs_count() == 0 {
+            self.remove_empty_leaf((surface_index, node_index));
+        }
+        if !surface_index.is_main() && self[surface_index].is_empty() {
+            self.remove_surface(surface_index);
+        }
+        removed_tab
+    }
+
+    /// Removes a tab like [`DockState::remove_tab`], but remembers it in the [`DockState::closed_tabs`] so that it can
+    /// be reopened with [`DockState::reopen_last_closed`]. Returns `false` if the tab doesn't exist.
+    ///
+    /// The tab is dropped right away if the capacity of the history is 0, which is the default.
+    pub fn close_tab(&mut self, tab: impl Into<TabRef>) -> bool {
+        let Some((surface_index, node_index, tab_index)) = self.resolve_tab(tab) else {
+            return false;
+        };
+        let Some(leaf) = self
+            .get_surface(surface_index)
+            .and_then(|surface| surface.node_tree())
+            .and_then(|tree| tree.nodes.get(node_index.0))
+            .and_then(|node| node.get_leaf())
+            .filter(|leaf| tab_index.0 < leaf.tabs.len())
+        else {
+            return false;
+        };
+        if self.closed_tabs.capacity() == 0 {
+            return self
+                .remove_tab((surface_index, node_index, tab_index))
+                .is_some();
+        }
+
+        let leaf = leaf.id();
+        let sibling = self.sibling_of((surface_index, node_index));
+        let ancestors = std::iter::successors(node_index.parent(), |node| node.parent())
+            .filter_map(|node| self.node_id((surface_index, node)))
+            .collect();
+        let window_state = match self.get_surface(surface_index) {
+            Some(Surface::Window(_, window_state)) => Some(window_state.clone()),
+            _ => None,
+        };
+        let Some(tab) = self.remove_tab((surface_index, node_index, tab_index)) else {
+            return false;
+        };
+        self.closed_tabs.push(ClosedTab {
+            tab,
+            leaf,
+            tab_index,
+            sibling,
+            ancestors,
+            surface_index,
+            window_state,
+        });
+        true
+    }
+
+    /// Removes a leaf like [`DockState::remove_leaf`], but remembers its tabs in the [`DockState::closed_tabs`] so
+    /// that they can be reopened with [`DockState::reopen_last_closed`], the first tab of the leaf being reopened
+    /// first.
+    pub fn close_leaf(&mut self, (surface_index, node_index): (SurfaceIndex, NodeIndex)) {
+        let Some(leaf) = self[surface_index][node_index].get_leaf() else {
+            return;
+        };
+        if leaf.is_central() {
+            return;
+        }
+        if leaf.tabs.is_empty() {
+            self.remove_leaf((surface_index, node_index));
+            return;
+        }
+        // Closing the last tab removes the leaf, along with its window if it was the only leaf there.
+        for tab_index in (0..leaf.tabs.len()).rev() {
+            self.close_tab((surface_index, node_index, TabIndex(tab_index)));
+        }
+    }
+
+    /// Puts the most recently closed tab back where it was, or as close to it as possible, makes it active and
+    /// focuses its leaf. Returns where the tab ended up, or `None` if there are no closed tabs to reopen.
+    ///
+    /// If the leaf of the tab is gone, it's recreated next to the node it was split off. If that one is gone too,
+    /// the tab goes into a leaf under the nearest surviving node above it, then into a new window if it was in a
+    /// window which was closed, and into the main surface otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// dock_state.closed_tabs_mut().set_capacity(10);
+    /// let [_, console] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_below(NodeIndex::root(), 0.7, vec!["Console", "Problems"]);
+    ///
+    /// // Closing the last tab of a leaf removes the leaf, but reopening the tab brings it back.
+    /// dock_state.close_tab((SurfaceIndex::main(), console, TabIndex(1)));
+    /// dock_state.close_tab((SurfaceIndex::main(), console, TabIndex(0)));
+    /// assert_eq!(dock_state.main_surface().num_tabs(), 1);
+    ///
+    /// dock_state.reopen_last_closed();
+    /// dock_state.reopen_last_closed();
+    /// assert_eq!(
+    ///     dock_state.main_surface()[console].tabs(),
+    ///     Some(["Console", "Problems"].as_slice())
+    /// );
+    /// ```
+    pub fn reopen_last_closed(&mut self) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        let ClosedTab {
+            tab,
+            leaf,
+            tab_index,
+            sibling,
+            ancestors,
+            surface_index: _,
+            window_state,
+        } = self.closed_tabs.pop()?;
+
+        let leaf_location = self
+            .find_node_by_id(leaf)
+            .filter(|&(surface_index, node_index)| self[surface_index][node_index].is_leaf());
+        let sibling_location = sibling.and_then(|(sibling, split, fraction)| {
+            Some((self.find_node_by_id(sibling)?, split, fraction))
+        });
+        let ancestor_location = ancestors
+            .iter()
+            .find_map(|&ancestor| self.find_node_by_id(ancestor));
+
+        let location = if let Some((surface_index, node_index)) = leaf_location {
+            let tab_index = TabIndex(
+                tab_index
+                    .0
+                    .min(self[surface_index][node_index].tabs_count()),
+            );
+            self[surface_index][node_index].insert_tab(tab_index, tab);
+            (surface_index, node_index, tab_index)
+        } else if let Some(((surface_index, sibling), split, fraction)) = sibling_location {
+            // The leaf gets its old ID back, so that the other tabs closed along with it come back to it.
+            let mut new_leaf = LeafNode::new(vec![tab]);
+            new_leaf.id = leaf;
+            let [_, node_index] =
+                self[surface_index].split(sibling, split, 0.5, Node::Leaf(new_leaf));
+            if let Some(Node::Horizontal(split) | Node::Vertical(split)) = node_index
+                .parent()
+                .map(|parent| &mut self[surface_index][parent])
+            {
+                split.fraction = fraction;
+            }
+            (surface_index, node_index, TabIndex(0))
+        } else if let Some((surface_index, mut node_index)) = ancestor_location {
+            let tree = &self[surface_index];
+            while !tree[node_index].is_leaf() {
+                node_index = if tree[node_index.left()].is_empty() {
+                    node_index.right()
+                } else {
+                    node_index.left()
+                };
+            }
+            self[surface_index][node_index].append_tab(tab);
+            let tab_index = TabIndex(self[surface_index][node_index].tabs_count() - 1);
+            (surface_index, node_index, tab_index)
+        } else if let Some(window_state) = window_state {
+            let surface_index = self.add_window(vec![tab]);
+            *self.get_window_state_mut(surface_index).unwrap() = window_state;
+            if let Some(new_leaf) = self[surface_index][NodeIndex::root()].get_leaf_mut() {
+                new_leaf.id = leaf;
+            }
+            (surface_index, NodeIndex::root(), TabIndex(0))
+        } else {
+            let tree = self.main_surface();
+            let first_leaf = tree
+                .breadth_first_index_iter()
+                .find(|&node_index| tree[node_index].is_leaf());
+            let (surface_index, node_index) = match first_leaf {
+                Some(node_index) => {
+                    self.insert_tab(tab, (SurfaceIndex::main(), node_index, TabInsert::Append))
+                }
+                None => self.insert_tab(tab, TabDestination::EmptySurface(SurfaceIndex::main())),
+            };
+            let tab_index = TabIndex(self[surface_index][node_index].tabs_count() - 1);
+            (surface_index, node_index, tab_index)
+        };
+
+        self.set_active_tab(location);
+        self.set_focused_node_and_surface((location.0, location.1));
+        Some(location)
+    }
+
+    /// Get an immutable borrow to the history of the tabs closed with [`DockState::close_tab`].
+    pub fn closed_tabs(&self) -> &ClosedTabHistory<Tab> {
+        &self.closed_tabs
+    }
+
+    /// Get a mutable borrow to the history of the tabs closed with [`DockState::close_tab`], e.g. to set its capacity.
+    pub fn closed_tabs_mut(&mut self) -> &mut ClosedTabHistory<Tab> {
+        &mut self.closed_tabs
+    }
+
+    /// Returns where the tab with the given [`TabId`] currently is, or `None` if it isn't in the [`DockState`].
+    ///
+    /// The returned [`NodeIndex`] will always point to a [`Node::Leaf`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex, TabDestination, TabIndex, TabInsert};
+    /// let mut dock_state = DockState::new(vec!["Editor", "Terminal"]);
+    /// let terminal = dock_state
+    ///     .tab_id((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)))
+    ///     .unwrap();
+    ///
+    /// // The ID keeps referring to the same tab after it's moved.
+    /// dock_state.move_tab(
+    ///     terminal,
+    ///     TabDestination::Node(SurfaceIndex::main(), NodeIndex::root(), TabInsert::Split(Split::Below)),
+    /// );
+    /// assert_eq!(
+    ///     dock_state.find_by_id(terminal),
+    ///     Some((SurfaceIndex::main(), NodeIndex(2), TabIndex(0)))
+    /// );
+    ///
+    /// dock_state.remove_tab(terminal);
+    /// assert_eq!(dock_state.find_by_id(terminal), None);
+    /// ```
+    pub fn find_by_id(&self, tab_id: TabId) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        self.valid_surface_indices()
+            .iter()
+            .find_map(|&surface_index| {
+                let (node_index, tab_index) = self[surface_index].find_by_id(tab_id)?;
+                Some((surface_index, node_index, tab_index))
+            })
+    }
+
+    /// Returns the [`TabId`] of the tab at the specified surface, node, and tab index,
+    /// or `None` if it doesn't exist.
+    pub fn tab_id(
+        &self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Option<TabId> {
+        self.get_surface(surface_index)?
+            .node_tree()?
+            .nodes
+            .get(node_index.0)?
+            .get_leaf()?
+            .tab_id(tab_index)
+    }
+
+    /// Returns where the node with the given [`NodeId`] currently is, or `None` if it isn't in the [`DockState`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [_, console] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_below(NodeIndex::root(), 0.7, vec!["Console"]);
+    /// let console = dock_state.node_id((SurfaceIndex::main(), console)).unwrap();
+    ///
+    /// // Splitting and removing leaves rearranges the tree, but the ID keeps referring to the same node.
+    /// let [_, outline] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_left(NodeIndex::root(), 0.2, vec!["Outline"]);
+    /// dock_state.remove_leaf((SurfaceIndex::main(), outline));
+    /// let (surface, node) = dock_state.find_node_by_id(console).unwrap();
+    /// assert_eq!(dock_state[surface][node].tabs(), Some(["Console"].as_slice()));
+    /// ```
+    pub fn find_node_by_id(&self, node_id: NodeId) -> Option<(SurfaceIndex, NodeIndex)> {
+        self.valid_surface_indices()
+            .iter()
+            .find_map(|&surface_index| {
+                let node_index = self[surface_index].find_node_by_id(node_id)?;
+                Some((surface_index, node_index))
+            })
+    }
+
+    /// Returns the [`NodeId`] of the node at the specified surface and node index,
+    /// or `None` if it doesn't exist or is [`Empty`](Node::Empty).
+    pub fn node_id(
+        &self,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+    ) -> Option<NodeId> {
+        self.get_surface(surface_index)?
+            .node_tree()?
+            .nodes
+            .get(node_index.0)?
+            .id()
+    }
+
+    /// Returns the surface, node and tab index of the tab referred to by `tab`.
+    ///
+    /// Tabs given by their indices are returned as they are, even if they don't exist.
+    fn resolve_tab(&self, tab: impl Into<TabRef>) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        match tab.into() {
+            TabRef::Index(surface_index, node_index, tab_index) => {
+                Some((surface_index, node_index, tab_index))
+            }
+            TabRef::Id(tab_id) => self.find_by_id(tab_id),
+        }
+    }
+
+    /// Gives the tab which was moved to the specified location the [`TabId`] it had before, if it had one.
+    fn restore_tab_id(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_id: Option<TabId>,
+    ) {
+        let Some(tab_id) = tab_id else {
+            return;
+        };
+        if let Some(Node::Leaf(leaf)) = self[surface_index].nodes.get_mut(node_index.0) {
+            if let Some(id) = leaf.tab_ids.get_mut(tab_index.0) {
+                *id = tab_id;
+            }
+        }
+    }
+
+    /// Gives [`TabId`]s to all tabs pushed directly onto [`LeafNode::tabs`].
+    pub(crate) fn sync_tab_ids(&mut self) {
+        for (_, leaf) in self.iter_leaves_mut() {
+            leaf.sync_tab_ids();
+        }
+    }
+
+    /// Remove a leaf at the specified surface, and node index.
+    ///
+    /// How the space of the leaf is shared among the remaining nodes is set with
+    /// [`DockState::set_proportional_leaf_removal`].
+    pub fn remove_leaf(&mut self, (surface_index, node_index): (SurfaceIndex, NodeIndex)) {
+        self.remove_empty_leaf((surface_index, node_index));
+        if !surface_index.is_main() && self[surface_index].is_empty() {
+            self.remove_surface(surface_index);
+        }
+    }
+
+    /// Sets whether the space of a removed leaf is shared among all the nodes of the same orientation around it in
+    /// proportion to their sizes, instead of being given to its sibling alone.
+    ///
+    /// With this enabled, closing one of three side by side panels leaves the other two with the same ratio of sizes
+    /// they had before, rather than doubling the size of its neighbour.
+    ///
+    /// By default it's `false`.
+    pub fn set_proportional_leaf_removal(&mut self, proportional: bool) {
+        self.proportional_leaf_removal = proportional;
+    }
+
+    /// Returns whether removed leaves share their space among all the nodes around them.
+    /// See [`DockState::set_proportional_leaf_removal`].
+    pub fn proportional_leaf_removal(&self) -> bool {
+        self.proportional_leaf_removal
+    }
+
+    /// Removes a leaf from its surface, sharing its space as set by [`DockState::set_proportional_leaf_removal`].
+    fn remove_empty_leaf(&mut self, (surface_index, node_index): (SurfaceIndex, NodeIndex)) {
+        if self.proportional_leaf_removal {
+            self[surface_index].remove_leaf_proportionally(node_index);
+        } else {
+            self[surface_index].remove_leaf(node_index);
+        }
+    }
+
+    /// Creates two new nodes by splitting a given `parent` node and assigns them as its children. The first (old) node
+    /// inherits content of the `parent` from before the split, and the second (new) has `tabs`.
+    ///
+    /// `fraction` (in range 0..=1) specifies how much of the `parent` node's area the old node will occupy after the
+    /// split.
+    ///
+    /// The new node is placed relatively to the old node, in the direction specified by `split`.
+    ///
+    /// Returns the indices of the old node and the new node.
+    pub fn split(
+        &mut self,
+        (surface, parent): (SurfaceIndex, NodeIndex),
+        split: Split,
         fraction: f32,
         new: Node<Tab>,
     ) -> [NodeIndex; 2] {
@@ -345,6 +1954,8 @@ impl<Tab> DockState<Tab> {
         } else {
             self.surfaces.push(surface);
         }
+        self.window_order.retain(|&window| window != index);
+        self.window_order.push(index);
         index
     }
 
@@ -437,31 +2048,199 @@ impl<Tab> DockState<Tab> {
     }
 
     /// Returns an [`Iterator`] of **all** tabs in the dock state,
-    /// and the indices of containing surfaces and nodes.
-    pub fn iter_all_tabs(&self) -> impl Iterator<Item = ((SurfaceIndex, NodeIndex), &Tab)> {
+    /// and the indices of containing surfaces and nodes, and of the tabs within their nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor", "Preview"]);
+    /// let window = dock_state.add_window(vec!["Console"]);
+    ///
+    /// let location = dock_state
+    ///     .iter_all_tabs()
+    ///     .find_map(|(location, tab)| (*tab == "Preview").then_some(location));
+    /// assert_eq!(location, Some((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1))));
+    ///
+    /// let (last, _) = dock_state.iter_all_tabs().last().unwrap();
+    /// assert_eq!(last, (window, NodeIndex::root(), TabIndex(0)));
+    /// ```
+    pub fn iter_all_tabs(
+        &self,
+    ) -> impl Iterator<Item = ((SurfaceIndex, NodeIndex, TabIndex), &Tab)> {
         self.iter_surfaces()
             .enumerate()
             .flat_map(|(surface_index, surface)| {
                 surface
-                    .iter_all_tabs()
-                    .map(move |(node_index, tab)| ((surface_index.into(), node_index), tab))
+                    .iter_nodes()
+                    .enumerate()
+                    .flat_map(move |(node_index, node)| {
+                        node.iter_tabs().enumerate().map(move |(tab_index, tab)| {
+                            let location = (
+                                SurfaceIndex(surface_index),
+                                NodeIndex(node_index),
+                                TabIndex(tab_index),
+                            );
+                            (location, tab)
+                        })
+                    })
             })
     }
 
     /// Returns a mutable [`Iterator`] of **all** tabs in the dock state,
-    /// and the indices of containing surfaces and nodes.
+    /// and the indices of containing surfaces and nodes, and of the tabs within their nodes.
     pub fn iter_all_tabs_mut(
         &mut self,
-    ) -> impl Iterator<Item = ((SurfaceIndex, NodeIndex), &mut Tab)> {
+    ) -> impl Iterator<Item = ((SurfaceIndex, NodeIndex, TabIndex), &mut Tab)> {
         self.iter_surfaces_mut()
             .enumerate()
             .flat_map(|(surface_index, surface)| {
                 surface
-                    .iter_all_tabs_mut()
-                    .map(move |(node_index, tab)| ((surface_index.into(), node_index), tab))
+                    .iter_nodes_mut()
+                    .enumerate()
+                    .flat_map(move |(node_index, node)| {
+                        node.iter_tabs_mut()
+                            .enumerate()
+                            .map(move |(tab_index, tab)| {
+                                let location = (
+                                    SurfaceIndex(surface_index),
+                                    NodeIndex(node_index),
+                                    TabIndex(tab_index),
+                                );
+                                (location, tab)
+                            })
+                    })
             })
     }
 
+    /// Takes all tabs out of the dock state along with their locations, leaving its leaves in place without tabs,
+    /// so that the tabs can be updated elsewhere, e.g. on worker threads, without borrowing the whole state.
+    ///
+    /// The tabs have to be put back with [`DockState::restore_tabs`] before the [`DockState`] is shown or changed
+    /// again, as the locations only stay valid until then.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// let mut dock_state = DockState::new(vec![1, 2]);
+    /// dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.5, vec![3]);
+    ///
+    /// let mut tabs = dock_state.take_tabs();
+    /// std::thread::scope(|scope| {
+    ///     for (_, tab) in &mut tabs {
+    ///         scope.spawn(move || *tab *= 10);
+    ///     }
+    /// });
+    /// dock_state.restore_tabs(tabs);
+    ///
+    /// let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| *tab).collect();
+    /// assert_eq!(tabs, vec![10, 20, 30]);
+    /// ```
+    pub fn take_tabs(&mut self) -> Vec<((SurfaceIndex, NodeIndex, TabIndex), Tab)> {
+        let mut tabs = Vec::new();
+        for (surface_index, surface) in self.surfaces.iter_mut().enumerate() {
+            for (node_index, node) in surface.iter_nodes_mut().enumerate() {
+                let Node::Leaf(leaf) = node else {
+                    continue;
+                };
+                tabs.extend(std::mem::take(&mut leaf.tabs).into_iter().enumerate().map(
+                    |(tab_index, tab)| {
+                        let location = (
+                            SurfaceIndex(surface_index),
+                            NodeIndex(node_index),
+                            TabIndex(tab_index),
+                        );
+                        (location, tab)
+                    },
+                ));
+            }
+        }
+        tabs
+    }
+
+    /// Puts the tabs taken out with [`DockState::take_tabs`] back where they were, in any order.
+    ///
+    /// Tabs which are left out are removed from their leaves like with [`DockState::remove_tab`], which removes the
+    /// leaves left without tabs. The other tabs keep their [`TabId`]s, except for the ones given the same location as
+    /// another tab, which get new ones. Tabs whose leaf no longer exists are pushed to the first leaf of the main
+    /// surface.
+    pub fn restore_tabs(
+        &mut self,
+        tabs: impl IntoIterator<Item = ((SurfaceIndex, NodeIndex, TabIndex), Tab)>,
+    ) {
+        let mut tabs: Vec<_> = tabs.into_iter().collect();
+        tabs.sort_by_key(|((surface_index, node_index, tab_index), _)| {
+            (surface_index.0, node_index.0, tab_index.0)
+        });
+
+        // The IDs are collected apart from the tabs, since the ones of the tabs left out have to be dropped.
+        let mut tab_ids: HashMap<(SurfaceIndex, NodeIndex), Vec<TabId>> = HashMap::default();
+        let mut restored_ids = HashSet::default();
+        let mut homeless = Vec::new();
+        for ((surface_index, node_index, tab_index), tab) in tabs {
+            let leaf = self
+                .surfaces
+                .get_mut(surface_index.0)
+                .and_then(Surface::node_tree_mut)
+                .and_then(|tree| tree.nodes.get_mut(node_index.0))
+                .and_then(Node::get_leaf_mut);
+            let Some(leaf) = leaf else {
+                homeless.push(tab);
+                continue;
+            };
+            // A tab restored to the same location as another one is a different tab.
+            let tab_id = leaf
+                .tab_ids
+                .get(tab_index.0)
+                .copied()
+                .filter(|&tab_id| restored_ids.insert(tab_id))
+                .unwrap_or_else(TabId::next);
+            tab_ids
+                .entry((surface_index, node_index))
+                .or_default()
+                .push(tab_id);
+            leaf.tabs.push(tab);
+        }
+
+        let mut emptied = Vec::new();
+        for (surface_index, surface) in self.surfaces.iter_mut().enumerate() {
+            for (node_index, node) in surface.iter_nodes_mut().enumerate() {
+                let Node::Leaf(leaf) = node else {
+                    continue;
+                };
+                let had_tabs = !leaf.tab_ids.is_empty();
+                leaf.tab_ids = tab_ids
+                    .remove(&(SurfaceIndex(surface_index), NodeIndex(node_index)))
+                    .unwrap_or_default();
+                leaf.selected.retain(|tab_id| leaf.tab_ids.contains(tab_id));
+                leaf.active = TabIndex(leaf.active.0.min(leaf.tabs.len().saturating_sub(1)));
+                if had_tabs && leaf.tabs.is_empty() {
+                    emptied.push(leaf.id());
+                }
+            }
+        }
+        for tab in homeless {
+            self.main_surface_mut().push_to_first_leaf(tab);
+        }
+
+        // The leaves are found by their IDs, as removing one may move the others around.
+        for node_id in emptied {
+            let Some((surface_index, node_index)) = self.find_node_by_id(node_id) else {
+                continue;
+            };
+            if self[surface_index][node_index].tabs_count() > 0 {
+                continue;
+            }
+            self.remove_empty_leaf((surface_index, node_index));
+            if !surface_index.is_main() && self[surface_index].is_empty() {
+                self.remove_surface(surface_index);
+            }
+        }
+    }
+
     /// Returns an [`Iterator`] of the underlying collection of nodes on the main surface.
     #[deprecated = "Use `dock_state.main_surface().iter()` instead"]
     pub fn iter_main_surface_nodes(&self) -> impl Iterator<Item = &Node<Tab>> {
@@ -483,6 +2262,39 @@ impl<Tab> DockState<Tab> {
             .flat_map(|nodes| nodes.iter())
     }
 
+    /// Returns an [`Iterator`] of the non-empty nodes of **all** surfaces and the rects they were laid out in,
+    /// e.g. to draw decorations around them after showing the [`DockArea`](crate::DockArea).
+    ///
+    /// See [`Tree::iter_rects`].
+    ///
+    /// ```rust
+    /// # use egui::{pos2, Rect};
+    /// # use egui_dock::{DockState, NodeIndex, Style};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.5, vec!["Console"]);
+    /// dock_state.layout(Rect::from_min_max(pos2(0.0, 0.0), pos2(400.0, 300.0)), &Style::default());
+    ///
+    /// for (surface_index, node_index, rect) in dock_state.iter_all_rects() {
+    ///     if dock_state[surface_index][node_index].is_leaf() {
+    ///         // Draw a focus outline around `rect`.
+    ///     }
+    /// }
+    /// assert_eq!(dock_state.iter_all_rects().count(), 3);
+    /// ```
+    pub fn iter_all_rects(&self) -> impl Iterator<Item = (SurfaceIndex, NodeIndex, Rect)> + '_ {
+        self.iter_surfaces()
+            .enumerate()
+            .filter_map(|(surface_index, surface)| {
+                Some((SurfaceIndex(surface_index), surface.node_tree()?))
+            })
+            .flat_map(|(surface_index, tree)| {
+                tree.iter_rects()
+                    .map(move |(node_index, rect)| (surface_index, node_index, rect))
+            })
+    }
+
     /// Returns an immutable [`Iterator`] of all [``LeafNode``]s in the dock state.
     pub fn iter_leaves(&self) -> impl Iterator<Item = (SurfaceIndex, &LeafNode<Tab>)> {
         self.iter_all_nodes()
@@ -509,26 +2321,175 @@ impl<Tab> DockState<Tab> {
     pub fn filter_map_tabs<F, NewTab>(&self, mut function: F) -> DockState<NewTab>
     where
         F: FnMut(&Tab) -> Option<NewTab>,
+    {
+        self.filter_map_tabs_indexed(move |_, tab| function(tab))
+    }
+
+    /// Returns a new [`DockState`] while mapping and filtering the tab type,
+    /// passing the location each tab had in this [`DockState`] to `function`.
+    /// Any remaining empty [`Node`]s and [`Surface`]s are removed.
+    ///
+    /// ```
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let dock_state = DockState::new(vec!["a", "b"]);
+    /// let mapped_dock_state = dock_state.filter_map_tabs_indexed(|(surface, node, tab_index), tab| {
+    ///     Some(format!("{tab}@{}:{}:{}", surface.0, node.0, tab_index.0))
+    /// });
+    ///
+    /// let tabs: Vec<_> = mapped_dock_state.iter_all_tabs().map(|(_, tab)| tab.to_owned()).collect();
+    /// assert_eq!(tabs, vec!["a@0:0:0".to_string(), "b@0:0:1".to_string()]);
+    /// ```
+    pub fn filter_map_tabs_indexed<F, NewTab>(&self, mut function: F) -> DockState<NewTab>
+    where
+        F: FnMut((SurfaceIndex, NodeIndex, TabIndex), &Tab) -> Option<NewTab>,
     {
         let DockState {
             surfaces,
             focused_surface,
             translations,
+            surface_styles,
+            overview_open,
+            tab_switcher_open,
+            pending_closes,
+            resolved_closes,
+            window_order,
+            window_order_applied: _,
+            raised_window: _,
+            closed_tabs,
+            proportional_leaf_removal,
         } = self;
+        let mut new_surface_styles = HashMap::default();
+        let mut new_indices = HashMap::default();
         let surfaces = surfaces
             .iter()
-            .filter_map(|surface| {
-                let surface = surface.filter_map_tabs(&mut function);
-                (!surface.is_empty()).then_some(surface)
+            .enumerate()
+            .filter_map(|(index, surface)| {
+                let surface = surface.filter_map_tabs_indexed(|(node_index, tab_index), tab| {
+                    function((SurfaceIndex(index), node_index, tab_index), tab)
+                });
+                (!surface.is_empty()).then_some((index, surface))
+            })
+            .enumerate()
+            .map(|(new_index, (index, surface))| {
+                // Empty surfaces are dropped, so the styles and the order of windows need to follow their surfaces.
+                if let Some(style) = surface_styles.get(&SurfaceIndex(index)) {
+                    new_surface_styles.insert(SurfaceIndex(new_index), style.clone());
+                }
+                new_indices.insert(SurfaceIndex(index), SurfaceIndex(new_index));
+                surface
             })
             .collect();
         DockState {
             surfaces,
-            focused_surface: *focused_surface,
+            focused_surface: focused_surface.and_then(|index| new_indices.get(&index).copied()),
             translations: translations.clone(),
+            surface_styles: new_surface_styles,
+            overview_open: *overview_open,
+            tab_switcher_open: *tab_switcher_open,
+            pending_closes: pending_closes.clone(),
+            resolved_closes: resolved_closes.clone(),
+            window_order: window_order
+                .iter()
+                .filter_map(|index| new_indices.get(index).copied())
+                .collect(),
+            window_order_applied: false,
+            raised_window: None,
+            closed_tabs: ClosedTabHistory::new(closed_tabs.capacity()),
+            proportional_leaf_removal: *proportional_leaf_removal,
+        }
+    }
+
+    /// Consumes this [`DockState`] and returns a new one while mapping and filtering the tab type.
+    /// Unlike [`DockState::filter_map_tabs`], the tabs are moved into `function` instead of being borrowed,
+    /// so converting between tab types doesn't require them to be cloned.
+    /// Any remaining empty [`Node`]s and [`Surface`]s are removed.
+    ///
+    /// ```
+    /// # use egui_dock::DockState;
+    /// struct Tab(String);
+    ///
+    /// let dock_state = DockState::new(vec![Tab("a".into()), Tab("b".into())]);
+    /// let mapped_dock_state = dock_state.into_filter_map_tabs(|Tab(title)| (title != "b").then_some(title));
+    ///
+    /// let tabs: Vec<_> = mapped_dock_state.iter_all_tabs().map(|(_, tab)| tab.to_owned()).collect();
+    /// assert_eq!(tabs, vec!["a".to_string()]);
+    /// ```
+    pub fn into_filter_map_tabs<F, NewTab>(self, mut function: F) -> DockState<NewTab>
+    where
+        F: FnMut(Tab) -> Option<NewTab>,
+    {
+        let DockState {
+            surfaces,
+            focused_surface,
+            translations,
+            mut surface_styles,
+            overview_open,
+            tab_switcher_open,
+            pending_closes,
+            resolved_closes,
+            window_order,
+            window_order_applied: _,
+            raised_window: _,
+            closed_tabs,
+            proportional_leaf_removal,
+        } = self;
+        let mut new_surface_styles = HashMap::default();
+        let mut new_indices = HashMap::default();
+        let surfaces = surfaces
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, surface)| {
+                let surface = surface.into_filter_map_tabs(&mut function);
+                (!surface.is_empty()).then_some((index, surface))
+            })
+            .enumerate()
+            .map(|(new_index, (index, surface))| {
+                // Empty surfaces are dropped, so the styles and the order of windows need to follow their surfaces.
+                if let Some(style) = surface_styles.remove(&SurfaceIndex(index)) {
+                    new_surface_styles.insert(SurfaceIndex(new_index), style);
+                }
+                new_indices.insert(SurfaceIndex(index), SurfaceIndex(new_index));
+                surface
+            })
+            .collect();
+        DockState {
+            surfaces,
+            focused_surface: focused_surface.and_then(|index| new_indices.get(&index).copied()),
+            translations,
+            surface_styles: new_surface_styles,
+            overview_open,
+            tab_switcher_open,
+            pending_closes,
+            resolved_closes,
+            window_order: window_order
+                .iter()
+                .filter_map(|index| new_indices.get(index).copied())
+                .collect(),
+            window_order_applied: false,
+            raised_window: None,
+            closed_tabs: ClosedTabHistory::new(closed_tabs.capacity()),
+            proportional_leaf_removal,
         }
     }
 
+    /// Consumes this [`DockState`] and returns a new one while mapping the tab type.
+    /// Unlike [`DockState::map_tabs`], the tabs are moved into `function` instead of being borrowed.
+    ///
+    /// ```
+    /// # use egui_dock::DockState;
+    /// let dock_state = DockState::new(vec![String::from("a"), String::from("b")]);
+    /// let mapped_dock_state = dock_state.into_map_tabs(|title| title.into_bytes());
+    ///
+    /// let tabs: Vec<_> = mapped_dock_state.iter_all_tabs().map(|(_, tab)| tab.to_owned()).collect();
+    /// assert_eq!(tabs, vec![b"a".to_vec(), b"b".to_vec()]);
+    /// ```
+    pub fn into_map_tabs<F, NewTab>(self, mut function: F) -> DockState<NewTab>
+    where
+        F: FnMut(Tab) -> NewTab,
+    {
+        self.into_filter_map_tabs(move |tab| Some(function(tab)))
+    }
+
     /// Returns a new [`DockState`] while mapping the tab type.
     ///
     /// ```
@@ -580,11 +2541,49 @@ impl<Tab> DockState<Tab> {
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        let mut main_surface = true;
-        self.surfaces.retain_mut(|surface| {
-            surface.retain_tabs(&mut predicate);
-            std::mem::take(&mut main_surface) || !surface.is_empty()
-        });
+        self.retain_tabs_with(|_, tab| predicate(tab));
+    }
+
+    /// Removes all tabs for which `predicate` returns `false`,
+    /// passing the location each tab had before any of them were removed to `predicate`.
+    /// Any remaining empty [`Node`]s and [`Surface`]s are also removed.
+    ///
+    /// Returns the removed tabs, so that the resources they hold (open files, textures, etc.) can be released.
+    ///
+    /// ```
+    /// # use egui_dock::{DockState, NodeIndex, SurfaceIndex, TabIndex};
+    /// let mut dock_state = DockState::new(["tab1", "tab2", "outlier"].map(str::to_string).to_vec());
+    /// let removed = dock_state.retain_tabs_with(|(_, _, tab_index), _| tab_index != TabIndex(2));
+    ///
+    /// assert_eq!(removed, vec!["outlier".to_string()]);
+    /// let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| tab.to_owned()).collect();
+    /// assert_eq!(tabs, vec!["tab1".to_string(), "tab2".to_string()]);
+    /// ```
+    pub fn retain_tabs_with<F>(&mut self, mut predicate: F) -> Vec<Tab>
+    where
+        F: FnMut((SurfaceIndex, NodeIndex, TabIndex), &mut Tab) -> bool,
+    {
+        let mut removed = Vec::new();
+        let mut emptied = Vec::new();
+        for surface_index in self.valid_surface_indices() {
+            let surface = &mut self.surfaces[surface_index.0];
+            removed.extend(surface.retain_tabs_with(|(node_index, tab_index), tab| {
+                predicate((surface_index, node_index, tab_index), tab)
+            }));
+            if !surface_index.is_main() && surface.is_empty() {
+                emptied.push(surface_index);
+            }
+        }
+
+        // Emptied windows are removed one by one, so that the indices of the other surfaces stay the same.
+        let focused_surface = self.focused_surface;
+        for &surface_index in emptied.iter().rev() {
+            self.remove_surface(surface_index);
+        }
+        if focused_surface.is_some_and(|surface| !emptied.contains(&surface)) {
+            self.focused_surface = focused_surface;
+        }
+        removed
     }
 
     /// Find a tab based on the conditions of a functino.
@@ -643,6 +2642,28 @@ where
 mod test {
     use super::*;
 
+    #[test]
+    fn split_tab_respects_the_allowed_splits_of_the_leaf() {
+        let mut dock_state = DockState::new(vec!["a", "b"]);
+        let tab = (SurfaceIndex::main(), NodeIndex::root(), TabIndex(1));
+        dock_state.set_allowed_splits(
+            SurfaceIndex::main(),
+            NodeIndex::root(),
+            AllowedSplits::TopBottomOnly,
+        );
+
+        assert_eq!(
+            dock_state.split_tab(tab, Split::Right, SplitBehavior::Move),
+            None
+        );
+        assert_eq!(dock_state.main_surface().num_tabs(), 2);
+        assert_eq!(dock_state.main_surface().len(), 1);
+
+        let new_leaf = dock_state.split_tab(tab, Split::Below, SplitBehavior::Move);
+        assert_eq!(new_leaf, Some(NodeIndex(2)));
+        assert!(dock_state.main_surface()[NodeIndex::root()].is_vertical());
+    }
+
     #[test]
     fn retain_none_then_push() {
         let mut t = DockState::new(vec![]);
@@ -652,4 +2673,777 @@ mod test {
         t.retain_tabs(|_| false);
         t.push_to_focused_leaf(0);
     }
+
+    #[test]
+    fn tab_ids_follow_tabs() {
+        let mut t = DockState::new(vec![0, 1, 2]);
+        let ids: Vec<_> = (0..3)
+            .map(|i| {
+                t.tab_id((SurfaceIndex::main(), NodeIndex::root(), TabIndex(i)))
+                    .unwrap()
+            })
+            .collect();
+        t.move_tab(
+            ids[0],
+            TabDestination::Node(SurfaceIndex::main(), NodeIndex::root(), TabInsert::Append),
+        );
+        t.retain_tabs(|tab| *tab != 1);
+        assert_eq!(
+            t.find_by_id(ids[0]),
+            Some((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)))
+        );
+        assert_eq!(t.find_by_id(ids[1]), None);
+        assert_eq!(t.remove_tab(ids[2]), Some(2));
+
+        // Tabs pushed directly onto a leaf get an ID once they're synced.
+        let leaf = t.main_surface_mut()[NodeIndex::root()]
+            .get_leaf_mut()
+            .unwrap();
+        leaf.tabs.push(3);
+        leaf.sync_tab_ids();
+        assert_eq!(leaf.tab_ids().len(), 2);
+        assert_ne!(leaf.tab_id(TabIndex(1)), Some(ids[0]));
+    }
+
+    #[test]
+    fn layout_matches_splits() {
+        let mut split = DockState::new(vec![0]);
+        let [_, right] = split
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.2, vec![1, 2]);
+        split.main_surface_mut().split_below(right, 0.7, vec![3]);
+
+        let mut layout = DockState::from(DockLayout::horizontal(
+            0.2,
+            crate::leaf![0],
+            DockLayout::vertical(0.7, crate::leaf![1, 2], crate::leaf![3]),
+        ));
+        let tabs = |t: &DockState<i32>| {
+            let tree = t.main_surface();
+            (0..tree.len())
+                .map(|i| tree[NodeIndex(i)].tabs().map(<[i32]>::to_vec))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tabs(&layout), tabs(&split));
+
+        // The tree can be rearranged like any other.
+        layout
+            .main_surface_mut()
+            .split_left(NodeIndex(6), 0.5, vec![4]);
+        assert_eq!(
+            layout.find_tab(&4).map(|(_, node, _)| node),
+            Some(NodeIndex(13))
+        );
+    }
+
+    #[test]
+    fn selection_moves_together() {
+        let mut dock_state = DockState::new(vec![0, 1, 2, 3]);
+        let leaf = dock_state.main_surface_mut()[NodeIndex::root()]
+            .get_leaf_mut()
+            .unwrap();
+        leaf.set_tab_selected(1, true);
+        leaf.set_tab_selected(3, true);
+        let ids = leaf.tab_ids().to_vec();
+
+        // A tab which isn't selected is moved on its own.
+        dock_state.move_tab_with_selection(
+            (SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)),
+            TabDestination::Node(SurfaceIndex::main(), NodeIndex::root(), TabInsert::Append),
+        );
+        dock_state.move_tab_with_selection(
+            (SurfaceIndex::main(), NodeIndex::root(), TabIndex(2)),
+            TabDestination::Node(
+                SurfaceIndex::main(),
+                NodeIndex::root(),
+                TabInsert::Split(Split::Below),
+            ),
+        );
+        let tree = dock_state.main_surface();
+        assert_eq!(tree[NodeIndex(1)].tabs(), Some([2, 0].as_slice()));
+        assert_eq!(tree[NodeIndex(2)].tabs(), Some([1, 3].as_slice()));
+
+        let leaf = tree[NodeIndex(2)].get_leaf().unwrap();
+        assert_eq!(leaf.tab_ids(), [ids[1], ids[3]]);
+        assert_eq!(leaf.selected_tabs(), [TabIndex(0), TabIndex(1)]);
+    }
+
+    #[test]
+    fn move_leaf_keeps_tabs_together() {
+        let mut dock_state = DockState::new(vec![0]);
+        let [_, right] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.5, vec![1, 2, 3]);
+        dock_state.set_active_tab((SurfaceIndex::main(), right, TabIndex(1)));
+        dock_state.main_surface_mut()[right]
+            .get_leaf_mut()
+            .unwrap()
+            .set_tab_selected(2, true);
+
+        // Dropping a leaf onto itself does nothing.
+        dock_state.move_leaf(
+            (SurfaceIndex::main(), right),
+            TabDestination::Node(SurfaceIndex::main(), right, TabInsert::Append),
+        );
+        assert_eq!(
+            dock_state.main_surface()[right].tabs(),
+            Some([1, 2, 3].as_slice())
+        );
+
+        dock_state.move_leaf(
+            (SurfaceIndex::main(), right),
+            TabDestination::Node(
+                SurfaceIndex::main(),
+                NodeIndex(1),
+                TabInsert::Insert(TabIndex(0)),
+            ),
+        );
+        let tree = dock_state.main_surface();
+        assert_eq!(tree.num_tabs(), 4);
+        let leaf = tree[NodeIndex::root()].get_leaf().unwrap();
+        assert_eq!(leaf.tabs(), [1, 2, 3, 0]);
+        assert_eq!(leaf.active, TabIndex(1));
+        assert_eq!(leaf.selected_tabs(), [TabIndex(2)]);
+    }
+
+    #[test]
+    fn window_order_follows_windows() {
+        let mut dock_state = DockState::new(vec![0]);
+        let first = dock_state.add_window(vec![1]);
+        let second = dock_state.add_window(vec![2]);
+        let third = dock_state.add_window(vec![3]);
+        assert_eq!(dock_state.window_order(), [first, second, third]);
+
+        dock_state.bring_window_to_front(first);
+        assert_eq!(dock_state.window_order(), [second, third, first]);
+        assert_eq!(dock_state.focused_leaf(), Some((first, NodeIndex::root())));
+
+        // The main surface isn't a window.
+        dock_state.bring_window_to_front(SurfaceIndex::main());
+        assert_eq!(dock_state.window_order(), [second, third, first]);
+
+        dock_state.remove_surface(third);
+        assert_eq!(dock_state.window_order(), [second, first]);
+
+        // Windows are renumbered when the empty surfaces are dropped.
+        let filtered = dock_state.filter_tabs(|_| true);
+        assert_eq!(filtered.window_order(), [SurfaceIndex(2), SurfaceIndex(1)]);
+    }
+
+    #[test]
+    fn modal_windows() {
+        let mut dock_state = DockState::new(vec![0]);
+        let palette = dock_state.add_window(vec![1]);
+        let dialog = dock_state.add_window(vec![2]);
+        assert_eq!(dock_state.modal_window(), None);
+
+        dock_state
+            .get_window_state_mut(palette)
+            .unwrap()
+            .set_modal(true);
+        assert_eq!(dock_state.modal_window(), Some(palette));
+        dock_state
+            .get_window_state_mut(dialog)
+            .unwrap()
+            .set_modal(true);
+        dock_state.bring_window_to_front(palette);
+        assert_eq!(dock_state.modal_window(), Some(palette));
+
+        dock_state.remove_surface(palette);
+        assert_eq!(dock_state.modal_window(), Some(dialog));
+    }
+
+    #[test]
+    fn detach_node_from_window() {
+        let mut dock_state = DockState::new(vec![0]);
+        let window = dock_state.add_window(vec![1]);
+        let [_, below] = dock_state[window].split_below(NodeIndex::root(), 0.5, vec![2, 3]);
+        dock_state.set_active_tab((window, below, TabIndex(1)));
+
+        let rect = Rect::from_min_size(Pos2::ZERO, egui::vec2(200.0, 100.0));
+        let new_window = dock_state.detach_node_to_window((window, below), rect);
+        assert_ne!(new_window, window);
+        assert_eq!(dock_state[window].num_tabs(), 1);
+        let leaf = dock_state[new_window][NodeIndex::root()]
+            .get_leaf()
+            .unwrap();
+        assert_eq!(leaf.tabs(), [2, 3]);
+        assert_eq!(leaf.active, TabIndex(1));
+        assert_eq!(
+            dock_state.focused_leaf(),
+            Some((new_window, NodeIndex::root()))
+        );
+        assert_eq!(dock_state.window_order().last(), Some(&new_window));
+    }
+
+    #[test]
+    fn transfer_tab_between_states() {
+        let mut source = DockState::new(vec![0]);
+        let window = source.add_window(vec![1]);
+        let mut target = DockState::new(vec![2]);
+        let tab_id = source.tab_id((window, NodeIndex::root(), TabIndex(0)));
+
+        let location = source.transfer_tab(
+            &mut target,
+            (window, NodeIndex::root(), TabIndex(0)),
+            TabDestination::Node(
+                SurfaceIndex::main(),
+                NodeIndex::root(),
+                TabInsert::Split(Split::Right),
+            ),
+        );
+        assert_eq!(
+            location,
+            Some((SurfaceIndex::main(), NodeIndex(2), TabIndex(0)))
+        );
+        // The window left empty is removed.
+        assert!(!source.is_surface_valid(window));
+        assert_eq!(
+            target.focused_leaf(),
+            Some((SurfaceIndex::main(), NodeIndex(2)))
+        );
+        assert_eq!(target.find_by_id(tab_id.unwrap()), location);
+
+        // Transferring a tab which doesn't exist does nothing.
+        let missing = source.transfer_tab(
+            &mut target,
+            (window, NodeIndex::root(), TabIndex(0)),
+            TabDestination::Window(Rect::NOTHING),
+        );
+        assert_eq!(missing, None);
+        assert_eq!(target.main_surface().num_tabs(), 2);
+    }
+
+    #[test]
+    fn filter_map_tabs_indexed_records_locations() {
+        let mut dock_state = DockState::new(vec![0, 1]);
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec![2]);
+        let dropped_window = dock_state.add_window(vec![3]);
+        let kept_window = dock_state.add_window(vec![4]);
+
+        let mut locations = Vec::new();
+        let mapped = dock_state.filter_map_tabs_indexed(|location, tab| {
+            locations.push((location, *tab));
+            (*tab != 3).then_some(*tab)
+        });
+        assert_eq!(
+            locations,
+            vec![
+                ((SurfaceIndex::main(), NodeIndex(1), TabIndex(0)), 0),
+                ((SurfaceIndex::main(), NodeIndex(1), TabIndex(1)), 1),
+                ((SurfaceIndex::main(), NodeIndex(2), TabIndex(0)), 2),
+                ((dropped_window, NodeIndex::root(), TabIndex(0)), 3),
+                ((kept_window, NodeIndex::root(), TabIndex(0)), 4),
+            ]
+        );
+        assert_eq!(mapped.find_tab(&3), None);
+        assert_eq!(mapped.iter_all_tabs().count(), 4);
+    }
+
+    #[test]
+    fn into_map_tabs_moves_tabs() {
+        // Deliberately not `Clone`.
+        #[derive(Debug, PartialEq)]
+        struct Tab(u32);
+
+        let mut dock_state = DockState::new(vec![Tab(0), Tab(1)]);
+        dock_state
+            .main_surface_mut()
+            .split_below(NodeIndex::root(), 0.5, vec![Tab(2)]);
+        let window = dock_state.add_window(vec![Tab(3)]);
+        let tab_id = dock_state.tab_id((window, NodeIndex::root(), TabIndex(0)));
+
+        let mapped = dock_state.into_filter_map_tabs(|Tab(n)| (n != 2).then_some(n));
+        let tabs: Vec<_> = mapped.iter_all_tabs().map(|(_, tab)| *tab).collect();
+        assert_eq!(tabs, vec![0, 1, 3]);
+        // The leaf which lost all of its tabs was removed, and the tabs kept their IDs.
+        assert!(mapped.main_surface()[NodeIndex::root()].is_leaf());
+        assert_eq!(
+            mapped.find_by_id(tab_id.unwrap()),
+            Some((window, NodeIndex::root(), TabIndex(0)))
+        );
+        assert_eq!(mapped.window_order(), &[window]);
+    }
+
+    #[test]
+    fn retain_tabs_with_returns_removed_tabs() {
+        let mut dock_state = DockState::new(vec![0, 1]);
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec![2, 3]);
+        let window = dock_state.add_window(vec![4]);
+
+        let mut locations = Vec::new();
+        let removed = dock_state.retain_tabs_with(|location, tab| {
+            locations.push(location);
+            *tab % 2 == 1
+        });
+        assert_eq!(removed, vec![0, 2, 4]);
+        assert_eq!(
+            locations,
+            vec![
+                (SurfaceIndex::main(), NodeIndex(1), TabIndex(0)),
+                (SurfaceIndex::main(), NodeIndex(1), TabIndex(1)),
+                (SurfaceIndex::main(), NodeIndex(2), TabIndex(0)),
+                (SurfaceIndex::main(), NodeIndex(2), TabIndex(1)),
+                (window, NodeIndex::root(), TabIndex(0)),
+            ]
+        );
+        let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| *tab).collect();
+        assert_eq!(tabs, vec![1, 3]);
+    }
+
+    #[test]
+    fn add_tabs_focus_policies() {
+        let mut dock_state = DockState::new(vec![0, 1]);
+        dock_state.set_active_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)));
+        let [_, right] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.5, vec![10]);
+        dock_state.set_focused_node_and_surface((SurfaceIndex::main(), right));
+        let active = |dock_state: &DockState<i32>, node: NodeIndex| {
+            let leaf = dock_state.main_surface()[node].get_leaf().unwrap();
+            leaf.tabs[leaf.active.0]
+        };
+
+        // Inserting before the active tab keeps it active, and the focus stays on the other leaf.
+        let (_, left, _) = dock_state.find_tab(&0).unwrap();
+        let location = dock_state.add_tabs(
+            (SurfaceIndex::main(), left, TabInsert::Insert(TabIndex(0))),
+            vec![2, 3],
+            FocusPolicy::None,
+        );
+        assert_eq!(location, Some((SurfaceIndex::main(), left)));
+        assert_eq!(
+            dock_state.main_surface()[left].tabs(),
+            Some([2, 3, 0, 1].as_slice())
+        );
+        assert_eq!(active(&dock_state, left), 1);
+        assert_eq!(
+            dock_state.focused_leaf(),
+            Some((SurfaceIndex::main(), right))
+        );
+
+        // Splitting moves the focused leaf, which keeps the focus anyway.
+        let (_, new) = dock_state
+            .add_tabs(
+                (SurfaceIndex::main(), right, TabInsert::Split(Split::Below)),
+                vec![20, 21],
+                FocusPolicy::None,
+            )
+            .unwrap();
+        let (_, right, _) = dock_state.find_tab(&10).unwrap();
+        assert_eq!(
+            dock_state.focused_leaf(),
+            Some((SurfaceIndex::main(), right))
+        );
+        assert_eq!(active(&dock_state, new), 20);
+
+        dock_state.add_tabs(
+            (SurfaceIndex::main(), right, TabInsert::Append),
+            vec![11, 12],
+            FocusPolicy::Last,
+        );
+        assert_eq!(active(&dock_state, right), 12);
+
+        let window = dock_state
+            .add_tabs(
+                TabDestination::Window(Rect::NOTHING),
+                vec![30, 31],
+                FocusPolicy::First,
+            )
+            .unwrap();
+        assert_eq!(dock_state.focused_leaf(), Some(window));
+        assert_eq!(
+            dock_state.find_active_focused().map(|(_, tab)| *tab),
+            Some(30)
+        );
+
+        assert_eq!(
+            dock_state.add_tabs(window.0, Vec::new(), FocusPolicy::First),
+            None
+        );
+    }
+
+    #[test]
+    fn session_restore_drops_unresolved_tabs() {
+        let mut dock_state = DockState::new(vec![0, 1]);
+        let window = dock_state.add_window(vec![2]);
+        let kept_window = dock_state.add_window(vec![3, 4]);
+        let last_window = dock_state.add_window(vec![6]);
+        dock_state.bring_window_to_front(kept_window);
+        dock_state.set_focused_node_and_surface((last_window, NodeIndex::root()));
+        let tab_id = dock_state.tab_id((kept_window, NodeIndex::root(), TabIndex(1)));
+
+        let session = SessionRestore::capture(&dock_state, |tab| tab.to_string());
+        let (restored, unresolved) = session.restore(|key| {
+            let tab: i32 = key.parse().unwrap();
+            (tab % 2 == 0).then_some(tab)
+        });
+        assert_eq!(unresolved, vec!["1".to_string(), "3".to_string()]);
+        let tabs: Vec<_> = restored.iter_all_tabs().map(|(_, tab)| *tab).collect();
+        assert_eq!(tabs, vec![0, 2, 4, 6]);
+        assert_eq!(
+            restored.find_by_id(tab_id.unwrap()),
+            Some((kept_window, NodeIndex::root(), TabIndex(0)))
+        );
+
+        // A window without any resolved tabs is removed, and the windows after it take its place.
+        let (restored, _) = SessionRestore::capture(&restored, |tab| *tab)
+            .restore(|&tab| (tab != 2).then_some(tab));
+        let tabs: Vec<_> = restored
+            .iter_all_tabs()
+            .map(|((surface_index, _, _), tab)| (surface_index, *tab))
+            .collect();
+        assert_eq!(
+            tabs,
+            vec![
+                (SurfaceIndex::main(), 0),
+                (SurfaceIndex(1), 4),
+                (SurfaceIndex(2), 6)
+            ]
+        );
+        assert_eq!(
+            restored.find_by_id(tab_id.unwrap()),
+            Some((window, NodeIndex::root(), TabIndex(0)))
+        );
+        assert_eq!(restored.window_order(), [SurfaceIndex(2), SurfaceIndex(1)]);
+        assert_eq!(
+            restored.focused_leaf(),
+            Some((SurfaceIndex(2), NodeIndex::root()))
+        );
+    }
+
+    #[test]
+    fn float_and_redock_restore_the_layout() {
+        let rect = Rect::from_min_size(Pos2::ZERO, egui::vec2(200.0, 100.0));
+        let mut dock_state = DockState::new(vec![0, 1, 2]);
+        let [_, bottom] =
+            dock_state
+                .main_surface_mut()
+                .split_below(NodeIndex::root(), 0.6, vec![3]);
+
+        // A tab sharing its leaf goes back to its position among the other tabs.
+        let window = dock_state.float_tab((SurfaceIndex::main(), NodeIndex(1), TabIndex(1)), rect);
+        assert_eq!(
+            dock_state
+                .get_window_state(window)
+                .unwrap()
+                .restore_location(),
+            dock_state
+                .node_id((SurfaceIndex::main(), NodeIndex(1)))
+                .map(|node_id| RestoreLocation::Leaf(node_id, TabIndex(1)))
+        );
+        assert_eq!(
+            dock_state.redock_window(window),
+            Some((SurfaceIndex::main(), NodeIndex(1)))
+        );
+        assert_eq!(
+            dock_state[SurfaceIndex::main()][NodeIndex(1)].tabs(),
+            Some([0, 1, 2].as_slice())
+        );
+
+        // A lonely tab gets its leaf back, with the same fraction.
+        let window = dock_state.float_tab((SurfaceIndex::main(), bottom, TabIndex(0)), rect);
+        assert_eq!(dock_state.main_surface().num_tabs(), 3);
+        assert_eq!(
+            dock_state.redock_window(window),
+            Some((SurfaceIndex::main(), bottom))
+        );
+        assert!(!dock_state.is_surface_valid(window));
+        assert_eq!(
+            dock_state.main_surface()[bottom].tabs(),
+            Some([3].as_slice())
+        );
+        let Node::Vertical(split) = &dock_state.main_surface()[NodeIndex::root()] else {
+            panic!("the root should be split vertically");
+        };
+        assert_eq!(split.fraction, 0.6);
+
+        // Windows which weren't floated can't be re-docked.
+        let window = dock_state.add_window(vec![4]);
+        assert_eq!(dock_state.redock_window(window), None);
+    }
+
+    #[test]
+    fn reopen_last_closed_restores_tabs_in_order() {
+        let mut dock_state = DockState::new(vec![0]);
+        assert!(dock_state.close_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0))));
+        assert!(dock_state.closed_tabs().is_empty());
+        assert_eq!(dock_state.reopen_last_closed(), None);
+
+        let mut dock_state = DockState::new(vec![0]);
+        dock_state.closed_tabs_mut().set_capacity(4);
+        let [_, right] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.3, vec![1, 2, 3]);
+        let window = dock_state.add_window(vec![4]);
+
+        // The whole leaf is closed and comes back with its tabs in order, at the same place.
+        dock_state.close_leaf((SurfaceIndex::main(), right));
+        assert_eq!(dock_state.main_surface().num_tabs(), 1);
+        for _ in 0..3 {
+            dock_state.reopen_last_closed();
+        }
+        assert_eq!(
+            dock_state.main_surface()[right].tabs(),
+            Some([1, 2, 3].as_slice())
+        );
+        let Node::Horizontal(split) = &dock_state.main_surface()[NodeIndex::root()] else {
+            panic!("the root should be split horizontally");
+        };
+        assert_eq!(split.fraction, 0.3);
+
+        // A closed window is reopened as a new window.
+        dock_state.close_tab((window, NodeIndex::root(), TabIndex(0)));
+        assert!(!dock_state.is_surface_valid(window));
+        let (surface, _, _) = dock_state.reopen_last_closed().unwrap();
+        assert!(!surface.is_main());
+        assert_eq!(dock_state[surface].num_tabs(), 1);
+
+        // Only the most recently closed tabs are kept.
+        for _ in 0..3 {
+            dock_state.close_tab((SurfaceIndex::main(), right, TabIndex(0)));
+        }
+        dock_state.close_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)));
+        dock_state.close_tab((surface, NodeIndex::root(), TabIndex(0)));
+        assert_eq!(dock_state.closed_tabs().len(), 4);
+        let closed: Vec<_> = dock_state
+            .closed_tabs()
+            .iter()
+            .map(|closed| *closed.tab())
+            .collect();
+        assert_eq!(closed, vec![4, 0, 3, 2]);
+    }
+
+    #[test]
+    fn proportional_leaf_removal_keeps_ratios() {
+        let mut dock_state = DockState::new(vec![0]);
+        let [_, middle] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.5, vec![1]);
+        let [middle, _] = dock_state
+            .main_surface_mut()
+            .split_right(middle, 0.5, vec![2]);
+        dock_state.set_proportional_leaf_removal(true);
+
+        // The left and right panels are twice as wide as the middle one was, and keep their ratio of 2:1.
+        dock_state.remove_tab((SurfaceIndex::main(), middle, TabIndex(0)));
+        let Node::Horizontal(split) = &dock_state.main_surface()[NodeIndex::root()] else {
+            panic!("the root should still be a horizontal split");
+        };
+        assert!((split.fraction - 2.0 / 3.0).abs() < 1e-5);
+        assert_eq!(dock_state.main_surface().num_tabs(), 2);
+    }
+
+    #[test]
+    fn layout_respects_min_sizes() {
+        let mut dock_state = DockState::new(vec![0]);
+        let [left, rest] =
+            dock_state
+                .main_surface_mut()
+                .split_right(NodeIndex::root(), 0.5, vec![1]);
+        let [middle, right] = dock_state
+            .main_surface_mut()
+            .split_right(rest, 0.5, vec![2]);
+        let mut style = Style::default();
+        style.separator.width = 0.0;
+        let width = |tree: &Tree<i32>, node: NodeIndex| tree[node].rect().unwrap().width();
+
+        let tree = dock_state.main_surface_mut();
+        tree[left]
+            .get_leaf_mut()
+            .unwrap()
+            .set_min_size(egui::vec2(150.0, 0.0));
+        tree.layout(
+            Rect::from_min_size(Pos2::ZERO, egui::vec2(200.0, 100.0)),
+            &style,
+        );
+        assert_eq!(width(tree, left), 150.0);
+        assert_eq!(width(tree, middle), 25.0);
+        assert_eq!(width(tree, right), 25.0);
+
+        // The fractions aren't changed, so the layout comes back once there's room.
+        tree.layout(
+            Rect::from_min_size(Pos2::ZERO, egui::vec2(400.0, 100.0)),
+            &style,
+        );
+        assert_eq!(width(tree, left), 200.0);
+
+        // Without room for all minimum sizes, the nodes shrink in proportion to them.
+        tree[middle]
+            .get_leaf_mut()
+            .unwrap()
+            .set_min_size(egui::vec2(100.0, 0.0));
+        tree[right]
+            .get_leaf_mut()
+            .unwrap()
+            .set_min_size(egui::vec2(100.0, 0.0));
+        tree.layout(
+            Rect::from_min_size(Pos2::ZERO, egui::vec2(175.0, 100.0)),
+            &style,
+        );
+        assert_eq!(width(tree, left), 75.0);
+        assert_eq!(width(tree, middle), 50.0);
+
+        // Or the side with the smaller share is collapsed.
+        style.separator.min_size_overflow = crate::MinSizeOverflow::Collapse;
+        tree.layout(
+            Rect::from_min_size(Pos2::ZERO, egui::vec2(175.0, 100.0)),
+            &style,
+        );
+        assert_eq!(width(tree, left), 175.0);
+        assert_eq!(width(tree, rest), 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn runtime_state_round_trips() {
+        let mut dock_state = DockState::new(vec![0, 1, 2]);
+        let [_, bottom] =
+            dock_state
+                .main_surface_mut()
+                .split_below(NodeIndex::root(), 0.5, vec![3]);
+        let first = dock_state.add_window(vec![4]);
+        let second = dock_state.add_window(vec![5]);
+        dock_state.bring_window_to_front(first);
+        dock_state.set_active_tab((SurfaceIndex::main(), NodeIndex(1), TabIndex(2)));
+        dock_state.set_focused_node_and_surface((SurfaceIndex::main(), bottom));
+        let tree = dock_state.main_surface_mut();
+        tree[NodeIndex(1)].get_leaf_mut().unwrap().scroll = 12.0;
+        tree[bottom].set_collapsed(true);
+        tree.node_update_collapsed(bottom);
+
+        let json = serde_json::to_string(&dock_state).unwrap();
+        let mut loaded: DockState<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.focused_leaf(), Some((SurfaceIndex::main(), bottom)));
+        assert_eq!(loaded.window_order(), [second, first]);
+        let leaf = loaded.main_surface()[NodeIndex(1)].get_leaf().unwrap();
+        assert_eq!((leaf.active, leaf.scroll), (TabIndex(2), 12.0));
+        assert!(loaded.main_surface()[bottom].is_collapsed());
+
+        loaded.strip_runtime_state();
+        assert_eq!(loaded.focused_leaf(), None);
+        assert!(loaded.window_order().is_empty());
+        let leaf = loaded.main_surface()[NodeIndex(1)].get_leaf().unwrap();
+        assert_eq!((leaf.active, leaf.scroll), (TabIndex(0), 0.0));
+        assert!(!loaded.main_surface()[bottom].is_collapsed());
+        assert_eq!(loaded.main_surface().collapsed_leaf_count(), 0);
+    }
+
+    #[test]
+    fn restoring_taken_tabs_keeps_their_ids() {
+        let mut dock_state = DockState::new(vec![0, 1, 2]);
+        let window = dock_state.add_window(vec![3]);
+        let ids: Vec<_> = (0..3)
+            .map(|i| dock_state.tab_id((SurfaceIndex::main(), NodeIndex::root(), TabIndex(i))))
+            .collect();
+
+        let mut tabs = dock_state.take_tabs();
+        assert_eq!(tabs.len(), 4);
+        assert_eq!(dock_state.iter_all_tabs().count(), 0);
+
+        // Tabs can come back in any order, and the ones left out are removed.
+        tabs.reverse();
+        tabs.retain(|(_, tab)| *tab != 1);
+        dock_state.restore_tabs(tabs);
+        let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| *tab).collect();
+        assert_eq!(tabs, vec![0, 2, 3]);
+        let location = |id: Option<TabId>| dock_state.find_by_id(id.unwrap());
+        assert_eq!(
+            location(ids[2]),
+            Some((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)))
+        );
+        assert_eq!(location(ids[1]), None);
+        assert_eq!(dock_state[window].num_tabs(), 1);
+    }
+
+    #[test]
+    fn restoring_taken_tabs_removes_emptied_leaves_and_selections() {
+        let mut dock_state = DockState::new(vec![0, 1, 2]);
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, vec![3]);
+        let window = dock_state.add_window(vec![4]);
+        let leaf = dock_state.main_surface_mut()[NodeIndex(1)]
+            .get_leaf_mut()
+            .unwrap();
+        leaf.set_tab_selected(1, true);
+        leaf.set_tab_selected(2, true);
+
+        // The leaf of 3 and the window of 4 are emptied, and 1 is given the location of 0.
+        let mut tabs = dock_state.take_tabs();
+        tabs.retain(|(_, tab)| *tab == 2);
+        let first = (SurfaceIndex::main(), NodeIndex(1), TabIndex(0));
+        tabs.extend([(first, 1), (first, 0)]);
+        dock_state.restore_tabs(tabs);
+
+        assert!(!dock_state.is_surface_valid(window));
+        assert_eq!(dock_state.main_surface().len(), 1);
+
+        // The first tab given a location keeps the ID of the tab which was there.
+        let leaf = dock_state.main_surface()[NodeIndex::root()]
+            .get_leaf()
+            .unwrap();
+        assert_eq!(leaf.tabs, [1, 0, 2]);
+        assert_ne!(leaf.tab_ids[0], leaf.tab_ids[1]);
+        assert_eq!(leaf.selected_tabs(), [TabIndex(2)]);
+        assert_eq!(leaf.selected.len(), 1);
+    }
+
+    #[test]
+    fn moving_a_tab_to_a_split_of_the_whole_surface() {
+        let mut dock_state = DockState::new(vec!["a"]);
+        let surface = dock_state.main_surface_mut();
+        let [_, right] = surface.split_right(NodeIndex::root(), 0.5, vec!["b"]);
+        surface.split_below(right, 0.5, vec!["c"]);
+
+        // "c" is under the root, so splitting the root moves its leaf before it's removed.
+        let c = dock_state.find_tab(&"c").unwrap();
+        dock_state.move_tab(
+            c,
+            TabDestination::Node(
+                SurfaceIndex::main(),
+                NodeIndex::root(),
+                TabInsert::Split(Split::Below),
+            ),
+        );
+        let tree = dock_state.main_surface();
+        assert!(tree[NodeIndex::root()].is_vertical());
+        assert_eq!(
+            tree[NodeIndex::root().right()].tabs(),
+            Some(["c"].as_slice())
+        );
+        let top = NodeIndex::root().left();
+        assert_eq!(tree[top.left()].tabs(), Some(["a"].as_slice()));
+        assert_eq!(tree[top.right()].tabs(), Some(["b"].as_slice()));
+        assert_eq!(tree.num_tabs(), 3);
+        assert_eq!(tree.iter().filter(|node| node.is_leaf()).count(), 3);
+    }
+
+    #[test]
+    fn retaining_tabs_keeps_the_indices_of_other_windows() {
+        let mut dock_state = DockState::new(vec![0]);
+        let first = dock_state.add_window(vec![1]);
+        let second = dock_state.add_window(vec![2]);
+        dock_state.set_surface_style(second, Style::default());
+        dock_state.set_focused_node_and_surface((second, NodeIndex::root()));
+
+        let removed = dock_state.retain_tabs_with(|_, tab| *tab != 1);
+        assert_eq!(removed, vec![1]);
+        assert!(!dock_state.is_surface_valid(first));
+        assert_eq!(
+            dock_state[second][NodeIndex::root()].tabs(),
+            Some([2].as_slice())
+        );
+        assert_eq!(dock_state.window_order(), [second]);
+        assert!(dock_state.surface_style(second).is_some());
+        assert_eq!(dock_state.focused_leaf(), Some((second, NodeIndex::root())));
+    }
 }
@@ -0,0 +1,87 @@
+use crate::DockState;
+
+/// A [`DockState`] whose tabs are replaced by keys, for saving a session and restoring it later.
+///
+/// Live tabs often hold things which can't be serialized, like open files or GPU resources. Instead, a session is
+/// [captured](SessionRestore::capture) with a key for each tab (a path, a document ID, etc.), which is what gets
+/// serialized. When loading, [`SessionRestore::restore`] turns the keys back into tabs, dropping the ones which can't
+/// be resolved anymore (e.g. deleted files) and reporting them.
+///
+/// With the `serde` feature, a [`SessionRestore`] is serialized the same way as the [`DockState`] it wraps.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockState, SessionRestore};
+/// struct Document {
+///     path: String,
+/// }
+///
+/// let dock_state = DockState::new(vec![
+///     Document { path: "notes.txt".to_string() },
+///     Document { path: "deleted.txt".to_string() },
+/// ]);
+/// let session = SessionRestore::capture(&dock_state, |document| document.path.clone());
+///
+/// let (restored, unresolved) = session.restore(|path| {
+///     (path != "deleted.txt").then(|| Document { path: path.clone() })
+/// });
+/// assert_eq!(restored.iter_all_tabs().count(), 1);
+/// assert_eq!(unresolved, vec!["deleted.txt".to_string()]);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SessionRestore<Key> {
+    dock_state: DockState<Key>,
+}
+
+impl<Key> SessionRestore<Key> {
+    /// Wraps a [`DockState`] whose tabs already are keys.
+    pub fn new(dock_state: DockState<Key>) -> Self {
+        Self { dock_state }
+    }
+
+    /// Captures the layout of `dock_state`, replacing every tab with the key returned by `key` for it.
+    ///
+    /// The tabs keep their [`TabId`](crate::TabId)s, as well as everything else about the layout.
+    pub fn capture<Tab>(dock_state: &DockState<Tab>, key: impl FnMut(&Tab) -> Key) -> Self {
+        Self::new(dock_state.map_tabs(key))
+    }
+
+    /// Get an immutable borrow to the [`DockState`] holding the keys.
+    pub fn dock_state(&self) -> &DockState<Key> {
+        &self.dock_state
+    }
+
+    /// Returns the [`DockState`] holding the keys.
+    pub fn into_inner(self) -> DockState<Key> {
+        self.dock_state
+    }
+
+    /// Turns the keys back into tabs using `resolver`.
+    ///
+    /// Tabs for which `resolver` returns `None` are dropped, along with the leaves and windows left without tabs. The
+    /// windows after a dropped window move up to take its index, keeping their order and the focus.
+    /// Returns the restored [`DockState`], and the keys which couldn't be resolved in the order they were found in.
+    pub fn restore<Tab>(
+        self,
+        mut resolver: impl FnMut(&Key) -> Option<Tab>,
+    ) -> (DockState<Tab>, Vec<Key>) {
+        let mut unresolved = Vec::new();
+        let dock_state = self.dock_state.into_filter_map_tabs(|key| {
+            let tab = resolver(&key);
+            if tab.is_none() {
+                unresolved.push(key);
+            }
+            tab
+        });
+        (dock_state, unresolved)
+    }
+}
+
+impl<Key> From<DockState<Key>> for SessionRestore<Key> {
+    fn from(dock_state: DockState<Key>) -> Self {
+        Self::new(dock_state)
+    }
+}
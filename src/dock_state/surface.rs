@@ -1,6 +1,6 @@
 use std::ops::{Index, IndexMut};
 
-use crate::{Node, NodeIndex, Tree, WindowState};
+use crate::{Node, NodeIndex, TabIndex, Tree, WindowState};
 
 /// A [`Surface`] is the highest level component in a [`DockState`](crate::DockState). [`Surface`]s represent an area
 /// in which nodes are placed.
@@ -102,15 +102,26 @@ impl<Tab> Surface<Tab> {
     /// Returns a new [`Surface`] while mapping and filtering the tab type.
     /// Any remaining empty [`Node`]s and are removed, and if this [`Surface`] remains empty,
     /// it'll change to [`Surface::Empty`].
-    pub fn filter_map_tabs<F, NewTab>(&self, function: F) -> Surface<NewTab>
+    pub fn filter_map_tabs<F, NewTab>(&self, mut function: F) -> Surface<NewTab>
     where
         F: FnMut(&Tab) -> Option<NewTab>,
+    {
+        self.filter_map_tabs_indexed(move |_, tab| function(tab))
+    }
+
+    /// Returns a new [`Surface`] while mapping and filtering the tab type,
+    /// passing the location each tab had in this [`Surface`] to `function`.
+    /// Any remaining empty [`Node`]s and are removed, and if this [`Surface`] remains empty,
+    /// it'll change to [`Surface::Empty`].
+    pub fn filter_map_tabs_indexed<F, NewTab>(&self, function: F) -> Surface<NewTab>
+    where
+        F: FnMut((NodeIndex, TabIndex), &Tab) -> Option<NewTab>,
     {
         match self {
             Surface::Empty => Surface::Empty,
-            Surface::Main(tree) => Surface::Main(tree.filter_map_tabs(function)),
+            Surface::Main(tree) => Surface::Main(tree.filter_map_tabs_indexed(function)),
             Surface::Window(tree, window_state) => {
-                let tree = tree.filter_map_tabs(function);
+                let tree = tree.filter_map_tabs_indexed(function);
                 if tree.is_empty() {
                     Surface::Empty
                 } else {
@@ -120,6 +131,37 @@ impl<Tab> Surface<Tab> {
         }
     }
 
+    /// Consumes this [`Surface`] and returns a new one while mapping and filtering the tab type.
+    /// Unlike [`Surface::filter_map_tabs`], the tabs are moved into `function` instead of being borrowed.
+    /// Any remaining empty [`Node`]s and are removed, and if this [`Surface`] remains empty,
+    /// it'll change to [`Surface::Empty`].
+    pub fn into_filter_map_tabs<F, NewTab>(self, function: F) -> Surface<NewTab>
+    where
+        F: FnMut(Tab) -> Option<NewTab>,
+    {
+        match self {
+            Surface::Empty => Surface::Empty,
+            Surface::Main(tree) => Surface::Main(tree.into_filter_map_tabs(function)),
+            Surface::Window(tree, window_state) => {
+                let tree = tree.into_filter_map_tabs(function);
+                if tree.is_empty() {
+                    Surface::Empty
+                } else {
+                    Surface::Window(tree, window_state)
+                }
+            }
+        }
+    }
+
+    /// Consumes this [`Surface`] and returns a new one while mapping the tab type.
+    /// Unlike [`Surface::map_tabs`], the tabs are moved into `function` instead of being borrowed.
+    pub fn into_map_tabs<F, NewTab>(self, mut function: F) -> Surface<NewTab>
+    where
+        F: FnMut(Tab) -> NewTab,
+    {
+        self.into_filter_map_tabs(move |tab| Some(function(tab)))
+    }
+
     /// Returns a new [`Surface`] while mapping the tab type.
     pub fn map_tabs<F, NewTab>(&self, mut function: F) -> Surface<NewTab>
     where
@@ -140,17 +182,32 @@ impl<Tab> Surface<Tab> {
     }
 
     /// Removes all tabs for which `predicate` returns `false`.
-    /// Any remaining empty [`Node`]s and are also removed, and if this [`Surface`] remains empty,
+    /// Any remaining empty [`Node`]s and are also removed, and if this [`Surface`] is a window which remains empty,
     /// it'll change to [`Surface::Empty`].
-    pub fn retain_tabs<F>(&mut self, predicate: F)
+    pub fn retain_tabs<F>(&mut self, mut predicate: F)
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        if let Surface::Main(tree) | Surface::Window(tree, _) = self {
-            tree.retain_tabs(predicate);
-            if tree.is_empty() {
-                *self = Surface::Empty;
-            }
+        self.retain_tabs_with(|_, tab| predicate(tab));
+    }
+
+    /// Removes all tabs for which `predicate` returns `false`,
+    /// passing the location each tab had before any of them were removed to `predicate`.
+    /// Any remaining empty [`Node`]s and are also removed, and if this [`Surface`] is a window which remains empty,
+    /// it'll change to [`Surface::Empty`].
+    ///
+    /// Returns the removed tabs, e.g. to release the resources they hold.
+    pub fn retain_tabs_with<F>(&mut self, predicate: F) -> Vec<Tab>
+    where
+        F: FnMut((NodeIndex, TabIndex), &mut Tab) -> bool,
+    {
+        let (Surface::Main(tree) | Surface::Window(tree, _)) = self else {
+            return Vec::new();
+        };
+        let removed = tree.retain_tabs_with(predicate);
+        if tree.is_empty() && matches!(self, Surface::Window(..)) {
+            *self = Surface::Empty;
         }
+        removed
     }
 }
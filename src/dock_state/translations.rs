@@ -6,6 +6,18 @@ pub struct Translations {
     pub tab_context_menu: TabContextMenuTranslations,
     /// Text overrides for buttons in windows.
     pub leaf: LeafTranslations,
+    /// Text overrides for buttons in separator context menus.
+    #[cfg_attr(feature = "serde", serde(default = "SeparatorTranslations::english"))]
+    pub separator: SeparatorTranslations,
+    /// Text overrides for the tab switcher.
+    #[cfg_attr(feature = "serde", serde(default = "TabSwitcherTranslations::english"))]
+    pub tab_switcher: TabSwitcherTranslations,
+    /// Text announced by screen readers, used if the `accesskit` feature is enabled.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "AccessibilityTranslations::english")
+    )]
+    pub accessibility: AccessibilityTranslations,
 }
 
 /// Specifies text in buttons displayed in the context menu displayed upon right-clicking on a tab.
@@ -16,6 +28,19 @@ pub struct TabContextMenuTranslations {
     pub close_button: String,
     /// Button that undocks the tab into a new window.
     pub eject_button: String,
+    /// Button that hides the leaf of the tab in a strip at the edge of its surface,
+    /// see [`LeafNode::set_auto_hidden`](crate::LeafNode::set_auto_hidden).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "TabContextMenuTranslations::english_auto_hide_button")
+    )]
+    pub auto_hide_button: String,
+    /// Button that puts an auto-hidden leaf of the tab back into the layout.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "TabContextMenuTranslations::english_dock_button")
+    )]
+    pub dock_button: String,
 }
 
 /// Specifies text displayed in the primary buttons on a tab bar.
@@ -50,6 +75,70 @@ pub struct LeafTranslations {
     /// Message in the tooltip shown while hovering over a collapse button of a leaf.
     /// Used when the secondary buttons are accessible using modifiers and from the context menu.
     pub minimize_button_modifier_menu_hint: String,
+    /// Message in the tooltip shown while hovering over the button which splits a leaf to the right.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "LeafTranslations::english_split_right_button")
+    )]
+    pub split_right_button: String,
+    /// Message in the tooltip shown while hovering over the button which splits a leaf downwards.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "LeafTranslations::english_split_below_button")
+    )]
+    pub split_below_button: String,
+}
+
+/// Specifies text in buttons displayed in the context menu displayed upon right-clicking on a separator.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SeparatorTranslations {
+    /// Button that resets the fractions of all splits under the separator, so that every leaf
+    /// takes up the same area.
+    pub distribute_evenly_button: String,
+    /// Button that turns the split of the separator from side by side into one above the other, or back.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "SeparatorTranslations::english_flip_orientation_button")
+    )]
+    pub flip_orientation_button: String,
+}
+
+/// Specifies text displayed in the tab switcher, see [`DockState::open_tab_switcher`](crate::DockState::open_tab_switcher).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TabSwitcherTranslations {
+    /// Hint shown in the search field while it's empty.
+    pub search_hint: String,
+    /// Message shown when no tab title matches the search.
+    pub no_matches: String,
+}
+
+/// Specifies text announced by screen readers for the parts of the [`DockArea`](crate::DockArea),
+/// used if the `accesskit` feature is enabled.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AccessibilityTranslations {
+    /// Name of a tab button, where `{name}` is replaced by [`TabViewer::accessible_name`](crate::TabViewer::accessible_name),
+    /// `{position}` by the position of the tab starting from 1 and `{count}` by the number of tabs in its leaf.
+    pub tab: String,
+    /// Name of the close button of a tab, where `{name}` is replaced by
+    /// [`TabViewer::accessible_name`](crate::TabViewer::accessible_name).
+    pub close_button: String,
+    /// Name of the separator between leaves side by side.
+    pub horizontal_separator: String,
+    /// Name of the separator between leaves above one another.
+    pub vertical_separator: String,
+    /// Name of the drop button which docks the dragged tab into a leaf.
+    pub dock_as_tab: String,
+    /// Name of the drop button which docks the dragged tab to the left of a leaf.
+    pub dock_left: String,
+    /// Name of the drop button which docks the dragged tab to the right of a leaf.
+    pub dock_right: String,
+    /// Name of the drop button which docks the dragged tab above a leaf.
+    pub dock_above: String,
+    /// Name of the drop button which docks the dragged tab below a leaf.
+    pub dock_below: String,
 }
 
 impl Translations {
@@ -58,6 +147,9 @@ impl Translations {
         Self {
             tab_context_menu: TabContextMenuTranslations::english(),
             leaf: LeafTranslations::english(),
+            separator: SeparatorTranslations::english(),
+            tab_switcher: TabSwitcherTranslations::english(),
+            accessibility: AccessibilityTranslations::english(),
         }
     }
 }
@@ -68,8 +160,19 @@ impl TabContextMenuTranslations {
         Self {
             close_button: String::from("Close"),
             eject_button: String::from("Eject"),
+            auto_hide_button: Self::english_auto_hide_button(),
+            dock_button: Self::english_dock_button(),
         }
     }
+
+    // Used as the default when deserializing translations saved before these were added.
+    fn english_auto_hide_button() -> String {
+        String::from("Auto-hide")
+    }
+
+    fn english_dock_button() -> String {
+        String::from("Dock")
+    }
 }
 
 impl LeafTranslations {
@@ -96,6 +199,58 @@ impl LeafTranslations {
             minimize_button_modifier_menu_hint: String::from(
                 "Press modifier keys (Shift by default) or right click to minimize this window.",
             ),
+            split_right_button: Self::english_split_right_button(),
+            split_below_button: Self::english_split_below_button(),
+        }
+    }
+
+    // Used as the default when deserializing translations saved before these were added.
+    fn english_split_right_button() -> String {
+        String::from("Split right")
+    }
+
+    fn english_split_below_button() -> String {
+        String::from("Split down")
+    }
+}
+
+impl SeparatorTranslations {
+    /// Default English translations.
+    pub fn english() -> Self {
+        Self {
+            distribute_evenly_button: String::from("Distribute evenly"),
+            flip_orientation_button: Self::english_flip_orientation_button(),
+        }
+    }
+
+    fn english_flip_orientation_button() -> String {
+        String::from("Flip split orientation")
+    }
+}
+
+impl TabSwitcherTranslations {
+    /// Default English translations.
+    pub fn english() -> Self {
+        Self {
+            search_hint: String::from("Search tabs…"),
+            no_matches: String::from("No matching tabs"),
+        }
+    }
+}
+
+impl AccessibilityTranslations {
+    /// Default English translations.
+    pub fn english() -> Self {
+        Self {
+            tab: String::from("Tab: {name}, {position} of {count}"),
+            close_button: String::from("Close {name}"),
+            horizontal_separator: String::from("Horizontal split separator"),
+            vertical_separator: String::from("Vertical split separator"),
+            dock_as_tab: String::from("Dock as a tab"),
+            dock_left: String::from("Dock to the left"),
+            dock_right: String::from("Dock to the right"),
+            dock_above: String::from("Dock above"),
+            dock_below: String::from("Dock below"),
         }
     }
 }
@@ -0,0 +1,345 @@
+//! Computing the rects of the nodes of a [`Tree`], shared by the [`DockArea`](crate::DockArea) and
+//! [`Tree::layout`].
+
+use duplicate::duplicate;
+use egui::{pos2, Rect};
+use paste::paste;
+
+use super::{Node, NodeIndex, Split, Tree};
+use crate::{
+    utils::{expand_to_pixel, map_to_pixel},
+    MinSizeOverflow, Style, TabBarPosition,
+};
+
+impl<Tab> Tree<Tab> {
+    /// Computes the rects of all nodes of the tree laid out in `rect` with `style`, and the
+    /// [`viewport`](crate::LeafNode::viewport) of each leaf, without showing anything.
+    ///
+    /// The results are the same as if the tree was shown by a [`DockArea`](crate::DockArea) filling `rect` at one
+    /// pixel per point, except that tab bars which would be hidden by
+    /// [`TabViewer::tab_bar_visibility`](crate::TabViewer::tab_bar_visibility) are still taken into account, and
+    /// [`DockArea::hide_single_tab_bars`](crate::DockArea::hide_single_tab_bars) is only honored when set on the
+    /// leaf itself with [`LeafNode::set_single_tab_bar_hidden`](crate::LeafNode::set_single_tab_bar_hidden).
+    /// Collapsed leaves and auto-hidden leaves keep their viewport.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui::{pos2, Rect};
+    /// # use egui_dock::{DockState, NodeIndex, Style};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let [_, right] = dock_state
+    ///     .main_surface_mut()
+    ///     .split_right(NodeIndex::root(), 0.5, vec!["Console"]);
+    ///
+    /// let mut style = Style::default();
+    /// style.separator.width = 0.0;
+    /// let tree = dock_state.main_surface_mut();
+    /// tree.layout(Rect::from_min_max(pos2(0.0, 0.0), pos2(200.0, 100.0)), &style);
+    ///
+    /// let leaf = tree[right].get_leaf().unwrap();
+    /// assert_eq!(leaf.rect(), Rect::from_min_max(pos2(100.0, 0.0), pos2(200.0, 100.0)));
+    /// assert_eq!(leaf.viewport.min.y, style.tab_bar.height);
+    /// ```
+    pub fn layout(&mut self, rect: Rect, style: &Style) {
+        if self.is_empty() {
+            return;
+        }
+        let (max_rect, _) = self.reserve_auto_hide_strips(rect, style.auto_hide.strip_width);
+        self[NodeIndex::root()].set_rect(max_rect);
+        for node_index in self.breadth_first_index_iter() {
+            if self[node_index].is_parent() {
+                self.compute_rect_sizes(node_index, max_rect, style, 1.0);
+                self.expand_over_auto_hidden(node_index);
+            }
+        }
+        for node_index in self.breadth_first_index_iter() {
+            if self.is_auto_hidden(node_index) {
+                continue;
+            }
+            let Node::Leaf(leaf) = &mut self[node_index] else {
+                continue;
+            };
+            if leaf.collapsed || (leaf.is_empty() && !leaf.is_central()) {
+                continue;
+            }
+            let mut viewport = leaf.rect;
+            if !leaf.tab_bar_hidden {
+                // The global `DockArea::hide_single_tab_bars` isn't known here, only the leaf's own setting.
+                let height = if leaf.tabs.len() == 1 && leaf.single_tab_bar_hidden == Some(true) {
+                    style.tab_bar.drag_handle_height
+                } else {
+                    style.tab_bar.height
+                };
+                match leaf.tab_bar_position.unwrap_or(style.tab_bar.position) {
+                    TabBarPosition::Top => viewport.min.y += height,
+                    TabBarPosition::Bottom => viewport.max.y -= height,
+                    TabBarPosition::Left => viewport.min.x += height,
+                    TabBarPosition::Right => viewport.max.x -= height,
+                }
+            }
+            leaf.viewport = viewport.intersect(leaf.rect);
+        }
+    }
+
+    /// Shrinks `rect` to leave room for the strips of the auto-hidden leaves, returning the shrunk rect and the
+    /// rects of the strips at each edge.
+    pub(crate) fn reserve_auto_hide_strips(
+        &self,
+        rect: Rect,
+        width: f32,
+    ) -> (Rect, Vec<(Split, Rect)>) {
+        let edges: Vec<Split> = self
+            .breadth_first_index_iter()
+            .filter(|&node_index| self[node_index].is_leaf())
+            .filter_map(|node_index| self.auto_hide_edge(node_index))
+            .collect();
+        if edges.is_empty() {
+            return (rect, Vec::new());
+        }
+
+        let mut inner = rect;
+        for (edge, side) in [
+            (Split::Above, &mut inner.min.y),
+            (Split::Below, &mut inner.max.y),
+            (Split::Left, &mut inner.min.x),
+            (Split::Right, &mut inner.max.x),
+        ] {
+            if edges.contains(&edge) {
+                *side += if matches!(edge, Split::Above | Split::Left) {
+                    width
+                } else {
+                    -width
+                };
+            }
+        }
+
+        // The strips at the top and bottom span the whole width, the ones on the sides fit between them.
+        let strips = [
+            (
+                Split::Above,
+                Rect::from_min_max(rect.min, pos2(rect.max.x, inner.min.y)),
+            ),
+            (
+                Split::Below,
+                Rect::from_min_max(pos2(rect.min.x, inner.max.y), rect.max),
+            ),
+            (
+                Split::Left,
+                Rect::from_min_max(
+                    pos2(rect.min.x, inner.min.y),
+                    pos2(inner.min.x, inner.max.y),
+                ),
+            ),
+            (
+                Split::Right,
+                Rect::from_min_max(
+                    pos2(inner.max.x, inner.min.y),
+                    pos2(rect.max.x, inner.max.y),
+                ),
+            ),
+        ]
+        .into_iter()
+        .filter(|(edge, _)| edges.contains(edge))
+        .collect();
+        (inner, strips)
+    }
+
+    /// Lets the child of a split take the whole rect of the split if its sibling is auto-hidden.
+    pub(crate) fn expand_over_auto_hidden(&mut self, node_index: NodeIndex) {
+        let (left, right) = (node_index.left(), node_index.right());
+        let expanded = match (self.is_auto_hidden(left), self.is_auto_hidden(right)) {
+            (true, false) => right,
+            (false, true) => left,
+            _ => return,
+        };
+        if let Some(rect) = self[node_index].rect() {
+            self[expanded].set_rect(rect);
+        }
+    }
+
+    /// Splits the rect of the parent `node_index` between its children, leaving room for the separator.
+    pub(crate) fn compute_rect_sizes(
+        &mut self,
+        node_index: NodeIndex,
+        max_rect: Rect,
+        style: &Style,
+        pixels_per_point: f32,
+    ) {
+        assert!(self[node_index].is_parent());
+
+        let left_collapsed_count = self[node_index.left()].collapsed_leaf_count();
+        let right_collapsed_count = self[node_index.right()].collapsed_leaf_count();
+        let left_collapsed = self[node_index.left()].is_collapsed();
+        let right_collapsed = self[node_index.right()].is_collapsed();
+
+        if left_collapsed || right_collapsed {
+            if let Node::Vertical(split) = &mut self[node_index] {
+                let rect = split.rect();
+                debug_assert!(!rect.any_nan() && rect.is_finite());
+                let rect = expand_to_pixel(rect, pixels_per_point);
+
+                // Collapsed leaves only keep the height of their tab bars.
+                let border_y = if left_collapsed {
+                    rect.min.y + (left_collapsed_count as f32) * style.tab_bar.height
+                } else {
+                    rect.max.y - (right_collapsed_count as f32) * style.tab_bar.height
+                };
+                let left_separator_border = map_to_pixel(
+                    border_y - style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round,
+                );
+                let right_separator_border = map_to_pixel(
+                    border_y + style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round,
+                );
+                let left = rect
+                    .intersect(Rect::everything_above(left_separator_border))
+                    .intersect(max_rect);
+                let right = rect
+                    .intersect(Rect::everything_below(right_separator_border))
+                    .intersect(max_rect);
+                self[node_index.left()].set_rect(left);
+                self[node_index.right()].set_rect(right);
+                return;
+            }
+        }
+
+        duplicate! {
+            [
+                orientation   dim_point  dim_size  left_of    right_of;
+                [Horizontal]  [x]        [width]   [left_of]  [right_of];
+                [Vertical]    [y]        [height]  [above]    [below];
+            ]
+            if let Node::orientation(split) = &self[node_index] {
+                let rect = split.rect;
+                debug_assert!(!rect.any_nan() && rect.is_finite());
+                let rect = expand_to_pixel(rect, pixels_per_point);
+
+                let fraction = self.fitted_fraction(node_index, style);
+                let midpoint = rect.min.dim_point + rect.dim_size() * fraction;
+                let left_separator_border = map_to_pixel(
+                    midpoint - style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round
+                );
+                let right_separator_border = map_to_pixel(
+                    midpoint + style.separator.width * 0.5,
+                    pixels_per_point,
+                    f32::round
+                );
+
+                paste! {
+                    let left = rect.intersect(Rect::[<everything_ left_of>](left_separator_border)).intersect(max_rect);
+                    let right = rect.intersect(Rect::[<everything_ right_of>](right_separator_border)).intersect(max_rect);
+                }
+
+                self[node_index.left()].set_rect(left);
+                self[node_index.right()].set_rect(right);
+            }
+        }
+    }
+
+    /// Returns the fraction the parent `node_index` is laid out with: its [`SplitNode::fraction`](crate::SplitNode)
+    /// moved as little as needed for the [`LeafNode::min_size`](crate::LeafNode::min_size) of its children, or if
+    /// they don't fit, the one chosen by [`SeparatorStyle::min_size_overflow`](crate::SeparatorStyle).
+    pub(crate) fn fitted_fraction(&self, node_index: NodeIndex, style: &Style) -> f32 {
+        let (Node::Horizontal(split) | Node::Vertical(split)) = &self[node_index] else {
+            return 0.5;
+        };
+        if let Some((min, max)) = self.min_size_fraction_limits(node_index, style.separator.width) {
+            return split.fraction.clamp(min, max);
+        }
+
+        let horizontal = self[node_index].is_horizontal();
+        let size = if horizontal {
+            split.rect.width()
+        } else {
+            split.rect.height()
+        };
+        if size <= 0.0 || !size.is_finite() {
+            return split.fraction;
+        }
+        let half_separator = style.separator.width * 0.5;
+        match style.separator.min_size_overflow {
+            MinSizeOverflow::Shrink => {
+                let left = self.min_extent(node_index.left(), horizontal, style.separator.width);
+                let right = self.min_extent(node_index.right(), horizontal, style.separator.width);
+                let room = (size - style.separator.width).max(0.0);
+                (room * left / (left + right) + half_separator) / size
+            }
+            MinSizeOverflow::Collapse if split.fraction >= 0.5 => 1.0 - half_separator / size,
+            MinSizeOverflow::Collapse => half_separator / size,
+        }
+    }
+
+    /// Returns the range of fractions of the parent `node_index` in which both of its children get at least the
+    /// [`LeafNode::min_size`](crate::LeafNode::min_size) of their leaves, or `None` if there's no room for them.
+    pub(crate) fn min_size_fraction_limits(
+        &self,
+        node_index: NodeIndex,
+        separator_width: f32,
+    ) -> Option<(f32, f32)> {
+        let (Node::Horizontal(split) | Node::Vertical(split)) = &self[node_index] else {
+            return None;
+        };
+        let horizontal = self[node_index].is_horizontal();
+        let size = if horizontal {
+            split.rect.width()
+        } else {
+            split.rect.height()
+        };
+        let left = self.min_extent(node_index.left(), horizontal, separator_width);
+        let right = self.min_extent(node_index.right(), horizontal, separator_width);
+        if left <= 0.0 && right <= 0.0 {
+            return Some((0.0, 1.0));
+        }
+        if size <= 0.0 || !size.is_finite() {
+            return None;
+        }
+
+        // The children end half a separator away from the point at the fraction of the split.
+        let min = (left + separator_width * 0.5) / size;
+        let max = 1.0 - (right + separator_width * 0.5) / size;
+        (min <= max).then_some((min, max))
+    }
+
+    /// Returns the smallest width, or height if not `horizontal`, the node at `node_index` fits the
+    /// [`LeafNode::min_size`](crate::LeafNode::min_size) of all its leaves in.
+    fn min_extent(&self, node_index: NodeIndex, horizontal: bool, separator_width: f32) -> f32 {
+        if self.is_auto_hidden(node_index) {
+            return 0.0;
+        }
+        match &self[node_index] {
+            Node::Empty => 0.0,
+            Node::Leaf(leaf) if horizontal => leaf.min_size.x,
+            Node::Leaf(leaf) => leaf.min_size.y,
+            node => {
+                let left = self.min_extent(node_index.left(), horizontal, separator_width);
+                let right = self.min_extent(node_index.right(), horizontal, separator_width);
+                // A split whose child is auto-hidden is taken up by the other one, without a separator.
+                if self.is_auto_hidden(node_index.left()) {
+                    right
+                } else if self.is_auto_hidden(node_index.right()) {
+                    left
+                } else if node.is_horizontal() == horizontal {
+                    left + right + separator_width
+                } else {
+                    left.max(right)
+                }
+            }
+        }
+    }
+}
+
+/// Returns the rect taken by the main surface of a [`DockArea`](crate::DockArea) shown in `rect`.
+pub(crate) fn main_surface_rect(rect: Rect, style: &Style) -> Rect {
+    let mut rect = rect;
+    if let Some(margin) = style.dock_area_padding {
+        rect.min += margin.left_top();
+        rect.max -= margin.right_bottom();
+    }
+    rect.expand(-style.main_surface_border_stroke.width / 2.0)
+}
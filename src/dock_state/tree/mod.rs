@@ -8,22 +8,43 @@
 //!  - left child of *n* will be at index *n * 2 + 1*.
 //!  - right child of *n* will be at index *n * 2 + 2*.
 
+#[macro_use]
+mod persistent_id;
+
 /// Iterates over all tabs in a [`Tree`].
 pub mod tab_iter;
 
+/// Walks the nodes of a [`Tree`] breadth first or depth first.
+pub mod node_iter;
+
 /// Identifies a tab within a [`Node`].
 pub mod tab_index;
 
+/// Stable identifier of a tab which doesn't change when the tab is moved.
+pub mod tab_id;
+
 /// Represents an abstract node of a [`Tree`].
 pub mod node;
 
 /// Wrapper around indices to the collection of nodes inside a [`Tree`].
 pub mod node_index;
 
+/// Persistent identifier of a node which doesn't change when the [`Tree`] is rearranged.
+pub mod node_id;
+
+mod layout;
+
+#[cfg(feature = "serde")]
+mod nested;
+
+pub(crate) use layout::main_surface_rect;
 pub use node::LeafNode;
 pub use node::Node;
 pub use node::SplitNode;
+pub use node_id::NodeId;
 pub use node_index::NodeIndex;
+pub use node_iter::{BreadthFirstIter, DepthFirstIter, NodeRef};
+pub use tab_id::TabId;
 pub use tab_index::TabIndex;
 pub use tab_iter::TabIter;
 
@@ -42,6 +63,7 @@ use crate::SurfaceIndex;
 
 /// Direction in which a new node is created relatively to the parent node at which the split occurs.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[allow(missing_docs)]
 pub enum Split {
     Left,
@@ -63,6 +85,7 @@ impl Split {
 }
 
 /// Specify how a tab should be added to a Node.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TabInsert {
     /// Split the node in the given direction.
     Split(Split),
@@ -75,6 +98,7 @@ pub enum TabInsert {
 }
 
 /// The destination for a tab which is being moved.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TabDestination {
     /// Move to a new window with this rect.
     Window(Rect),
@@ -105,6 +129,42 @@ impl TabDestination {
     }
 }
 
+/// Which of the tabs added with [`DockState::add_tabs`](crate::DockState::add_tabs) gets focus.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FocusPolicy {
+    /// The first added tab becomes the active tab of its leaf, which is focused.
+    #[default]
+    First,
+
+    /// The last added tab becomes the active tab of its leaf, which is focused.
+    Last,
+
+    /// None of the added tabs become active, and the focus stays where it was.
+    None,
+}
+
+/// Refers to a tab in a [`DockState`](crate::DockState), either by where it is or by its [`TabId`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TabRef {
+    /// The tab at this surface, node and tab index.
+    Index(SurfaceIndex, NodeIndex, TabIndex),
+
+    /// The tab with this [`TabId`], wherever it is.
+    Id(TabId),
+}
+
+impl From<(SurfaceIndex, NodeIndex, TabIndex)> for TabRef {
+    fn from(value: (SurfaceIndex, NodeIndex, TabIndex)) -> TabRef {
+        TabRef::Index(value.0, value.1, value.2)
+    }
+}
+
+impl From<TabId> for TabRef {
+    fn from(value: TabId) -> TabRef {
+        TabRef::Id(value)
+    }
+}
+
 /// Binary tree representing the relationships between [`Node`]s.
 ///
 /// # Implementation details
@@ -122,8 +182,10 @@ impl TabDestination {
 /// For "Vertical" nodes:
 ///  - left child contains Top node.
 ///  - right child contains Bottom node.
+///
+/// With the `serde` feature, human readable formats store the nodes nested the way they're laid out,
+/// while other formats store the vector of nodes.
 #[derive(Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Tree<Tab> {
     // Binary tree vector
     pub(super) nodes: Vec<Node<Tab>>,
@@ -179,6 +241,14 @@ impl<Tab> Tree<Tab> {
         }
     }
 
+    /// Pads the nodes with [`Node::Empty`] up to the end of the level of the deepest node.
+    pub(crate) fn fill_last_level(&mut self) {
+        if let Some(last) = self.nodes.len().checked_sub(1) {
+            let level = NodeIndex(last).level();
+            self.nodes.resize_with((1 << level) - 1, || Node::Empty);
+        }
+    }
+
     /// Returns the viewport [`Rect`] and the `Tab` inside the first leaf node,
     /// or `None` if no leaf exists in the [`Tree`].
     #[inline]
@@ -222,6 +292,48 @@ impl<Tab> Tree<Tab> {
         self.nodes.iter_mut()
     }
 
+    /// Returns an [`Iterator`] visiting the non-empty nodes level by level, starting at the root, along with their
+    /// parents, depths and the sides of the splits they're on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex, Split};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let tree = dock_state.main_surface_mut();
+    /// let [_, console] = tree.split_below(NodeIndex::root(), 0.7, vec!["Console"]);
+    ///
+    /// let node = tree.bfs().find(|node| node.index == console).unwrap();
+    /// assert_eq!(node.parent, Some(NodeIndex::root()));
+    /// assert_eq!(node.depth, 1);
+    /// assert_eq!(node.side, Some(Split::Below));
+    /// ```
+    #[inline]
+    pub fn bfs(&self) -> BreadthFirstIter<'_, Tab> {
+        BreadthFirstIter::new(self)
+    }
+
+    /// Returns an [`Iterator`] visiting the non-empty nodes depth first, each split before its left or top child
+    /// and that child's descendants, then its right or bottom child.
+    ///
+    /// The nodes come along with their parents, depths and the sides of the splits they're on, like with
+    /// [`Tree::bfs`].
+    #[inline]
+    pub fn dfs(&self) -> DepthFirstIter<'_, Tab> {
+        DepthFirstIter::new(self)
+    }
+
+    /// Returns an [`Iterator`] of the non-empty nodes and the rects they were laid out in the last time the tree
+    /// was shown, or laid out with [`Tree::layout`].
+    ///
+    /// Nodes which haven't been laid out yet are skipped. The rects of splits cover both of their children.
+    pub fn iter_rects(&self) -> impl Iterator<Item = (NodeIndex, Rect)> + '_ {
+        self.breadth_first_index_iter().filter_map(|node_index| {
+            let rect = self[node_index].rect()?;
+            rect.is_positive().then_some((node_index, rect))
+        })
+    }
+
     /// Returns an [`Iterator`] of [`NodeIndex`] ordered in a breadth first manner.
     #[inline(always)]
     pub(crate) fn breadth_first_index_iter(&self) -> impl Iterator<Item = NodeIndex> {
@@ -583,6 +695,8 @@ impl<Tab> Tree<Tab> {
 
     /// Removes the given node from the [`Tree`].
     ///
+    /// [Central](LeafNode::set_central) leaves aren't removed.
+    ///
     /// # Panics
     ///
     /// - If the tree is empty.
@@ -590,6 +704,9 @@ impl<Tab> Tree<Tab> {
     pub fn remove_leaf(&mut self, node: NodeIndex) {
         assert!(!self.is_empty());
         assert!(self[node].is_leaf());
+        if self[node].get_leaf().is_some_and(LeafNode::is_central) {
+            return;
+        }
 
         let Some(parent) = node.parent() else {
             self.nodes.clear();
@@ -665,13 +782,108 @@ impl<Tab> Tree<Tab> {
         }
     }
 
+    /// Removes the given leaf from the [`Tree`] like [`Tree::remove_leaf`], but instead of giving all of its space to
+    /// its sibling, shares it out among all the nodes laid out next to it in the same direction, in proportion to
+    /// their sizes. Nodes in the other direction keep their sizes.
+    ///
+    /// # Panics
+    ///
+    /// - If the tree is empty.
+    /// - If the node at index `node` is not a [`Leaf`](Node::Leaf).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, Node, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["Files"]);
+    /// let tree = dock_state.main_surface_mut();
+    /// let [_, editor] = tree.split_right(NodeIndex::root(), 1.0 / 3.0, vec!["Editor"]);
+    /// let [_, outline] = tree.split_right(editor, 0.5, vec!["Outline"]);
+    ///
+    /// // The files and the editor take half of the width each, rather than the editor taking two thirds of it.
+    /// tree.remove_leaf_proportionally(outline);
+    /// let Node::Horizontal(split) = &tree[NodeIndex::root()] else { unreachable!() };
+    /// assert!((split.fraction() - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn remove_leaf_proportionally(&mut self, node: NodeIndex) {
+        assert!(!self.is_empty());
+        assert!(self[node].is_leaf());
+        let Some(parent) = node.parent() else {
+            self.remove_leaf(node);
+            return;
+        };
+        if self[node].get_leaf().is_some_and(LeafNode::is_central) {
+            return;
+        }
+
+        // The splits in the same direction as the parent, up to the first one in the other direction.
+        let horizontal = self[parent].is_horizontal();
+        let mut top = parent;
+        while let Some(up) = top.parent() {
+            if self[up].is_horizontal() != horizontal {
+                break;
+            }
+            top = up;
+        }
+
+        // The new fractions of these splits, made from the sizes of their children without the removed leaf.
+        let mut fractions = Vec::new();
+        self.sizes_without(top, 1.0, horizontal, node, &mut fractions);
+        self.remove_leaf(node);
+        for (id, fraction) in fractions {
+            if let Some(index) = self.find_node_by_id(id) {
+                self.set_split_fraction(index, fraction);
+            }
+        }
+    }
+
+    /// Returns the size of the node at `index` in the direction of the splits, given that it's `size` large with the
+    /// `removed` node, and puts the fractions its splits in the same direction get without it into `fractions`.
+    fn sizes_without(
+        &self,
+        index: NodeIndex,
+        size: f32,
+        horizontal: bool,
+        removed: NodeIndex,
+        fractions: &mut Vec<(NodeId, f32)>,
+    ) -> f32 {
+        if index == removed {
+            return 0.0;
+        }
+        match &self[index] {
+            Node::Horizontal(split) | Node::Vertical(split)
+                if self[index].is_horizontal() == horizontal =>
+            {
+                let left = self.sizes_without(
+                    index.left(),
+                    size * split.fraction,
+                    horizontal,
+                    removed,
+                    fractions,
+                );
+                let right = self.sizes_without(
+                    index.right(),
+                    size * (1.0 - split.fraction),
+                    horizontal,
+                    removed,
+                    fractions,
+                );
+                // The parent of the removed leaf is replaced by its sibling, so its fraction doesn't matter.
+                if left > 0.0 && right > 0.0 {
+                    fractions.push((split.id, left / (left + right)));
+                }
+                left + right
+            }
+            _ => size,
+        }
+    }
+
     /// Pushes a tab to the first `Leaf` it finds or create a new leaf if an `Empty` node is encountered.
     pub fn push_to_first_leaf(&mut self, tab: Tab) {
         for (index, node) in &mut self.nodes.iter_mut().enumerate() {
             match node {
                 Node::Leaf(leaf) => {
-                    leaf.active = TabIndex(leaf.tabs.len());
-                    leaf.tabs.push(tab);
+                    leaf.append_tab(tab);
                     self.focused_node = Some(NodeIndex(index));
                     return;
                 }
@@ -688,6 +900,47 @@ impl<Tab> Tree<Tab> {
         self.focused_node = Some(NodeIndex(0));
     }
 
+    /// Resets the fractions of all splits in the subtree starting at `subtree`, so that every leaf
+    /// within it takes up the same area.
+    ///
+    /// [Locked](SplitNode::set_locked) splits keep their fractions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use egui_dock::{Node, NodeIndex, Tree};
+    /// let mut tree = Tree::new(vec!["a"]);
+    /// tree.split_right(NodeIndex::root(), 0.7, vec!["b"]);
+    /// tree.split_below(NodeIndex::root().right(), 0.2, vec!["c"]);
+    ///
+    /// tree.distribute_evenly(NodeIndex::root());
+    /// let Node::Horizontal(split) = &tree[NodeIndex::root()] else { unreachable!() };
+    /// assert_eq!(split.fraction, 1.0 / 3.0);
+    /// let Node::Vertical(split) = &tree[NodeIndex::root().right()] else { unreachable!() };
+    /// assert_eq!(split.fraction, 0.5);
+    /// ```
+    pub fn distribute_evenly(&mut self, subtree: NodeIndex) {
+        self.distribute_evenly_recursive(subtree);
+    }
+
+    /// Returns the number of leaves in the subtree starting at `node_index`.
+    fn distribute_evenly_recursive(&mut self, node_index: NodeIndex) -> usize {
+        match self.nodes.get(node_index.0) {
+            Some(Node::Leaf(_)) => 1,
+            Some(Node::Horizontal(_) | Node::Vertical(_)) => {
+                let left = self.distribute_evenly_recursive(node_index.left());
+                let right = self.distribute_evenly_recursive(node_index.right());
+                if let Node::Horizontal(split) | Node::Vertical(split) = &mut self[node_index] {
+                    if left + right > 0 && !split.locked {
+                        split.fraction = left as f32 / (left + right) as f32;
+                    }
+                }
+                left + right
+            }
+            _ => 0,
+        }
+    }
+
     /// Sets which is the active tab within a specific node.
     #[inline]
     pub fn set_active_tab(
@@ -700,6 +953,108 @@ impl<Tab> Tree<Tab> {
         };
     }
 
+    /// Exchanges the contents of the leaves `a` and `b`, so that each takes the place of the other.
+    ///
+    /// The tabs move along with everything else about the leaves, like their active tabs and [`NodeId`]s, while the
+    /// rects stay where they are. If one of the leaves was focused, the focus follows it.
+    ///
+    /// Does nothing if `a` or `b` isn't a [`Leaf`](Node::Leaf).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let tree = dock_state.main_surface_mut();
+    /// let [editor, console] = tree.split_below(NodeIndex::root(), 0.7, vec!["Console"]);
+    ///
+    /// tree.swap_leaves(editor, console);
+    /// assert_eq!(tree[editor].tabs(), Some(["Console"].as_slice()));
+    /// assert_eq!(tree[console].tabs(), Some(["Editor"].as_slice()));
+    /// ```
+    pub fn swap_leaves(&mut self, a: NodeIndex, b: NodeIndex) {
+        let geometry = |node: Option<&Node<Tab>>| match node {
+            Some(Node::Leaf(leaf)) => Some((leaf.rect, leaf.viewport)),
+            _ => None,
+        };
+        let (Some(geometry_a), Some(geometry_b)) =
+            (geometry(self.nodes.get(a.0)), geometry(self.nodes.get(b.0)))
+        else {
+            return;
+        };
+        if a == b {
+            return;
+        }
+
+        // The leaves trade places but not geometry.
+        self.nodes.swap(a.0, b.0);
+        for (node_index, (rect, viewport)) in [(a, geometry_a), (b, geometry_b)] {
+            if let Node::Leaf(leaf) = &mut self[node_index] {
+                leaf.rect = rect;
+                leaf.viewport = viewport;
+            }
+        }
+        self.focused_node = match self.focused_node {
+            Some(node) if node == a => Some(b),
+            Some(node) if node == b => Some(a),
+            focused => focused,
+        };
+        self.node_update_collapsed(a);
+        self.node_update_collapsed(b);
+    }
+
+    /// Turns the split at `node_index` from [`Horizontal`](Node::Horizontal) into [`Vertical`](Node::Vertical) or
+    /// the other way around, keeping its fraction. The left child ends up on top, and the top child on the left.
+    ///
+    /// Does nothing if `node_index` isn't a split.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["Files"]);
+    /// let tree = dock_state.main_surface_mut();
+    /// tree.split_right(NodeIndex::root(), 0.3, vec!["Editor"]);
+    ///
+    /// tree.rotate_split(NodeIndex::root());
+    /// assert!(tree[NodeIndex::root()].is_vertical());
+    /// ```
+    pub fn rotate_split(&mut self, node_index: NodeIndex) {
+        let rotated = match self.nodes.get(node_index.0) {
+            Some(Node::Horizontal(split)) => Node::Vertical(split.clone()),
+            Some(Node::Vertical(split)) => Node::Horizontal(split.clone()),
+            _ => return,
+        };
+        self[node_index] = rotated;
+        // Collapsed leaves are counted differently side by side than one above the other.
+        self.node_update_collapsed(node_index.left());
+    }
+
+    /// Sets the fraction of the area of a split taken by its left or top child, see [`SplitNode::set_fraction`].
+    ///
+    /// Does nothing if `node_index` isn't a [`Horizontal`](Node::Horizontal) or [`Vertical`](Node::Vertical) node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, Node, NodeIndex};
+    /// let mut dock_state = DockState::new(vec!["Editor"]);
+    /// let tree = dock_state.main_surface_mut();
+    /// tree.split_below(NodeIndex::root(), 0.5, vec!["Console"]);
+    ///
+    /// tree.set_split_fraction(NodeIndex::root(), 0.8);
+    /// let Node::Vertical(split) = &tree[NodeIndex::root()] else { unreachable!() };
+    /// assert_eq!(split.fraction(), 0.8);
+    /// ```
+    #[inline]
+    pub fn set_split_fraction(&mut self, node_index: impl Into<NodeIndex>, fraction: f32) {
+        if let Some(Node::Horizontal(split) | Node::Vertical(split)) =
+            self.nodes.get_mut(node_index.into().0)
+        {
+            split.set_fraction(fraction);
+        }
+    }
+
     /// Pushes `tab` to the currently focused leaf.
     ///
     /// If no leaf is focused it will be pushed to the first available leaf.
@@ -738,9 +1093,34 @@ impl<Tab> Tree<Tab> {
         }
     }
 
+    /// Inserts `tab` into the leaf at `node_index`, at the position given by `tab_index`,
+    /// shifting the tabs after it to the right.
+    ///
+    /// The inserted tab becomes the active tab of the leaf, and the leaf becomes the focused node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node at `node_index` isn't a [`Node::Leaf`],
+    /// or if `tab_index` is greater than the number of tabs in the leaf.
+    #[track_caller]
+    pub fn insert_tab(&mut self, (node_index, tab_index): (NodeIndex, TabIndex), tab: Tab) {
+        let Node::Leaf(leaf) = &mut self[node_index] else {
+            panic!("node was not a leaf");
+        };
+        assert!(
+            tab_index.0 <= leaf.tabs.len(),
+            "tab index {} is out of bounds for a leaf with {} tabs",
+            tab_index.0,
+            leaf.tabs.len()
+        );
+        leaf.insert_tab(tab_index, tab);
+        self.focused_node = Some(node_index);
+    }
+
     /// Removes the tab at the given ([`NodeIndex`], [`TabIndex`]) pair.
     ///
-    /// If the node is emptied after the tab is removed, the node will also be removed.
+    /// If the node is emptied after the tab is removed, the node will also be removed,
+    /// unless it's a [central](LeafNode::set_central) leaf.
     ///
     /// Returns the removed tab if it exists, or `None` otherwise.
     pub fn remove_tab(&mut self, (node_index, tab_index): (NodeIndex, TabIndex)) -> Option<Tab> {
@@ -757,6 +1137,16 @@ impl<Tab> Tree<Tab> {
     pub fn filter_map_tabs<F, NewTab>(&self, mut function: F) -> Tree<NewTab>
     where
         F: FnMut(&Tab) -> Option<NewTab>,
+    {
+        self.filter_map_tabs_indexed(move |_, tab| function(tab))
+    }
+
+    /// Returns a new [`Tree`] while mapping and filtering the tab type,
+    /// passing the location each tab had in this [`Tree`] to `function`.
+    /// Any remaining empty [`Node`]s are removed.
+    pub fn filter_map_tabs_indexed<F, NewTab>(&self, mut function: F) -> Tree<NewTab>
+    where
+        F: FnMut((NodeIndex, TabIndex), &Tab) -> Option<NewTab>,
     {
         let Tree {
             focused_node,
@@ -769,7 +1159,9 @@ impl<Tab> Tree<Tab> {
             .iter()
             .enumerate()
             .map(|(index, node)| {
-                let filtered_node = node.filter_map_tabs(&mut function);
+                let filtered_node = node.filter_map_tabs_indexed(|tab_index, tab| {
+                    function((NodeIndex(index), tab_index), tab)
+                });
                 if filtered_node.is_empty() && !node.is_empty() {
                     emptied_nodes.insert(NodeIndex(index));
                 }
@@ -786,6 +1178,51 @@ impl<Tab> Tree<Tab> {
         new_tree
     }
 
+    /// Consumes this [`Tree`] and returns a new one while mapping and filtering the tab type.
+    /// Unlike [`Tree::filter_map_tabs`], the tabs are moved into `function` instead of being borrowed.
+    /// Any remaining empty [`Node`]s are removed.
+    pub fn into_filter_map_tabs<F, NewTab>(self, mut function: F) -> Tree<NewTab>
+    where
+        F: FnMut(Tab) -> Option<NewTab>,
+    {
+        let Tree {
+            focused_node,
+            nodes,
+            collapsed,
+            collapsed_leaf_count,
+        } = self;
+        let mut emptied_nodes = HashSet::default();
+        let nodes = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let was_empty = node.is_empty();
+                let filtered_node = node.into_filter_map_tabs(&mut function);
+                if filtered_node.is_empty() && !was_empty {
+                    emptied_nodes.insert(NodeIndex(index));
+                }
+                filtered_node
+            })
+            .collect();
+        let mut new_tree = Tree {
+            nodes,
+            focused_node,
+            collapsed,
+            collapsed_leaf_count,
+        };
+        new_tree.balance(emptied_nodes);
+        new_tree
+    }
+
+    /// Consumes this [`Tree`] and returns a new one while mapping the tab type.
+    /// Unlike [`Tree::map_tabs`], the tabs are moved into `function` instead of being borrowed.
+    pub fn into_map_tabs<F, NewTab>(self, mut function: F) -> Tree<NewTab>
+    where
+        F: FnMut(Tab) -> NewTab,
+    {
+        self.into_filter_map_tabs(move |tab| Some(function(tab)))
+    }
+
     /// Returns a new [`Tree`] while mapping the tab type.
     pub fn map_tabs<F, NewTab>(&self, mut function: F) -> Tree<NewTab>
     where
@@ -809,15 +1246,52 @@ impl<Tab> Tree<Tab> {
     pub fn retain_tabs<F>(&mut self, mut predicate: F)
     where
         F: FnMut(&mut Tab) -> bool,
+    {
+        self.retain_tabs_with(|_, tab| predicate(tab));
+    }
+
+    /// Removes all tabs for which `predicate` returns `false`,
+    /// passing the location each tab had before any of them were removed to `predicate`.
+    /// Any remaining empty [`Node`]s are also removed.
+    ///
+    /// Returns the removed tabs, e.g. to release the resources they hold.
+    pub fn retain_tabs_with<F>(&mut self, mut predicate: F) -> Vec<Tab>
+    where
+        F: FnMut((NodeIndex, TabIndex), &mut Tab) -> bool,
     {
         let mut emptied_nodes = HashSet::default();
+        let mut removed = Vec::new();
         for (index, node) in self.nodes.iter_mut().enumerate() {
-            node.retain_tabs(&mut predicate);
+            removed.extend(
+                node.retain_tabs_with(|tab_index, tab| {
+                    predicate((NodeIndex(index), tab_index), tab)
+                }),
+            );
             if node.is_empty() {
                 emptied_nodes.insert(NodeIndex(index));
             }
         }
         self.balance(emptied_nodes);
+        removed
+    }
+
+    /// Forgets the focused leaf, expands collapsed leaves, and makes the first tab of each leaf active with its tab
+    /// bar scrolled to the start. See [`DockState::strip_runtime_state`](crate::DockState::strip_runtime_state).
+    pub(crate) fn strip_runtime_state(&mut self) {
+        self.focused_node = None;
+        for index in 0..self.nodes.len() {
+            let Node::Leaf(leaf) = &mut self.nodes[index] else {
+                continue;
+            };
+            leaf.active = TabIndex(0);
+            leaf.scroll = 0.0;
+            leaf.scroll_target = None;
+            leaf.clear_selection();
+            if leaf.collapsed {
+                leaf.collapsed = false;
+                self.node_update_collapsed(NodeIndex(index));
+            }
+        }
     }
 
     /// Sets the collapsing state of the [`Tree`].
@@ -859,6 +1333,9 @@ impl<Tab> Tree<Tab> {
         }
         if !emptied_parents.is_empty() {
             self.balance(emptied_parents);
+        } else if self.nodes.first().is_some_and(Node::is_empty) {
+            // Like `remove_leaf`, a tree without leaves has no nodes.
+            self.nodes.clear();
         }
     }
 
@@ -934,6 +1411,73 @@ impl<Tab> Tree<Tab> {
         }
         None
     }
+
+    /// Find the node with the given [`NodeId`].
+    ///
+    /// Returns the index of that node in this surface.
+    pub fn find_node_by_id(&self, node_id: NodeId) -> Option<NodeIndex> {
+        self.nodes
+            .iter()
+            .position(|node| node.id() == Some(node_id))
+            .map(NodeIndex)
+    }
+
+    /// Find the tab with the given [`TabId`].
+    ///
+    /// Returns the indices in where that node and tab is in this surface.
+    ///
+    /// The returned [`NodeIndex`] will always point to a [`Node::Leaf`].
+    pub fn find_by_id(&self, tab_id: TabId) -> Option<(NodeIndex, TabIndex)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .find_map(|(node_index, node)| {
+                let tab_index = node.get_leaf()?.tab_index(tab_id)?;
+                Some((NodeIndex(node_index), tab_index))
+            })
+    }
+
+    /// Returns `true` if every leaf under `node_index` is auto-hidden.
+    fn is_fully_auto_hidden(&self, node_index: NodeIndex) -> bool {
+        match self.nodes.get(node_index.0) {
+            Some(Node::Leaf(leaf)) => leaf.auto_hidden,
+            Some(Node::Horizontal(_) | Node::Vertical(_)) => {
+                self.is_fully_auto_hidden(node_index.left())
+                    && self.is_fully_auto_hidden(node_index.right())
+            }
+            Some(Node::Empty) | None => false,
+        }
+    }
+
+    /// Returns `true` if the node takes no space in the layout, because all of its leaves are auto-hidden.
+    ///
+    /// Nothing is hidden if all leaves of the tree are auto-hidden.
+    pub(crate) fn is_auto_hidden(&self, node_index: NodeIndex) -> bool {
+        self.is_fully_auto_hidden(node_index) && !self.is_fully_auto_hidden(NodeIndex::root())
+    }
+
+    /// Returns the edge of the surface at which the strip listing an auto-hidden node is shown,
+    /// or `None` if the node isn't auto-hidden.
+    ///
+    /// It's the side of the split which the topmost auto-hidden ancestor of the node is on.
+    pub(crate) fn auto_hide_edge(&self, node_index: NodeIndex) -> Option<Split> {
+        if !self.is_auto_hidden(node_index) {
+            return None;
+        }
+        let mut node = node_index;
+        while let Some(parent) = node.parent() {
+            if !self.is_fully_auto_hidden(parent) {
+                return Some(match (&self[parent], node.is_left()) {
+                    (Node::Horizontal(_), true) => Split::Left,
+                    (Node::Horizontal(_), false) => Split::Right,
+                    (_, true) => Split::Above,
+                    (_, false) => Split::Below,
+                });
+            }
+            node = parent;
+        }
+        None
+    }
 }
 
 impl<Tab> Tree<Tab>
@@ -986,4 +1530,205 @@ mod test {
         tree.retain_tabs(|_| true);
         assert!(tree.find_tab(&Tab(0)).is_some());
     }
+
+    /// Checks on which edge auto-hidden leaves are listed, that nothing is hidden once all leaves are, and that a
+    /// subtree whose leaves are all auto-hidden is listed on the subtree's edge while its parent stays visible.
+    #[test]
+    fn auto_hide_edges() {
+        let mut tree: Tree<Tab> = Tree::new(vec![Tab(0)]);
+        tree.split_below(NodeIndex::root(), 0.5, vec![Tab(1)]);
+        let [_, left] = tree.split_left(NodeIndex::root(), 0.5, vec![Tab(2)]);
+        let (top, _) = tree.find_tab(&Tab(0)).unwrap();
+        let (bottom, _) = tree.find_tab(&Tab(1)).unwrap();
+
+        tree[left].get_leaf_mut().unwrap().set_auto_hidden(true);
+        assert_eq!(tree.auto_hide_edge(left), Some(Split::Left));
+        assert_eq!(tree.auto_hide_edge(top), None);
+
+        // Nothing is hidden once all leaves are.
+        tree[bottom].get_leaf_mut().unwrap().set_auto_hidden(true);
+        tree[top].get_leaf_mut().unwrap().set_auto_hidden(true);
+        assert_eq!(tree.auto_hide_edge(left), None);
+        assert!(!tree.is_auto_hidden(top));
+
+        // A subtree whose leaves are all hidden is listed on the edge of the subtree.
+        tree[left].get_leaf_mut().unwrap().set_auto_hidden(false);
+        assert_eq!(tree.auto_hide_edge(top), Some(Split::Right));
+        assert_eq!(tree.auto_hide_edge(bottom), Some(Split::Right));
+        assert!(!tree.is_auto_hidden(NodeIndex::root()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn nested_serialization() {
+        let tabs = |tree: &Tree<u64>| {
+            (0..tree.len())
+                .map(|i| tree[NodeIndex(i)].tabs().map(<[u64]>::to_vec))
+                .collect::<Vec<_>>()
+        };
+
+        let mut tree = Tree::new(vec![1]);
+        let [_, right] = tree.split_right(NodeIndex::root(), 0.3, vec![2]);
+        tree.split_below(right, 0.6, vec![3, 4]);
+
+        let json = serde_json::to_value(&tree).unwrap();
+        assert!(json.get("nodes").is_none());
+        let leaf = &json["root"]["Horizontal"]["right"]["Vertical"]["bottom"]["Leaf"];
+        assert_eq!(leaf["tabs"], serde_json::json!([3, 4]));
+        let loaded: Tree<u64> = serde_json::from_value(json).unwrap();
+        assert_eq!(tabs(&loaded), tabs(&tree));
+
+        // Trees saved as a vector of nodes still load.
+        let leaf = |tab: u64| {
+            serde_json::json!({
+                "Leaf": { "tabs": [tab], "active": 0, "scroll": 0.0, "collapsed": false }
+            })
+        };
+        let split = serde_json::json!({
+            "Horizontal": { "fraction": 0.3, "fully_collapsed": false, "collapsed_leaf_count": 0 }
+        });
+        let legacy = serde_json::json!({
+            "nodes": [split, leaf(1), leaf(2)],
+            "focused_node": null,
+            "collapsed": false,
+            "collapsed_leaf_count": 0,
+        });
+        let loaded: Tree<u64> = serde_json::from_value(legacy).unwrap();
+        assert_eq!(tabs(&loaded), [None, Some(vec![1]), Some(vec![2])]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn node_ids_survive_serialization_and_are_reserved() {
+        let id = NodeId::next();
+        let json = serde_json::to_value(id).unwrap();
+        let loaded: NodeId = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded, id);
+
+        // Loading a larger ID than any handed out so far makes later ones larger still.
+        let loaded: NodeId = serde_json::from_value(serde_json::json!(1_u64 << 40)).unwrap();
+        assert!(NodeId::next() > loaded);
+    }
+
+    #[test]
+    fn central_leaf_stays() {
+        let mut tree = Tree::new(vec![Tab(0)]);
+        let [editor, _] = tree.split_below(NodeIndex::root(), 0.7, vec![Tab(1)]);
+        tree[editor].get_leaf_mut().unwrap().set_central(true);
+
+        assert_eq!(tree.remove_tab((editor, TabIndex(0))), Some(Tab(0)));
+        assert_eq!(tree[editor].tabs(), Some([].as_slice()));
+        tree.remove_leaf(editor);
+        assert!(tree[editor].is_leaf());
+
+        tree[editor].append_tab(Tab(2));
+        tree.retain_tabs(|tab| tab.0 == 1);
+        assert!(tree[editor].is_leaf());
+
+        // The emptied sibling is removed, so the central leaf takes the place of their parent.
+        assert!(tree.filter_tabs(|_| false)[NodeIndex::root()].is_leaf());
+    }
+
+    #[test]
+    fn locked_split_keeps_fraction() {
+        let mut tree = Tree::new(vec![Tab(0)]);
+        let [main, _] = tree.split_below(NodeIndex::root(), 0.8, vec![Tab(1)]);
+        tree.split_right(main, 0.7, vec![Tab(2)]);
+        if let Node::Vertical(split) = &mut tree[NodeIndex::root()] {
+            split.set_locked(true);
+        }
+
+        tree.distribute_evenly(NodeIndex::root());
+        let Node::Vertical(split) = &tree[NodeIndex::root()] else {
+            unreachable!()
+        };
+        assert_eq!(split.fraction, 0.8);
+        let Node::Horizontal(split) = &tree[main] else {
+            unreachable!()
+        };
+        assert_eq!(split.fraction, 0.5);
+    }
+
+    #[test]
+    fn insert_tab_at_position() {
+        let mut tree = Tree::new(vec![Tab(0), Tab(2)]);
+        let [left, right] = tree.split_right(NodeIndex::root(), 0.5, vec![Tab(3)]);
+
+        tree.insert_tab((left, TabIndex(1)), Tab(1));
+        assert_eq!(tree[left].tabs(), Some([Tab(0), Tab(1), Tab(2)].as_slice()));
+        assert_eq!(tree[left].get_leaf().unwrap().active, TabIndex(1));
+        assert_eq!(tree.focused_leaf(), Some(left));
+
+        // Inserting right after the last tab appends it.
+        tree.insert_tab((right, TabIndex(1)), Tab(4));
+        assert_eq!(tree[right].tabs(), Some([Tab(3), Tab(4)].as_slice()));
+        assert_eq!(tree.focused_leaf(), Some(right));
+    }
+
+    /// Checks that a headless layout leaves room for separators and tab bars, and skips collapsed leaves.
+    #[test]
+    fn layout_without_ui() {
+        let mut tree = Tree::new(vec![Tab(0)]);
+        let [top, bottom] = tree.split_below(NodeIndex::root(), 0.5, vec![Tab(1)]);
+        let [_, right] = tree.split_right(top, 0.5, vec![Tab(2)]);
+        let style = crate::Style::default();
+        let rect = Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(400.0, 400.0));
+        tree.layout(rect, &style);
+
+        let right = tree[right].get_leaf().unwrap();
+        let bottom = tree[bottom].get_leaf().unwrap();
+        assert_eq!(right.rect().max.x, 400.0);
+        assert!(right.rect().min.x >= 200.0 + style.separator.width / 2.0 - 1.0);
+        assert_eq!(
+            right.viewport.min.y,
+            right.rect().min.y + style.tab_bar.height
+        );
+        assert_eq!(bottom.rect().max.y, 400.0);
+        assert!(bottom.rect().min.y > right.rect().max.y);
+    }
+
+    /// Checks that only laid out, non-empty nodes get their rects listed.
+    #[test]
+    fn iter_rects_after_layout() {
+        let mut tree = Tree::new(vec![Tab(0)]);
+        assert_eq!(tree.iter_rects().count(), 0);
+
+        let [left, right] = tree.split_right(NodeIndex::root(), 0.5, vec![Tab(1)]);
+        let rect = Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(400.0, 300.0));
+        tree.layout(rect, &crate::Style::default());
+        let rects: Vec<_> = tree.iter_rects().collect();
+        assert_eq!(
+            rects,
+            vec![
+                (NodeIndex::root(), rect),
+                (left, tree[left].rect().unwrap()),
+                (right, tree[right].rect().unwrap()),
+            ]
+        );
+    }
+
+    /// Checks that swapped leaves keep their places' rects, and that the focus follows the focused leaf.
+    #[test]
+    fn swap_leaves_keeps_geometry() {
+        let mut tree = Tree::new(vec![Tab(0)]);
+        let [left, right] = tree.split_right(NodeIndex::root(), 0.5, vec![Tab(1)]);
+        tree.layout(
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(400.0, 300.0)),
+            &crate::Style::default(),
+        );
+        let (left_rect, right_rect) = (tree[left].rect(), tree[right].rect());
+        tree.set_focused_node(right);
+
+        tree.swap_leaves(left, right);
+        assert_eq!(tree[left].tabs(), Some([Tab(1)].as_slice()));
+        assert_eq!(tree[right].tabs(), Some([Tab(0)].as_slice()));
+        assert_eq!(
+            (tree[left].rect(), tree[right].rect()),
+            (left_rect, right_rect)
+        );
+        assert_eq!(tree.focused_leaf(), Some(left));
+
+        tree.swap_leaves(left, NodeIndex::root());
+        assert_eq!(tree[left].tabs(), Some([Tab(1)].as_slice()));
+    }
 }
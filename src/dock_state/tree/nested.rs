@@ -0,0 +1,182 @@
+//! Serialization of a [`Tree`] as nested nodes.
+//!
+//! Human readable formats, like JSON or RON, get the nodes nested the way they're laid out, so the
+//! output doesn't depend on the holes of the binary tree vector and is easy to edit by hand.
+//! Other formats keep the more compact vector of nodes, which human readable formats also still
+//! accept, so snapshots saved by older versions can be loaded.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{LeafNode, Node, NodeIndex, SplitNode, Tree};
+
+/// A node and all of its children.
+#[derive(Serialize)]
+enum NestedRef<'a, Tab> {
+    Empty,
+    Leaf(&'a LeafNode<Tab>),
+    Horizontal {
+        split: &'a SplitNode,
+        left: Box<NestedRef<'a, Tab>>,
+        right: Box<NestedRef<'a, Tab>>,
+    },
+    Vertical {
+        split: &'a SplitNode,
+        top: Box<NestedRef<'a, Tab>>,
+        bottom: Box<NestedRef<'a, Tab>>,
+    },
+}
+
+/// Owned counterpart of [`NestedRef`].
+#[derive(Deserialize)]
+enum Nested<Tab> {
+    Empty,
+    Leaf(LeafNode<Tab>),
+    Horizontal {
+        split: SplitNode,
+        left: Box<Nested<Tab>>,
+        right: Box<Nested<Tab>>,
+    },
+    Vertical {
+        split: SplitNode,
+        top: Box<Nested<Tab>>,
+        bottom: Box<Nested<Tab>>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Tree")]
+struct NestedTreeRef<'a, Tab> {
+    root: NestedRef<'a, Tab>,
+    focused_node: Option<NodeIndex>,
+    collapsed: bool,
+    collapsed_leaf_count: i32,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Tree")]
+struct FlatTreeRef<'a, Tab> {
+    nodes: &'a [Node<Tab>],
+    focused_node: Option<NodeIndex>,
+    collapsed: bool,
+    collapsed_leaf_count: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Tree")]
+struct FlatTree<Tab> {
+    nodes: Vec<Node<Tab>>,
+    focused_node: Option<NodeIndex>,
+    collapsed: bool,
+    collapsed_leaf_count: i32,
+}
+
+/// Either layout of a tree in a human readable format.
+#[derive(Deserialize)]
+#[serde(rename = "Tree", bound(deserialize = "Tab: Deserialize<'de>"))]
+struct AnyTree<Tab> {
+    #[serde(default)]
+    root: Option<Nested<Tab>>,
+    #[serde(default)]
+    nodes: Option<Vec<Node<Tab>>>,
+    #[serde(default)]
+    focused_node: Option<NodeIndex>,
+    collapsed: bool,
+    collapsed_leaf_count: i32,
+}
+
+impl<'a, Tab> NestedRef<'a, Tab> {
+    fn new(nodes: &'a [Node<Tab>], index: NodeIndex) -> Self {
+        let child = |index: NodeIndex| Box::new(Self::new(nodes, index));
+        match nodes.get(index.0) {
+            None | Some(Node::Empty) => NestedRef::Empty,
+            Some(Node::Leaf(leaf)) => NestedRef::Leaf(leaf),
+            Some(Node::Horizontal(split)) => NestedRef::Horizontal {
+                split,
+                left: child(index.left()),
+                right: child(index.right()),
+            },
+            Some(Node::Vertical(split)) => NestedRef::Vertical {
+                split,
+                top: child(index.left()),
+                bottom: child(index.right()),
+            },
+        }
+    }
+}
+
+impl<Tab> Nested<Tab> {
+    /// Puts this node and its children into `nodes`, rooted at `index`.
+    fn place(self, nodes: &mut Vec<Node<Tab>>, index: NodeIndex) {
+        let (node, children) = match self {
+            Nested::Empty => return,
+            Nested::Leaf(leaf) => (Node::Leaf(leaf), None),
+            Nested::Horizontal { split, left, right } => {
+                (Node::Horizontal(split), Some((left, right)))
+            }
+            Nested::Vertical { split, top, bottom } => (Node::Vertical(split), Some((top, bottom))),
+        };
+        if nodes.len() <= index.0 {
+            nodes.resize_with(index.0 + 1, || Node::Empty);
+        }
+        nodes[index.0] = node;
+        if let Some((left, right)) = children {
+            left.place(nodes, index.left());
+            right.place(nodes, index.right());
+        }
+    }
+}
+
+impl<Tab: Serialize> Serialize for Tree<Tab> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            NestedTreeRef {
+                root: NestedRef::new(&self.nodes, NodeIndex::root()),
+                focused_node: self.focused_node,
+                collapsed: self.collapsed,
+                collapsed_leaf_count: self.collapsed_leaf_count,
+            }
+            .serialize(serializer)
+        } else {
+            FlatTreeRef {
+                nodes: &self.nodes,
+                focused_node: self.focused_node,
+                collapsed: self.collapsed,
+                collapsed_leaf_count: self.collapsed_leaf_count,
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl<'de, Tab: Deserialize<'de>> Deserialize<'de> for Tree<Tab> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let tree = AnyTree::deserialize(deserializer)?;
+            let nodes = match (tree.root, tree.nodes) {
+                (Some(root), _) => {
+                    let mut nodes = Vec::new();
+                    root.place(&mut nodes, NodeIndex::root());
+                    nodes
+                }
+                (None, Some(nodes)) => nodes,
+                (None, None) => return Err(serde::de::Error::missing_field("root")),
+            };
+            let mut tree = Tree {
+                nodes,
+                focused_node: tree.focused_node,
+                collapsed: tree.collapsed,
+                collapsed_leaf_count: tree.collapsed_leaf_count,
+            };
+            tree.fill_last_level();
+            Ok(tree)
+        } else {
+            let tree = FlatTree::deserialize(deserializer)?;
+            Ok(Tree {
+                nodes: tree.nodes,
+                focused_node: tree.focused_node,
+                collapsed: tree.collapsed,
+                collapsed_leaf_count: tree.collapsed_leaf_count,
+            })
+        }
+    }
+}
@@ -1,20 +1,34 @@
-use egui::Rect;
+use egui::{Rect, Vec2};
 
-use crate::TabIndex;
+use crate::{AllowedSplits, NodeId, TabBarPosition, TabId, TabIndex};
 
 /// The inner data of a [``Node::Leaf``](crate::Node), which contains tabs and can be collapsed.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct LeafNode<Tab> {
+    /// The persistent identifier of this node.
+    #[cfg_attr(feature = "serde", serde(default = "NodeId::next"))]
+    pub(crate) id: NodeId,
+
     /// The full rectangle - tab bar plus tab body.
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::utils::rect_nothing"))]
     pub rect: Rect,
 
     /// The tab body rectangle.
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::utils::rect_nothing"))]
     pub viewport: Rect,
 
     /// All the tabs in this node.
     pub tabs: Vec<Tab>,
 
+    /// The [`TabId`]s of the tabs in this node, in the same order as [`LeafNode::tabs`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tab_ids: Vec<TabId>,
+
+    /// The [`TabId`]s of the selected tabs, see [`LeafNode::set_tab_selected`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) selected: Vec<TabId>,
+
     /// The opened tab.
     pub active: TabIndex,
 
@@ -23,22 +37,73 @@ pub struct LeafNode<Tab> {
 
     /// Whether the leaf is collapsed.
     pub collapsed: bool,
+
+    /// Whether the tab bar of the leaf is hidden.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tab_bar_hidden: bool,
+
+    /// Whether the tab bar of the leaf is hidden while it has a single tab, or `None` to follow
+    /// [`DockArea::hide_single_tab_bars`](crate::DockArea::hide_single_tab_bars).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub single_tab_bar_hidden: Option<bool>,
+
+    /// Whether the leaf is hidden in a strip at the edge of its surface.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_hidden: bool,
+
+    /// Whether the leaf stays in the layout when all of its tabs are closed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub central: bool,
+
+    /// The directions in which this leaf can be split by dropping a tab onto it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allowed_splits: AllowedSplits,
+
+    /// Side of this leaf its tab bar is shown on, overriding [`TabBarStyle::position`](crate::TabBarStyle::position).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tab_bar_position: Option<TabBarPosition>,
+
+    /// The size below which this leaf isn't shrunk when its surface gets smaller, as long as there's room for it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_size: Vec2,
+
+    /// The tab which the tab bar should scroll to the next time it's shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scroll_target: Option<TabIndex>,
+
+    /// The rectangles of the tab buttons, clipped to the visible part of the tab bar.
+    ///
+    /// Empty if the tab bar is hidden.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub tab_rects: Vec<Rect>,
 }
 
 impl<Tab> LeafNode<Tab> {
     /// Create New LeafNode with specified ``tabs``, all other internal values will be filled by "nothing" defaults.
-    pub const fn new(tabs: Vec<Tab>) -> Self {
+    pub fn new(tabs: Vec<Tab>) -> Self {
         LeafNode {
+            id: NodeId::next(),
             rect: Rect::NOTHING,
             viewport: Rect::NOTHING,
+            tab_ids: tabs.iter().map(|_| TabId::next()).collect(),
+            selected: Vec::new(),
             tabs,
             active: TabIndex(0),
             scroll: 0.0,
             collapsed: false,
+            tab_bar_hidden: false,
+            single_tab_bar_hidden: None,
+            auto_hidden: false,
+            central: false,
+            allowed_splits: AllowedSplits::All,
+            tab_bar_position: None,
+            min_size: Vec2::ZERO,
+            scroll_target: None,
+            tab_rects: Vec::new(),
         }
     }
 
-    /// Set the active tab of this [`LeafNode`]
+    /// Set the active tab of this [`LeafNode`], and scroll the tab bar so that it's in view.
     ///
     /// If ``active_tab`` is out of bounds, it will be ignored and the active tab will not be changed.
     #[inline]
@@ -46,9 +111,148 @@ impl<Tab> LeafNode<Tab> {
         let index = active_tab.into();
         if index.0 < self.len() {
             self.active = index;
+            self.scroll_target = Some(index);
         }
     }
 
+    /// Get the index of the active tab of this [`LeafNode`], or `None` if it has no tabs.
+    #[inline]
+    pub fn active_tab(&self) -> Option<TabIndex> {
+        (self.active.0 < self.len()).then_some(self.active)
+    }
+
+    /// Scroll the tab bar of this [`LeafNode`] so that the tab at ``tab_index`` is in view
+    /// the next time it's shown.
+    ///
+    /// If ``tab_index`` is out of bounds, it will be ignored.
+    #[inline]
+    pub fn scroll_tab_into_view(&mut self, tab_index: impl Into<TabIndex>) {
+        let index = tab_index.into();
+        if index.0 < self.len() {
+            self.scroll_target = Some(index);
+        }
+    }
+
+    /// Hide or show the tab bar of this [`LeafNode`].
+    ///
+    /// A leaf without a tab bar only shows the body of its active tab, but tabs can still be dropped onto it.
+    ///
+    /// See also [`TabViewer::tab_bar_visibility`](crate::TabViewer::tab_bar_visibility).
+    #[inline]
+    pub fn set_tab_bar_hidden(&mut self, hidden: bool) {
+        self.tab_bar_hidden = hidden;
+    }
+
+    /// Returns `true` if the tab bar of this [`LeafNode`] is hidden.
+    #[inline]
+    pub fn is_tab_bar_hidden(&self) -> bool {
+        self.tab_bar_hidden
+    }
+
+    /// Hide the tab bar of this [`LeafNode`] while it has a single tab, or show it, overriding
+    /// [`DockArea::hide_single_tab_bars`](crate::DockArea::hide_single_tab_bars) unless it's `None`.
+    ///
+    /// Unlike [`LeafNode::set_tab_bar_hidden`], a thin handle is left in place of the tab bar, by which the tab can
+    /// still be dragged.
+    #[inline]
+    pub fn set_single_tab_bar_hidden(&mut self, hidden: Option<bool>) {
+        self.single_tab_bar_hidden = hidden;
+    }
+
+    /// Returns whether the tab bar of this [`LeafNode`] is hidden while it has a single tab, if it overrides the
+    /// [`DockArea`](crate::DockArea).
+    #[inline]
+    pub fn single_tab_bar_hidden(&self) -> Option<bool> {
+        self.single_tab_bar_hidden
+    }
+
+    /// Get the [`NodeId`] of this [`LeafNode`].
+    #[inline]
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Hide this [`LeafNode`] in a strip at the edge of its surface, or put it back into the layout.
+    ///
+    /// The space of an auto-hidden leaf is taken by its sibling, and the strip lists the titles of its tabs.
+    /// Hovering over or clicking a title slides the leaf out over the layout, with that tab active.
+    /// The leaf goes back into the strip once the pointer leaves it, or, if it was opened by a click,
+    /// once something else is clicked.
+    ///
+    /// A leaf is shown in the layout as usual if every other leaf of its surface is auto-hidden too.
+    #[inline]
+    pub fn set_auto_hidden(&mut self, auto_hidden: bool) {
+        self.auto_hidden = auto_hidden;
+    }
+
+    /// Returns `true` if this [`LeafNode`] is hidden in a strip at the edge of its surface.
+    #[inline]
+    pub fn is_auto_hidden(&self) -> bool {
+        self.auto_hidden
+    }
+
+    /// Make this [`LeafNode`] the central leaf of its surface, like the editor area of an IDE, or a regular leaf.
+    ///
+    /// A central leaf stays in the layout when all of its tabs are closed or moved out of it, and isn't removed by
+    /// [`Tree::remove_leaf`](crate::Tree::remove_leaf). While it has no tabs, its body shows
+    /// [`TabViewer::empty_ui`](crate::TabViewer::empty_ui).
+    #[inline]
+    pub fn set_central(&mut self, central: bool) {
+        self.central = central;
+    }
+
+    /// Returns `true` if this [`LeafNode`] stays in the layout when all of its tabs are closed.
+    #[inline]
+    pub fn is_central(&self) -> bool {
+        self.central
+    }
+
+    /// Shows the tab bar of this [`LeafNode`] on the given side, or on the side set by
+    /// [`TabBarStyle::position`](crate::TabBarStyle::position) if it's `None`.
+    #[inline]
+    pub fn set_tab_bar_position(&mut self, position: Option<TabBarPosition>) {
+        self.tab_bar_position = position;
+    }
+
+    /// Returns the side this [`LeafNode`] shows its tab bar on, if it overrides the one of the style.
+    #[inline]
+    pub fn tab_bar_position(&self) -> Option<TabBarPosition> {
+        self.tab_bar_position
+    }
+
+    /// Sets the size below which this [`LeafNode`] isn't shrunk when its surface gets smaller, and below which its
+    /// separators can't be dragged.
+    ///
+    /// When there isn't room for the minimum sizes of all leaves, splits are laid out as set by
+    /// [`SeparatorStyle::min_size_overflow`](crate::SeparatorStyle::min_size_overflow). The fractions of the splits
+    /// aren't changed, so the layout comes back once there's room again.
+    #[inline]
+    pub fn set_min_size(&mut self, min_size: Vec2) {
+        self.min_size = min_size;
+    }
+
+    /// Returns the size below which this [`LeafNode`] isn't shrunk, see [`LeafNode::set_min_size`].
+    #[inline]
+    pub fn min_size(&self) -> Vec2 {
+        self.min_size
+    }
+
+    /// Sets the directions in which this [`LeafNode`] can be split by dropping a tab onto it, with its split
+    /// buttons or with [`DockState::split_tab`](crate::DockState::split_tab).
+    ///
+    /// Only the splits allowed by both this and [`DockArea::allowed_splits`](crate::DockArea::allowed_splits)
+    /// are offered.
+    #[inline]
+    pub fn set_allowed_splits(&mut self, allowed_splits: AllowedSplits) {
+        self.allowed_splits = allowed_splits;
+    }
+
+    /// Returns the directions in which this [`LeafNode`] can be split, see [`LeafNode::set_allowed_splits`].
+    #[inline]
+    pub fn allowed_splits(&self) -> AllowedSplits {
+        self.allowed_splits
+    }
+
     /// Set the area this [`LeafNode`] Occupies on screen.
     #[inline]
     pub fn set_rect(&mut self, new_rect: Rect) {
@@ -70,6 +274,74 @@ impl<Tab> LeafNode<Tab> {
         self.rect
     }
 
+    /// Get the [`TabId`] of the tab at ``tab_index``, or `None` if it's out of bounds.
+    #[inline]
+    pub fn tab_id(&self, tab_index: impl Into<TabIndex>) -> Option<TabId> {
+        self.tab_ids.get(tab_index.into().0).copied()
+    }
+
+    /// Get the [`TabId`]s of the ``Tab``s of this [`LeafNode`], in the same order as the tabs.
+    #[inline]
+    pub fn tab_ids(&self) -> &[TabId] {
+        &self.tab_ids
+    }
+
+    /// Get the index of the tab with the given [`TabId`], or `None` if it isn't in this [`LeafNode`].
+    #[inline]
+    pub fn tab_index(&self, tab_id: TabId) -> Option<TabIndex> {
+        self.tab_ids
+            .iter()
+            .position(|&id| id == tab_id)
+            .map(TabIndex)
+    }
+
+    /// Select or deselect the tab at `tab_index`.
+    ///
+    /// When a selected tab is dragged, the other selected tabs of this [`LeafNode`] are moved along with it,
+    /// and when it's closed, they're closed too. Users select tabs by clicking them with
+    /// [`Modifiers::COMMAND`](egui::Modifiers::COMMAND) or [`Modifiers::SHIFT`](egui::Modifiers::SHIFT) held.
+    pub fn set_tab_selected(&mut self, tab_index: impl Into<TabIndex>, selected: bool) {
+        let Some(tab_id) = self.tab_id(tab_index) else {
+            return;
+        };
+        self.selected.retain(|&id| id != tab_id);
+        if selected {
+            self.selected.push(tab_id);
+        }
+    }
+
+    /// Returns `true` if the tab at `tab_index` is selected.
+    #[inline]
+    pub fn is_tab_selected(&self, tab_index: impl Into<TabIndex>) -> bool {
+        self.tab_id(tab_index)
+            .is_some_and(|tab_id| self.selected.contains(&tab_id))
+    }
+
+    /// Returns the indices of the selected tabs, in the order of the tabs.
+    pub fn selected_tabs(&self) -> Vec<TabIndex> {
+        self.tab_ids
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| self.selected.contains(id))
+            .map(|(index, _)| TabIndex(index))
+            .collect()
+    }
+
+    /// Deselects all tabs.
+    #[inline]
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Gives new [`TabId`]s to tabs pushed directly onto [`LeafNode::tabs`], and forgets the ones of tabs
+    /// removed from it, so that there is exactly one [`TabId`] per tab.
+    pub(crate) fn sync_tab_ids(&mut self) {
+        self.tab_ids.truncate(self.tabs.len());
+        while self.tab_ids.len() < self.tabs.len() {
+            self.tab_ids.push(TabId::next());
+        }
+    }
+
     /// Get immutable access to the ``Tab``s of this [`LeafNode`]
     #[inline]
     pub fn tabs(&self) -> &[Tab] {
@@ -88,8 +360,10 @@ impl<Tab> LeafNode<Tab> {
     #[track_caller]
     #[inline]
     pub fn append_tab(&mut self, tab: Tab) {
+        self.sync_tab_ids();
         self.active = TabIndex(self.tabs.len());
         self.tabs.push(tab);
+        self.tab_ids.push(TabId::next());
     }
 
     /// Insert a ``Tab`` to this [`LeafNode`]s tab list at the specified [`TabIndex`].
@@ -103,7 +377,9 @@ impl<Tab> LeafNode<Tab> {
     #[inline]
     pub fn insert_tab(&mut self, tab_index: impl Into<TabIndex>, tab: Tab) {
         let tab_index = tab_index.into();
+        self.sync_tab_ids();
         self.tabs.insert(tab_index.0, tab);
+        self.tab_ids.insert(tab_index.0, TabId::next());
         self.active = tab_index;
     }
 
@@ -117,18 +393,43 @@ impl<Tab> LeafNode<Tab> {
     #[inline]
     pub fn remove_tab(&mut self, tab_index: impl Into<TabIndex>) -> Option<Tab> {
         let index = tab_index.into();
+        self.sync_tab_ids();
         if index <= self.active {
             self.active.0 = self.active.0.saturating_sub(1);
         }
+        self.tab_ids.remove(index.0);
         Some(self.tabs.remove(index.0))
     }
 
     /// Removes all tabs for which `predicate` returns `false`.
-    pub fn retain_tabs<F>(&mut self, predicate: F)
+    pub fn retain_tabs<F>(&mut self, mut predicate: F)
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        self.tabs.retain_mut(predicate);
+        self.retain_tabs_with(|_, tab| predicate(tab));
+    }
+
+    /// Removes all tabs for which `predicate` returns `false`,
+    /// passing the index each tab had before any of them were removed to `predicate`.
+    ///
+    /// Returns the removed tabs.
+    pub fn retain_tabs_with<F>(&mut self, mut predicate: F) -> Vec<Tab>
+    where
+        F: FnMut(TabIndex, &mut Tab) -> bool,
+    {
+        self.sync_tab_ids();
+        let tabs = std::mem::take(&mut self.tabs);
+        let tab_ids = std::mem::take(&mut self.tab_ids);
+        let mut removed = Vec::new();
+        for (index, (mut tab, tab_id)) in tabs.into_iter().zip(tab_ids).enumerate() {
+            if predicate(TabIndex(index), &mut tab) {
+                self.tabs.push(tab);
+                self.tab_ids.push(tab_id);
+            } else {
+                removed.push(tab);
+            }
+        }
+        removed
     }
 
     /// Return the area and tab which is currently representing this [`LeafNode`]
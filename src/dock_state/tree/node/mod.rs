@@ -1,4 +1,4 @@
-use crate::{Split, TabIndex};
+use crate::{NodeId, Split, TabId, TabIndex};
 use egui::Rect;
 
 mod leaf;
@@ -48,7 +48,7 @@ impl<Tab> Node<Tab> {
 
     /// Constructs a leaf node with a given list of `tabs`.
     #[inline(always)]
-    pub const fn leaf_with(tabs: Vec<Tab>) -> Self {
+    pub fn leaf_with(tabs: Vec<Tab>) -> Self {
         Self::Leaf(LeafNode::new(tabs))
     }
 
@@ -76,6 +76,18 @@ impl<Tab> Node<Tab> {
         }
     }
 
+    /// Get the [`NodeId`] of the node, which stays the same when the node is moved around the [`Tree`](crate::Tree).
+    ///
+    /// Returns [`None`] if node is of the [`Empty`](Node::Empty) variant.
+    #[inline]
+    pub fn id(&self) -> Option<NodeId> {
+        match self {
+            Self::Empty => None,
+            Self::Leaf(leaf) => Some(leaf.id()),
+            Self::Vertical(split) | Self::Horizontal(split) => Some(split.id()),
+        }
+    }
+
     /// Returns `true` if the node is a [`Empty`](Node::Empty), otherwise `false`.
     #[inline(always)]
     pub const fn is_empty(&self) -> bool {
@@ -322,41 +334,136 @@ impl<Tab> Node<Tab> {
     }
 
     /// Returns a new [`Node`] while mapping and filtering the tab type.
-    /// If this [`Node`] remains empty, it will change to [`Node::Empty`].
-    pub fn filter_map_tabs<F, NewTab>(&self, function: F) -> Node<NewTab>
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's a
+    /// [central](LeafNode::set_central) leaf.
+    pub fn filter_map_tabs<F, NewTab>(&self, mut function: F) -> Node<NewTab>
     where
         F: FnMut(&Tab) -> Option<NewTab>,
+    {
+        self.filter_map_tabs_indexed(move |_, tab| function(tab))
+    }
+
+    /// Returns a new [`Node`] while mapping and filtering the tab type,
+    /// passing the index each tab had in this [`Node`] to `function`.
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's a
+    /// [central](LeafNode::set_central) leaf.
+    pub fn filter_map_tabs_indexed<F, NewTab>(&self, function: F) -> Node<NewTab>
+    where
+        F: FnMut(TabIndex, &Tab) -> Option<NewTab>,
     {
         match self {
-            Node::Leaf(leaf) => {
-                let LeafNode {
-                    rect,
-                    viewport,
-                    tabs,
-                    active,
-                    scroll,
-                    collapsed,
-                } = leaf;
-                let tabs: Vec<_> = tabs.iter().filter_map(function).collect();
-                if tabs.is_empty() {
-                    Node::Empty
-                } else {
-                    Node::Leaf(LeafNode {
-                        rect: *rect,
-                        viewport: *viewport,
-                        tabs,
-                        active: *active,
-                        scroll: *scroll,
-                        collapsed: *collapsed,
-                    })
-                }
-            }
+            Node::Leaf(leaf) => Self::filter_map_leaf(leaf, &leaf.tabs, function),
             Node::Empty => Node::Empty,
             Node::Vertical(split) => Node::Vertical(split.clone()),
             Node::Horizontal(split) => Node::Horizontal(split.clone()),
         }
     }
 
+    /// Consumes this [`Node`] and returns a new one while mapping and filtering the tab type.
+    /// Unlike [`Node::filter_map_tabs`], the tabs are moved into `function` instead of being borrowed.
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's a
+    /// [central](LeafNode::set_central) leaf.
+    pub fn into_filter_map_tabs<F, NewTab>(self, mut function: F) -> Node<NewTab>
+    where
+        F: FnMut(Tab) -> Option<NewTab>,
+    {
+        match self {
+            Node::Leaf(mut leaf) => {
+                let tabs = std::mem::take(&mut leaf.tabs);
+                Self::filter_map_leaf(&leaf, tabs, |_, tab| function(tab))
+            }
+            Node::Empty => Node::Empty,
+            Node::Vertical(split) => Node::Vertical(split),
+            Node::Horizontal(split) => Node::Horizontal(split),
+        }
+    }
+
+    /// Builds a copy of `leaf` holding the `tabs` for which `function` returns a new tab,
+    /// or [`Node::Empty`] if there are none left and the leaf isn't [central](LeafNode::set_central).
+    ///
+    /// `tabs` are the tabs of `leaf`, either borrowed or taken out of it.
+    fn filter_map_leaf<T, NewTab>(
+        leaf: &LeafNode<Tab>,
+        tabs: impl IntoIterator<Item = T>,
+        mut function: impl FnMut(TabIndex, T) -> Option<NewTab>,
+    ) -> Node<NewTab> {
+        let LeafNode {
+            id,
+            rect,
+            viewport,
+            tabs: _,
+            tab_ids,
+            selected,
+            active,
+            scroll,
+            collapsed,
+            tab_bar_hidden,
+            single_tab_bar_hidden,
+            auto_hidden,
+            central,
+            allowed_splits,
+            tab_bar_position,
+            min_size,
+            scroll_target,
+            tab_rects,
+        } = leaf;
+        let mut tab_count = 0;
+        // Tabs which were pushed directly onto `tabs` get new IDs, like in `LeafNode::sync_tab_ids`.
+        let (tabs, tab_ids): (Vec<_>, Vec<_>) = tabs
+            .into_iter()
+            .zip(
+                tab_ids
+                    .iter()
+                    .copied()
+                    .chain(std::iter::repeat_with(TabId::next)),
+            )
+            .enumerate()
+            .filter_map(|(index, (tab, tab_id))| {
+                tab_count += 1;
+                function(TabIndex(index), tab).map(|tab| (tab, tab_id))
+            })
+            .unzip();
+        // The rectangles no longer match the tabs if any of them were filtered out.
+        let tab_rects = if tabs.len() == tab_count {
+            tab_rects.clone()
+        } else {
+            Vec::new()
+        };
+        if tabs.is_empty() && !central {
+            Node::Empty
+        } else {
+            Node::Leaf(LeafNode {
+                id: *id,
+                rect: *rect,
+                viewport: *viewport,
+                tabs,
+                tab_ids,
+                selected: selected.clone(),
+                active: *active,
+                scroll: *scroll,
+                collapsed: *collapsed,
+                tab_bar_hidden: *tab_bar_hidden,
+                single_tab_bar_hidden: *single_tab_bar_hidden,
+                auto_hidden: *auto_hidden,
+                central: *central,
+                allowed_splits: *allowed_splits,
+                tab_bar_position: *tab_bar_position,
+                min_size: *min_size,
+                scroll_target: *scroll_target,
+                tab_rects,
+            })
+        }
+    }
+
+    /// Consumes this [`Node`] and returns a new one while mapping the tab type.
+    /// Unlike [`Node::map_tabs`], the tabs are moved into `function` instead of being borrowed.
+    pub fn into_map_tabs<F, NewTab>(self, mut function: F) -> Node<NewTab>
+    where
+        F: FnMut(Tab) -> NewTab,
+    {
+        self.into_filter_map_tabs(move |tab| Some(function(tab)))
+    }
+
     /// Returns a new [`Node`] while mapping the tab type.
     pub fn map_tabs<F, NewTab>(&self, mut function: F) -> Node<NewTab>
     where
@@ -366,7 +473,8 @@ impl<Tab> Node<Tab> {
     }
 
     /// Returns a new [`Node`] while filtering the tab type.
-    /// If this [`Node`] remains empty, it will change to [`Node::Empty`].
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's a
+    /// [central](LeafNode::set_central) leaf.
     pub fn filter_tabs<F>(&self, mut predicate: F) -> Node<Tab>
     where
         F: FnMut(&Tab) -> bool,
@@ -376,16 +484,32 @@ impl<Tab> Node<Tab> {
     }
 
     /// Removes all tabs for which `predicate` returns `false`.
-    /// If this [`Node`] remains empty, it will change to [`Node::Empty`].
-    pub fn retain_tabs<F>(&mut self, predicate: F)
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's a
+    /// [central](LeafNode::set_central) leaf.
+    pub fn retain_tabs<F>(&mut self, mut predicate: F)
     where
         F: FnMut(&mut Tab) -> bool,
     {
-        if let Node::Leaf(leaf) = self {
-            leaf.retain_tabs(predicate);
-            if leaf.tabs.is_empty() {
-                *self = Node::Empty;
-            }
+        self.retain_tabs_with(|_, tab| predicate(tab));
+    }
+
+    /// Removes all tabs for which `predicate` returns `false`,
+    /// passing the index each tab had before any of them were removed to `predicate`.
+    /// If this [`Node`] remains empty, it will change to [`Node::Empty`], unless it's a
+    /// [central](LeafNode::set_central) leaf.
+    ///
+    /// Returns the removed tabs.
+    pub fn retain_tabs_with<F>(&mut self, predicate: F) -> Vec<Tab>
+    where
+        F: FnMut(TabIndex, &mut Tab) -> bool,
+    {
+        let Node::Leaf(leaf) = self else {
+            return Vec::new();
+        };
+        let removed = leaf.retain_tabs_with(predicate);
+        if leaf.tabs.is_empty() && !leaf.central {
+            *self = Node::Empty;
         }
+        removed
     }
 }
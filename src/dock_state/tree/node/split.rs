@@ -1,37 +1,65 @@
 use egui::Rect;
 
+use crate::NodeId;
+
 ///the inner data of a [``Node::Horizontal``](crate::Node)/[``Node::Vertical``](crate::Node), which splits into two further nodes.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct SplitNode {
+    /// The persistent identifier of this node.
+    #[cfg_attr(feature = "serde", serde(default = "NodeId::next"))]
+    pub(crate) id: NodeId,
+
     /// The rectangle in which all children of this node are drawn.
+    #[cfg_attr(feature = "serde", serde(skip, default = "crate::utils::rect_nothing"))]
     pub rect: Rect,
 
     /// The fraction taken by the top child of this node.
     pub fraction: f32,
 
+    /// The fraction which double-clicking the separator of this node resets [`fraction`](Self::fraction) to,
+    /// if [`SeparatorStyle::double_click_reset`](crate::SeparatorStyle::double_click_reset) is enabled.
+    ///
+    /// Set to the fraction the node was split with. Nodes saved before this was added reset to `0.5`.
+    #[cfg_attr(feature = "serde", serde(default = "default_fraction"))]
+    pub default_fraction: f32,
+
     /// Whether all subnodes are collapsed.
     pub fully_collapsed: bool,
 
     /// The number of collapsed leaf subnodes.
     pub collapsed_leaf_count: i32,
+
+    /// Whether the separator of this node can't be moved by the user, see [`SplitNode::set_locked`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) locked: bool,
 }
 
 impl SplitNode {
-    /// Create a new ``SplitNode``
-    pub const fn new(
+    /// Create a new ``SplitNode``, which remembers `fraction` as its [`default_fraction`](Self::default_fraction).
+    pub fn new(
         rect: Rect,
         fraction: f32,
         fully_collapsed: bool,
         collapsed_leaf_count: i32,
     ) -> Self {
         Self {
+            id: NodeId::next(),
             rect,
             fraction,
+            default_fraction: fraction,
             fully_collapsed,
             collapsed_leaf_count,
+            locked: false,
         }
     }
+
+    /// Get the [`NodeId`] of this ``SplitNode``.
+    #[inline]
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
     /// Set the Area which this ``SplitNode`` occupies.
     #[inline]
     pub fn set_rect(&mut self, new_rect: Rect) {
@@ -42,4 +70,41 @@ impl SplitNode {
     pub fn rect(&self) -> Rect {
         self.rect
     }
+
+    /// Get the fraction of the area of this ``SplitNode`` taken by its left or top child.
+    #[inline]
+    pub fn fraction(&self) -> f32 {
+        self.fraction
+    }
+
+    /// Set the fraction of the area of this ``SplitNode`` taken by its left or top child, clamped to `0.0..=1.0`.
+    ///
+    /// The separator still keeps the children at least as large as
+    /// [`SeparatorStyle::extra`](crate::SeparatorStyle::extra) allows when the split is shown. `NaN` is ignored.
+    #[inline]
+    pub fn set_fraction(&mut self, fraction: f32) {
+        if !fraction.is_nan() {
+            self.fraction = fraction.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Locks the separator of this ``SplitNode``, so that it can't be dragged, moved with the keyboard or reset
+    /// by the user, e.g. to give a status bar or console a size controlled by the application.
+    ///
+    /// [`fraction`](Self::fraction) can still be changed from code.
+    #[inline]
+    pub fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+    }
+
+    /// Returns `true` if the separator of this ``SplitNode`` is locked, see [`SplitNode::set_locked`].
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_fraction() -> f32 {
+    0.5
 }
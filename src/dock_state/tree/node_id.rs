@@ -0,0 +1,8 @@
+persistent_id! {
+    /// Persistent identifier of a [`Node`](crate::Node), assigned when the node is created.
+    ///
+    /// Unlike a [`NodeIndex`](crate::NodeIndex), it stays the same when the [`Tree`](crate::Tree) is rearranged,
+    /// e.g. after a leaf is removed, and it's saved along with the [`DockState`](crate::DockState).
+    /// Use [`DockState::find_node_by_id`](crate::DockState::find_node_by_id) to find out where the node currently is.
+    pub struct NodeId(next: NEXT_NODE_ID);
+}
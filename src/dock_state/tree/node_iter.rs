@@ -0,0 +1,159 @@
+use crate::{Node, NodeIndex, Split, Tree};
+
+/// A non-empty node of a [`Tree`] visited by [`Tree::bfs`] or [`Tree::dfs`], with where it is in the tree.
+pub struct NodeRef<'a, Tab> {
+    /// The index of the node.
+    pub index: NodeIndex,
+
+    /// The index of the split containing the node, or `None` for the root.
+    pub parent: Option<NodeIndex>,
+
+    /// The number of splits containing the node, `0` for the root.
+    pub depth: usize,
+
+    /// The side of the split containing the node which the node is on, or `None` for the root.
+    ///
+    /// The left child of a [`Horizontal`](Node::Horizontal) node is on its [`Split::Left`], the right one on its
+    /// [`Split::Right`]. The top child of a [`Vertical`](Node::Vertical) node is on its [`Split::Above`], the
+    /// bottom one on its [`Split::Below`].
+    pub side: Option<Split>,
+
+    /// The node itself.
+    pub node: &'a Node<Tab>,
+}
+
+impl<'a, Tab> NodeRef<'a, Tab> {
+    fn new(tree: &'a Tree<Tab>, index: NodeIndex) -> Option<Self> {
+        let node = tree.nodes.get(index.0)?;
+        if node.is_empty() {
+            return None;
+        }
+        let parent = index.parent();
+        let side = parent.and_then(|parent| match (&tree[parent], index.is_left()) {
+            (Node::Horizontal(_), true) => Some(Split::Left),
+            (Node::Horizontal(_), false) => Some(Split::Right),
+            (Node::Vertical(_), true) => Some(Split::Above),
+            (Node::Vertical(_), false) => Some(Split::Below),
+            _ => None,
+        });
+        Some(Self {
+            index,
+            parent,
+            depth: index.level() - 1,
+            side,
+            node,
+        })
+    }
+}
+
+impl<Tab> Clone for NodeRef<'_, Tab> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Tab> Copy for NodeRef<'_, Tab> {}
+
+impl<Tab> std::fmt::Debug for NodeRef<'_, Tab> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeRef")
+            .field("index", &self.index)
+            .field("parent", &self.parent)
+            .field("depth", &self.depth)
+            .field("side", &self.side)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Visits the non-empty nodes of a [`Tree`] level by level, see [`Tree::bfs`].
+pub struct BreadthFirstIter<'a, Tab> {
+    tree: &'a Tree<Tab>,
+    node_idx: usize,
+}
+
+impl<'a, Tab> BreadthFirstIter<'a, Tab> {
+    pub(super) fn new(tree: &'a Tree<Tab>) -> Self {
+        Self { tree, node_idx: 0 }
+    }
+}
+
+impl<'a, Tab> Iterator for BreadthFirstIter<'a, Tab> {
+    type Item = NodeRef<'a, Tab>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The nodes are stored level by level, so walking the vector skipping holes visits them breadth first.
+        while self.node_idx < self.tree.nodes.len() {
+            let index = NodeIndex(self.node_idx);
+            self.node_idx += 1;
+            if let Some(node) = NodeRef::new(self.tree, index) {
+                return Some(node);
+            }
+        }
+        None
+    }
+}
+
+impl<Tab> std::fmt::Debug for BreadthFirstIter<'_, Tab> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BreadthFirstIter").finish_non_exhaustive()
+    }
+}
+
+/// Visits the non-empty nodes of a [`Tree`] depth first, see [`Tree::dfs`].
+pub struct DepthFirstIter<'a, Tab> {
+    tree: &'a Tree<Tab>,
+    stack: Vec<NodeIndex>,
+}
+
+impl<'a, Tab> DepthFirstIter<'a, Tab> {
+    pub(super) fn new(tree: &'a Tree<Tab>) -> Self {
+        Self {
+            tree,
+            stack: vec![NodeIndex::root()],
+        }
+    }
+}
+
+impl<'a, Tab> Iterator for DepthFirstIter<'a, Tab> {
+    type Item = NodeRef<'a, Tab>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.stack.pop() {
+            let Some(node) = NodeRef::new(self.tree, index) else {
+                continue;
+            };
+            if node.node.is_parent() {
+                self.stack.push(index.right());
+                self.stack.push(index.left());
+            }
+            return Some(node);
+        }
+        None
+    }
+}
+
+impl<Tab> std::fmt::Debug for DepthFirstIter<'_, Tab> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DepthFirstIter").finish_non_exhaustive()
+    }
+}
+
+#[test]
+fn test_traversal_order() {
+    let mut tree = Tree::new(vec![0]);
+    let [left, right] = tree.split_right(NodeIndex::root(), 0.5, vec![1]);
+    let [top, bottom] = tree.split_below(left, 0.5, vec![2]);
+
+    let bfs: Vec<_> = tree.bfs().map(|node| node.index).collect();
+    assert_eq!(bfs, vec![NodeIndex::root(), left, right, top, bottom]);
+    let dfs: Vec<_> = tree.dfs().map(|node| node.index).collect();
+    assert_eq!(dfs, vec![NodeIndex::root(), left, top, bottom, right]);
+
+    let bottom = tree.dfs().find(|node| node.index == bottom).unwrap();
+    assert_eq!(bottom.parent, Some(left));
+    assert_eq!(bottom.depth, 2);
+    assert_eq!(bottom.side, Some(Split::Below));
+    assert_eq!(bottom.node.tabs(), Some([2].as_slice()));
+    let root = tree.bfs().next().unwrap();
+    assert_eq!((root.parent, root.depth, root.side), (None, 0, None));
+}
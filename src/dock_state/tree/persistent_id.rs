@@ -0,0 +1,30 @@
+/// Defines an identifier type which is handed out from a counter shared by the whole process.
+///
+/// IDs loaded with `serde` are reserved, so that the ones handed out later don't collide with them.
+macro_rules! persistent_id {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident(next: $next:ident);) => {
+        #[doc = concat!("The next [`", stringify!($name), "`] to be handed out.")]
+        static $next: ::std::sync::atomic::AtomicU64 = ::std::sync::atomic::AtomicU64::new(0);
+
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+        $vis struct $name(u64);
+
+        impl $name {
+            #[doc = concat!("Hands out a new [`", stringify!($name), "`] which hasn't been used before.")]
+            pub(crate) fn next() -> Self {
+                $name($next.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let id = <u64 as serde::Deserialize>::deserialize(deserializer)?;
+                $next.fetch_max(id.saturating_add(1), ::std::sync::atomic::Ordering::Relaxed);
+                Ok($name(id))
+            }
+        }
+    };
+}
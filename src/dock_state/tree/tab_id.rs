@@ -0,0 +1,8 @@
+persistent_id! {
+    /// Stable identifier of a tab, assigned when it's put into a [`LeafNode`](crate::LeafNode).
+    ///
+    /// Unlike a [`TabIndex`](crate::TabIndex), it stays the same while the tab is moved around the
+    /// [`DockState`](crate::DockState), so it can be kept to refer to the tab later.
+    /// Use [`DockState::find_by_id`](crate::DockState::find_by_id) to find out where the tab currently is.
+    pub struct TabId(next: NEXT_TAB_ID);
+}
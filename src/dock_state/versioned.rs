@@ -0,0 +1,431 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{
+        value::MapAccessDeserializer, DeserializeSeed, Error, IgnoredAny, IntoDeserializer,
+        MapAccess, Visitor,
+    },
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::DockState;
+
+/// Reads and upgrades a [`DockState`] saved with an older layout schema version.
+///
+/// Implemented for all closures of the form `FnMut(u32, DockState<Tab>) -> DockState<Tab>`, which read snapshots
+/// as today's `DockState<Tab>` and only upgrade them afterwards.
+pub trait LayoutMigrator<Tab> {
+    /// Reads the `dock_state` of a snapshot saved with layout schema `version`, before any migrations are applied.
+    ///
+    /// Override this when a snapshot can't be read as today's `DockState<Tab>`, for example because `Tab` changed:
+    /// deserialize the data the way it was saved, then convert it. By default it's read as a `DockState<Tab>`.
+    fn decode<'de, D>(&mut self, version: u32, deserializer: D) -> Result<DockState<Tab>, D::Error>
+    where
+        D: Deserializer<'de>,
+        Tab: Deserialize<'de>,
+    {
+        let _ = version;
+        DockState::deserialize(deserializer)
+    }
+
+    /// Migrates `dock_state` from layout schema `version` to `version + 1`.
+    fn migrate(&mut self, version: u32, dock_state: DockState<Tab>) -> DockState<Tab>;
+}
+
+impl<Tab, F> LayoutMigrator<Tab> for F
+where
+    F: FnMut(u32, DockState<Tab>) -> DockState<Tab>,
+{
+    #[inline]
+    fn migrate(&mut self, version: u32, dock_state: DockState<Tab>) -> DockState<Tab> {
+        self(version, dock_state)
+    }
+}
+
+/// A [`DockState`] tagged with the version of the layout schema it was saved with.
+///
+/// Serialize this instead of a bare [`DockState`] when your application ships layout upgrades across releases.
+/// When loading, [`DockStateVersioned::deserialize_migrated`] brings older snapshots up to date using a
+/// [`LayoutMigrator`], which also gets to read snapshots that no longer deserialize as today's [`DockState`].
+///
+/// Snapshots of a bare [`DockState`], saved before switching to this wrapper, deserialize as version `0`.
+/// The `version` is expected to be saved before the `dock_state`, as this wrapper serializes them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockState, DockStateVersioned};
+/// const LAYOUT_VERSION: u32 = 2;
+///
+/// // A layout saved by a previous release of the application.
+/// let saved = serde_json::to_string(&DockStateVersioned::new(1, DockState::new(vec![
+///     "Files".to_string(),
+/// ])))
+/// .unwrap();
+///
+/// let loaded: DockStateVersioned<String> = serde_json::from_str(&saved).unwrap();
+/// let dock_state = loaded.migrate(LAYOUT_VERSION, |version, mut dock_state: DockState<String>| {
+///     // Version 2 introduced the "Search" tab.
+///     if version == 1 {
+///         dock_state.push_to_first_leaf("Search".to_string());
+///     }
+///     dock_state
+/// });
+///
+/// let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| tab.as_str()).collect();
+/// assert_eq!(tabs, vec!["Files", "Search"]);
+/// ```
+#[derive(Clone, Debug, Serialize)]
+pub struct DockStateVersioned<Tab> {
+    version: u32,
+    dock_state: DockState<Tab>,
+}
+
+impl<Tab> DockStateVersioned<Tab> {
+    /// Tags `dock_state` with the layout schema `version`.
+    pub fn new(version: u32, dock_state: DockState<Tab>) -> Self {
+        Self {
+            version,
+            dock_state,
+        }
+    }
+
+    /// The layout schema version the [`DockState`] was saved with.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Get an immutable borrow to the [`DockState`] as it was saved, without any migrations applied.
+    pub fn dock_state(&self) -> &DockState<Tab> {
+        &self.dock_state
+    }
+
+    /// Returns the [`DockState`] as it was saved, without any migrations applied.
+    pub fn into_inner(self) -> DockState<Tab> {
+        self.dock_state
+    }
+
+    /// Returns the [`DockState`] upgraded to the `current_version` of the layout schema.
+    ///
+    /// The `migrator` is called once for every version between the saved one and `current_version`,
+    /// each time upgrading the layout by one version. The snapshot must already have been read as a
+    /// `DockState<Tab>`, see [`DockStateVersioned::deserialize_migrated`] for snapshots which can't be.
+    ///
+    /// Snapshots saved with a version newer than `current_version` are returned unchanged.
+    pub fn migrate(
+        self,
+        current_version: u32,
+        mut migrator: impl LayoutMigrator<Tab>,
+    ) -> DockState<Tab> {
+        self.migrate_with(current_version, &mut migrator)
+    }
+
+    fn migrate_with(
+        self,
+        current_version: u32,
+        migrator: &mut impl LayoutMigrator<Tab>,
+    ) -> DockState<Tab> {
+        let Self {
+            version,
+            mut dock_state,
+        } = self;
+        for version in version..current_version {
+            dock_state = migrator.migrate(version, dock_state);
+        }
+        dock_state
+    }
+
+    /// Deserializes a snapshot and upgrades it to the `current_version` of the layout schema.
+    ///
+    /// The saved version is read first, and the undecoded [`DockState`] is handed to [`LayoutMigrator::decode`]
+    /// along with it. The result is then upgraded like [`DockStateVersioned::migrate`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use egui_dock::{DockState, DockStateVersioned, LayoutMigrator};
+    /// # use serde::{Deserialize, Deserializer};
+    /// #[derive(serde::Deserialize, serde::Serialize)]
+    /// struct Tab {
+    ///     title: String,
+    ///     pinned: bool,
+    /// }
+    ///
+    /// // Version 0 saved tabs as their titles.
+    /// struct Migrator;
+    ///
+    /// impl LayoutMigrator<Tab> for Migrator {
+    ///     fn decode<'de, D>(&mut self, version: u32, deserializer: D) -> Result<DockState<Tab>, D::Error>
+    ///     where
+    ///         D: Deserializer<'de>,
+    ///     {
+    ///         if version == 0 {
+    ///             let dock_state = DockState::<String>::deserialize(deserializer)?;
+    ///             Ok(dock_state.into_map_tabs(|title| Tab { title, pinned: false }))
+    ///         } else {
+    ///             DockState::deserialize(deserializer)
+    ///         }
+    ///     }
+    ///
+    ///     fn migrate(&mut self, _version: u32, dock_state: DockState<Tab>) -> DockState<Tab> {
+    ///         dock_state
+    ///     }
+    /// }
+    ///
+    /// let saved = serde_json::to_string(&DockStateVersioned::new(0, DockState::new(vec![
+    ///     "Files".to_string(),
+    /// ])))
+    /// .unwrap();
+    ///
+    /// let mut deserializer = serde_json::Deserializer::from_str(&saved);
+    /// let dock_state = DockStateVersioned::deserialize_migrated(&mut deserializer, 1, Migrator).unwrap();
+    ///
+    /// let tabs: Vec<_> = dock_state.iter_all_tabs().map(|(_, tab)| tab.title.as_str()).collect();
+    /// assert_eq!(tabs, vec!["Files"]);
+    /// ```
+    pub fn deserialize_migrated<'de, D>(
+        deserializer: D,
+        current_version: u32,
+        mut migrator: impl LayoutMigrator<Tab>,
+    ) -> Result<DockState<Tab>, D::Error>
+    where
+        D: Deserializer<'de>,
+        Tab: Deserialize<'de>,
+    {
+        let (version, dock_state) = deserializer.deserialize_map(VersionedVisitor {
+            migrator: &mut migrator,
+            marker: PhantomData,
+        })?;
+        Ok(Self::new(version, dock_state).migrate_with(current_version, &mut migrator))
+    }
+}
+
+impl<Tab> From<DockStateVersioned<Tab>> for DockState<Tab> {
+    fn from(versioned: DockStateVersioned<Tab>) -> Self {
+        versioned.into_inner()
+    }
+}
+
+impl<'de, Tab> Deserialize<'de> for DockStateVersioned<Tab>
+where
+    Tab: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (version, dock_state) = deserializer.deserialize_map(VersionedVisitor {
+            migrator: &mut AsSaved,
+            marker: PhantomData,
+        })?;
+        Ok(Self::new(version, dock_state))
+    }
+}
+
+/// Reads snapshots as today's [`DockState`] without upgrading them.
+struct AsSaved;
+
+impl<Tab> LayoutMigrator<Tab> for AsSaved {
+    fn migrate(&mut self, _version: u32, dock_state: DockState<Tab>) -> DockState<Tab> {
+        dock_state
+    }
+}
+
+/// Reads either a versioned snapshot, or a bare [`DockState`] from before versioning was introduced, telling them
+/// apart by whether the first key is `version`.
+struct VersionedVisitor<'a, Tab, M> {
+    migrator: &'a mut M,
+    marker: PhantomData<Tab>,
+}
+
+impl<'de, Tab, M> Visitor<'de> for VersionedVisitor<'_, Tab, M>
+where
+    Tab: Deserialize<'de>,
+    M: LayoutMigrator<Tab>,
+{
+    type Value = (u32, DockState<Tab>);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a versioned or bare DockState")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let first_key = map.next_key::<String>()?;
+        match first_key.as_deref() {
+            Some("version") => {}
+            Some("dock_state") => {
+                return Err(A::Error::custom("`version` must come before `dock_state`"));
+            }
+            _ => {
+                let bare = ReplayFirstKey {
+                    key: first_key,
+                    map,
+                };
+                let dock_state = self.migrator.decode(0, MapAccessDeserializer::new(bare))?;
+                return Ok((0, dock_state));
+            }
+        }
+
+        let version = map.next_value()?;
+        match map.next_key::<String>()?.as_deref() {
+            Some("dock_state") => {}
+            Some(key) => return Err(A::Error::unknown_field(key, &["dock_state"])),
+            None => return Err(A::Error::missing_field("dock_state")),
+        }
+        let dock_state = map.next_value_seed(DecodeSeed {
+            migrator: self.migrator,
+            version,
+            marker: PhantomData,
+        })?;
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok((version, dock_state))
+    }
+}
+
+/// Hands the undecoded `dock_state` of a snapshot to [`LayoutMigrator::decode`].
+struct DecodeSeed<'a, Tab, M> {
+    migrator: &'a mut M,
+    version: u32,
+    marker: PhantomData<Tab>,
+}
+
+impl<'de, Tab, M> DeserializeSeed<'de> for DecodeSeed<'_, Tab, M>
+where
+    Tab: Deserialize<'de>,
+    M: LayoutMigrator<Tab>,
+{
+    type Value = DockState<Tab>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.migrator.decode(self.version, deserializer)
+    }
+}
+
+/// Yields the already read first key of a bare [`DockState`] before the rest of its map.
+struct ReplayFirstKey<A> {
+    key: Option<String>,
+    map: A,
+}
+
+impl<'de, A> MapAccess<'de> for ReplayFirstKey<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.key.take() {
+            Some(key) => seed.deserialize(key.into_deserializer()).map(Some),
+            None => self.map.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.map.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map
+            .size_hint()
+            .map(|len| len + usize::from(self.key.is_some()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Tab {
+        title: String,
+        pinned: bool,
+    }
+
+    /// Reads version `0` snapshots, which saved tabs as their titles, and pins the first tab in version `1`.
+    struct Migrator;
+
+    impl LayoutMigrator<Tab> for Migrator {
+        fn decode<'de, D>(
+            &mut self,
+            version: u32,
+            deserializer: D,
+        ) -> Result<DockState<Tab>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if version == 0 {
+                let dock_state = DockState::<String>::deserialize(deserializer)?;
+                Ok(dock_state.into_map_tabs(|title| Tab {
+                    title,
+                    pinned: false,
+                }))
+            } else {
+                DockState::deserialize(deserializer)
+            }
+        }
+
+        fn migrate(&mut self, version: u32, mut dock_state: DockState<Tab>) -> DockState<Tab> {
+            if version == 1 {
+                if let Some((_, tab)) = dock_state.iter_all_tabs_mut().next() {
+                    tab.pinned = true;
+                }
+            }
+            dock_state
+        }
+    }
+
+    fn load(json: &str) -> Result<DockState<Tab>, serde_json::Error> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        DockStateVersioned::deserialize_migrated(&mut deserializer, 2, Migrator)
+    }
+
+    fn tabs(dock_state: &DockState<Tab>) -> Vec<(&str, bool)> {
+        dock_state
+            .iter_all_tabs()
+            .map(|(_, tab)| (tab.title.as_str(), tab.pinned))
+            .collect()
+    }
+
+    #[test]
+    fn changed_tab_format() {
+        let titles = DockState::new(vec!["Files".to_string(), "Search".to_string()]);
+
+        let versioned = serde_json::to_string(&DockStateVersioned::new(0, titles.clone())).unwrap();
+        let dock_state = load(&versioned).unwrap();
+        assert_eq!(tabs(&dock_state), vec![("Files", true), ("Search", false)]);
+
+        let bare = serde_json::to_string(&titles).unwrap();
+        let dock_state = load(&bare).unwrap();
+        assert_eq!(tabs(&dock_state), vec![("Files", true), ("Search", false)]);
+
+        // Today's format isn't read as titles.
+        let saved = DockState::new(vec![Tab {
+            title: "Files".to_string(),
+            pinned: false,
+        }]);
+        let current = serde_json::to_string(&DockStateVersioned::new(2, saved)).unwrap();
+        let dock_state = load(&current).unwrap();
+        assert_eq!(tabs(&dock_state), vec![("Files", false)]);
+    }
+
+    #[test]
+    fn reports_dock_state_errors() {
+        let error = load(r#"{"version":1,"dock_state":{"surfaces":5}}"#).unwrap_err();
+        assert!(error.to_string().contains("expected a sequence"), "{error}");
+
+        let error = load(r#"{"surfaces":5}"#).unwrap_err();
+        assert!(error.to_string().contains("expected a sequence"), "{error}");
+    }
+}
@@ -1,4 +1,6 @@
-use egui::{Id, Pos2, Rect, Vec2};
+use egui::{Id, Order, Pos2, Rect, Vec2};
+
+use crate::{NodeId, Split, TabIndex};
 
 /// The state of a [`Surface::Window`](crate::Surface::Window).
 ///
@@ -27,6 +29,67 @@ pub struct WindowState {
 
     /// True if the window is minimized
     minimized: bool,
+
+    /// The screen position this window is pinned to, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pinned_position: Option<Pos2>,
+
+    /// The smallest size this window can be resized to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    min_size: Option<Vec2>,
+
+    /// The largest size this window can be resized to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_size: Option<Vec2>,
+
+    /// Was this window moved by the user in the last frame?
+    #[cfg_attr(feature = "serde", serde(default))]
+    moved: bool,
+
+    /// Whether this window is shown above the windows which aren't.
+    #[cfg_attr(feature = "serde", serde(default))]
+    always_on_top: bool,
+
+    /// Whether this window blocks interaction with the other surfaces until it's closed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    modal: bool,
+
+    /// Where the tab of this window came from, if it was floated with [`DockState::float_tab`](crate::DockState::float_tab).
+    #[cfg_attr(feature = "serde", serde(default))]
+    restore_location: Option<RestoreLocation>,
+
+    /// The area this window is moved back inside of when it ends up out of it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    work_area: Option<Rect>,
+
+    /// The scale factor of the monitor this window was last shown on, if it's a native window.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pixels_per_point: Option<f32>,
+
+    /// The opacity of the window and its contents.
+    #[cfg_attr(feature = "serde", serde(default = "WindowState::default_opacity"))]
+    opacity: f32,
+
+    /// Whether this window is minimized into the taskbar of the [`DockArea`](crate::DockArea).
+    #[cfg_attr(feature = "serde", serde(default))]
+    in_taskbar: bool,
+
+    /// Whether this window was left out for being in the taskbar the last time it was shown.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hidden_in_taskbar: bool,
+}
+
+/// Where the tab of a window created by [`DockState::float_tab`](crate::DockState::float_tab) came from, so that
+/// [`DockState::redock_window`](crate::DockState::redock_window) can put it back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum RestoreLocation {
+    /// Among the tabs of a leaf, at the given position.
+    Leaf(NodeId, TabIndex),
+
+    /// In a new leaf split off a node with the given fraction, for tabs which were the only tab of their leaf.
+    /// The node was the sibling of that leaf.
+    Split(NodeId, Split, f32),
 }
 
 impl Default for WindowState {
@@ -39,6 +102,18 @@ impl Default for WindowState {
             expanded_height: None,
             new: true,
             minimized: false,
+            pinned_position: None,
+            min_size: None,
+            max_size: None,
+            moved: false,
+            always_on_top: false,
+            modal: false,
+            restore_location: None,
+            work_area: None,
+            pixels_per_point: None,
+            opacity: Self::default_opacity(),
+            in_taskbar: false,
+            hidden_in_taskbar: false,
         }
     }
 }
@@ -75,6 +150,178 @@ impl WindowState {
         self.dragged
     }
 
+    /// Returns if this window was moved by the user in the last frame.
+    ///
+    /// Unlike [`dragged`](Self::dragged), this is `false` while the window is held still,
+    /// and is never `true` for positions set through [`set_position`](Self::set_position).
+    pub fn moved(&self) -> bool {
+        self.moved
+    }
+
+    /// Pin this window to a position in screen coordinates.
+    /// A pinned window can't be moved by the user until it is [unpinned](Self::unpin).
+    pub fn pin(&mut self, position: Pos2) -> &mut Self {
+        self.pinned_position = Some(position);
+        self
+    }
+
+    /// Unpin this window, allowing the user to move it again.
+    pub fn unpin(&mut self) -> &mut Self {
+        self.pinned_position = None;
+        self
+    }
+
+    /// Returns the position this window is pinned to, if it is pinned.
+    pub fn pinned_position(&self) -> Option<Pos2> {
+        self.pinned_position
+    }
+
+    /// Returns `true` if this window is pinned to a position.
+    pub fn is_pinned(&self) -> bool {
+        self.pinned_position.is_some()
+    }
+
+    /// Set the smallest size this window can be resized to, in egui points.
+    pub fn set_min_size(&mut self, min_size: Vec2) -> &mut Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    /// Get the smallest size this window can be resized to, if one was set.
+    pub fn min_size(&self) -> Option<Vec2> {
+        self.min_size
+    }
+
+    /// Set the largest size this window can be resized to, in egui points.
+    pub fn set_max_size(&mut self, max_size: Vec2) -> &mut Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Get the largest size this window can be resized to, if one was set.
+    pub fn max_size(&self) -> Option<Vec2> {
+        self.max_size
+    }
+
+    /// Set whether this window is shown above the windows which aren't always on top, even when they're brought
+    /// to the front. Native windows are kept above all other windows of the system.
+    pub fn set_always_on_top(&mut self, always_on_top: bool) -> &mut Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+
+    /// Returns `true` if this window is always on top, which [modal](Self::set_modal) windows also are.
+    pub fn is_always_on_top(&self) -> bool {
+        self.always_on_top || self.modal
+    }
+
+    /// Set whether this window is modal.
+    ///
+    /// While a modal window is open, the other surfaces of the [`DockState`](crate::DockState) are disabled and
+    /// tabs can't be dropped onto them, like for a tool palette which has to be dealt with before going back to the
+    /// rest of the layout. Modal windows are always on top.
+    pub fn set_modal(&mut self, modal: bool) -> &mut Self {
+        self.modal = modal;
+        self
+    }
+
+    /// Returns `true` if this window is modal.
+    pub fn is_modal(&self) -> bool {
+        self.modal
+    }
+
+    /// Sets where the tabs of this window go when it's [re-docked](crate::DockState::redock_window).
+    pub fn set_restore_location(&mut self, restore_location: Option<RestoreLocation>) -> &mut Self {
+        self.restore_location = restore_location;
+        self
+    }
+
+    /// Returns where the tabs of this window go when it's [re-docked](crate::DockState::redock_window), if anywhere.
+    pub fn restore_location(&self) -> Option<RestoreLocation> {
+        self.restore_location
+    }
+
+    /// Set the visible area of the screen this window is kept inside of, in the same coordinates as
+    /// [`set_position`](Self::set_position), e.g. the work area of the monitors without the task bar.
+    ///
+    /// When the window ends up outside of it, it's moved back inside once it's no longer dragged. Windows larger
+    /// than the work area are aligned to its top left corner. Windows shown inside the [`DockArea`](crate::DockArea)
+    /// are also kept inside of [`DockArea::window_bounds`](crate::DockArea::window_bounds).
+    pub fn set_work_area(&mut self, work_area: Option<Rect>) -> &mut Self {
+        self.work_area = work_area;
+        self
+    }
+
+    /// Returns the visible area of the screen this window is kept inside of, if one was set.
+    pub fn work_area(&self) -> Option<Rect> {
+        self.work_area
+    }
+
+    /// Returns the scale factor of the monitor a native window was last shown on, in physical pixels per point.
+    ///
+    /// When a native window is moved onto a monitor with a different scale factor, its size in points is kept the
+    /// same, so that its content isn't cut off or padded.
+    pub fn pixels_per_point(&self) -> Option<f32> {
+        self.pixels_per_point
+    }
+
+    /// Set the opacity of this window and its contents, from `0.0` (invisible) to `1.0` (opaque).
+    ///
+    /// It's combined with [`FocusHighlightStyle::unfocused_window_opacity`](crate::FocusHighlightStyle) while the
+    /// window isn't focused. Native windows aren't made translucent. `NaN` makes the window opaque.
+    pub fn set_opacity(&mut self, opacity: f32) -> &mut Self {
+        self.opacity = if opacity.is_nan() {
+            1.0
+        } else {
+            opacity.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Returns the opacity of this window and its contents, which is `1.0` by default.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Set whether this window is minimized into the strip at the bottom of the main surface, see
+    /// [`DockArea::window_taskbar`](crate::DockArea::window_taskbar). Windows in the strip aren't shown, until the
+    /// user clicks their title in it.
+    ///
+    /// Windows of a [`DockArea`](crate::DockArea) without the strip are shown either way.
+    pub fn set_in_taskbar(&mut self, in_taskbar: bool) -> &mut Self {
+        self.in_taskbar = in_taskbar;
+        self
+    }
+
+    /// Returns `true` if this window is minimized into the strip at the bottom of the main surface.
+    pub fn is_in_taskbar(&self) -> bool {
+        self.in_taskbar
+    }
+
+    /// Record whether this window was left out in the taskbar this frame, instead of being shown.
+    pub(crate) fn set_hidden_in_taskbar(&mut self, hidden: bool) {
+        self.hidden_in_taskbar = hidden;
+    }
+
+    /// Returns `true` if this window was left out in the taskbar the last time it was shown, which only windows
+    /// of a [`DockArea`](crate::DockArea) with a taskbar are.
+    pub(crate) fn is_hidden_in_taskbar(&self) -> bool {
+        self.hidden_in_taskbar
+    }
+
+    fn default_opacity() -> f32 {
+        1.0
+    }
+
+    /// The order of the layer this window is shown in.
+    pub(crate) fn order(&self) -> Order {
+        if self.is_always_on_top() {
+            Order::Foreground
+        } else {
+            Order::Middle
+        }
+    }
+
     /// Set the height of this window when it is expanded.
     #[inline(always)]
     pub(crate) fn set_expanded_height(&mut self, height: f32) -> &mut Self {
@@ -113,17 +360,96 @@ impl WindowState {
         self.minimized
     }
 
+    /// Record where the window ended up after being shown this frame.
+    pub(crate) fn update(&mut self, rect: Rect, dragged: bool) {
+        self.moved = dragged && self.screen_rect.is_some_and(|last| last.min != rect.min);
+        self.dragged = dragged;
+        self.screen_rect = Some(rect);
+        if !dragged {
+            self.clamp_to_work_area(rect);
+        }
+    }
+
+    /// Move the window back inside of its work area next frame if it isn't anymore.
+    fn clamp_to_work_area(&mut self, rect: Rect) {
+        let Some(work_area) = self.work_area else {
+            return;
+        };
+        if self.pinned_position.is_some() || work_area.contains_rect(rect) {
+            return;
+        }
+        let max = (work_area.max - rect.size()).max(work_area.min);
+        let position = rect.min.clamp(work_area.min, max);
+        if position != rect.min {
+            self.next_position = Some(position);
+        }
+    }
+
+    /// Create the builder for the native window of this window.
+    ///
+    /// Positions are relative to `origin`, which is where the viewport of the [`DockArea`](crate::DockArea)
+    /// is located on the screen. The pending position and size are kept until [`Self::viewport_shown`] is called.
+    pub(crate) fn create_viewport(&self, title: String, origin: Vec2) -> egui::ViewportBuilder {
+        let mut builder = egui::ViewportBuilder::default().with_title(title);
+
+        if let Some(position) = self.pinned_position.or(self.next_position) {
+            builder = builder.with_position(position + origin);
+        }
+        if let Some(size) = self.next_size {
+            builder = builder.with_inner_size(size);
+        }
+        if let Some(min_size) = self.min_size {
+            builder = builder.with_min_inner_size(min_size);
+        }
+        if let Some(max_size) = self.max_size {
+            builder = builder.with_max_inner_size(max_size);
+        }
+        if self.is_always_on_top() {
+            builder = builder.with_window_level(egui::WindowLevel::AlwaysOnTop);
+        }
+        builder
+    }
+
+    /// Record where the native window ended up after being shown this frame, and on a monitor with which scale
+    /// factor.
+    ///
+    /// If the scale factor changed since last frame, the window is resized back to the size in points it had before.
+    pub(crate) fn viewport_shown(&mut self, rect: Option<Rect>, pixels_per_point: Option<f32>) {
+        self.next_position = None;
+        self.next_size = None;
+        self.new = false;
+        let rescaled = pixels_per_point.is_some()
+            && self.pixels_per_point.is_some()
+            && pixels_per_point != self.pixels_per_point;
+        if rescaled {
+            self.next_size = self.screen_rect.map(|last| last.size());
+        }
+        self.pixels_per_point = pixels_per_point.or(self.pixels_per_point);
+        if let Some(rect) = rect {
+            self.update(rect, false);
+        }
+    }
+
     //the 'static in this case means that the `open` field is always `None`
     pub(crate) fn create_window(&mut self, id: Id, bounds: Rect) -> egui::Window<'static> {
         let new = self.new;
         let mut window_constructor = egui::Window::new("")
             .id(id)
             .constrain_to(bounds)
+            .order(self.order())
             .title_bar(false);
 
-        if let Some(position) = self.next_position() {
+        if let Some(position) = self.pinned_position {
+            window_constructor = window_constructor.fixed_pos(position);
+        } else if let Some(position) = self.next_position() {
             window_constructor = window_constructor.current_pos(position);
         }
+        if let Some(min_size) = self.min_size {
+            window_constructor = window_constructor.min_size(min_size);
+        }
+        if let Some(max_size) = self.max_size {
+            window_constructor = window_constructor.max_size(max_size);
+        }
         if let Some(size) = self.next_size() {
             window_constructor = window_constructor.fixed_size(size);
         }
@@ -137,3 +463,60 @@ impl WindowState {
         window_constructor
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let mut state = WindowState::new();
+        state
+            .pin(Pos2::new(10.0, 20.0))
+            .set_min_size(Vec2::new(100.0, 50.0))
+            .set_max_size(Vec2::new(400.0, 300.0))
+            .set_modal(true);
+        state.update(
+            Rect::from_min_size(Pos2::new(10.0, 20.0), Vec2::splat(200.0)),
+            false,
+        );
+
+        let json = serde_json::to_string(&state).unwrap();
+        let state: WindowState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.pinned_position(), Some(Pos2::new(10.0, 20.0)));
+        assert_eq!(state.min_size(), Some(Vec2::new(100.0, 50.0)));
+        assert_eq!(state.max_size(), Some(Vec2::new(400.0, 300.0)));
+        assert_eq!(
+            state.rect(),
+            Rect::from_min_size(Pos2::new(10.0, 20.0), Vec2::splat(200.0))
+        );
+        assert!(!state.moved());
+        assert!(state.is_modal() && state.is_always_on_top());
+    }
+
+    #[test]
+    fn windows_are_kept_inside_the_work_area() {
+        let mut state = WindowState::new();
+        state.set_work_area(Some(Rect::from_min_max(
+            Pos2::ZERO,
+            Pos2::new(800.0, 600.0),
+        )));
+
+        let rect = Rect::from_min_size(Pos2::new(700.0, -50.0), Vec2::new(200.0, 100.0));
+        state.update(rect, true);
+        assert_eq!(state.next_position(), None);
+        state.update(rect, false);
+        assert_eq!(state.next_position(), Some(Pos2::new(600.0, 0.0)));
+
+        let size = Vec2::new(300.0, 200.0);
+        state.viewport_shown(Some(Rect::from_min_size(Pos2::ZERO, size)), Some(1.0));
+        state.viewport_shown(Some(Rect::from_min_size(Pos2::ZERO, size * 0.5)), Some(2.0));
+        assert_eq!(state.next_size(), Some(size));
+        assert_eq!(state.pixels_per_point(), Some(2.0));
+    }
+
+    #[test]
+    fn nan_opacity_is_opaque() {
+        assert_eq!(WindowState::new().set_opacity(f32::NAN).opacity(), 1.0);
+    }
+}
@@ -0,0 +1,266 @@
+use std::ops::{Index, IndexMut};
+
+use egui::Ui;
+
+use crate::DockState;
+
+/// Several named [`DockState`]s of which one is shown at a time, like virtual desktops for layouts.
+///
+/// There's always at least one workspace, and exactly one of them is active. Show the active one with a
+/// [`DockArea`](crate::DockArea), and switch between them with [`Workspaces::switch_to`] or the strip drawn by
+/// [`Workspaces::show_tab_strip`]. The inactive workspaces keep their layouts and tabs untouched.
+///
+/// Each workspace should be shown with a [`DockArea`](crate::DockArea) [`Id`](egui::Id) of its own (e.g. made from
+/// [`Workspaces::active_index`]), so that the state the [`DockArea`](crate::DockArea) keeps between frames isn't mixed
+/// up between them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockState, Workspaces};
+/// let mut workspaces = Workspaces::new("Code", DockState::new(vec!["main.rs", "lib.rs"]));
+/// let debug = workspaces.push("Debug", DockState::new(vec!["Variables", "Call Stack"]));
+///
+/// assert!(workspaces.switch_to(debug));
+/// assert_eq!(workspaces.active_name(), "Debug");
+/// assert_eq!(workspaces.active().iter_all_tabs().count(), 2);
+///
+/// let names: Vec<&str> = workspaces.iter().map(|(name, _)| name).collect();
+/// assert_eq!(names, vec!["Code", "Debug"]);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "WorkspacesData<Tab>"))]
+pub struct Workspaces<Tab> {
+    workspaces: Vec<(String, DockState<Tab>)>,
+    active: usize,
+}
+
+/// The fields of [`Workspaces`] as they're deserialized, before it's checked that there's a workspace and that the
+/// active one exists.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct WorkspacesData<Tab> {
+    workspaces: Vec<(String, DockState<Tab>)>,
+    active: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<Tab> TryFrom<WorkspacesData<Tab>> for Workspaces<Tab> {
+    type Error = &'static str;
+
+    fn try_from(data: WorkspacesData<Tab>) -> Result<Self, Self::Error> {
+        let WorkspacesData { workspaces, active } = data;
+        if workspaces.is_empty() {
+            Err("there must be at least one workspace")
+        } else if active >= workspaces.len() {
+            Err("the active workspace doesn't exist")
+        } else {
+            Ok(Self { workspaces, active })
+        }
+    }
+}
+
+impl<Tab> Workspaces<Tab> {
+    /// Creates a single active workspace named `name`.
+    pub fn new(name: impl Into<String>, dock_state: DockState<Tab>) -> Self {
+        Self {
+            workspaces: vec![(name.into(), dock_state)],
+            active: 0,
+        }
+    }
+
+    /// Adds a workspace after the last one without switching to it, and returns its index.
+    pub fn push(&mut self, name: impl Into<String>, dock_state: DockState<Tab>) -> usize {
+        self.workspaces.push((name.into(), dock_state));
+        self.workspaces.len() - 1
+    }
+
+    /// Removes the workspace at `index`, and returns its name and [`DockState`].
+    ///
+    /// Returns `None` if there's no such workspace, or if it's the last one left. When the active workspace is
+    /// removed, the one before it becomes active, or the one after it if it was the first.
+    pub fn remove(&mut self, index: usize) -> Option<(String, DockState<Tab>)> {
+        if index >= self.workspaces.len() || self.workspaces.len() == 1 {
+            return None;
+        }
+        let removed = self.workspaces.remove(index);
+        if self.active >= index && self.active > 0 {
+            self.active -= 1;
+        }
+        Some(removed)
+    }
+
+    /// Returns the number of workspaces, which is never 0.
+    pub fn len(&self) -> usize {
+        self.workspaces.len()
+    }
+
+    /// Always returns `false`, as there's always at least one workspace.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Makes the workspace at `index` the active one.
+    ///
+    /// Returns `false` and keeps the active workspace if there's no such workspace.
+    pub fn switch_to(&mut self, index: usize) -> bool {
+        if index >= self.workspaces.len() {
+            return false;
+        }
+        self.active = index;
+        true
+    }
+
+    /// Returns the index of the active workspace.
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Returns the name of the active workspace.
+    pub fn active_name(&self) -> &str {
+        &self.workspaces[self.active].0
+    }
+
+    /// Get an immutable borrow to the [`DockState`] of the active workspace.
+    pub fn active(&self) -> &DockState<Tab> {
+        &self.workspaces[self.active].1
+    }
+
+    /// Get a mutable borrow to the [`DockState`] of the active workspace.
+    pub fn active_mut(&mut self) -> &mut DockState<Tab> {
+        &mut self.workspaces[self.active].1
+    }
+
+    /// Get an immutable borrow to the [`DockState`] of the workspace at `index`, if there's one.
+    pub fn get(&self, index: usize) -> Option<&DockState<Tab>> {
+        self.workspaces.get(index).map(|(_, dock_state)| dock_state)
+    }
+
+    /// Get a mutable borrow to the [`DockState`] of the workspace at `index`, if there's one.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut DockState<Tab>> {
+        self.workspaces
+            .get_mut(index)
+            .map(|(_, dock_state)| dock_state)
+    }
+
+    /// Returns the name of the workspace at `index`, if there's one.
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.workspaces.get(index).map(|(name, _)| name.as_str())
+    }
+
+    /// Renames the workspace at `index`. Returns `false` if there's no such workspace.
+    pub fn rename(&mut self, index: usize, name: impl Into<String>) -> bool {
+        let Some((old_name, _)) = self.workspaces.get_mut(index) else {
+            return false;
+        };
+        *old_name = name.into();
+        true
+    }
+
+    /// Returns an [`Iterator`] over the names and [`DockState`]s of all the workspaces, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DockState<Tab>)> {
+        self.workspaces
+            .iter()
+            .map(|(name, dock_state)| (name.as_str(), dock_state))
+    }
+
+    /// Returns an [`Iterator`] over the names and mutable [`DockState`]s of all the workspaces, in order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut DockState<Tab>)> {
+        self.workspaces
+            .iter_mut()
+            .map(|(name, dock_state)| (name.as_str(), dock_state))
+    }
+
+    /// Shows a horizontal strip with a button for every workspace, to be placed above the
+    /// [`DockArea`](crate::DockArea). Clicking a button switches to its workspace.
+    ///
+    /// Returns the index of the workspace switched to this frame, if any.
+    pub fn show_tab_strip(&mut self, ui: &mut Ui) -> Option<usize> {
+        let mut switched = None;
+        ui.horizontal(|ui| {
+            for (index, (name, _)) in self.workspaces.iter().enumerate() {
+                let response = ui.selectable_label(index == self.active, name.as_str());
+                if response.clicked() && index != self.active {
+                    switched = Some(index);
+                }
+            }
+        });
+        if let Some(index) = switched {
+            self.active = index;
+        }
+        switched
+    }
+}
+
+impl<Tab> Index<usize> for Workspaces<Tab> {
+    type Output = DockState<Tab>;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.workspaces[index].1
+    }
+}
+
+impl<Tab> IndexMut<usize> for Workspaces<Tab> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.workspaces[index].1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn removing_workspaces_keeps_the_active_one() {
+        let mut workspaces = Workspaces::new("a", DockState::new(vec![0]));
+        workspaces.push("b", DockState::new(vec![1]));
+        workspaces.push("c", DockState::new(vec![2]));
+
+        assert!(workspaces.switch_to(2));
+        assert!(!workspaces.switch_to(3));
+        assert!(workspaces.remove(0).is_some());
+        assert_eq!(workspaces.active_name(), "c");
+
+        assert!(workspaces.remove(1).is_some());
+        assert_eq!(workspaces.active_name(), "b");
+        assert!(workspaces.remove(0).is_none());
+        assert_eq!(workspaces.len(), 1);
+    }
+
+    #[test]
+    fn removing_the_active_workspace_activates_the_one_before_it() {
+        let mut workspaces = Workspaces::new("a", DockState::new(vec![0]));
+        workspaces.push("b", DockState::new(vec![1]));
+        workspaces.push("c", DockState::new(vec![2]));
+
+        assert!(workspaces.switch_to(1));
+        assert!(workspaces.remove(1).is_some());
+        assert_eq!(workspaces.active_name(), "a");
+
+        // The first workspace has none before it, so the one after it takes its place.
+        assert!(workspaces.remove(0).is_some());
+        assert_eq!(workspaces.active_name(), "c");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn workspaces_without_an_active_one_are_not_deserialized() {
+        let mut workspaces = Workspaces::new("a", DockState::new(vec![0]));
+        workspaces.push("b", DockState::new(vec![1]));
+        let json = serde_json::to_value(&workspaces).unwrap();
+        let loaded: Workspaces<i32> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(loaded.len(), 2);
+
+        let mut out_of_range = json.clone();
+        out_of_range["active"] = 2.into();
+        assert!(serde_json::from_value::<Workspaces<i32>>(out_of_range).is_err());
+
+        let mut empty = json;
+        empty["workspaces"] = serde_json::Value::Array(Vec::new());
+        empty["active"] = 0.into();
+        assert!(serde_json::from_value::<Workspaces<i32>>(empty).is_err());
+    }
+}
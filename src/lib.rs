@@ -192,12 +192,15 @@
 //! Example usage:
 //!
 //! ```rust
-//! # use egui_dock::{DockState, TabContextMenuTranslations, Translations, LeafTranslations};
+//! # use egui_dock::{DockState, TabContextMenuTranslations, Translations, LeafTranslations, SeparatorTranslations, TabSwitcherTranslations};
+//! # use egui_dock::AccessibilityTranslations;
 //! # type Tab = ();
 //! let translations_pl = Translations {
 //!     tab_context_menu: TabContextMenuTranslations {
 //!         close_button: "Zamknij zakładkę".to_string(),
 //!         eject_button: "Przenieś zakładkę do nowego okna".to_string(),
+//!         auto_hide_button: "Automatycznie ukrywaj".to_string(),
+//!         dock_button: "Zadokuj".to_string(),
 //!     },
 //!     leaf: LeafTranslations {
 //!         close_button_disabled_tooltip: "Ten węzeł zawiera niezamykalne zakładki.".to_string(),
@@ -210,7 +213,28 @@
 //!         minimize_button_menu_hint: "Kliknij prawym przyciskiem myszy, aby zminimalizować to okno.".to_string(),
 //!         minimize_button_modifier_hint: "Naciśnij klawisze modyfikujące (domyślnie Shift), aby zminimalizować to okno.".to_string(),
 //!         minimize_button_modifier_menu_hint: "Naciśnij klawisze modyfikujące (domyślnie Shift) lub kliknij prawym przyciskiem myszy, aby zminimalizować to okno.".to_string(),
-//!     }
+//!         split_right_button: "Podziel w prawo".to_string(),
+//!         split_below_button: "Podziel w dół".to_string(),
+//!     },
+//!     separator: SeparatorTranslations {
+//!         distribute_evenly_button: "Rozłóż równomiernie".to_string(),
+//!         flip_orientation_button: "Odwróć kierunek podziału".to_string(),
+//!     },
+//!     tab_switcher: TabSwitcherTranslations {
+//!         search_hint: "Szukaj zakładek…".to_string(),
+//!         no_matches: "Brak pasujących zakładek".to_string(),
+//!     },
+//!     accessibility: AccessibilityTranslations {
+//!         tab: "Zakładka: {name}, {position} z {count}".to_string(),
+//!         close_button: "Zamknij {name}".to_string(),
+//!         horizontal_separator: "Poziomy separator podziału".to_string(),
+//!         vertical_separator: "Pionowy separator podziału".to_string(),
+//!         dock_as_tab: "Zadokuj jako zakładkę".to_string(),
+//!         dock_left: "Zadokuj po lewej".to_string(),
+//!         dock_right: "Zadokuj po prawej".to_string(),
+//!         dock_above: "Zadokuj powyżej".to_string(),
+//!         dock_below: "Zadokuj poniżej".to_string(),
+//!     },
 //! };
 //! let dock_state = DockState::<Tab>::new(vec![]).with_translations(translations_pl);
 //!
@@ -218,6 +242,8 @@
 //! let mut dock_state = DockState::<Tab>::new(vec![]);
 //! dock_state.translations.tab_context_menu.close_button = "タブを閉じる".to_string();
 //! dock_state.translations.tab_context_menu.eject_button = "タブを新しいウィンドウへ移動".to_string();
+//! dock_state.translations.tab_context_menu.auto_hide_button = "自動的に隠す".to_string();
+//! dock_state.translations.tab_context_menu.dock_button = "ドッキング".to_string();
 //! dock_state.translations.leaf.close_button_disabled_tooltip = "このノードは閉じられないタブがある".to_string();
 //! dock_state.translations.leaf.close_all_button = "ウィンドウを閉じる".to_string();
 //! dock_state.translations.leaf.close_all_button_menu_hint = "右クリックでこのウィンドウを閉じる".to_string();
@@ -228,6 +254,12 @@
 //! dock_state.translations.leaf.minimize_button_menu_hint = "右クリックでウィンドウを最小化する".to_string();
 //! dock_state.translations.leaf.minimize_button_modifier_hint = "修飾キー（デフォルトではShift）を押すと、このウィンドウが最小化されます".to_string();
 //! dock_state.translations.leaf.minimize_button_modifier_menu_hint = "修飾キー（デフォルトではShift）を押すか、右クリックしてこのウィンドウを最小化する".to_string();
+//! dock_state.translations.leaf.split_right_button = "右に分割".to_string();
+//! dock_state.translations.leaf.split_below_button = "下に分割".to_string();
+//! dock_state.translations.separator.distribute_evenly_button = "均等に配置".to_string();
+//! dock_state.translations.separator.flip_orientation_button = "分割の向きを反転".to_string();
+//! dock_state.translations.tab_switcher.search_hint = "タブを検索…".to_string();
+//! dock_state.translations.tab_switcher.no_matches = "一致するタブがありません".to_string();
 //! ```
 
 #![warn(missing_docs)]
@@ -250,4 +282,11 @@ pub mod style;
 /// Widgets provided by the library.
 pub mod widgets;
 
+/// Helpers for testing how a [`DockArea`] reacts to the user, without a window.
+///
+/// This is a self-contained input driver which doesn't depend on `egui_kittest`, and doesn't render or compare
+/// snapshots. Tests check the [`DockState`] and the rects of the last frame instead.
+#[cfg(feature = "testing")]
+pub mod testing;
+
 mod utils;
@@ -1,4 +1,4 @@
-use egui::{ecolor::*, CornerRadius, Margin, Stroke};
+use egui::{ecolor::*, CornerRadius, Margin, NumExt, Stroke, TextStyle, Vec2, WidgetText};
 
 /// Left or right alignment for tab add button.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -9,6 +9,32 @@ pub enum TabAddAlign {
     Right,
 }
 
+/// Side of a leaf its tab bar is shown on, see [`TabBarStyle::position`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TabBarPosition {
+    /// Above the tab body.
+    #[default]
+    Top,
+
+    /// Below the tab body.
+    Bottom,
+
+    /// On the left of the tab body, with the titles read from top to bottom.
+    Left,
+
+    /// On the right of the tab body, with the titles read from top to bottom.
+    Right,
+}
+
+impl TabBarPosition {
+    /// Returns `true` if the tab bar runs down the side of the tab body.
+    #[inline(always)]
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+}
+
 /// Lets you change how tabs and the [`DockArea`](crate::DockArea) should look and feel.
 /// [`Style`] is divided into several, more specialized structs that handle individual
 /// elements of the UI.
@@ -60,6 +86,33 @@ pub struct Style {
     pub tab_bar: TabBarStyle,
     pub tab: TabStyle,
     pub overlay: OverlayStyle,
+
+    /// Look of the strips listing auto-hidden leaves and of the flyouts showing them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_hide: AutoHideStyle,
+
+    /// How leaves move to their new places when the layout changes, e.g. when a leaf is split, closed or
+    /// collapsed. Resizing the [`DockArea`](crate::DockArea) or dragging a separator isn't animated.
+    /// By `Default` it takes `0.15` seconds with [`Easing::CubicOut`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub animation: AnimationStyle,
+
+    /// Look of the preview following the pointer while a tab is dragged.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub drag_preview: DragPreviewStyle,
+
+    /// How the focused leaf stands out from the others. By `Default` it doesn't.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub focus_highlight: FocusHighlightStyle,
+
+    /// Look of the tabs which are [loading](crate::TabViewer::is_loading).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub loading: LoadingStyle,
+
+    /// Look of the strip listing the windows minimized into it, see
+    /// [`DockArea::window_taskbar`](crate::DockArea::window_taskbar).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub window_taskbar: WindowTaskbarStyle,
 }
 
 /// Specifies the look and feel of buttons.
@@ -117,6 +170,34 @@ pub struct ButtonsStyle {
     /// Color of the collapse tabs button's left border.
     pub collapse_tabs_border_color: Color32,
 
+    /// Color of the split leaf buttons.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "ButtonsStyle::default_split_leaf_color")
+    )]
+    pub split_leaf_color: Color32,
+
+    /// Color of the active split leaf buttons.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "ButtonsStyle::default_split_leaf_color")
+    )]
+    pub split_leaf_active_color: Color32,
+
+    /// Color of the split leaf buttons' background.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "ButtonsStyle::default_split_leaf_bg_fill")
+    )]
+    pub split_leaf_bg_fill: Color32,
+
+    /// Color of the split leaf buttons' left border.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "ButtonsStyle::default_split_leaf_border_color")
+    )]
+    pub split_leaf_border_color: Color32,
+
     /// Color of the minimize window button.
     pub minimize_window_color: Color32,
 
@@ -130,6 +211,20 @@ pub struct ButtonsStyle {
     pub minimize_window_border_color: Color32,
 }
 
+impl ButtonsStyle {
+    fn default_split_leaf_color() -> Color32 {
+        Color32::WHITE
+    }
+
+    fn default_split_leaf_bg_fill() -> Color32 {
+        Color32::GRAY
+    }
+
+    fn default_split_leaf_border_color() -> Color32 {
+        Color32::BLACK
+    }
+}
+
 /// Specifies the look and feel of node separators.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -138,9 +233,18 @@ pub struct SeparatorStyle {
     pub width: f32,
 
     /// Extra width added to the "logical thickness" of the rectangle so it's
-    /// easier to grab. By `Default` it's `4.0`.
+    /// easier to grab. By `Default` it's `2.0`.
     pub extra_interact_width: f32,
 
+    /// Width the rectangle separator grows to while it's hovered or dragged, if it's wider than
+    /// [`SeparatorStyle::width`]. The separator grows over [`egui::style::Style::animation_time`].
+    /// By `Default` it's `1.0`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "SeparatorStyle::default_hover_width")
+    )]
+    pub hover_width: f32,
+
     /// Limit for the allowed area for the separator offset. By `Default` it's `175.0`.
     /// `bigger value > less allowed offset` for the current window size.
     pub extra: f32,
@@ -153,6 +257,46 @@ pub struct SeparatorStyle {
 
     /// Dragged color of the rectangle separator. By `Default` it's [`Color32::WHITE`].
     pub color_dragged: Color32,
+
+    /// Whether double-clicking the separator resets the split to its
+    /// [`SplitNode::default_fraction`](crate::SplitNode::default_fraction). By `Default` it's `true`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "SeparatorStyle::default_double_click_reset")
+    )]
+    pub double_click_reset: bool,
+
+    /// Positions the separator snaps to while it's dragged or moved with the keyboard.
+    /// By `Default` it's [`SeparatorSnap::None`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub snap: SeparatorSnap,
+
+    /// How close, in points, the separator has to come to one of the [`SeparatorSnap::Fractions`] to snap to it.
+    /// By `Default` it's `8.0`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "SeparatorStyle::default_snap_distance")
+    )]
+    pub snap_distance: f32,
+
+    /// How a split is laid out when it's too small for the [`LeafNode::min_size`](crate::LeafNode::min_size) of
+    /// the leaves on both sides of its separator. By `Default` it's [`MinSizeOverflow::Shrink`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_size_overflow: MinSizeOverflow,
+}
+
+impl SeparatorStyle {
+    fn default_hover_width() -> f32 {
+        1.0
+    }
+
+    fn default_double_click_reset() -> bool {
+        true
+    }
+
+    fn default_snap_distance() -> f32 {
+        8.0
+    }
 }
 
 /// Specifies the look and feel of tab bars.
@@ -181,6 +325,302 @@ pub struct TabBarStyle {
     /// Whether tab titles expand to fill the width of their tab bars.
     /// By `Default` it's `false`.
     pub fill_tab_bar: bool,
+
+    /// Look of the add button at the end of the tab bar, see [`DockArea::show_add_buttons`](crate::DockArea::show_add_buttons).
+    /// Its colors are set in [`ButtonsStyle`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub add_button: AddButtonStyle,
+
+    /// How tabs slide apart to show where a tab dragged within its own tab bar would be inserted.
+    /// By `Default` it takes `0.15` seconds with [`Easing::CubicOut`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reorder_animation: AnimationStyle,
+
+    /// Side of the leaves their tab bars are shown on, unless a leaf overrides it with
+    /// [`LeafNode::set_tab_bar_position`](crate::LeafNode::set_tab_bar_position).
+    ///
+    /// Tab bars on the left or right are as wide as [`height`](Self::height), and only show the tabs, without
+    /// the buttons and custom widgets at the ends of the tab bar. By `Default` it's [`TabBarPosition::Top`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub position: TabBarPosition,
+
+    /// If set, scrolling the mouse wheel over a tab bar switches its active tab instead of scrolling the tabs.
+    /// By `Default` it's `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scroll_to_switch: Option<ScrollToSwitch>,
+
+    /// Whether the tabs flow from right to left, for right-to-left languages. The close buttons of the tabs, the
+    /// buttons at the ends of the tab bar and the custom widgets of
+    /// [`TabViewer::tab_bar_leading_ui`](crate::TabViewer::tab_bar_leading_ui) and
+    /// [`TabViewer::tab_bar_trailing_ui`](crate::TabViewer::tab_bar_trailing_ui) swap sides as well.
+    ///
+    /// By `Default` it's `false`, but the tab bars are laid out from right to left anyway if the [`Ui`](egui::Ui)
+    /// the [`DockArea`](crate::DockArea) is shown inside of has a right-to-left layout.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub right_to_left: bool,
+
+    /// Height of the handle shown instead of the tab bar of a leaf with a single tab, by which the tab can still be
+    /// dragged, see [`DockArea::hide_single_tab_bars`](crate::DockArea::hide_single_tab_bars).
+    /// By `Default` it's `6.0`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "TabBarStyle::default_drag_handle_height")
+    )]
+    pub drag_handle_height: f32,
+}
+
+impl TabBarStyle {
+    fn default_drag_handle_height() -> f32 {
+        6.0
+    }
+}
+
+/// Specifies how scrolling over a tab bar switches its active tab, see [`TabBarStyle::scroll_to_switch`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ScrollToSwitch {
+    /// If `false`, scrolling down or right switches to the next tab. If `true`, it switches to the previous one.
+    /// By `Default` it's `false`.
+    pub reversed: bool,
+
+    /// Whether scrolling past the last tab switches to the first one and the other way around.
+    /// By `Default` it's `false`.
+    pub wrap_around: bool,
+}
+
+/// Specifies the size of the add button in tab bars.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AddButtonStyle {
+    /// Width of the button. By `Default` it's `24.0`.
+    pub width: f32,
+
+    /// Width and height of the plus sign. By `Default` it's `12.0`.
+    pub plus_size: f32,
+
+    /// Width of the lines of the plus sign. By `Default` it's `1.0`.
+    pub plus_stroke_width: f32,
+}
+
+/// Specifies the duration and easing curve of an animation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AnimationStyle {
+    /// Duration of the animation in seconds, `0.0` disables the animation.
+    pub duration: f32,
+
+    /// Easing curve of the animation.
+    pub easing: Easing,
+}
+
+/// Easing curve of an animation, see [`egui::emath::easing`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[allow(missing_docs)]
+pub enum Easing {
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    #[default]
+    CubicOut,
+    CubicInOut,
+    SinIn,
+    SinOut,
+    SinInOut,
+}
+
+/// Specifies the look of the preview following the pointer while a tab is dragged,
+/// see [`TabViewer::drag_preview_ui`](crate::TabViewer::drag_preview_ui).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DragPreviewStyle {
+    /// Opacity of the preview, from `0.0` to `1.0`. By `Default` it's `1.0`.
+    pub opacity: f32,
+
+    /// Offset from the pointer to the top left corner of the preview. `None` keeps the preview where
+    /// the tab was grabbed. By `Default` it's `None`.
+    pub cursor_offset: Option<Vec2>,
+}
+
+impl Default for DragPreviewStyle {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            cursor_offset: None,
+        }
+    }
+}
+
+/// Specifies how the focused leaf, the one which last had a tab interacted with, stands out from the others.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FocusHighlightStyle {
+    /// Color painted over the tab bar of the focused leaf, usually translucent.
+    /// By `Default` it's [`Color32::TRANSPARENT`].
+    pub tab_bar_tint: Color32,
+
+    /// Outline around the focused leaf, both its tab bar and body. By `Default` it's [`Stroke::NONE`].
+    pub outline: Stroke,
+
+    /// How much the tab bars of the other leaves fade into their background color while a leaf is focused,
+    /// from `0.0` (not at all) to `1.0` (until their tabs can't be seen). By `Default` it's `0.0`.
+    pub unfocused_tab_bar_dimming: f32,
+
+    /// Opacity of the windows other than the one holding the focused leaf, from `0.0` to `1.0`, so that the focused
+    /// window stands out. Native windows aren't faded. By `Default` it's `1.0`.
+    pub unfocused_window_opacity: f32,
+
+    /// Animation of the windows fading as they lose focus, and back as they gain it.
+    pub window_fade_animation: AnimationStyle,
+}
+
+impl Default for FocusHighlightStyle {
+    fn default() -> Self {
+        Self {
+            tab_bar_tint: Color32::TRANSPARENT,
+            outline: Stroke::NONE,
+            unfocused_tab_bar_dimming: 0.0,
+            unfocused_window_opacity: 1.0,
+            window_fade_animation: AnimationStyle::default(),
+        }
+    }
+}
+
+/// Specifies the look of the tabs which are [loading](crate::TabViewer::is_loading).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LoadingStyle {
+    /// Size of the spinner shown in the body of a loading tab, instead of its contents. By `Default` it's `24.0`.
+    pub body_spinner_size: f32,
+
+    /// Color of the spinner shown in the body of a loading tab. By `Default` it's [`Color32::GRAY`].
+    pub body_spinner_color: Color32,
+
+    /// Whether a spinner is shown before the title of a loading tab, in the color of the title.
+    /// By `Default` it's `true`.
+    pub title_spinner: bool,
+}
+
+impl Default for LoadingStyle {
+    fn default() -> Self {
+        Self {
+            body_spinner_size: 24.0,
+            body_spinner_color: Color32::GRAY,
+            title_spinner: true,
+        }
+    }
+}
+
+/// Specifies the look of the strip at the bottom of the main surface listing the minimized windows, see
+/// [`DockArea::window_taskbar`](crate::DockArea::window_taskbar).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WindowTaskbarStyle {
+    /// Height of the strip. By `Default` it's `24.0`.
+    pub height: f32,
+
+    /// Background color of the strip. By `Default` it's [`Color32::WHITE`].
+    pub bg_fill: Color32,
+
+    /// Color of the line between the strip and the main surface. By `Default` it's [`Color32::BLACK`].
+    pub hline_color: Color32,
+
+    /// Color of the titles of the minimized windows. By `Default` it's [`Color32::DARK_GRAY`].
+    pub label_color: Color32,
+
+    /// Color of the title of a minimized window when it's hovered. By `Default` it's [`Color32::BLACK`].
+    pub label_active_color: Color32,
+}
+
+impl Default for WindowTaskbarStyle {
+    fn default() -> Self {
+        Self {
+            height: 24.0,
+            bg_fill: Color32::WHITE,
+            hline_color: Color32::BLACK,
+            label_color: Color32::DARK_GRAY,
+            label_active_color: Color32::BLACK,
+        }
+    }
+}
+
+/// Positions a separator snaps to, see [`SeparatorStyle::snap`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SeparatorSnap {
+    /// The separator moves freely.
+    #[default]
+    None,
+
+    /// The separator snaps to these fractions of its split, e.g. `vec![0.25, 0.5, 0.75]`,
+    /// when it comes within [`SeparatorStyle::snap_distance`] of one of them.
+    Fractions(Vec<f32>),
+
+    /// The separator only stops every this many points from the start of its split.
+    Grid(f32),
+}
+
+impl SeparatorSnap {
+    /// Snaps the `fraction` of a split which is `length` points long.
+    pub(crate) fn snap(&self, fraction: f32, length: f32, distance: f32) -> f32 {
+        match self {
+            SeparatorSnap::None => fraction,
+            SeparatorSnap::Fractions(fractions) => fractions
+                .iter()
+                .copied()
+                .filter(|snapped| ((snapped - fraction) * length).abs() <= distance)
+                .min_by(|a, b| (a - fraction).abs().total_cmp(&(b - fraction).abs()))
+                .unwrap_or(fraction),
+            SeparatorSnap::Grid(grid) if *grid > 0.0 && length > 0.0 => {
+                (fraction * length / grid).round() * grid / length
+            }
+            SeparatorSnap::Grid(_) => fraction,
+        }
+    }
+}
+
+/// How a split too small for the minimum sizes of its children is laid out, see
+/// [`SeparatorStyle::min_size_overflow`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MinSizeOverflow {
+    /// Both children shrink below their minimum size, in proportion to it.
+    #[default]
+    Shrink,
+
+    /// The child with the smaller share of the split is collapsed to nothing, leaving the whole split to the other.
+    Collapse,
+}
+
+/// Specifies the look and feel of auto-hidden leaves, see [`LeafNode::set_auto_hidden`](crate::LeafNode::set_auto_hidden).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AutoHideStyle {
+    /// Width of the strips at the edges of a surface which list the tabs of its auto-hidden leaves.
+    /// By `Default` it's `24.0`.
+    pub strip_width: f32,
+
+    /// Background color of the strips. By `Default` it's [`Color32::WHITE`].
+    pub strip_bg_fill: Color32,
+
+    /// Color of the tab titles in the strips. By `Default` it's [`Color32::DARK_GRAY`].
+    pub label_color: Color32,
+
+    /// Color of the tab title in a strip while it's hovered or its leaf is shown.
+    /// By `Default` it's [`Color32::BLACK`].
+    pub label_active_color: Color32,
+
+    /// Spacing between the tab titles in the strips. By `Default` it's `12.0`.
+    pub label_spacing: f32,
+
+    /// Stroke around the flyout showing an auto-hidden leaf. By `Default` it's [`Stroke::NONE`].
+    pub flyout_stroke: Stroke,
+
+    /// How the flyout slides out of the strip. By `Default` it takes `0.15` seconds with [`Easing::CubicOut`].
+    pub flyout_animation: AnimationStyle,
 }
 
 /// Specifies the look and feel of an individual tab.
@@ -224,6 +664,105 @@ pub struct TabStyle {
     /// The tab title or [`TabBarStyle::fill_tab_bar`] may make the tab
     /// wider than this but never shorter.
     pub minimum_width: Option<f32>,
+
+    /// The maximum width of the tab, or `None` if it can be as wide as its title.
+    ///
+    /// Titles which don't fit are cut short as set by [`TabStyle::title_truncation`]. This wins over
+    /// [`TabStyle::minimum_width`] and [`TabBarStyle::fill_tab_bar`]. By `Default` it's `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub maximum_width: Option<f32>,
+
+    /// Where the titles of tabs which don't fit in [`TabStyle::maximum_width`] are cut short with an ellipsis.
+    /// By `Default` it's [`TitleTruncation::End`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub title_truncation: TitleTruncation,
+
+    /// Delay in seconds before the tooltip of a hovered tab is shown, see [`TabViewer::tab_tooltip`](crate::TabViewer::tab_tooltip).
+    /// The tooltip waits for the longer of this and the [`egui::style::Interaction::tooltip_delay`] of the context,
+    /// which is left untouched. By `Default` it's `None`, which only uses the delay of the context.
+    pub tooltip_delay: Option<f32>,
+
+    /// Shape of the tabs in tab bars above or below their leaves. By `Default` it's [`TabShape::Rectangle`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shape: TabShape,
+
+    /// Text style of the tab titles which don't set their own. By `Default` it's `None`, which uses
+    /// [`TextStyle::Button`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub title_text_style: Option<TextStyle>,
+
+    /// Whether the tab titles are in italics. By `Default` it's `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub title_italics: bool,
+
+    /// Outline drawn inside a tab or its close button while it has keyboard focus, on top of the
+    /// `*_with_kb_focus` styles. By `Default` it's 2 points wide and dark blue.
+    #[cfg_attr(feature = "serde", serde(default = "TabStyle::default_focus_ring"))]
+    pub focus_ring: Stroke,
+}
+
+/// Where the titles of tabs are cut short, see [`TabStyle::title_truncation`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TitleTruncation {
+    /// The end of the title is replaced with an ellipsis, e.g. `src/widgets/do…`.
+    #[default]
+    End,
+
+    /// The middle of the title is replaced with an ellipsis, keeping both ends visible, e.g. `src/wid…leaf.rs`.
+    Middle,
+}
+
+/// Shape of the tabs in tab bars, see [`TabStyle::shape`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TabShape {
+    /// Rectangles next to each other, with their corners rounded by [`TabInteractionStyle::corner_radius`].
+    #[default]
+    Rectangle,
+
+    /// Trapezoids like the tabs of web browsers, with the corners away from the tab body rounded by
+    /// [`TabInteractionStyle::corner_radius`].
+    ///
+    /// The slanted sides are `slant` points wide and overlap the sides of the neighbouring tabs, the active tab
+    /// being drawn on top of them. Each tab reacts to the pointer up to the middle of its slanted sides, where they
+    /// cross the sides of its neighbours.
+    Browser {
+        /// Width of the slanted sides.
+        slant: f32,
+    },
+}
+
+impl TabStyle {
+    fn default_focus_ring() -> Stroke {
+        Stroke::new(2.0, Color32::from_rgb(0, 83, 125))
+    }
+
+    /// Lays out the title of a tab with [`TabStyle::title_text_style`] and [`TabStyle::title_italics`].
+    pub(crate) fn title_galley(
+        &self,
+        ui: &egui::Ui,
+        title: WidgetText,
+        wrap_mode: Option<egui::TextWrapMode>,
+    ) -> std::sync::Arc<egui::Galley> {
+        let title = if self.title_italics {
+            title.italics()
+        } else {
+            title
+        };
+        let text_style = self.title_text_style.clone().unwrap_or(TextStyle::Button);
+        title.into_galley(ui, wrap_mode, f32::INFINITY, text_style)
+    }
+}
+
+impl TabShape {
+    /// How much neighbouring tabs overlap.
+    pub(crate) fn overlap(self) -> f32 {
+        match self {
+            TabShape::Rectangle => 0.0,
+            TabShape::Browser { slant } => slant.at_least(0.0),
+        }
+    }
 }
 
 /// Specifies the look and feel of individual tabs while they are being interacted with.
@@ -296,18 +835,41 @@ pub struct OverlayStyle {
 
     /// The feel of the overlay, timings, detection, etc.
     pub feel: OverlayFeel,
+
+    /// If `true`, the leaves of the hovered surface are outlined as they would be laid out if the dragged tab was
+    /// dropped, with the leaf the tab goes to filled with [`OverlayStyle::selection_color`].
+    /// By `Default` it's `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub layout_preview: bool,
 }
 
 /// Specifies the feel of the tab drop overlay, i.e anything non visual about the overlay.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct OverlayFeel {
-    /// range is `0.0..=1.0`.
+    /// Size of the area in the middle of a hovered leaf where a dropped tab goes into a new window, relative to the
+    /// leaf. Only used with [`OverlayType::HighlightedAreas`]. Where windows aren't allowed, the tab is added to the
+    /// leaf instead. The band outside of this area, along the edges of the leaf, splits the leaf. The smaller it is,
+    /// the thicker the band.
+    ///
+    /// range is `0.0..=1.0`. By `Default` it's `0.5`.
     pub window_drop_coverage: f32,
 
-    /// range is `0.0..=1.0`.
+    /// Size of the area in the middle of a hovered leaf where a dropped tab is added to the leaf, relative to the
+    /// leaf. Only used with [`OverlayType::HighlightedAreas`]. It takes priority over
+    /// [`OverlayFeel::window_drop_coverage`], so making it larger makes it harder to split the leaf by accident.
+    ///
+    /// range is `0.0..=1.0`. By `Default` it's `0.25`.
     pub center_drop_coverage: f32,
 
+    /// Size of the corners of a hovered leaf where a dropped tab doesn't split the leaf, relative to the leaf, as
+    /// the side the leaf would be split at is ambiguous there. Only used with [`OverlayType::HighlightedAreas`].
+    /// The corners act like the area of [`OverlayFeel::window_drop_coverage`] instead.
+    ///
+    /// range is `0.0..=0.5`. By `Default` it's `0.0`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub corner_dead_zone: f32,
+
     /// The amount of time windows should stay faded despite not needing to, prevents quick mouse movements from causing flashing.
     pub fade_hold_time: f32,
 
@@ -316,6 +878,19 @@ pub struct OverlayFeel {
 
     /// Units which the buttons interact area will be expanded by.
     pub interact_expansion: f32,
+
+    /// Width of the bands along the outer edges of a surface where a dropped tab splits the whole surface rather
+    /// than the leaf under it, see [`DockArea::surface_edge_splits`](crate::DockArea::surface_edge_splits).
+    ///
+    /// By `Default` it's `12.0`.
+    #[cfg_attr(feature = "serde", serde(default = "OverlayFeel::default_edge_width"))]
+    pub edge_width: f32,
+}
+
+impl OverlayFeel {
+    fn default_edge_width() -> f32 {
+        12.0
+    }
 }
 
 /// Specifies the type of overlay used.
@@ -361,6 +936,12 @@ impl Default for Style {
             tab_bar: TabBarStyle::default(),
             tab: TabStyle::default(),
             overlay: OverlayStyle::default(),
+            auto_hide: AutoHideStyle::default(),
+            animation: AnimationStyle::default(),
+            drag_preview: DragPreviewStyle::default(),
+            focus_highlight: FocusHighlightStyle::default(),
+            loading: LoadingStyle::default(),
+            window_taskbar: WindowTaskbarStyle::default(),
         }
     }
 }
@@ -389,6 +970,11 @@ impl Default for ButtonsStyle {
             collapse_tabs_bg_fill: Color32::GRAY,
             collapse_tabs_border_color: Color32::BLACK,
 
+            split_leaf_color: ButtonsStyle::default_split_leaf_color(),
+            split_leaf_active_color: ButtonsStyle::default_split_leaf_color(),
+            split_leaf_bg_fill: ButtonsStyle::default_split_leaf_bg_fill(),
+            split_leaf_border_color: ButtonsStyle::default_split_leaf_border_color(),
+
             minimize_window_color: Color32::WHITE,
             minimize_window_active_color: Color32::WHITE,
             minimize_window_bg_fill: Color32::GRAY,
@@ -402,10 +988,15 @@ impl Default for SeparatorStyle {
         Self {
             width: 1.0,
             extra_interact_width: 2.0,
+            hover_width: SeparatorStyle::default_hover_width(),
             extra: 175.0,
             color_idle: Color32::BLACK,
             color_hovered: Color32::GRAY,
             color_dragged: Color32::WHITE,
+            double_click_reset: SeparatorStyle::default_double_click_reset(),
+            snap: SeparatorSnap::None,
+            snap_distance: SeparatorStyle::default_snap_distance(),
+            min_size_overflow: MinSizeOverflow::Shrink,
         }
     }
 }
@@ -420,6 +1011,45 @@ impl Default for TabBarStyle {
             corner_radius: CornerRadius::default(),
             hline_color: Color32::BLACK,
             fill_tab_bar: false,
+            add_button: AddButtonStyle::default(),
+            reorder_animation: AnimationStyle::default(),
+            position: TabBarPosition::Top,
+            scroll_to_switch: None,
+            right_to_left: false,
+            drag_handle_height: TabBarStyle::default_drag_handle_height(),
+        }
+    }
+}
+
+impl Default for AddButtonStyle {
+    fn default() -> Self {
+        Self {
+            width: 24.0,
+            plus_size: 12.0,
+            plus_stroke_width: 1.0,
+        }
+    }
+}
+
+impl Default for AnimationStyle {
+    fn default() -> Self {
+        Self {
+            duration: 0.15,
+            easing: Easing::default(),
+        }
+    }
+}
+
+impl Default for AutoHideStyle {
+    fn default() -> Self {
+        Self {
+            strip_width: 24.0,
+            strip_bg_fill: Color32::WHITE,
+            label_color: Color32::DARK_GRAY,
+            label_active_color: Color32::BLACK,
+            label_spacing: 12.0,
+            flyout_stroke: Stroke::NONE,
+            flyout_animation: AnimationStyle::default(),
         }
     }
 }
@@ -453,6 +1083,13 @@ impl Default for TabStyle {
             tab_body: TabBodyStyle::default(),
             hline_below_active_tab_name: false,
             minimum_width: None,
+            maximum_width: None,
+            title_truncation: TitleTruncation::End,
+            tooltip_delay: None,
+            shape: TabShape::Rectangle,
+            title_text_style: None,
+            title_italics: false,
+            focus_ring: TabStyle::default_focus_ring(),
         }
     }
 }
@@ -494,6 +1131,7 @@ impl Default for OverlayStyle {
             button_border_stroke: Stroke::new(1.0, Color32::from_gray(60)),
             overlay_type: OverlayType::Widgets,
             feel: Default::default(),
+            layout_preview: false,
         }
     }
 }
@@ -504,8 +1142,10 @@ impl Default for OverlayFeel {
             max_preference_time: 0.3,
             window_drop_coverage: 0.5,
             center_drop_coverage: 0.25,
+            corner_dead_zone: 0.0,
             fade_hold_time: 0.2,
             interact_expansion: 20.0,
+            edge_width: Self::default_edge_width(),
         }
     }
 }
@@ -522,16 +1162,24 @@ impl Default for LeafHighlighting {
 }
 
 impl Style {
-    pub(crate) const TAB_ADD_BUTTON_SIZE: f32 = 24.0;
-    pub(crate) const TAB_ADD_PLUS_SIZE: f32 = 12.0;
     pub(crate) const TAB_CLOSE_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_CLOSE_X_SIZE: f32 = 9.0;
     pub(crate) const TAB_CLOSE_ALL_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_CLOSE_ALL_SIZE: f32 = 10.0;
     pub(crate) const TAB_COLLAPSE_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_COLLAPSE_ARROW_SIZE: f32 = 10.0;
+    pub(crate) const TAB_SPLIT_BUTTON_SIZE: f32 = 24.0;
+    pub(crate) const TAB_SPLIT_ICON_SIZE: f32 = 10.0;
     pub(crate) const TAB_EXPAND_BUTTON_SIZE: f32 = 24.0;
     pub(crate) const TAB_EXPAND_ARROW_SIZE: f32 = 10.0;
+    pub(crate) const TAB_RENAME_MIN_WIDTH: f32 = 48.0;
+    pub(crate) const OVERVIEW_CARD_WIDTH: f32 = 192.0;
+    pub(crate) const OVERVIEW_CARD_HEIGHT: f32 = 144.0;
+    pub(crate) const OVERVIEW_CARD_SPACING: f32 = 12.0;
+    pub(crate) const OVERVIEW_CARD_PADDING: f32 = 6.0;
+    pub(crate) const TAB_SWITCHER_WIDTH: f32 = 400.0;
+    pub(crate) const TAB_SWITCHER_MARGIN: f32 = 32.0;
+    pub(crate) const TAB_SWITCHER_MAX_HEIGHT: f32 = 320.0;
 }
 
 impl Style {
@@ -541,7 +1189,8 @@ impl Style {
     /// - [`Style::main_surface_border_stroke`]
     ///
     /// See also: [`ButtonsStyle::from_egui`], [`SeparatorStyle::from_egui`], [`TabBarStyle::from_egui`],
-    /// [`TabStyle::from_egui`]
+    /// [`TabStyle::from_egui`], [`AutoHideStyle::from_egui`], [`LoadingStyle::from_egui`],
+    /// [`WindowTaskbarStyle::from_egui`]
     pub fn from_egui(style: &egui::Style) -> Self {
         Self {
             main_surface_border_stroke: Stroke::NONE,
@@ -551,9 +1200,125 @@ impl Style {
             tab_bar: TabBarStyle::from_egui(style),
             tab: TabStyle::from_egui(style),
             overlay: OverlayStyle::from_egui(style),
+            auto_hide: AutoHideStyle::from_egui(style),
+            loading: LoadingStyle::from_egui(style),
+            window_taskbar: WindowTaskbarStyle::from_egui(style),
             ..Self::default()
         }
     }
+
+    /// Derives the style from [`egui::Visuals`] alone, e.g. [`Visuals::light`](egui::Visuals::light) or
+    /// [`Visuals::dark`](egui::Visuals::dark), using the default [`egui::Style`] for everything else.
+    ///
+    /// This makes it possible to prepare the style of a theme before switching to it.
+    /// See also [`Style::from_egui`].
+    pub fn from_egui_visuals(visuals: &egui::Visuals) -> Self {
+        Self::from_egui(&egui::Style {
+            visuals: visuals.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// A dark style resembling Visual Studio Code: flat tabs on a darker tab bar, with the active tab
+    /// merged with its body.
+    pub fn vscode_dark() -> Self {
+        const EDITOR: Color32 = Color32::from_rgb(0x1e, 0x1e, 0x1e);
+        const TAB_BAR: Color32 = Color32::from_rgb(0x25, 0x25, 0x26);
+        const INACTIVE_TAB: Color32 = Color32::from_rgb(0x2d, 0x2d, 0x2d);
+        const BORDER: Color32 = Color32::from_rgb(0x25, 0x25, 0x26);
+        const ACCENT: Color32 = Color32::from_rgb(0x00, 0x7a, 0xcc);
+        const TEXT: Color32 = Color32::from_rgb(0xcc, 0xcc, 0xcc);
+        const DIM_TEXT: Color32 = Color32::from_rgb(0x96, 0x96, 0x96);
+
+        let mut style = Self::from_egui_visuals(&egui::Visuals::dark());
+        style.tab_bar.bg_fill = TAB_BAR;
+        style.tab_bar.corner_radius = CornerRadius::ZERO;
+        style.tab_bar.hline_color = BORDER;
+        style.tab_bar.height = 35.0;
+        style.tab.minimum_width = Some(120.0);
+
+        let tab = |bg_fill, text_color| TabInteractionStyle {
+            outline_color: BORDER,
+            corner_radius: CornerRadius::ZERO,
+            bg_fill,
+            text_color,
+        };
+        style.tab.active = tab(EDITOR, TEXT);
+        style.tab.focused = tab(EDITOR, Color32::WHITE);
+        style.tab.inactive = tab(INACTIVE_TAB, DIM_TEXT);
+        style.tab.hovered = tab(INACTIVE_TAB, TEXT);
+        style.tab.active_with_kb_focus = TabInteractionStyle {
+            outline_color: ACCENT,
+            ..style.tab.active.clone()
+        };
+        style.tab.inactive_with_kb_focus = TabInteractionStyle {
+            outline_color: ACCENT,
+            ..style.tab.inactive.clone()
+        };
+        style.tab.focused_with_kb_focus = TabInteractionStyle {
+            outline_color: ACCENT,
+            ..style.tab.focused.clone()
+        };
+        style.tab.focus_ring = Stroke::new(1.0, ACCENT);
+        style.tab.tab_body.bg_fill = EDITOR;
+        style.tab.tab_body.stroke = Stroke::NONE;
+        style.tab.tab_body.corner_radius = CornerRadius::ZERO;
+
+        style.separator.color_idle = BORDER;
+        style.separator.color_hovered = ACCENT;
+        style.separator.color_dragged = ACCENT;
+        style.separator.hover_width = 4.0;
+        style.focus_highlight.unfocused_tab_bar_dimming = 0.25;
+        style.focus_highlight.unfocused_window_opacity = 0.9;
+        style.loading.body_spinner_color = DIM_TEXT;
+        style.overlay.selection_color = ACCENT.linear_multiply(0.3);
+        style.auto_hide.strip_bg_fill = TAB_BAR;
+        style.auto_hide.label_color = DIM_TEXT;
+        style.auto_hide.label_active_color = Color32::WHITE;
+        style.window_taskbar.bg_fill = TAB_BAR;
+        style.window_taskbar.hline_color = BORDER;
+        style.window_taskbar.label_color = DIM_TEXT;
+        style.window_taskbar.label_active_color = Color32::WHITE;
+        style
+    }
+
+    /// A style derived from the default [`egui::Style`] which takes up less room: the tab bars are lower,
+    /// and the tabs and their bodies have smaller margins.
+    pub fn compact() -> Self {
+        let mut style = Self::from_egui(&egui::Style::default());
+        style.tab_bar.height = 20.0;
+        style.tab_bar.add_button.width = 20.0;
+        style.tab_bar.add_button.plus_size = 10.0;
+        style.tab.tab_body.inner_margin = Margin::same(2);
+        style.tab.minimum_width = None;
+        style.auto_hide.strip_width = 20.0;
+        style.auto_hide.label_spacing = 8.0;
+        style.overlay.button_spacing = 6.0;
+        style.overlay.max_button_size = 70.0;
+        style
+    }
+
+    /// A style derived from the default [`egui::Style`] with square tabs and a line below the tab bars,
+    /// like earlier versions of `egui_dock` looked by default.
+    pub fn classic() -> Self {
+        let mut style = Self::from_egui(&egui::Style::default());
+        style.tab_bar.corner_radius = CornerRadius::ZERO;
+        style.tab.hline_below_active_tab_name = true;
+        for tab in [
+            &mut style.tab.active,
+            &mut style.tab.inactive,
+            &mut style.tab.focused,
+            &mut style.tab.hovered,
+            &mut style.tab.active_with_kb_focus,
+            &mut style.tab.inactive_with_kb_focus,
+            &mut style.tab.focused_with_kb_focus,
+        ] {
+            tab.corner_radius = CornerRadius::ZERO;
+        }
+        style.tab.tab_body.corner_radius = CornerRadius::ZERO;
+        style.main_surface_border_stroke = style.tab.tab_body.stroke;
+        style
+    }
 }
 
 impl ButtonsStyle {
@@ -575,6 +1340,10 @@ impl ButtonsStyle {
     /// - [`ButtonsStyle::collapse_tabs_color`]
     /// - [`ButtonsStyle::collapse_tabs_active_color`]
     /// - [`ButtonsStyle::collapse_tabs_border_color`]
+    /// - [`ButtonsStyle::split_leaf_bg_fill`]
+    /// - [`ButtonsStyle::split_leaf_color`]
+    /// - [`ButtonsStyle::split_leaf_active_color`]
+    /// - [`ButtonsStyle::split_leaf_border_color`]
     pub fn from_egui(style: &egui::Style) -> Self {
         Self {
             close_tab_bg_fill: style.visuals.widgets.hovered.bg_fill,
@@ -593,6 +1362,10 @@ impl ButtonsStyle {
             collapse_tabs_color: style.visuals.text_color(),
             collapse_tabs_active_color: style.visuals.strong_text_color(),
             collapse_tabs_border_color: style.visuals.widgets.noninteractive.bg_fill,
+            split_leaf_bg_fill: style.visuals.widgets.hovered.bg_fill,
+            split_leaf_color: style.visuals.text_color(),
+            split_leaf_active_color: style.visuals.strong_text_color(),
+            split_leaf_border_color: style.visuals.widgets.noninteractive.bg_fill,
             minimize_window_bg_fill: style.visuals.widgets.hovered.bg_fill,
             minimize_window_color: style.visuals.text_color(),
             minimize_window_active_color: style.visuals.strong_text_color(),
@@ -641,6 +1414,77 @@ impl TabBarStyle {
     }
 }
 
+impl AutoHideStyle {
+    /// Derives relevant fields from `egui::Style` and sets the remaining fields to their default values.
+    ///
+    /// Fields overwritten by [`egui::Style`] are:
+    /// - [`AutoHideStyle::strip_bg_fill`]
+    /// - [`AutoHideStyle::label_color`]
+    /// - [`AutoHideStyle::label_active_color`]
+    /// - [`AutoHideStyle::flyout_stroke`]
+    pub fn from_egui(style: &egui::Style) -> Self {
+        Self {
+            strip_bg_fill: style.visuals.extreme_bg_color,
+            label_color: style.visuals.widgets.inactive.fg_stroke.color,
+            label_active_color: style.visuals.widgets.active.fg_stroke.color,
+            flyout_stroke: style.visuals.window_stroke,
+            ..AutoHideStyle::default()
+        }
+    }
+}
+
+impl LoadingStyle {
+    /// Derives relevant fields from `egui::Style` and sets the remaining fields to their default values.
+    ///
+    /// Fields overwritten by [`egui::Style`] are:
+    /// - [`LoadingStyle::body_spinner_color`]
+    pub fn from_egui(style: &egui::Style) -> Self {
+        Self {
+            body_spinner_color: style.visuals.strong_text_color(),
+            ..LoadingStyle::default()
+        }
+    }
+}
+
+impl WindowTaskbarStyle {
+    /// Derives relevant fields from `egui::Style` and sets the remaining fields to their default values.
+    ///
+    /// Fields overwritten by [`egui::Style`] are:
+    /// - [`WindowTaskbarStyle::bg_fill`]
+    /// - [`WindowTaskbarStyle::hline_color`]
+    /// - [`WindowTaskbarStyle::label_color`]
+    /// - [`WindowTaskbarStyle::label_active_color`]
+    pub fn from_egui(style: &egui::Style) -> Self {
+        Self {
+            bg_fill: style.visuals.extreme_bg_color,
+            hline_color: style.visuals.widgets.noninteractive.bg_stroke.color,
+            label_color: style.visuals.widgets.inactive.fg_stroke.color,
+            label_active_color: style.visuals.widgets.active.fg_stroke.color,
+            ..WindowTaskbarStyle::default()
+        }
+    }
+}
+
+impl Easing {
+    /// Returns the easing function, which maps the linear progress of an animation in `0.0..=1.0` to the
+    /// eased one.
+    pub fn function(self) -> fn(f32) -> f32 {
+        use egui::emath::easing;
+        match self {
+            Easing::Linear => easing::linear,
+            Easing::QuadraticIn => easing::quadratic_in,
+            Easing::QuadraticOut => easing::quadratic_out,
+            Easing::QuadraticInOut => easing::quadratic_in_out,
+            Easing::CubicIn => easing::cubic_in,
+            Easing::CubicOut => easing::cubic_out,
+            Easing::CubicInOut => easing::cubic_in_out,
+            Easing::SinIn => easing::sin_in,
+            Easing::SinOut => easing::sin_out,
+            Easing::SinInOut => easing::sin_in_out,
+        }
+    }
+}
+
 impl TabStyle {
     /// Derives tab styles from `egui::Style`.
     ///
@@ -656,6 +1500,7 @@ impl TabStyle {
             inactive_with_kb_focus: TabInteractionStyle::from_egui_inactive_with_kb_focus(style),
             focused_with_kb_focus: TabInteractionStyle::from_egui_focused_with_kb_focus(style),
             tab_body: TabBodyStyle::from_egui(style),
+            focus_ring: Stroke::new(2.0, style.visuals.selection.stroke.color),
             ..Default::default()
         }
     }
@@ -804,3 +1649,623 @@ impl OverlayStyle {
         }
     }
 }
+
+/// Smoothly blends between two [`Style`]s, e.g. when the application switches between a light and a dark theme.
+///
+/// Colors, strokes and sizes are interpolated, while the settings which can't be, like [`OverlayType`], switch
+/// halfway through the animation.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockArea, DockState, Style, StyleAnimator, TabViewer};
+/// # use egui::{Ui, WidgetText};
+/// # struct MyTabViewer;
+/// # impl TabViewer for MyTabViewer {
+/// #     type Tab = String;
+/// #     fn title(&mut self, tab: &mut Self::Tab) -> WidgetText { WidgetText::from(tab.as_str()) }
+/// #     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {}
+/// # }
+/// # let mut dock_state = DockState::new(vec!["tab".to_string()]);
+/// let mut animator = StyleAnimator::new(Style::from_egui_visuals(&egui::Visuals::dark()));
+/// # egui::__run_test_ctx(|ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// if ui.button("Light theme").clicked() {
+///     ui.ctx().set_visuals(egui::Visuals::light());
+///     animator.transition_to(Style::from_egui_visuals(&egui::Visuals::light()));
+/// }
+///
+/// DockArea::new(&mut dock_state)
+///     .style(animator.style(ui.ctx()).clone())
+///     .show_inside(ui, &mut MyTabViewer);
+/// # });
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct StyleAnimator {
+    from: Style,
+    to: Style,
+    current: Style,
+    animation: AnimationStyle,
+    start_time: Option<f64>,
+    animating: bool,
+}
+
+impl StyleAnimator {
+    /// Creates an animator showing `style`, which blends to other styles in `0.15` seconds with
+    /// [`Easing::CubicOut`] by default.
+    pub fn new(style: Style) -> Self {
+        Self {
+            from: style.clone(),
+            to: style.clone(),
+            current: style,
+            animation: AnimationStyle::default(),
+            start_time: None,
+            animating: false,
+        }
+    }
+
+    /// Sets the duration and easing curve of the transitions.
+    pub fn with_animation(mut self, animation: AnimationStyle) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Starts blending from the style currently shown to `style`.
+    ///
+    /// The transition starts the next time [`StyleAnimator::style`] is called.
+    pub fn transition_to(&mut self, style: Style) {
+        self.from = self.current.clone();
+        self.to = style;
+        self.start_time = None;
+        self.animating = true;
+    }
+
+    /// Switches to `style` right away, cancelling any ongoing transition.
+    pub fn set_style(&mut self, style: Style) {
+        *self = Self::new(style).with_animation(self.animation);
+    }
+
+    /// The style the animator is blending to, or the one it shows if there's no transition.
+    pub fn target(&self) -> &Style {
+        &self.to
+    }
+
+    /// Whether a transition is still ongoing.
+    pub fn is_animating(&self) -> bool {
+        self.animating
+    }
+
+    /// Returns the style to show this frame, requesting a repaint while the transition is ongoing.
+    pub fn style(&mut self, ctx: &egui::Context) -> &Style {
+        if self.animating {
+            let now = ctx.input(|input| input.time);
+            let start_time = *self.start_time.get_or_insert(now);
+            let progress = if self.animation.duration > 0.0 {
+                ((now - start_time) as f32 / self.animation.duration).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            if progress < 1.0 {
+                let t = (self.animation.easing.function())(progress);
+                self.current = self.from.blend_to(&self.to, t);
+                ctx.request_repaint();
+            } else {
+                self.current = self.to.clone();
+                self.animating = false;
+            }
+        }
+        &self.current
+    }
+}
+
+/// Interpolation between two values of a style, used by [`StyleAnimator`].
+trait Blend {
+    fn blend_to(&self, other: &Self, t: f32) -> Self;
+}
+
+/// Settings which can't be interpolated switch halfway through.
+fn pick<T: Clone>(from: &T, to: &T, t: f32) -> T {
+    if t < 0.5 {
+        from.clone()
+    } else {
+        to.clone()
+    }
+}
+
+impl Blend for f32 {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        egui::lerp(*self..=*other, t)
+    }
+}
+
+impl Blend for Color32 {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        self.lerp_to_gamma(*other, t)
+    }
+}
+
+impl Blend for Stroke {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        Stroke::new(
+            self.width.blend_to(&other.width, t),
+            self.color.blend_to(&other.color, t),
+        )
+    }
+}
+
+impl Blend for CornerRadius {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        let blend = |from: u8, to: u8| f32::from(from).blend_to(&f32::from(to), t).round() as u8;
+        CornerRadius {
+            nw: blend(self.nw, other.nw),
+            ne: blend(self.ne, other.ne),
+            sw: blend(self.sw, other.sw),
+            se: blend(self.se, other.se),
+        }
+    }
+}
+
+impl Blend for Margin {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        let blend = |from: i8, to: i8| f32::from(from).blend_to(&f32::from(to), t).round() as i8;
+        Margin {
+            left: blend(self.left, other.left),
+            right: blend(self.right, other.right),
+            top: blend(self.top, other.top),
+            bottom: blend(self.bottom, other.bottom),
+        }
+    }
+}
+
+impl Blend for Style {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        Style {
+            dock_area_padding: match (self.dock_area_padding, other.dock_area_padding) {
+                (Some(from), Some(to)) => Some(from.blend_to(&to, t)),
+                (from, to) => pick(&from, &to, t),
+            },
+            main_surface_border_stroke: self
+                .main_surface_border_stroke
+                .blend_to(&other.main_surface_border_stroke, t),
+            main_surface_border_rounding: self
+                .main_surface_border_rounding
+                .blend_to(&other.main_surface_border_rounding, t),
+            buttons: self.buttons.blend_to(&other.buttons, t),
+            separator: self.separator.blend_to(&other.separator, t),
+            tab_bar: self.tab_bar.blend_to(&other.tab_bar, t),
+            tab: self.tab.blend_to(&other.tab, t),
+            overlay: self.overlay.blend_to(&other.overlay, t),
+            auto_hide: self.auto_hide.blend_to(&other.auto_hide, t),
+            animation: pick(&self.animation, &other.animation, t),
+            drag_preview: DragPreviewStyle {
+                opacity: self
+                    .drag_preview
+                    .opacity
+                    .blend_to(&other.drag_preview.opacity, t),
+                cursor_offset: pick(
+                    &self.drag_preview.cursor_offset,
+                    &other.drag_preview.cursor_offset,
+                    t,
+                ),
+            },
+            focus_highlight: FocusHighlightStyle {
+                tab_bar_tint: self
+                    .focus_highlight
+                    .tab_bar_tint
+                    .blend_to(&other.focus_highlight.tab_bar_tint, t),
+                outline: self
+                    .focus_highlight
+                    .outline
+                    .blend_to(&other.focus_highlight.outline, t),
+                unfocused_tab_bar_dimming: self
+                    .focus_highlight
+                    .unfocused_tab_bar_dimming
+                    .blend_to(&other.focus_highlight.unfocused_tab_bar_dimming, t),
+                unfocused_window_opacity: self
+                    .focus_highlight
+                    .unfocused_window_opacity
+                    .blend_to(&other.focus_highlight.unfocused_window_opacity, t),
+                window_fade_animation: pick(
+                    &self.focus_highlight.window_fade_animation,
+                    &other.focus_highlight.window_fade_animation,
+                    t,
+                ),
+            },
+            loading: LoadingStyle {
+                body_spinner_size: self
+                    .loading
+                    .body_spinner_size
+                    .blend_to(&other.loading.body_spinner_size, t),
+                body_spinner_color: self
+                    .loading
+                    .body_spinner_color
+                    .blend_to(&other.loading.body_spinner_color, t),
+                title_spinner: pick(&self.loading.title_spinner, &other.loading.title_spinner, t),
+            },
+            window_taskbar: WindowTaskbarStyle {
+                height: self
+                    .window_taskbar
+                    .height
+                    .blend_to(&other.window_taskbar.height, t),
+                bg_fill: self
+                    .window_taskbar
+                    .bg_fill
+                    .blend_to(&other.window_taskbar.bg_fill, t),
+                hline_color: self
+                    .window_taskbar
+                    .hline_color
+                    .blend_to(&other.window_taskbar.hline_color, t),
+                label_color: self
+                    .window_taskbar
+                    .label_color
+                    .blend_to(&other.window_taskbar.label_color, t),
+                label_active_color: self
+                    .window_taskbar
+                    .label_active_color
+                    .blend_to(&other.window_taskbar.label_active_color, t),
+            },
+        }
+    }
+}
+
+impl Blend for ButtonsStyle {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        ButtonsStyle {
+            close_tab_color: self.close_tab_color.blend_to(&other.close_tab_color, t),
+            close_tab_active_color: self
+                .close_tab_active_color
+                .blend_to(&other.close_tab_active_color, t),
+            close_tab_bg_fill: self.close_tab_bg_fill.blend_to(&other.close_tab_bg_fill, t),
+            add_tab_align: pick(&self.add_tab_align, &other.add_tab_align, t),
+            add_tab_color: self.add_tab_color.blend_to(&other.add_tab_color, t),
+            add_tab_active_color: self
+                .add_tab_active_color
+                .blend_to(&other.add_tab_active_color, t),
+            add_tab_bg_fill: self.add_tab_bg_fill.blend_to(&other.add_tab_bg_fill, t),
+            add_tab_border_color: self
+                .add_tab_border_color
+                .blend_to(&other.add_tab_border_color, t),
+            close_all_tabs_color: self
+                .close_all_tabs_color
+                .blend_to(&other.close_all_tabs_color, t),
+            close_all_tabs_active_color: self
+                .close_all_tabs_active_color
+                .blend_to(&other.close_all_tabs_active_color, t),
+            close_all_tabs_bg_fill: self
+                .close_all_tabs_bg_fill
+                .blend_to(&other.close_all_tabs_bg_fill, t),
+            close_all_tabs_border_color: self
+                .close_all_tabs_border_color
+                .blend_to(&other.close_all_tabs_border_color, t),
+            close_all_tabs_disabled_color: self
+                .close_all_tabs_disabled_color
+                .blend_to(&other.close_all_tabs_disabled_color, t),
+            collapse_tabs_color: self
+                .collapse_tabs_color
+                .blend_to(&other.collapse_tabs_color, t),
+            collapse_tabs_active_color: self
+                .collapse_tabs_active_color
+                .blend_to(&other.collapse_tabs_active_color, t),
+            collapse_tabs_bg_fill: self
+                .collapse_tabs_bg_fill
+                .blend_to(&other.collapse_tabs_bg_fill, t),
+            collapse_tabs_border_color: self
+                .collapse_tabs_border_color
+                .blend_to(&other.collapse_tabs_border_color, t),
+            split_leaf_color: self.split_leaf_color.blend_to(&other.split_leaf_color, t),
+            split_leaf_active_color: self
+                .split_leaf_active_color
+                .blend_to(&other.split_leaf_active_color, t),
+            split_leaf_bg_fill: self
+                .split_leaf_bg_fill
+                .blend_to(&other.split_leaf_bg_fill, t),
+            split_leaf_border_color: self
+                .split_leaf_border_color
+                .blend_to(&other.split_leaf_border_color, t),
+            minimize_window_color: self
+                .minimize_window_color
+                .blend_to(&other.minimize_window_color, t),
+            minimize_window_active_color: self
+                .minimize_window_active_color
+                .blend_to(&other.minimize_window_active_color, t),
+            minimize_window_bg_fill: self
+                .minimize_window_bg_fill
+                .blend_to(&other.minimize_window_bg_fill, t),
+            minimize_window_border_color: self
+                .minimize_window_border_color
+                .blend_to(&other.minimize_window_border_color, t),
+        }
+    }
+}
+
+impl Blend for SeparatorStyle {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        SeparatorStyle {
+            width: self.width.blend_to(&other.width, t),
+            extra_interact_width: self
+                .extra_interact_width
+                .blend_to(&other.extra_interact_width, t),
+            hover_width: self.hover_width.blend_to(&other.hover_width, t),
+            extra: self.extra.blend_to(&other.extra, t),
+            color_idle: self.color_idle.blend_to(&other.color_idle, t),
+            color_hovered: self.color_hovered.blend_to(&other.color_hovered, t),
+            color_dragged: self.color_dragged.blend_to(&other.color_dragged, t),
+            double_click_reset: pick(&self.double_click_reset, &other.double_click_reset, t),
+            snap: pick(&self.snap, &other.snap, t),
+            snap_distance: self.snap_distance.blend_to(&other.snap_distance, t),
+            min_size_overflow: pick(&self.min_size_overflow, &other.min_size_overflow, t),
+        }
+    }
+}
+
+impl Blend for TabBarStyle {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        TabBarStyle {
+            bg_fill: self.bg_fill.blend_to(&other.bg_fill, t),
+            height: self.height.blend_to(&other.height, t),
+            inner_margin: self.inner_margin.blend_to(&other.inner_margin, t),
+            show_scroll_bar_on_overflow: pick(
+                &self.show_scroll_bar_on_overflow,
+                &other.show_scroll_bar_on_overflow,
+                t,
+            ),
+            corner_radius: self.corner_radius.blend_to(&other.corner_radius, t),
+            hline_color: self.hline_color.blend_to(&other.hline_color, t),
+            fill_tab_bar: pick(&self.fill_tab_bar, &other.fill_tab_bar, t),
+            add_button: AddButtonStyle {
+                width: self.add_button.width.blend_to(&other.add_button.width, t),
+                plus_size: self
+                    .add_button
+                    .plus_size
+                    .blend_to(&other.add_button.plus_size, t),
+                plus_stroke_width: self
+                    .add_button
+                    .plus_stroke_width
+                    .blend_to(&other.add_button.plus_stroke_width, t),
+            },
+            reorder_animation: pick(&self.reorder_animation, &other.reorder_animation, t),
+            position: pick(&self.position, &other.position, t),
+            scroll_to_switch: pick(&self.scroll_to_switch, &other.scroll_to_switch, t),
+            right_to_left: pick(&self.right_to_left, &other.right_to_left, t),
+            drag_handle_height: self
+                .drag_handle_height
+                .blend_to(&other.drag_handle_height, t),
+        }
+    }
+}
+
+impl Blend for AutoHideStyle {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        AutoHideStyle {
+            strip_width: self.strip_width.blend_to(&other.strip_width, t),
+            strip_bg_fill: self.strip_bg_fill.blend_to(&other.strip_bg_fill, t),
+            label_color: self.label_color.blend_to(&other.label_color, t),
+            label_active_color: self
+                .label_active_color
+                .blend_to(&other.label_active_color, t),
+            label_spacing: self.label_spacing.blend_to(&other.label_spacing, t),
+            flyout_stroke: self.flyout_stroke.blend_to(&other.flyout_stroke, t),
+            flyout_animation: pick(&self.flyout_animation, &other.flyout_animation, t),
+        }
+    }
+}
+
+impl Blend for TabStyle {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        TabStyle {
+            active: self.active.blend_to(&other.active, t),
+            inactive: self.inactive.blend_to(&other.inactive, t),
+            focused: self.focused.blend_to(&other.focused, t),
+            hovered: self.hovered.blend_to(&other.hovered, t),
+            inactive_with_kb_focus: self
+                .inactive_with_kb_focus
+                .blend_to(&other.inactive_with_kb_focus, t),
+            active_with_kb_focus: self
+                .active_with_kb_focus
+                .blend_to(&other.active_with_kb_focus, t),
+            focused_with_kb_focus: self
+                .focused_with_kb_focus
+                .blend_to(&other.focused_with_kb_focus, t),
+            tab_body: TabBodyStyle {
+                inner_margin: self
+                    .tab_body
+                    .inner_margin
+                    .blend_to(&other.tab_body.inner_margin, t),
+                stroke: self.tab_body.stroke.blend_to(&other.tab_body.stroke, t),
+                corner_radius: self
+                    .tab_body
+                    .corner_radius
+                    .blend_to(&other.tab_body.corner_radius, t),
+                bg_fill: self.tab_body.bg_fill.blend_to(&other.tab_body.bg_fill, t),
+            },
+            hline_below_active_tab_name: pick(
+                &self.hline_below_active_tab_name,
+                &other.hline_below_active_tab_name,
+                t,
+            ),
+            spacing: self.spacing.blend_to(&other.spacing, t),
+            minimum_width: pick(&self.minimum_width, &other.minimum_width, t),
+            maximum_width: pick(&self.maximum_width, &other.maximum_width, t),
+            title_truncation: pick(&self.title_truncation, &other.title_truncation, t),
+            tooltip_delay: pick(&self.tooltip_delay, &other.tooltip_delay, t),
+            shape: match (self.shape, other.shape) {
+                (TabShape::Browser { slant: from }, TabShape::Browser { slant: to }) => {
+                    TabShape::Browser {
+                        slant: from.blend_to(&to, t),
+                    }
+                }
+                (from, to) => pick(&from, &to, t),
+            },
+            title_text_style: pick(&self.title_text_style, &other.title_text_style, t),
+            title_italics: pick(&self.title_italics, &other.title_italics, t),
+            focus_ring: self.focus_ring.blend_to(&other.focus_ring, t),
+        }
+    }
+}
+
+impl Blend for TabInteractionStyle {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        TabInteractionStyle {
+            outline_color: self.outline_color.blend_to(&other.outline_color, t),
+            corner_radius: self.corner_radius.blend_to(&other.corner_radius, t),
+            bg_fill: self.bg_fill.blend_to(&other.bg_fill, t),
+            text_color: self.text_color.blend_to(&other.text_color, t),
+        }
+    }
+}
+
+impl Blend for OverlayStyle {
+    fn blend_to(&self, other: &Self, t: f32) -> Self {
+        let (from, to) = (&self.hovered_leaf_highlight, &other.hovered_leaf_highlight);
+        OverlayStyle {
+            selection_color: self.selection_color.blend_to(&other.selection_color, t),
+            selection_stroke_width: self
+                .selection_stroke_width
+                .blend_to(&other.selection_stroke_width, t),
+            button_spacing: self.button_spacing.blend_to(&other.button_spacing, t),
+            max_button_size: self.max_button_size.blend_to(&other.max_button_size, t),
+            hovered_leaf_highlight: LeafHighlighting {
+                color: from.color.blend_to(&to.color, t),
+                corner_radius: from.corner_radius.blend_to(&to.corner_radius, t),
+                stroke: from.stroke.blend_to(&to.stroke, t),
+                expansion: from.expansion.blend_to(&to.expansion, t),
+            },
+            surface_fade_opacity: self
+                .surface_fade_opacity
+                .blend_to(&other.surface_fade_opacity, t),
+            button_color: self.button_color.blend_to(&other.button_color, t),
+            button_border_stroke: self
+                .button_border_stroke
+                .blend_to(&other.button_border_stroke, t),
+            overlay_type: pick(&self.overlay_type, &other.overlay_type, t),
+            feel: pick(&self.feel, &other.feel, t),
+            layout_preview: pick(&self.layout_preview, &other.layout_preview, t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn style_animator_blends_to_target() {
+        let dark = Style::from_egui_visuals(&egui::Visuals::dark());
+        let light = Style::from_egui_visuals(&egui::Visuals::light());
+        let mut animator = StyleAnimator::new(dark.clone()).with_animation(AnimationStyle {
+            duration: 1.0,
+            easing: Easing::Linear,
+        });
+        let ctx = egui::Context::default();
+        let style_at = |animator: &mut StyleAnimator, time: f64| {
+            let input = egui::RawInput {
+                time: Some(time),
+                ..Default::default()
+            };
+            let mut style = None;
+            let _ = ctx.run(input, |ctx| style = Some(animator.style(ctx).clone()));
+            style.unwrap()
+        };
+
+        assert_eq!(
+            style_at(&mut animator, 0.0).tab_bar.bg_fill,
+            dark.tab_bar.bg_fill
+        );
+        animator.transition_to(light.clone());
+        assert!(animator.is_animating());
+        assert_eq!(
+            style_at(&mut animator, 1.0).tab_bar.bg_fill,
+            dark.tab_bar.bg_fill
+        );
+
+        let halfway = style_at(&mut animator, 1.5).tab_bar.bg_fill;
+        assert_eq!(
+            halfway,
+            dark.tab_bar
+                .bg_fill
+                .lerp_to_gamma(light.tab_bar.bg_fill, 0.5)
+        );
+
+        assert_eq!(
+            style_at(&mut animator, 2.0).tab_bar.bg_fill,
+            light.tab_bar.bg_fill
+        );
+        assert!(!animator.is_animating());
+    }
+
+    #[test]
+    fn browser_tabs_overlap_by_their_slant() {
+        assert_eq!(TabShape::Rectangle.overlap(), 0.0);
+        assert_eq!(TabShape::Browser { slant: 6.0 }.overlap(), 6.0);
+        assert_eq!(TabShape::Browser { slant: -3.0 }.overlap(), 0.0);
+        assert_eq!(TabStyle::default().shape, TabShape::Rectangle);
+    }
+
+    /// Serializes the default [`Style`] without the fields added after it was first released, like a `Style` saved
+    /// by an older version, and loads it back.
+    #[cfg(feature = "serde")]
+    fn load_without(fields: &[(&str, &str)]) -> Style {
+        let mut json = serde_json::to_value(Style::default()).unwrap();
+        for (parent, field) in fields {
+            json[parent]
+                .as_object_mut()
+                .unwrap()
+                .remove(*field)
+                .unwrap();
+        }
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn older_styles_still_load() {
+        let style = load_without(&[
+            ("buttons", "split_leaf_color"),
+            ("buttons", "split_leaf_active_color"),
+            ("buttons", "split_leaf_bg_fill"),
+            ("buttons", "split_leaf_border_color"),
+            ("tab_bar", "add_button"),
+            ("tab_bar", "reorder_animation"),
+            ("separator", "hover_width"),
+            ("separator", "double_click_reset"),
+            ("separator", "snap"),
+            ("separator", "snap_distance"),
+        ]);
+        let default = Style::default();
+        assert_eq!(
+            style.buttons.split_leaf_color,
+            default.buttons.split_leaf_color
+        );
+        assert_eq!(
+            style.buttons.split_leaf_active_color,
+            default.buttons.split_leaf_active_color
+        );
+        assert_eq!(
+            style.buttons.split_leaf_bg_fill,
+            default.buttons.split_leaf_bg_fill
+        );
+        assert_eq!(
+            style.buttons.split_leaf_border_color,
+            default.buttons.split_leaf_border_color
+        );
+    }
+
+    #[test]
+    fn separators_snap_to_nearby_fractions_and_grid_lines() {
+        let fractions = SeparatorSnap::Fractions(vec![0.25, 0.5]);
+        // 0.27 of 100 points is 2 points from 0.25, and 23 from 0.5.
+        assert_eq!(fractions.snap(0.27, 100.0, 8.0), 0.25);
+        assert_eq!(fractions.snap(0.46, 100.0, 8.0), 0.5);
+        // Both are within reach, the closer one wins.
+        assert_eq!(fractions.snap(0.36, 100.0, 20.0), 0.25);
+        assert_eq!(fractions.snap(0.37, 100.0, 8.0), 0.37);
+
+        let grid = SeparatorSnap::Grid(40.0);
+        assert_eq!(grid.snap(0.45, 200.0, 8.0), 0.4);
+        assert_eq!(grid.snap(0.55, 200.0, 8.0), 0.6);
+        assert_eq!(SeparatorSnap::Grid(0.0).snap(0.45, 200.0, 8.0), 0.45);
+        assert_eq!(grid.snap(0.45, 0.0, 8.0), 0.45);
+        assert_eq!(SeparatorSnap::None.snap(0.45, 200.0, 8.0), 0.45);
+    }
+}
@@ -0,0 +1,351 @@
+use egui::{
+    pos2, vec2, Context, Event, Key, Modifiers, MouseWheelUnit, PointerButton, Pos2, RawInput,
+    Rect, Vec2,
+};
+
+use crate::{DockArea, DockResponse, DockState, NodeIndex, SurfaceIndex, TabIndex, TabViewer};
+
+/// Changes the settings of the [`DockArea`] shown by a [`DockHarness`].
+type Configure<Tab> = Box<dyn for<'a> Fn(DockArea<'a, Tab>) -> DockArea<'a, Tab>>;
+
+/// Shows a [`DockArea`] in a headless [`Context`] and drives it with simulated pointer input, for writing
+/// integration tests of docking flows.
+///
+/// Every action runs as many frames as the [`DockArea`] needs to react to it, so the [`DockState`] can be checked
+/// right after it. Tabs and separators are found through the rects from the last frame, see
+/// [`DockHarness::tab_rect`] and [`DockHarness::separator_pos`].
+///
+/// Text is laid out with the fonts of the [`Context`], so egui's `default_fonts` feature should be enabled
+/// for the tests.
+///
+/// Nothing is rendered, so there are no image snapshots to compare. It isn't built on `egui_kittest`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{testing::DockHarness, DockState, NodeIndex, SurfaceIndex, TabIndex, TabViewer};
+/// # use egui_dock::{OverlayType, Style};
+/// # use egui::{Ui, WidgetText};
+/// struct MyTabViewer;
+///
+/// impl TabViewer for MyTabViewer {
+///     type Tab = String;
+///
+///     fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+///         tab.as_str().into()
+///     }
+///
+///     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+///         ui.label(tab.as_str());
+///     }
+/// }
+///
+/// let dock_state = DockState::new(vec!["Files".to_owned(), "Editor".to_owned()]);
+/// let mut harness = DockHarness::new(dock_state, MyTabViewer).with_dock_area(|dock_area| {
+///     let mut style = Style::default();
+///     style.overlay.overlay_type = OverlayType::HighlightedAreas;
+///     dock_area.style(style)
+/// });
+///
+/// // Clicking a tab makes it the active one.
+/// harness.click_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)));
+/// let leaf = harness.dock_state.main_surface()[NodeIndex::root()].get_leaf().unwrap();
+/// assert_eq!(leaf.active, TabIndex(1));
+///
+/// // Dropping a tab onto the right edge of its leaf splits it.
+/// let rect = harness.leaf_rect(SurfaceIndex::main(), NodeIndex::root()).unwrap();
+/// harness.drag_tab(
+///     (SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)),
+///     rect.right_center() - egui::vec2(10.0, 0.0),
+/// );
+/// assert_eq!(harness.dock_state.main_surface().num_tabs(), 2);
+/// assert!(harness.dock_state.main_surface()[NodeIndex::root()].is_horizontal());
+/// ```
+pub struct DockHarness<Tab, Viewer> {
+    /// The context the [`DockArea`] is shown in.
+    pub ctx: Context,
+
+    /// The state shown by the [`DockArea`].
+    pub dock_state: DockState<Tab>,
+
+    /// The [`TabViewer`] the [`DockArea`] is shown with.
+    pub tab_viewer: Viewer,
+
+    configure: Configure<Tab>,
+    screen_rect: Rect,
+    time: f64,
+    pointer: Pos2,
+    modifiers: Modifiers,
+    events: Vec<Event>,
+    response: DockResponse,
+}
+
+impl<Tab, Viewer> DockHarness<Tab, Viewer>
+where
+    Viewer: TabViewer<Tab = Tab>,
+{
+    /// Creates a harness showing `dock_state` with `tab_viewer` on an 800 by 600 points screen,
+    /// and runs its first frames.
+    pub fn new(dock_state: DockState<Tab>, tab_viewer: Viewer) -> Self {
+        let mut harness = Self {
+            ctx: Context::default(),
+            dock_state,
+            tab_viewer,
+            configure: Box::new(|dock_area| dock_area),
+            screen_rect: Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0)),
+            time: 0.0,
+            pointer: Pos2::ZERO,
+            modifiers: Modifiers::NONE,
+            events: Vec::new(),
+            response: DockResponse::default(),
+        };
+        harness.run_frames(2);
+        harness
+    }
+
+    /// Sets the size of the screen the [`DockArea`] fills.
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.screen_rect = Rect::from_min_size(Pos2::ZERO, size);
+        self.run_frames(2);
+        self
+    }
+
+    /// Changes the settings of the [`DockArea`] before it's shown in each frame.
+    ///
+    /// ```rust
+    /// # use egui_dock::{testing::DockHarness, DockState, TabViewer};
+    /// # struct MyTabViewer;
+    /// # impl TabViewer for MyTabViewer {
+    /// #     type Tab = String;
+    /// #     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText { tab.as_str().into() }
+    /// #     fn ui(&mut self, _ui: &mut egui::Ui, _tab: &mut Self::Tab) {}
+    /// # }
+    /// let harness = DockHarness::new(DockState::new(vec!["Tab".to_owned()]), MyTabViewer)
+    ///     .with_dock_area(|dock_area| dock_area.show_close_buttons(false));
+    /// ```
+    pub fn with_dock_area(
+        mut self,
+        configure: impl for<'a> Fn(DockArea<'a, Tab>) -> DockArea<'a, Tab> + 'static,
+    ) -> Self {
+        self.configure = Box::new(configure);
+        self.run_frames(2);
+        self
+    }
+
+    /// The [`DockResponse`] of the last frame.
+    pub fn response(&self) -> &DockResponse {
+        &self.response
+    }
+
+    /// Runs a frame with the input given since the last one, and returns its [`DockResponse`].
+    pub fn run(&mut self) -> &DockResponse {
+        self.time += 1.0 / 60.0;
+        let input = RawInput {
+            screen_rect: Some(self.screen_rect),
+            time: Some(self.time),
+            predicted_dt: 1.0 / 60.0,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+        let mut response = None;
+        let _ = self.ctx.run(input, |ctx| {
+            let dock_area = (self.configure)(DockArea::new(&mut self.dock_state));
+            response = Some(dock_area.show(ctx, &mut self.tab_viewer));
+        });
+        self.response = response.unwrap_or_default();
+        &self.response
+    }
+
+    /// Runs `count` frames.
+    pub fn run_frames(&mut self, count: usize) {
+        for _ in 0..count {
+            self.run();
+        }
+    }
+
+    /// Holds down `modifiers` for the following input, until they're changed again.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Moves the pointer to `pos`.
+    pub fn hover(&mut self, pos: Pos2) {
+        self.pointer = pos;
+        self.events.push(Event::PointerMoved(pos));
+        self.run_frames(2);
+    }
+
+    /// Clicks the primary button at `pos`.
+    pub fn click(&mut self, pos: Pos2) {
+        self.click_with(pos, PointerButton::Primary);
+    }
+
+    /// Clicks `button` at `pos`.
+    pub fn click_with(&mut self, pos: Pos2, button: PointerButton) {
+        self.hover(pos);
+        self.button(button, true);
+        self.run();
+        self.button(button, false);
+        self.run_frames(2);
+    }
+
+    /// Double clicks the primary button at `pos`.
+    pub fn double_click(&mut self, pos: Pos2) {
+        self.hover(pos);
+        for _ in 0..2 {
+            self.button(PointerButton::Primary, true);
+            self.run();
+            self.button(PointerButton::Primary, false);
+            self.run();
+        }
+        self.run_frames(2);
+    }
+
+    /// Types `text` into the focused widget.
+    pub fn type_text(&mut self, text: &str) {
+        self.events.push(Event::Text(text.to_owned()));
+        self.run_frames(2);
+    }
+
+    /// Presses and releases `key`.
+    pub fn press_key(&mut self, key: Key) {
+        for pressed in [true, false] {
+            self.events.push(Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: self.modifiers,
+            });
+            self.run();
+        }
+        self.run_frames(2);
+    }
+
+    /// Scrolls the mouse wheel by `delta` points at `pos`, and runs frames until the scrolling has settled.
+    pub fn scroll(&mut self, pos: Pos2, delta: Vec2) {
+        self.hover(pos);
+        self.events.push(Event::MouseWheel {
+            unit: MouseWheelUnit::Point,
+            delta,
+            modifiers: self.modifiers,
+        });
+        self.run_frames(60);
+    }
+
+    /// Drags the primary button from `from` to `to` in a few steps, and releases it there.
+    pub fn drag(&mut self, from: Pos2, to: Pos2) {
+        self.drag_and_hold(from, to);
+        self.release();
+    }
+
+    /// Drags the primary button from `from` to `to` in a few steps, and keeps holding it down there
+    /// until [`DockHarness::release`] is called.
+    pub fn drag_and_hold(&mut self, from: Pos2, to: Pos2) {
+        const STEPS: usize = 8;
+
+        self.hover(from);
+        self.button(PointerButton::Primary, true);
+        self.run();
+        for step in 1..=STEPS {
+            let pos = from.lerp(to, step as f32 / STEPS as f32);
+            self.pointer = pos;
+            self.events.push(Event::PointerMoved(pos));
+            self.run();
+        }
+        // The dock area reacts to where the pointer was in the previous frame.
+        self.run_frames(2);
+    }
+
+    /// Releases the primary button held down by [`DockHarness::drag_and_hold`].
+    pub fn release(&mut self) {
+        self.button(PointerButton::Primary, false);
+        self.run_frames(2);
+    }
+
+    /// Where the button of a tab was shown in the last frame, if it was shown.
+    pub fn tab_rect(&self, location: (SurfaceIndex, NodeIndex, TabIndex)) -> Option<Rect> {
+        self.response.tab_rect(location)?.button
+    }
+
+    /// Where a leaf was shown in the last frame, including its tab bar.
+    pub fn leaf_rect(&self, surface_index: SurfaceIndex, node_index: NodeIndex) -> Option<Rect> {
+        let tree = self.dock_state.get_surface(surface_index)?.node_tree()?;
+        let leaf = tree.iter().nth(node_index.0)?.get_leaf()?;
+        Some(leaf.rect())
+    }
+
+    /// The middle of the separator between the children of a split shown in the last frame.
+    pub fn separator_pos(
+        &self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+    ) -> Option<Pos2> {
+        let tree = self.dock_state.get_surface(surface_index)?.node_tree()?;
+        let left = tree.iter().nth(node_index.left().0)?.rect()?;
+        let right = tree.iter().nth(node_index.right().0)?.rect()?;
+        match tree.iter().nth(node_index.0)? {
+            node if node.is_horizontal() => Some(pos2(
+                (left.max.x + right.min.x) / 2.0,
+                (left.center().y + right.center().y) / 2.0,
+            )),
+            node if node.is_vertical() => Some(pos2(
+                (left.center().x + right.center().x) / 2.0,
+                (left.max.y + right.min.y) / 2.0,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Clicks the button of a tab.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tab wasn't shown in the last frame.
+    pub fn click_tab(&mut self, location: (SurfaceIndex, NodeIndex, TabIndex)) {
+        let rect = self.expect_tab_rect(location);
+        self.click(rect.center());
+    }
+
+    /// Drags the button of a tab to `to` and drops it there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tab wasn't shown in the last frame.
+    pub fn drag_tab(&mut self, location: (SurfaceIndex, NodeIndex, TabIndex), to: Pos2) {
+        let rect = self.expect_tab_rect(location);
+        self.drag(rect.center(), to);
+    }
+
+    /// Drags the separator between the children of a split by `delta`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node_index` isn't a split shown in the last frame.
+    pub fn drag_separator(
+        &mut self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        delta: Vec2,
+    ) {
+        let pos = self
+            .separator_pos(surface_index, node_index)
+            .expect("the separator wasn't shown in the last frame");
+        self.drag(pos, pos + delta);
+    }
+
+    fn expect_tab_rect(&self, location: (SurfaceIndex, NodeIndex, TabIndex)) -> Rect {
+        self.tab_rect(location)
+            .expect("the tab wasn't shown in the last frame")
+    }
+
+    fn button(&mut self, button: PointerButton, pressed: bool) {
+        self.events.push(Event::PointerButton {
+            pos: self.pointer,
+            button,
+            pressed,
+            modifiers: self.modifiers,
+        });
+    }
+}
@@ -1,10 +1,20 @@
 use egui::emath::*;
 
 use crate::{
-    ButtonsStyle, SeparatorStyle, Style, TabBarStyle, TabBodyStyle, TabInteractionStyle, TabStyle,
+    AutoHideStyle, ButtonsStyle, SeparatorStyle, Style, TabBarStyle, TabBodyStyle,
+    TabInteractionStyle, TabStyle, WindowTaskbarStyle,
 };
 use egui::style::{Visuals, WidgetVisuals, Widgets};
 
+/// Opens a profiling scope lasting until the end of the enclosing block, if the `profiling` feature is enabled.
+macro_rules! profile_scope {
+    ($name:expr) => {
+        #[cfg(feature = "profiling")]
+        profiling::scope!($name);
+    };
+}
+pub(crate) use profile_scope;
+
 #[inline(always)]
 pub fn expand_to_pixel(mut rect: Rect, ppi: f32) -> Rect {
     rect.min = map_to_pixel_pos(rect.min, ppi, f32::floor);
@@ -31,6 +41,12 @@ pub fn rect_set_size_centered(rect: &mut Rect, size: Vec2) {
     rect.set_center(center);
 }
 
+/// Rectangles which are recomputed every frame deserialize to this value.
+#[cfg(feature = "serde")]
+pub(crate) fn rect_nothing() -> Rect {
+    Rect::NOTHING
+}
+
 /// Shrink a rectangle so that the stroke is fully contained inside
 /// the original rectangle.
 pub fn rect_stroke_box(rect: Rect, width: f32) -> Rect {
@@ -47,6 +63,22 @@ pub(super) fn fade_dock_style(style: &mut Style, factor: f32) {
     fade_button_style(&mut style.buttons, factor);
     fade_seperator_style(&mut style.separator, factor);
     fade_tab_bar_style(&mut style.tab_bar, factor);
+    fade_auto_hide_style(&mut style.auto_hide, factor);
+    style.loading.body_spinner_color = style.loading.body_spinner_color.linear_multiply(factor);
+    fade_window_taskbar_style(&mut style.window_taskbar, factor);
+}
+
+fn fade_window_taskbar_style(style: &mut WindowTaskbarStyle, factor: f32) {
+    style.bg_fill = style.bg_fill.linear_multiply(factor);
+    style.hline_color = style.hline_color.linear_multiply(factor);
+    style.label_color = style.label_color.linear_multiply(factor);
+    style.label_active_color = style.label_active_color.linear_multiply(factor);
+}
+
+fn fade_auto_hide_style(style: &mut AutoHideStyle, factor: f32) {
+    style.strip_bg_fill = style.strip_bg_fill.linear_multiply(factor);
+    style.label_color = style.label_color.linear_multiply(factor);
+    style.label_active_color = style.label_active_color.linear_multiply(factor);
 }
 
 fn fade_tab_bar_style(style: &mut TabBarStyle, factor: f32) {
@@ -68,6 +100,10 @@ fn fade_button_style(style: &mut ButtonsStyle, factor: f32) {
     style.add_tab_active_color = style.add_tab_active_color.linear_multiply(factor);
     style.add_tab_bg_fill = style.add_tab_bg_fill.linear_multiply(factor);
     style.add_tab_border_color = style.add_tab_border_color.linear_multiply(factor);
+    style.split_leaf_color = style.split_leaf_color.linear_multiply(factor);
+    style.split_leaf_active_color = style.split_leaf_active_color.linear_multiply(factor);
+    style.split_leaf_bg_fill = style.split_leaf_bg_fill.linear_multiply(factor);
+    style.split_leaf_border_color = style.split_leaf_border_color.linear_multiply(factor);
 }
 
 fn fade_tab_style(style: &mut TabStyle, factor: f32) {
@@ -76,6 +112,7 @@ fn fade_tab_style(style: &mut TabStyle, factor: f32) {
     fade_tab_interaction_style(&mut style.focused, factor);
     fade_tab_interaction_style(&mut style.hovered, factor);
     fade_tab_body_style(&mut style.tab_body, factor);
+    style.focus_ring.color = style.focus_ring.color.linear_multiply(factor);
 }
 
 fn fade_tab_interaction_style(style: &mut TabInteractionStyle, factor: f32) {
@@ -84,7 +121,7 @@ fn fade_tab_interaction_style(style: &mut TabInteractionStyle, factor: f32) {
     style.text_color = style.text_color.linear_multiply(factor);
 }
 
-fn fade_tab_body_style(style: &mut TabBodyStyle, factor: f32) {
+pub(super) fn fade_tab_body_style(style: &mut TabBodyStyle, factor: f32) {
     style.stroke.color = style.stroke.color.linear_multiply(factor);
     style.bg_fill = style.bg_fill.linear_multiply(factor);
 }
@@ -1,5 +1,8 @@
+use crate::Split;
+
 /// What directions can this dock be split in?
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum AllowedSplits {
     #[default]
     /// Allow splits in any direction (horizontal and vertical).
@@ -24,6 +27,23 @@ impl std::ops::BitAnd for AllowedSplits {
 }
 
 impl AllowedSplits {
+    /// Returns whether splitting in the direction of `split` is allowed.
+    ///
+    /// ```rust
+    /// # use egui_dock::{AllowedSplits, Split};
+    /// assert!(AllowedSplits::LeftRightOnly.allows(Split::Right));
+    /// assert!(!AllowedSplits::LeftRightOnly.allows(Split::Below));
+    /// ```
+    #[inline]
+    pub fn allows(self, split: Split) -> bool {
+        match self {
+            AllowedSplits::All => true,
+            AllowedSplits::LeftRightOnly => split.is_left_right(),
+            AllowedSplits::TopBottomOnly => split.is_top_bottom(),
+            AllowedSplits::None => false,
+        }
+    }
+
     /// Create allowed splits from a u8, panics if an invalid value is given.
     #[inline(always)]
     fn from_u8(u8: u8) -> Self {
@@ -5,7 +5,7 @@ use crate::{
 };
 use egui::{
     emath::{inverse_lerp, GuiRounding},
-    vec2, Context, Id, LayerId, NumExt, Order, Painter, Pos2, Rect, Stroke, StrokeKind, Ui, Vec2,
+    vec2, Context, LayerId, NumExt, Order, Painter, Pos2, Rect, Stroke, StrokeKind, Ui, Vec2,
 };
 
 #[derive(Debug, Clone)]
@@ -18,15 +18,35 @@ pub(super) struct HoverData {
 
     /// If a tab title or the tab head is hovered, this is the rect of it.
     pub tab: Option<Rect>,
+
+    /// If the band along an outer edge of a surface is hovered, this is the side of the surface it's on.
+    /// `dst` is then the root node of the surface.
+    pub edge: Option<Split>,
 }
 
 /// Specifies the location of a tab on the tree, used when moving tabs.
 #[derive(Debug, Clone)]
 pub(super) struct DragData {
-    pub src: TreeComponent,
+    /// The dragged tab, or `None` for an [`ExternalPayload`](crate::ExternalPayload).
+    pub src: Option<DragSource>,
     pub rect: Rect,
 }
 
+/// The tab or leaf being dragged.
+#[derive(Debug, Clone)]
+pub(super) enum DragSource {
+    Node(SurfaceIndex, NodeIndex),
+    Tab(SurfaceIndex, NodeIndex, TabIndex),
+}
+
+impl DragSource {
+    pub(super) fn node_address(&self) -> (SurfaceIndex, NodeIndex) {
+        match *self {
+            DragSource::Node(surface, node) | DragSource::Tab(surface, node, _) => (surface, node),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(super) enum TreeComponent {
     Surface(SurfaceIndex),
@@ -68,10 +88,84 @@ impl TreeComponent {
     }
 }
 
-fn make_overlay_painter(ui: &Ui) -> Painter {
-    let id = Id::new("overlay");
-    let layer_id = LayerId::new(Order::Foreground, id);
-    ui.ctx().layer_painter(layer_id)
+/// Describes the drop indicators shown while a tab is dragged over a leaf or an empty surface,
+/// see [`TabViewer::drop_overlay_ui`](crate::TabViewer::drop_overlay_ui).
+#[derive(Clone, Debug)]
+pub struct DropOverlay {
+    /// Rect of the leaf or empty surface the tab is dragged over.
+    pub hovered_rect: Rect,
+
+    /// Where the tab goes if it's dropped now, or `None` if it can't be dropped here.
+    pub destination: Option<TabDestination>,
+
+    /// Area the tab ends up in if it's dropped now: the tab title or tab bar it's dropped onto, the part of the
+    /// leaf it splits off, or the rect of the new window. `None` if the tab can't be dropped here.
+    pub drop_rect: Option<Rect>,
+
+    /// The buttons choosing where the tab is dropped, shown for [`OverlayType::Widgets`](crate::OverlayType::Widgets).
+    /// Empty for [`OverlayType::HighlightedAreas`](crate::OverlayType::HighlightedAreas), and while the tab is
+    /// dragged over a tab bar.
+    pub buttons: Vec<DropButton>,
+
+    /// Rects of the leaves of the hovered surface as they would be laid out if the tab was dropped now, when
+    /// [`OverlayStyle::layout_preview`](crate::OverlayStyle::layout_preview) is set. The leaf the tab goes to is
+    /// [`DropOverlay::drop_rect`]. Empty otherwise.
+    pub leaf_previews: Vec<Rect>,
+
+    /// Position of the pointer.
+    pub pointer: Pos2,
+}
+
+/// One of the buttons of a [`DropOverlay`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DropButton {
+    /// Rect of the button.
+    pub rect: Rect,
+
+    /// The side of the hovered leaf the tab is split off to, or `None` for the button adding the tab to the leaf.
+    pub split: Option<Split>,
+
+    /// Whether the pointer is over the button.
+    pub hovered: bool,
+}
+
+impl DropOverlay {
+    fn new(hovered_rect: Rect, pointer: Pos2) -> Self {
+        Self {
+            hovered_rect,
+            destination: None,
+            drop_rect: None,
+            buttons: Vec::new(),
+            leaf_previews: Vec::new(),
+            pointer,
+        }
+    }
+
+    /// Draws the overlay as specified by [`Style::overlay`].
+    pub(super) fn paint(&self, ui: &Ui, style: &Style) {
+        draw_highlight_rect(self.hovered_rect, ui, style);
+        for &rect in &self.leaf_previews {
+            draw_window_rect(rect, ui, style);
+        }
+        // With buttons, the hovered button shows where the tab goes, unless the new layout is previewed.
+        match (self.destination, self.drop_rect) {
+            (Some(TabDestination::Window(_)), Some(rect)) => draw_window_rect(rect, ui, style),
+            (Some(_), Some(rect)) if self.buttons.is_empty() || !self.leaf_previews.is_empty() => {
+                draw_drop_rect(rect, ui, style)
+            }
+            _ => (),
+        }
+        for button in &self.buttons {
+            draw_button(button, ui, style);
+        }
+    }
+}
+
+pub(super) fn make_overlay_painter(ui: &Ui) -> Painter {
+    let layer_id = LayerId::new(Order::Foreground, ui.id());
+    ui.ctx()
+        .layer_painter(layer_id)
+        .with_clip_rect(ui.clip_rect())
 }
 
 fn draw_highlight_rect(rect: Rect, ui: &Ui, style: &Style) {
@@ -85,15 +179,35 @@ fn draw_highlight_rect(rect: Rect, ui: &Ui, style: &Style) {
     );
 }
 
-// Draws one of the Tab drop destination icons inside `rect`, which one you get is specified by `is_top_bottom`.
-fn button_ui(
+// Checks whether the pointer is over one of the Tab drop destination icons inside `rect`, which one it is is
+// specified by `split`.
+fn drop_button(
     rect: Rect,
-    ui: &Ui,
     lock: &mut bool,
     mouse_pos: Pos2,
     style: &Style,
     split: Option<Split>,
-) -> bool {
+) -> (DropButton, bool) {
+    let is_mouse_over = rect
+        .shrink(rect.width() * 0.1)
+        .expand(style.overlay.feel.interact_expansion)
+        .contains(mouse_pos);
+    let button = DropButton {
+        rect,
+        split,
+        hovered: is_mouse_over && !*lock,
+    };
+    lock.bitor_assign(is_mouse_over);
+    (button, is_mouse_over)
+}
+
+// Draws one of the Tab drop destination icons.
+fn draw_button(button: &DropButton, ui: &Ui, style: &Style) {
+    let DropButton {
+        rect,
+        split,
+        hovered,
+    } = *button;
     let visuals = &style.overlay;
     let button_stroke = Stroke::new(1.0, visuals.button_color);
     let painter = make_overlay_painter(ui);
@@ -116,10 +230,7 @@ fn button_ui(
             painter.line_segment([start, end], button_stroke);
         }
     }
-    let is_mouse_over = rect
-        .expand(style.overlay.feel.interact_expansion)
-        .contains(mouse_pos);
-    if is_mouse_over && !*lock {
+    if hovered {
         let vertical_alphas = vec2(1.0, 0.5);
         let horizontal_alphas = vec2(0.5, 1.0);
         let rect = match split {
@@ -137,8 +248,6 @@ fn button_ui(
         };
         painter.rect_filled(rect, 0.0, style.overlay.selection_color);
     }
-    lock.bitor_assign(is_mouse_over);
-    is_mouse_over
 }
 
 const DASHED_LINE_ALPHAS: [f32; 8] = [
@@ -179,10 +288,10 @@ impl DragDropState {
         allowed_splits: AllowedSplits,
         windows_allowed: bool,
         window_bounds: Rect,
-    ) -> Option<TabDestination> {
+    ) -> DropOverlay {
         assert!(!self.is_on_title_bar());
 
-        draw_highlight_rect(self.hover.rect, ui, style);
+        let mut overlay = DropOverlay::new(self.hover.rect, self.pointer);
         let mut hovering_buttons = false;
         let total_button_spacing = style.overlay.button_spacing * 2.0;
         let (rect, pointer) = (self.hover.rect, self.pointer);
@@ -197,7 +306,9 @@ impl DragDropState {
         let center = rect.center();
         let rect = Rect::from_center_size(center, Vec2::splat(shortest_side));
 
-        if button_ui(rect, ui, &mut hovering_buttons, pointer, style, None) {
+        let (button, hovered) = drop_button(rect, &mut hovering_buttons, pointer, style, None);
+        overlay.buttons.push(button);
+        if hovered {
             match self.hover.dst {
                 TreeComponent::Node(surface, node) => {
                     destination = Some(TabDestination::Node(surface, node, TabInsert::Append))
@@ -222,14 +333,15 @@ impl DragDropState {
                         Split::Left => vec2(-offset_value, 0.0),
                         Split::Right => vec2(offset_value, 0.0),
                     };
-                    if button_ui(
+                    let (button, hovered) = drop_button(
                         Rect::from_center_size(center + offset_vector, Vec2::splat(shortest_side)),
-                        ui,
                         &mut hovering_buttons,
                         pointer,
                         style,
                         Some(split),
-                    ) {
+                    );
+                    overlay.buttons.push(button);
+                    if hovered {
                         if let TreeComponent::Node(surface, node) = self.hover.dst {
                             destination =
                                 Some(TabDestination::Node(surface, node, TabInsert::Split(split)))
@@ -245,12 +357,19 @@ impl DragDropState {
             (true, _) => LockState::SoftLock,
         };
         self.update_lock(target_lock_state, style, ui.ctx());
-        if let Some(TabDestination::Window(rect)) = destination {
-            let rect = self.window_preview_rect(rect);
-            let rect_bounded = constrain_rect_to_area(ui, rect, window_bounds);
-            draw_window_rect(rect_bounded, ui, style);
-        }
-        destination
+        overlay.drop_rect = match destination {
+            Some(TabDestination::Window(rect)) => {
+                let rect = self.window_preview_rect(rect);
+                Some(constrain_rect_to_area(ui, rect, window_bounds))
+            }
+            Some(TabDestination::Node(_, _, TabInsert::Split(split))) => {
+                Some(split_half(self.hover.rect, split))
+            }
+            Some(_) => Some(self.hover.rect),
+            None => None,
+        };
+        overlay.destination = destination;
+        overlay
     }
 
     pub(super) fn resolve_traditional(
@@ -260,23 +379,24 @@ impl DragDropState {
         allowed_splits: AllowedSplits,
         windows_allowed: bool,
         window_bounds: Rect,
-    ) -> Option<TabDestination> {
+    ) -> Option<DropOverlay> {
         // If windows are not allowed, any hover over a window is immediately disallowed.
         if !windows_allowed && self.hover.dst.surface_address() != SurfaceIndex::main() {
             return None;
         }
-        draw_highlight_rect(self.hover.rect, ui, style);
+        let mut overlay = DropOverlay::new(self.hover.rect, self.pointer);
 
         // Deals with hovers over tab bar and tab titles.
         if let Some(rect) = self.hover.tab {
-            draw_drop_rect(rect, ui, style);
             let target_lock_state = if rect.contains(self.pointer) {
                 LockState::SoftLock
             } else {
                 LockState::Unlocked
             };
             self.update_lock(target_lock_state, style, ui.ctx());
-            return Some(self.hover.dst.as_tab_destination());
+            overlay.destination = Some(self.hover.dst.as_tab_destination());
+            overlay.drop_rect = Some(rect);
+            return Some(overlay);
         }
 
         // Main cases, splits, window creations, etc.
@@ -310,6 +430,9 @@ impl DragDropState {
                     false => (Some(TabInsert::Append), Rect::EVERYTHING),
                 }
             } else {
+                let corner = 0.5 - style.overlay.feel.corner_dead_zone.clamp(0.0, 0.5);
+                let in_dead_corner = a_pos.x.abs() > corner && a_pos.y.abs() > corner;
+
                 // Assessing if were above/below the two linear functions x-y=0 and -x-y=0 determines
                 // what "diagonal" quadrant were in.
                 let a_pos = match allowed_splits {
@@ -318,7 +441,7 @@ impl DragDropState {
                     AllowedSplits::TopBottomOnly => Pos2::new(0.0, a_pos.y),
                     AllowedSplits::None => Pos2::ZERO,
                 };
-                if a_pos == Pos2::ZERO {
+                if a_pos == Pos2::ZERO || in_dead_corner {
                     match windows_allowed {
                         true => (None, Rect::NOTHING),
                         false => (Some(TabInsert::Append), Rect::EVERYTHING),
@@ -356,20 +479,32 @@ impl DragDropState {
 
         self.update_lock(LockState::SoftLock, style, ui.ctx());
 
-        // Draw the overlay
-        match final_result {
+        overlay.drop_rect = match final_result {
             Some(TabDestination::Window(rect)) => {
                 let rect = self.window_preview_rect(rect);
-                let rect_bounded = constrain_rect_to_area(ui, rect, window_bounds);
-                draw_window_rect(rect_bounded, ui, style);
-            }
-            Some(_) => {
-                draw_drop_rect(hover_rect.intersect(overlay_rect), ui, style);
+                Some(constrain_rect_to_area(ui, rect, window_bounds))
             }
-            None => (),
-        }
+            Some(_) => Some(hover_rect.intersect(overlay_rect)),
+            None => None,
+        };
+        overlay.destination = final_result;
+        Some(overlay)
+    }
 
-        final_result
+    /// Resolves a hover over the band along an outer edge of a surface, which splits the whole surface.
+    pub(super) fn resolve_edge(&mut self, ui: &Ui, style: &Style) -> DropOverlay {
+        let split = self.hover.edge.expect("the hovered component is an edge");
+        let (surface, root) = match self.hover.dst {
+            TreeComponent::Node(surface, node) => (surface, node),
+            _ => unreachable!("edges belong to the root node of a surface"),
+        };
+        // The edges are narrow, so they don't hold on to the pointer once it leaves them.
+        self.update_lock(LockState::Unlocked, style, ui.ctx());
+
+        let mut overlay = DropOverlay::new(self.hover.rect, self.pointer);
+        overlay.drop_rect = Some(split_half(self.hover.rect, split));
+        overlay.destination = Some(TabDestination::Node(surface, root, TabInsert::Split(split)));
+        overlay
     }
 
     fn update_lock(&mut self, target_state: LockState, style: &Style, ctx: &Context) {
@@ -408,7 +543,12 @@ impl DragDropState {
     }
 
     fn window_preview_rect(&self, rect: Rect) -> Rect {
-        if self.drag.src.surface_address() == SurfaceIndex::main() {
+        if self
+            .drag
+            .src
+            .as_ref()
+            .is_none_or(|src| src.node_address().0 == SurfaceIndex::main())
+        {
             Rect::from_min_size(rect.min, rect.size() * 0.8)
         } else {
             rect
@@ -425,6 +565,17 @@ const fn lerp_vec(split: Split, alpha: f32) -> Vec2 {
     }
 }
 
+// The half of `rect` a tab split off to the `split` side of it takes up.
+fn split_half(rect: Rect, split: Split) -> Rect {
+    let center = rect.center();
+    rect.intersect(match split {
+        Split::Above => Rect::everything_above(center.y),
+        Split::Below => Rect::everything_below(center.y),
+        Split::Left => Rect::everything_left_of(center.x),
+        Split::Right => Rect::everything_right_of(center.x),
+    })
+}
+
 // Draws a filled rect describing where a tab will be dropped.
 #[inline(always)]
 fn draw_drop_rect(rect: Rect, ui: &Ui, style: &Style) {
@@ -0,0 +1,67 @@
+use std::{any::Any, sync::Arc};
+
+use egui::{Context, DroppedFile, Id};
+
+/// Data dragged into a [`DockArea`](crate::DockArea) from outside of it.
+///
+/// While such a payload is dragged over the dock, the same drop overlays are shown as for dragged tabs.
+/// Once it's dropped, [`TabViewer::on_external_drop`](crate::TabViewer::on_external_drop) decides which tab,
+/// if any, is put at the chosen [`TabDestination`](crate::TabDestination).
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::ExternalPayload;
+/// # egui::__run_test_ui(|ui| {
+/// struct Asset(&'static str);
+///
+/// let response = ui.add(egui::Label::new("texture.png").sense(egui::Sense::drag()));
+/// if response.drag_started() {
+///     ExternalPayload::start_drag(ui.ctx(), Asset("texture.png"));
+/// }
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub enum ExternalPayload {
+    /// Files dropped from the operating system, e.g. from a file manager.
+    ///
+    /// This requires that [`DockArea::accept_dropped_files`](crate::DockArea::accept_dropped_files)
+    /// is set to `true`.
+    Files(Vec<DroppedFile>),
+
+    /// A custom payload dragged from elsewhere in the application, see [`ExternalPayload::start_drag`].
+    Custom(Arc<dyn Any + Send + Sync>),
+}
+
+impl ExternalPayload {
+    /// Starts dragging a custom `payload` into the dock, e.g. when a widget of your application starts being dragged.
+    ///
+    /// The payload is dropped once the primary pointer button is released,
+    /// unless [`ExternalPayload::cancel_drag`] is called before that.
+    pub fn start_drag(ctx: &Context, payload: impl Any + Send + Sync) {
+        let payload: Arc<dyn Any + Send + Sync> = Arc::new(payload);
+        ctx.data_mut(|d| d.insert_temp(Self::id(), payload));
+    }
+
+    /// Stops dragging the custom payload started with [`ExternalPayload::start_drag`] without dropping it.
+    pub fn cancel_drag(ctx: &Context) {
+        ctx.data_mut(|d| d.remove::<Arc<dyn Any + Send + Sync>>(Self::id()));
+    }
+
+    /// Returns the custom payload if it's of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match self {
+            Self::Files(_) => None,
+            Self::Custom(payload) => payload.downcast_ref(),
+        }
+    }
+
+    /// The custom payload which is currently being dragged, if any.
+    pub(super) fn dragged(ctx: &Context) -> Option<Arc<dyn Any + Send + Sync>> {
+        ctx.data(|d| d.get_temp(Self::id()))
+    }
+
+    fn id() -> Id {
+        Id::new("egui_dock::ExternalPayload")
+    }
+}
@@ -0,0 +1,49 @@
+use egui::{Key, KeyboardShortcut, Modifiers};
+
+/// Keyboard shortcuts of a [`DockArea`](crate::DockArea), see [`DockArea::keybindings`](crate::DockArea::keybindings).
+///
+/// Shortcuts set to `None` are disabled.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct KeyBindings {
+    /// Moves the nearest separator to the left of the focused leaf to the left, or if there is none,
+    /// the one to its right. By `Default` it's `Ctrl+Alt+Left` (`Cmd+Option+Left` on macOS).
+    pub resize_left: Option<KeyboardShortcut>,
+
+    /// Moves the nearest separator to the right of the focused leaf to the right, or if there is none,
+    /// the one to its left. By `Default` it's `Ctrl+Alt+Right` (`Cmd+Option+Right` on macOS).
+    pub resize_right: Option<KeyboardShortcut>,
+
+    /// Moves the nearest separator above the focused leaf up, or if there is none, the one below it.
+    /// By `Default` it's `Ctrl+Alt+Up` (`Cmd+Option+Up` on macOS).
+    pub resize_up: Option<KeyboardShortcut>,
+
+    /// Moves the nearest separator below the focused leaf down, or if there is none, the one above it.
+    /// By `Default` it's `Ctrl+Alt+Down` (`Cmd+Option+Down` on macOS).
+    pub resize_down: Option<KeyboardShortcut>,
+
+    /// How far, in points, a separator moves each time a resize shortcut is pressed.
+    /// With [`SeparatorSnap::Grid`](crate::SeparatorSnap::Grid) it moves one cell of the grid instead.
+    /// By `Default` it's `16.0`.
+    pub resize_step: f32,
+
+    /// Cancels the drag of a tab or leaf, which slides the dragged tab back to its place with
+    /// [`Style::animation`](crate::Style::animation). Nothing is dropped when the pointer is released.
+    /// By `Default` it's `Escape`.
+    pub cancel_drag: Option<KeyboardShortcut>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let modifiers = Modifiers::COMMAND | Modifiers::ALT;
+        Self {
+            resize_left: Some(KeyboardShortcut::new(modifiers, Key::ArrowLeft)),
+            resize_right: Some(KeyboardShortcut::new(modifiers, Key::ArrowRight)),
+            resize_up: Some(KeyboardShortcut::new(modifiers, Key::ArrowUp)),
+            resize_down: Some(KeyboardShortcut::new(modifiers, Key::ArrowDown)),
+            resize_step: 16.0,
+            cancel_drag: Some(KeyboardShortcut::new(Modifiers::NONE, Key::Escape)),
+        }
+    }
+}
@@ -6,12 +6,26 @@ mod show;
 // Various components of the `DockArea` which is used when rendering
 mod allowed_splits;
 mod drag_and_drop;
+mod external_payload;
+mod keybindings;
+mod nested;
+mod response;
 mod state;
+mod tab_previews;
 mod tab_removal;
+mod tear_off;
 
-use crate::{dock_state::DockState, NodeIndex, Style, SurfaceIndex, TabIndex};
+use crate::{
+    dock_state::DockState, NodeId, NodeIndex, Split, Style, SurfaceIndex, TabId, TabIndex,
+};
 pub use allowed_splits::AllowedSplits;
+pub use drag_and_drop::{DropButton, DropOverlay};
+pub use external_payload::ExternalPayload;
+pub use keybindings::KeyBindings;
+pub use nested::{BridgedTab, NestedDrags};
+pub use response::{DockEvent, DockResponse, TabRects};
 use tab_removal::TabRemoval;
+pub use tear_off::TearOff;
 
 use egui::{emath::*, Id, Modifiers};
 
@@ -24,23 +38,44 @@ pub struct DockArea<'tree, Tab> {
     show_add_buttons: bool,
     show_close_buttons: bool,
     tab_context_menus: bool,
+    tab_bar_context_menus: bool,
+    empty_leaf_context_menus: bool,
     draggable_tabs: bool,
     show_tab_name_on_hover: bool,
+    tab_renaming: bool,
+    double_click_to_float: bool,
+    tab_scroll_areas: bool,
     show_window_close_buttons: bool,
     show_window_collapse_buttons: bool,
+    show_window_title_bars: bool,
+    window_taskbar: bool,
     show_leaf_close_all_buttons: bool,
     show_leaf_collapse_buttons: bool,
+    show_leaf_split_buttons: bool,
+    hide_single_tab_bars: bool,
     show_secondary_button_hint: bool,
     secondary_button_modifiers: Modifiers,
     secondary_button_on_modifier: bool,
     secondary_button_context_menu: bool,
     allowed_splits: AllowedSplits,
+    surface_edge_splits: bool,
     window_bounds: Option<Rect>,
+    clip_to_parent: bool,
+    native_windows: bool,
+    accept_dropped_files: bool,
+    tab_previews: bool,
+    keybindings: KeyBindings,
+    tear_off: TearOff,
+    nested_drags: NestedDrags,
 
     to_remove: Vec<TabRemoval>,
     to_detach: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+    to_float: Vec<TabId>,
+    to_split: Vec<(NodeId, Split)>,
     new_focused: Option<(SurfaceIndex, NodeIndex)>,
+    refocus_leaf: Option<(SurfaceIndex, NodeIndex)>,
     tab_hover_rect: Option<(Rect, TabIndex)>,
+    response: DockResponse,
 }
 
 // Builder
@@ -56,18 +91,39 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
             show_add_buttons: false,
             show_close_buttons: true,
             tab_context_menus: true,
+            tab_bar_context_menus: false,
+            empty_leaf_context_menus: false,
             draggable_tabs: true,
             show_tab_name_on_hover: false,
+            tab_renaming: false,
+            double_click_to_float: false,
+            tab_scroll_areas: true,
             allowed_splits: AllowedSplits::default(),
+            surface_edge_splits: false,
             to_remove: Vec::new(),
             to_detach: Vec::new(),
+            to_float: Vec::new(),
+            to_split: Vec::new(),
             new_focused: None,
+            refocus_leaf: None,
             tab_hover_rect: None,
+            response: DockResponse::default(),
             window_bounds: None,
+            clip_to_parent: false,
+            native_windows: false,
+            accept_dropped_files: false,
+            tab_previews: false,
+            keybindings: KeyBindings::default(),
+            tear_off: TearOff::default(),
+            nested_drags: NestedDrags::default(),
             show_window_close_buttons: true,
             show_window_collapse_buttons: true,
+            show_window_title_bars: false,
+            window_taskbar: false,
             show_leaf_close_all_buttons: true,
             show_leaf_collapse_buttons: true,
+            show_leaf_split_buttons: false,
+            hide_single_tab_bars: false,
             show_secondary_button_hint: true,
             secondary_button_modifiers: Modifiers::SHIFT,
             secondary_button_on_modifier: true,
@@ -76,6 +132,9 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
     }
 
     /// Sets the [`DockArea`] ID. Useful if you have more than one [`DockArea`].
+    ///
+    /// A [`DockArea`] shown inside a tab of another one without an ID of its own gets one derived from the
+    /// dock area and the [`Ui`](egui::Ui) it's shown in.
     #[inline(always)]
     pub fn id(mut self, id: Id) -> Self {
         self.id = id;
@@ -96,7 +155,8 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
-    /// Shows or hides the tab add buttons.
+    /// Shows or hides the tab add buttons, which call [`TabViewer::on_add`](crate::TabViewer::on_add) when pressed.
+    /// Use [`TabViewer::show_add_button`](crate::TabViewer::show_add_button) to hide them in specific leaves.
     /// By default it's `false`.
     pub fn show_add_buttons(mut self, show_add_buttons: bool) -> Self {
         self.show_add_buttons = show_add_buttons;
@@ -117,6 +177,22 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether the empty space of tab bars shows a context menu when right-clicked, with the content of
+    /// [`TabViewer::tab_bar_context_menu`](crate::TabViewer::tab_bar_context_menu).
+    /// By default it's `false`.
+    pub fn tab_bar_context_menus(mut self, tab_bar_context_menus: bool) -> Self {
+        self.tab_bar_context_menus = tab_bar_context_menus;
+        self
+    }
+
+    /// Whether the bodies of [central](crate::LeafNode::set_central) leaves without tabs show a context menu when
+    /// right-clicked, with the content of [`TabViewer::empty_context_menu`](crate::TabViewer::empty_context_menu).
+    /// By default it's `false`.
+    pub fn empty_leaf_context_menus(mut self, empty_leaf_context_menus: bool) -> Self {
+        self.empty_leaf_context_menus = empty_leaf_context_menus;
+        self
+    }
+
     /// Whether tabs can be dragged between nodes and reordered on the tab bar.
     /// By default it's `true`.
     pub fn draggable_tabs(mut self, draggable_tabs: bool) -> Self {
@@ -131,6 +207,39 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether tabs can be renamed by double-clicking their title,
+    /// which is committed using [`TabViewer::on_rename`](crate::TabViewer::on_rename).
+    /// By default it's `false`.
+    pub fn tab_renaming(mut self, tab_renaming: bool) -> Self {
+        self.tab_renaming = tab_renaming;
+        self
+    }
+
+    /// Whether double-clicking a tab floats it into a window the size of its body, see
+    /// [`DockState::float_tab`](crate::DockState::float_tab). Double-clicking the tab of such a window docks it back
+    /// where it came from, see [`DockState::redock_window`](crate::DockState::redock_window).
+    ///
+    /// Only tabs [allowed in windows](crate::TabViewer::allowed_in_windows) can be floated, and
+    /// [`DockArea::tab_renaming`] takes precedence.
+    /// By default it's `false`.
+    pub fn double_click_to_float(mut self, double_click_to_float: bool) -> Self {
+        self.double_click_to_float = double_click_to_float;
+        self
+    }
+
+    /// Whether the bodies of tabs are wrapped in an [`egui::ScrollArea`], with the scroll bars given by
+    /// [`TabViewer::scroll_bars`](crate::TabViewer::scroll_bars).
+    ///
+    /// Each tab keeps its own scroll offset, stored under its [`TabViewer::id`](crate::TabViewer::id), so it's
+    /// remembered while the tab is hidden or moved to another leaf or window, and saved along with the
+    /// [`egui::Memory`] when egui's `persistence` feature is enabled. Turn this off for tabs which all manage their
+    /// scrolling themselves.
+    /// By default it's `true`.
+    pub fn tab_scroll_areas(mut self, tab_scroll_areas: bool) -> Self {
+        self.tab_scroll_areas = tab_scroll_areas;
+        self
+    }
+
     /// What directions can a node be split in: left-right, top-bottom, all, or none.
     /// By default it's all.
     pub fn allowed_splits(mut self, allowed_splits: AllowedSplits) -> Self {
@@ -138,6 +247,15 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Whether a tab dropped onto a band along the outer edges of a surface splits the whole surface, putting the
+    /// tab next to all of its leaves, rather than only the leaf under it.
+    /// The width of the bands is [`OverlayFeel::edge_width`](crate::OverlayFeel::edge_width).
+    /// By default it's `false`.
+    pub fn surface_edge_splits(mut self, surface_edge_splits: bool) -> Self {
+        self.surface_edge_splits = surface_edge_splits;
+        self
+    }
+
     /// Whether tooltip hints are shown for secondary buttons on tab bars.
     /// By default it's `true`.
     pub fn show_secondary_button_hint(mut self, show_secondary_button_hint: bool) -> Self {
@@ -174,10 +292,97 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
-    /// Enables or disables the close button on windows.
+    /// Whether the [`DockArea`] keeps its windows and drag and drop overlays inside the [`Ui`](egui::Ui) it's shown in
+    /// with [`DockArea::show_inside`], e.g. when it's embedded in an [`egui::Window`] or a side panel.
+    ///
+    /// When set, the windows are bounded by the rect the [`DockArea`] is given (unless [`DockArea::window_bounds`] is
+    /// set), and the overlays are clipped to it. Otherwise they can cover the whole screen.
+    ///
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn clip_to_parent(mut self, clip_to_parent: bool) -> Self {
+        self.clip_to_parent = clip_to_parent;
+        self
+    }
+
+    /// Whether windows are shown as native windows of their own instead of inside the [`DockArea`],
+    /// which allows them to be moved onto other monitors.
+    ///
+    /// Native windows are shown using [immediate viewports](egui::Context::show_viewport_immediate), since
+    /// deferred viewports can't borrow the [`DockState`] and [`TabViewer`](crate::TabViewer). When the backend
+    /// doesn't support multiple viewports, windows are shown inside the [`DockArea`] as usual.
+    ///
+    /// Tabs released outside of the main viewport are moved into a new native window.
+    /// Tabs can't be dragged into or out of native windows (yet).
+    ///
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn native_windows(mut self, native_windows: bool) -> Self {
+        self.native_windows = native_windows;
+        self
+    }
+
+    /// Whether files dragged in from the operating system can be dropped into the [`DockArea`].
+    ///
+    /// Dropped files are passed to [`TabViewer::on_external_drop`](crate::TabViewer::on_external_drop)
+    /// as [`ExternalPayload::Files`]. Drop overlays are only shown while the files are dragged over the
+    /// [`DockArea`] if the backend reports the pointer position during the drag.
+    ///
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn accept_dropped_files(mut self, accept_dropped_files: bool) -> Self {
+        self.accept_dropped_files = accept_dropped_files;
+        self
+    }
+
+    /// Whether downscaled snapshots of the shown tabs are captured, so they can be previewed while hidden.
+    ///
+    /// Snapshots are cut out of screenshots of the viewport, taken about once a second while a shown tab has
+    /// no recent snapshot, so this requires a backend which supports [`ViewportCommand::Screenshot`].
+    /// Tabs in native windows are never captured.
+    ///
+    /// The snapshot of an inactive tab is shown in its tooltip if
+    /// [`DockArea::show_tab_name_on_hover`] is enabled, and is available through
+    /// [`DockResponse::tab_preview`].
+    ///
+    /// By default it's `false`.
+    ///
+    /// [`ViewportCommand::Screenshot`]: egui::ViewportCommand::Screenshot
+    #[inline(always)]
+    pub fn tab_previews(mut self, tab_previews: bool) -> Self {
+        self.tab_previews = tab_previews;
+        self
+    }
+
+    /// Sets the keyboard shortcuts of the [`DockArea`].
+    /// By default it's [`KeyBindings::default`].
+    #[inline(always)]
+    pub fn keybindings(mut self, keybindings: KeyBindings) -> Self {
+        self.keybindings = keybindings;
+        self
+    }
+
+    /// Sets when a dragged tab may be torn off into a window of its own.
+    /// By default it's [`TearOff::default`], which always allows it.
+    #[inline(always)]
+    pub fn tear_off(mut self, tear_off: TearOff) -> Self {
+        self.tear_off = tear_off;
+        self
+    }
+
+    /// Sets whether tabs can be dragged between this [`DockArea`] and the dock areas it's nested in or which are
+    /// nested in it. By default it's [`NestedDrags::Isolated`].
+    #[inline(always)]
+    pub fn nested_drags(mut self, nested_drags: NestedDrags) -> Self {
+        self.nested_drags = nested_drags;
+        self
+    }
+
+    /// Enables or disables the close button in the title bars of windows, see
+    /// [`DockArea::show_window_title_bars`]. Closing a window asks
+    /// [`TabViewer::on_window_close`](crate::TabViewer::on_window_close) first, and then each of its tabs.
     /// By default it's `true`.
     #[inline(always)]
-    #[deprecated = "consider using `show_leaf_close_buttons` instead."]
     pub fn show_window_close_buttons(mut self, show_window_close_buttons: bool) -> Self {
         self.show_window_close_buttons = show_window_close_buttons;
         self
@@ -192,6 +397,30 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self
     }
 
+    /// Shows a title bar at the top of windows, with their title and the widgets of
+    /// [`TabViewer::window_title_bar_ui`](crate::TabViewer::window_title_bar_ui). Native windows use the title bars
+    /// of the system instead.
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn show_window_title_bars(mut self, show_window_title_bars: bool) -> Self {
+        self.show_window_title_bars = show_window_title_bars;
+        self
+    }
+
+    /// Shows a strip at the bottom of the main surface listing the windows minimized into it by title, like a
+    /// taskbar. Clicking a title restores its window.
+    ///
+    /// Windows are minimized into the strip instead of into their title, and get a minimize button in their title
+    /// bars, see [`DockArea::show_window_title_bars`]. The strip is only shown while a window is minimized into it,
+    /// see [`WindowState::set_in_taskbar`](crate::WindowState::set_in_taskbar), and its look is set by
+    /// [`Style::window_taskbar`](crate::Style::window_taskbar).
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn window_taskbar(mut self, window_taskbar: bool) -> Self {
+        self.window_taskbar = window_taskbar;
+        self
+    }
+
     /// Enables or disables the close all tabs button on tab bars.
     /// By default it's `true`.
     #[inline(always)]
@@ -207,6 +436,26 @@ impl<'tree, Tab> DockArea<'tree, Tab> {
         self.show_leaf_collapse_buttons = show_leaf_collapse_buttons;
         self
     }
+
+    /// Hides the tab bars of leaves with a single tab, leaving a handle as tall as
+    /// [`TabBarStyle::drag_handle_height`](crate::TabBarStyle::drag_handle_height) in their place, by which the tab
+    /// can still be dragged. Leaves can override this with
+    /// [`LeafNode::set_single_tab_bar_hidden`](crate::LeafNode::set_single_tab_bar_hidden).
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn hide_single_tab_bars(mut self, hide_single_tab_bars: bool) -> Self {
+        self.hide_single_tab_bars = hide_single_tab_bars;
+        self
+    }
+
+    /// Enables or disables the buttons on tab bars which split a leaf to the right or downwards,
+    /// putting its active tab into the new leaf as decided by [`TabViewer::on_split`](crate::TabViewer::on_split).
+    /// By default it's `false`.
+    #[inline(always)]
+    pub fn show_leaf_split_buttons(mut self, show_leaf_split_buttons: bool) -> Self {
+        self.show_leaf_split_buttons = show_leaf_split_buttons;
+        self
+    }
 }
 
 impl<Tab> std::fmt::Debug for DockArea<'_, Tab> {
@@ -0,0 +1,139 @@
+use egui::{Context, Id, Pos2, Rect, Ui};
+
+use crate::TabId;
+
+/// How tab drags cross between a [`DockArea`](crate::DockArea) shown inside a tab of another one and the
+/// other dock areas, see [`DockArea::nested_drags`](crate::DockArea::nested_drags).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NestedDrags {
+    /// Tabs can only be dropped into the dock area they belong to.
+    #[default]
+    Isolated,
+
+    /// Tabs dragged out of the dock area are offered to the other dock areas as an
+    /// [`ExternalPayload::Custom`](crate::ExternalPayload::Custom) holding a [`BridgedTab`].
+    ///
+    /// If [`TabViewer::on_external_drop`](crate::TabViewer::on_external_drop) of the dock area the tab is dropped
+    /// into returns a tab for it, the dragged tab is removed from its own dock area the next time it's shown.
+    ///
+    /// While a tab is dragged over a bridged dock area, the dock areas it's nested in leave the drop to it.
+    Bridged,
+}
+
+/// A tab dragged out of a [`DockArea`](crate::DockArea) which bridges its drags with [`NestedDrags::Bridged`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{BridgedTab, ExternalPayload, TabDestination};
+/// # struct Document(String);
+/// # struct TabViewer { documents: Vec<Document> }
+/// # impl TabViewer {
+/// fn on_external_drop(
+///     &mut self,
+///     payload: &ExternalPayload,
+///     _destination: TabDestination,
+/// ) -> Option<Document> {
+///     let bridged = payload.downcast_ref::<BridgedTab>()?;
+///     // Look up the dragged tab in your application's data, e.g. by the title it was given.
+///     let index = self.documents.iter().position(|doc| doc.0 == bridged.title)?;
+///     Some(self.documents.remove(index))
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BridgedTab {
+    /// The ID of the [`DockArea`](crate::DockArea) the tab belongs to.
+    pub dock_area: Id,
+
+    /// The persistent identifier of the tab in its [`DockState`](crate::DockState).
+    pub tab: TabId,
+
+    /// The title of the tab.
+    pub title: String,
+}
+
+/// The dock areas which are currently being shown, from the outermost one to the innermost one.
+fn nesting_id() -> Id {
+    Id::new("egui_dock::nesting")
+}
+
+/// The bridged dock areas shown in the current and the previous frame, with their nesting depth.
+fn bridged_areas_id() -> Id {
+    Id::new("egui_dock::bridged_areas")
+}
+
+/// The [`BridgedTab`]s which were accepted by another dock area and still have to be removed from their own.
+fn accepted_id() -> Id {
+    Id::new("egui_dock::accepted_bridged_tabs")
+}
+
+/// Starts showing the dock area `id` in `ui`, returning the ID it's shown with and how deeply it's nested.
+///
+/// A dock area shown inside another one with the default ID, or with the ID of one it's nested in, gets an ID
+/// derived from the dock area it's shown in and from `ui`, so that nested dock areas don't share their state.
+pub(super) fn enter(ui: &Ui, id: Id) -> (Id, usize) {
+    ui.data_mut(|d| {
+        let stack = d.get_temp_mut_or_default::<Vec<Id>>(nesting_id());
+        let id = match stack.last() {
+            Some(&parent) if id == Id::new("egui_dock::DockArea") || stack.contains(&id) => {
+                parent.with(ui.id())
+            }
+            _ => id,
+        };
+        let depth = stack.len();
+        stack.push(id);
+        (id, depth)
+    })
+}
+
+/// Stops showing the innermost dock area.
+pub(super) fn exit(ctx: &Context) {
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<Vec<Id>>(nesting_id()).pop();
+    });
+}
+
+/// Remembers that the bridged dock area `id` was shown in `rect` this frame.
+pub(super) fn register_bridged(ctx: &Context, id: Id, depth: usize, rect: Rect) {
+    let frame = ctx.cumulative_frame_nr();
+    ctx.data_mut(|d| {
+        let areas = d.get_temp_mut_or_default::<Vec<(u64, Id, usize, Rect)>>(bridged_areas_id());
+        areas.retain(|&(shown, area, ..)| shown + 1 >= frame && area != id);
+        areas.push((frame, id, depth, rect));
+    });
+}
+
+/// Returns `true` if `pos` is over a bridged dock area nested deeper than `depth`, other than `id`.
+pub(super) fn is_over_nested_bridged(ctx: &Context, id: Id, depth: usize, pos: Pos2) -> bool {
+    let frame = ctx.cumulative_frame_nr();
+    ctx.data(|d| {
+        d.get_temp::<Vec<(u64, Id, usize, Rect)>>(bridged_areas_id())
+            .unwrap_or_default()
+            .iter()
+            .any(|&(shown, area, area_depth, rect)| {
+                shown + 1 >= frame && area != id && area_depth > depth && rect.contains(pos)
+            })
+    })
+}
+
+/// Remembers that another dock area accepted `tab`.
+pub(super) fn accept(ctx: &Context, tab: BridgedTab) {
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<Vec<BridgedTab>>(accepted_id())
+            .push(tab)
+    });
+}
+
+/// Takes the tabs of the dock area `id` which were accepted by other dock areas.
+pub(super) fn take_accepted(ctx: &Context, id: Id) -> Vec<TabId> {
+    ctx.data_mut(|d| {
+        let accepted = d.get_temp_mut_or_default::<Vec<BridgedTab>>(accepted_id());
+        let (taken, kept) = std::mem::take(accepted)
+            .into_iter()
+            .partition::<Vec<_>, _>(|tab| tab.dock_area == id);
+        *accepted = kept;
+        taken.into_iter().map(|tab| tab.tab).collect()
+    })
+}
@@ -0,0 +1,171 @@
+use egui::{ahash::HashMap, load::SizedTexture, Rect};
+
+use crate::{NodeIndex, SurfaceIndex, TabDestination, TabIndex};
+
+/// What happened inside a [`DockArea`](crate::DockArea) during the frame it was shown.
+///
+/// Returned by [`DockArea::show`](crate::DockArea::show) and
+/// [`DockArea::show_inside`](crate::DockArea::show_inside). It isn't `#[must_use]`, as most applications only
+/// react to the [`TabViewer`](crate::TabViewer) callbacks and can drop it.
+///
+/// All indices refer to the layout as it was drawn this frame, before any tabs were moved,
+/// closed or detached as a result of the user's actions.
+///
+/// # Examples
+///
+/// ```rust
+/// # use egui_dock::{DockArea, DockState};
+/// # struct TabViewer {}
+/// # impl egui_dock::TabViewer for TabViewer {
+/// #     type Tab = String;
+/// #     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText { (&*tab).into() }
+/// #     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {}
+/// # }
+/// # let mut dock_state = DockState::new(vec!["tab".to_string()]);
+/// # let mut tab_viewer = TabViewer {};
+/// # egui::__run_test_ctx(|ctx| {
+/// let response = DockArea::new(&mut dock_state).show(ctx, &mut tab_viewer);
+///
+/// // Dim the rest of the application while a tab is being dragged around.
+/// if response.dragged_tab().is_some() {
+///     // ...
+/// }
+/// # });
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DockResponse {
+    pub(super) clicked_tab: Option<(SurfaceIndex, NodeIndex, TabIndex)>,
+    pub(super) hovered_node: Option<(SurfaceIndex, NodeIndex)>,
+    pub(super) dragged_tab: Option<(SurfaceIndex, NodeIndex, TabIndex)>,
+    pub(super) drop_destination: Option<TabDestination>,
+    pub(super) events: Vec<DockEvent>,
+    pub(super) visible_tabs: Vec<(SurfaceIndex, NodeIndex, TabIndex)>,
+    pub(super) tab_previews: Vec<((SurfaceIndex, NodeIndex, TabIndex), SizedTexture)>,
+    tab_rects: Vec<((SurfaceIndex, NodeIndex, TabIndex), TabRects)>,
+
+    /// Where each tab is in `tab_rects`.
+    tab_rect_indices: HashMap<(SurfaceIndex, NodeIndex, TabIndex), usize>,
+}
+
+impl DockResponse {
+    /// The tab whose button was clicked this frame, if any.
+    #[inline]
+    pub fn clicked_tab(&self) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        self.clicked_tab
+    }
+
+    /// The leaf node under the pointer, if any.
+    #[inline]
+    pub fn hovered_node(&self) -> Option<(SurfaceIndex, NodeIndex)> {
+        self.hovered_node
+    }
+
+    /// The tab which is currently being dragged, if any.
+    #[inline]
+    pub fn dragged_tab(&self) -> Option<(SurfaceIndex, NodeIndex, TabIndex)> {
+        self.dragged_tab
+    }
+
+    /// Where the dragged tab would go if it was released this frame.
+    ///
+    /// Is `None` when no tab is being dragged, or when dropping it would not move it anywhere.
+    #[inline]
+    pub fn drop_destination(&self) -> Option<TabDestination> {
+        self.drop_destination
+    }
+
+    /// Returns `true` if a tab is being dragged.
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.dragged_tab.is_some()
+    }
+
+    /// The tabs whose [`TabViewer::ui`](crate::TabViewer::ui) was called this frame.
+    ///
+    /// All other tabs had [`TabViewer::on_hidden_update`](crate::TabViewer::on_hidden_update) called instead.
+    #[inline]
+    pub fn visible_tabs(&self) -> &[(SurfaceIndex, NodeIndex, TabIndex)] {
+        &self.visible_tabs
+    }
+
+    /// The last captured snapshot of the tab at `location`, if any.
+    ///
+    /// Always `None` unless [`DockArea::tab_previews`](crate::DockArea::tab_previews) is enabled.
+    #[inline]
+    pub fn tab_preview(
+        &self,
+        location: (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Option<SizedTexture> {
+        self.tab_previews
+            .iter()
+            .find_map(|&(tab, preview)| (tab == location).then_some(preview))
+    }
+
+    /// Where the tabs were drawn this frame, e.g. for pointing at them in a tutorial.
+    ///
+    /// Only holds the tabs whose button or body was shown.
+    #[inline]
+    pub fn tab_rects(&self) -> &[((SurfaceIndex, NodeIndex, TabIndex), TabRects)] {
+        &self.tab_rects
+    }
+
+    /// Where the tab at `location` was drawn this frame, if it was shown at all.
+    #[inline]
+    pub fn tab_rect(&self, location: (SurfaceIndex, NodeIndex, TabIndex)) -> Option<TabRects> {
+        let &index = self.tab_rect_indices.get(&location)?;
+        Some(self.tab_rects[index].1)
+    }
+
+    /// Records where a part of the tab at `location` was drawn.
+    pub(super) fn set_tab_rect(
+        &mut self,
+        location: (SurfaceIndex, NodeIndex, TabIndex),
+        set: impl FnOnce(&mut TabRects),
+    ) {
+        let index = *self.tab_rect_indices.entry(location).or_insert_with(|| {
+            self.tab_rects.push((location, TabRects::default()));
+            self.tab_rects.len() - 1
+        });
+        set(&mut self.tab_rects[index].1);
+    }
+
+    /// Changes made to the layout this frame, in the order they were made.
+    ///
+    /// Unlike the rest of the response, the indices in each event refer to the layout as it was
+    /// when the event happened.
+    #[inline]
+    pub fn events(&self) -> &[DockEvent] {
+        &self.events
+    }
+}
+
+/// The areas a tab was drawn in during a frame, see [`DockResponse::tab_rects`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TabRects {
+    /// The visible part of the button of the tab in its tab bar, if the button was shown.
+    pub button: Option<Rect>,
+
+    /// The body of the tab, if it was the active tab of a leaf which wasn't collapsed.
+    pub body: Option<Rect>,
+}
+
+/// A change made to the layout of a [`DockArea`](crate::DockArea) as a result of the user's actions.
+///
+/// See [`DockResponse::events`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DockEvent {
+    /// A different tab became the active tab of the focused leaf. Holds the location of the tab at the end of the frame.
+    TabFocused(SurfaceIndex, NodeIndex, TabIndex),
+
+    /// A tab was closed. Holds the location the tab had right before it was closed.
+    TabClosed(SurfaceIndex, NodeIndex, TabIndex),
+
+    /// A leaf was split. Holds the location of the new leaf right after the split.
+    NodeSplit(SurfaceIndex, NodeIndex),
+
+    /// A new window was created.
+    WindowCreated(SurfaceIndex),
+
+    /// A window was closed, or removed because it no longer contained any tabs.
+    WindowClosed(SurfaceIndex),
+}
@@ -0,0 +1,144 @@
+use egui::{Response, Ui, WidgetInfo, WidgetType};
+
+use crate::{AccessibilityTranslations, DropButton, TabViewer};
+
+/// The roles of the widgets of a [`DockArea`](crate::DockArea) that egui has no [`WidgetType`] for.
+#[derive(Clone, Copy)]
+pub(super) enum AccessRole {
+    Tab,
+    Splitter,
+}
+
+/// The name screen readers announce `tab` with. It's only asked for if the `accesskit` feature is enabled,
+/// as it isn't used otherwise.
+pub(super) fn tab_name<Tab>(tab_viewer: &mut impl TabViewer<Tab = Tab>, tab: &mut Tab) -> String {
+    if cfg!(feature = "accesskit") {
+        tab_viewer.accessible_name(tab)
+    } else {
+        String::new()
+    }
+}
+
+/// Describes a tab button to screen readers, e.g. as "Tab: Console, 2 of 5", selected if it's `active`.
+pub(super) fn describe_tab(
+    response: &Response,
+    translations: &AccessibilityTranslations,
+    name: &str,
+    index: usize,
+    count: usize,
+    active: bool,
+) {
+    describe(
+        response,
+        || {
+            WidgetInfo::selected(
+                WidgetType::SelectableLabel,
+                response.enabled(),
+                active,
+                translations
+                    .tab
+                    .replace("{name}", name)
+                    .replace("{position}", &(index + 1).to_string())
+                    .replace("{count}", &count.to_string()),
+            )
+        },
+        Some(AccessRole::Tab),
+    );
+}
+
+/// Describes the close button of the tab `name` to screen readers.
+pub(super) fn describe_close_button(
+    response: &Response,
+    translations: &AccessibilityTranslations,
+    name: &str,
+) {
+    describe(
+        response,
+        || {
+            WidgetInfo::labeled(
+                WidgetType::Button,
+                response.enabled(),
+                translations.close_button.replace("{name}", name),
+            )
+        },
+        None,
+    );
+}
+
+/// Describes a separator to screen readers, with the percentage of the split taken by its first child as its value.
+pub(super) fn describe_separator(response: &Response, label: &str, fraction: f32, locked: bool) {
+    describe(
+        response,
+        || WidgetInfo::slider(!locked, (fraction * 100.0).round() as f64, label),
+        Some(AccessRole::Splitter),
+    );
+}
+
+/// Adds the buttons of a drop overlay to the accessibility tree, so that screen readers can tell where the dragged
+/// tab would be docked. Does nothing unless the `accesskit` feature is enabled.
+pub(super) fn describe_drop_buttons(
+    ui: &Ui,
+    translations: &AccessibilityTranslations,
+    buttons: &[DropButton],
+) {
+    #[cfg(feature = "accesskit")]
+    for (index, button) in buttons.iter().enumerate() {
+        ui.ctx()
+            .accesskit_node_builder(ui.id().with(("drop_button", index)), |node| {
+                node.set_role(egui::accesskit::Role::Button);
+                node.set_label(drop_button_label(translations, button.split).as_str());
+                node.set_bounds(egui::accesskit::Rect {
+                    x0: button.rect.min.x.into(),
+                    y0: button.rect.min.y.into(),
+                    x1: button.rect.max.x.into(),
+                    y1: button.rect.max.y.into(),
+                });
+                if button.hovered {
+                    node.set_selected(true);
+                }
+            });
+    }
+    #[cfg(not(feature = "accesskit"))]
+    let _ = (ui, translations, buttons);
+}
+
+/// The label screen readers announce a drop button with.
+#[cfg(feature = "accesskit")]
+fn drop_button_label(
+    translations: &AccessibilityTranslations,
+    split: Option<crate::Split>,
+) -> &String {
+    use crate::Split;
+    match split {
+        None => &translations.dock_as_tab,
+        Some(Split::Left) => &translations.dock_left,
+        Some(Split::Right) => &translations.dock_right,
+        Some(Split::Above) => &translations.dock_above,
+        Some(Split::Below) => &translations.dock_below,
+    }
+}
+
+/// Gives `response` the [`WidgetInfo`] made by `make_info`, and overrides the role egui derives from its
+/// [`WidgetType`] with `role`. Does nothing unless the `accesskit` feature is enabled.
+fn describe(response: &Response, make_info: impl Fn() -> WidgetInfo, role: Option<AccessRole>) {
+    #[cfg(feature = "accesskit")]
+    {
+        response.widget_info(&make_info);
+        if let Some(role) = role {
+            let selected = make_info().selected;
+            response.ctx.accesskit_node_builder(response.id, |node| {
+                node.set_role(match role {
+                    AccessRole::Tab => egui::accesskit::Role::Tab,
+                    AccessRole::Splitter => egui::accesskit::Role::Splitter,
+                });
+                // Tabs are selected rather than toggled like the selectable labels egui describes them as.
+                if let Some(selected) = selected {
+                    node.clear_toggled();
+                    node.set_selected(selected);
+                }
+            });
+        }
+    }
+    #[cfg(not(feature = "accesskit"))]
+    let _ = (response, make_info, role);
+}
@@ -0,0 +1,252 @@
+use std::f32::consts::FRAC_PI_2;
+
+use egui::{
+    epaint::TextShape, pos2, vec2, CornerRadius, Key, Order, Popup, Rect, Sense, StrokeKind,
+    TextStyle, TextWrapMode, Ui,
+};
+
+use crate::{
+    dock_area::state::{AutoHideFlyout, State},
+    DockArea, NodeIndex, Split, Style, SurfaceIndex, TabIndex, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Takes the space of the strips listing the auto-hidden leaves of a surface from the edges of `rect`,
+    /// and gives what's left to the root node.
+    ///
+    /// Returns the edges which have strips and their rectangles.
+    pub(super) fn reserve_auto_hide_strips(
+        &mut self,
+        surface_index: SurfaceIndex,
+        rect: Rect,
+    ) -> Vec<(Split, Rect)> {
+        let width = self.style.as_ref().unwrap().auto_hide.strip_width;
+        let tree = &mut self.dock_state[surface_index];
+        let (inner, strips) = tree.reserve_auto_hide_strips(rect, width);
+        if !strips.is_empty() {
+            tree[NodeIndex::root()].set_rect(inner);
+        }
+        strips
+    }
+
+    /// Shows the titles of the tabs of the auto-hidden leaves in the strips at the edges of a surface.
+    ///
+    /// Hovering over or clicking a title slides its leaf out with the [`AutoHideFlyout`].
+    pub(super) fn show_auto_hide_strips(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        surface_index: SurfaceIndex,
+        strips: &[(Split, Rect)],
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+    ) {
+        let style = fade_style
+            .unwrap_or_else(|| self.style.as_ref().unwrap())
+            .auto_hide
+            .clone();
+        let flyout = state
+            .auto_hide_flyout
+            .filter(|flyout| flyout.surface == surface_index && flyout.open);
+
+        for &(edge, strip_rect) in strips {
+            ui.painter()
+                .rect_filled(strip_rect, CornerRadius::ZERO, style.strip_bg_fill);
+            let vertical = matches!(edge, Split::Left | Split::Right);
+            let mut cursor = if vertical {
+                strip_rect.min.y
+            } else {
+                strip_rect.min.x
+            } + style.label_spacing / 2.0;
+
+            for node_index in self.dock_state[surface_index].breadth_first_index_iter() {
+                if self.dock_state[surface_index].auto_hide_edge(node_index) != Some(edge)
+                    || !self.dock_state[surface_index][node_index].is_leaf()
+                {
+                    continue;
+                }
+                let leaf = self.dock_state[surface_index][node_index]
+                    .get_leaf_mut()
+                    .expect("This node must be a leaf");
+                let node_id = leaf.id;
+                let active = leaf.active;
+                for tab_index in 0..leaf.tabs.len() {
+                    let tab = &mut leaf.tabs[tab_index];
+                    let galley = tab_viewer.title(tab).into_galley(
+                        ui,
+                        Some(TextWrapMode::Extend),
+                        f32::INFINITY,
+                        TextStyle::Button,
+                    );
+                    let length = galley.size().x + style.label_spacing;
+                    let label_rect = if vertical {
+                        Rect::from_x_y_ranges(strip_rect.x_range(), cursor..=cursor + length)
+                    } else {
+                        Rect::from_x_y_ranges(cursor..=cursor + length, strip_rect.y_range())
+                    };
+                    cursor += length;
+
+                    let response = ui.interact(
+                        label_rect,
+                        self.id
+                            .with((surface_index, node_id, tab_index, "auto_hide")),
+                        Sense::click(),
+                    );
+                    let is_shown = flyout.is_some_and(|flyout| flyout.node == node_id)
+                        && active == TabIndex(tab_index);
+                    let color = if response.hovered() || is_shown {
+                        style.label_active_color
+                    } else {
+                        style.label_color
+                    };
+
+                    // Titles in the strips on the sides are read from top to bottom.
+                    let text_shape = if vertical {
+                        let pos = pos2(
+                            label_rect.center().x + galley.size().y / 2.0,
+                            label_rect.min.y + style.label_spacing / 2.0,
+                        );
+                        TextShape::new(pos, galley, color).with_angle(FRAC_PI_2)
+                    } else {
+                        let pos = pos2(
+                            label_rect.min.x + style.label_spacing / 2.0,
+                            label_rect.center().y - galley.size().y / 2.0,
+                        );
+                        TextShape::new(pos, galley, color)
+                    };
+                    ui.painter().add(text_shape);
+
+                    let clicked = response.clicked();
+                    if !(clicked || response.hovered() && state.dnd.is_none()) {
+                        continue;
+                    }
+                    let was_clicked = flyout
+                        .filter(|flyout| flyout.node == node_id && active == TabIndex(tab_index))
+                        .map(|flyout| flyout.clicked);
+                    if clicked && was_clicked == Some(true) {
+                        // Clicking the title of the shown tab again puts it back.
+                        state.auto_hide_flyout = flyout.map(|flyout| AutoHideFlyout {
+                            open: false,
+                            ..flyout
+                        });
+                        continue;
+                    }
+                    self.dock_state[surface_index][node_index]
+                        .get_leaf_mut()
+                        .expect("This node must be a leaf")
+                        .set_active_tab(tab_index);
+                    if clicked {
+                        self.new_focused = Some((surface_index, node_index));
+                    }
+                    state.auto_hide_flyout = Some(AutoHideFlyout {
+                        surface: surface_index,
+                        node: node_id,
+                        clicked: clicked || was_clicked == Some(true),
+                        open: true,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Shows the auto-hidden leaf of the [`AutoHideFlyout`] over the layout of a surface,
+    /// sliding it in and out of its strip.
+    pub(super) fn show_auto_hide_flyout(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        surface_index: SurfaceIndex,
+        strips: &[(Split, Rect)],
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<(&Style, f32)>,
+    ) {
+        let Some(flyout) = state
+            .auto_hide_flyout
+            .filter(|flyout| flyout.surface == surface_index)
+        else {
+            return;
+        };
+        let tree = &self.dock_state[surface_index];
+        let Some((node_index, edge)) = tree
+            .find_node_by_id(flyout.node)
+            .filter(|&node_index| tree[node_index].is_leaf())
+            .and_then(|node_index| Some((node_index, tree.auto_hide_edge(node_index)?)))
+        else {
+            // The leaf was removed or put back into the layout.
+            state.auto_hide_flyout = None;
+            return;
+        };
+
+        let style = fade_style.map_or_else(|| self.style.as_ref().unwrap(), |(style, _)| style);
+        let animation = style.auto_hide.flyout_animation;
+        let stroke = style.auto_hide.flyout_stroke;
+        let bg_fill = style.tab.tab_body.bg_fill;
+        let shown = ui.ctx().animate_bool_with_time_and_easing(
+            self.id.with((flyout.node, "auto_hide_flyout")),
+            flyout.open,
+            animation.duration,
+            animation.easing.function(),
+        );
+        if shown == 0.0 && !flyout.open {
+            state.auto_hide_flyout = None;
+            return;
+        }
+
+        // The leaf keeps the rectangle it would have in the layout, and slides out of the edge towards it.
+        let rect = self.dock_state[surface_index][node_index]
+            .rect()
+            .expect("This node must be a leaf");
+        let hidden_offset = match edge {
+            Split::Left => vec2(-rect.width(), 0.0),
+            Split::Right => vec2(rect.width(), 0.0),
+            Split::Above => vec2(0.0, -rect.height()),
+            Split::Below => vec2(0.0, rect.height()),
+        };
+        let rect = rect.translate(hidden_offset * (1.0 - shown));
+        self.dock_state[surface_index][node_index].set_rect(rect);
+
+        egui::Area::new(self.id.with((surface_index, "auto_hide_flyout")))
+            .order(Order::Foreground)
+            .fixed_pos(rect.min)
+            .constrain(false)
+            .show(ui.ctx(), |ui| {
+                ui.painter().rect_filled(rect, CornerRadius::ZERO, bg_fill);
+                self.show_leaf(
+                    ui,
+                    state,
+                    (surface_index, node_index),
+                    tab_viewer,
+                    fade_style,
+                );
+                ui.painter()
+                    .rect_stroke(rect, CornerRadius::ZERO, stroke, StrokeKind::Outside);
+            });
+
+        if !flyout.open {
+            return;
+        }
+        let strip_rect = strips
+            .iter()
+            .find(|(strip_edge, _)| *strip_edge == edge)
+            .map_or(Rect::NOTHING, |(_, strip_rect)| *strip_rect);
+        let pointer_inside = ui
+            .input(|i| i.pointer.hover_pos())
+            .is_some_and(|pos| rect.contains(pos) || strip_rect.contains(pos));
+        // Menus opened from the flyout, like the context menus of its tabs, keep it open.
+        let keep_open = pointer_inside || Popup::is_any_open(ui.ctx()) || state.dnd.is_some();
+        let close = ui.input(|i| i.key_pressed(Key::Escape))
+            || !keep_open
+                && if flyout.clicked {
+                    ui.input(|i| i.pointer.any_click())
+                } else {
+                    !ui.input(|i| i.pointer.any_down())
+                };
+        if close {
+            state.auto_hide_flyout = Some(AutoHideFlyout {
+                open: false,
+                ..flyout
+            });
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use egui::{KeyboardShortcut, Ui};
+
+use super::{clamp_to_min_sizes, fraction_limits};
+use crate::{DockArea, Node, NodeIndex, SeparatorSnap, Split, Tree};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Moves a separator next to the focused leaf when one of the resize shortcuts of the
+    /// [`KeyBindings`](crate::KeyBindings) is pressed.
+    pub(super) fn resize_focused_leaf(&mut self, ui: &Ui) {
+        let Some((surface_index, node_index)) = self.dock_state.focused_leaf() else {
+            return;
+        };
+        let pressed = |shortcut: Option<KeyboardShortcut>| {
+            shortcut.is_some_and(|shortcut| ui.input_mut(|i| i.consume_shortcut(&shortcut)))
+        };
+        let direction = if pressed(self.keybindings.resize_left) {
+            Split::Left
+        } else if pressed(self.keybindings.resize_right) {
+            Split::Right
+        } else if pressed(self.keybindings.resize_up) {
+            Split::Above
+        } else if pressed(self.keybindings.resize_down) {
+            Split::Below
+        } else {
+            return;
+        };
+
+        let separator = &self.style.as_ref().unwrap().separator;
+        let step = match separator.snap {
+            SeparatorSnap::Grid(grid) if grid > 0.0 => grid,
+            _ => self.keybindings.resize_step,
+        };
+        let delta = match direction {
+            Split::Left | Split::Above => -step,
+            Split::Right | Split::Below => step,
+        };
+
+        let tree = &mut self.dock_state[surface_index];
+        let Some(split_index) = separator_towards(tree, node_index, direction) else {
+            return;
+        };
+        let min_size_limits = tree.min_size_fraction_limits(split_index, separator.width);
+        if let Node::Horizontal(split) | Node::Vertical(split) = &mut tree[split_index] {
+            let range = if direction.is_left_right() {
+                split.rect.width()
+            } else {
+                split.rect.height()
+            };
+            if range <= 0.0 {
+                return;
+            }
+            let (min, max) = fraction_limits(separator.extra, range);
+            split.fraction = clamp_to_min_sizes(
+                separator
+                    .snap
+                    .snap(
+                        split.fraction + delta / range,
+                        range,
+                        separator.snap_distance,
+                    )
+                    .clamp(min, max),
+                min_size_limits,
+            );
+        }
+    }
+}
+
+/// Returns the split whose separator is the nearest one on the side of `node_index` which `direction`
+/// points to, or if there is none, the nearest one on the opposite side.
+fn separator_towards<Tab>(
+    tree: &Tree<Tab>,
+    node_index: NodeIndex,
+    direction: Split,
+) -> Option<NodeIndex> {
+    let mut opposite = None;
+    let mut child = node_index;
+    while let Some(parent) = child.parent() {
+        let along_axis = match &tree[parent] {
+            Node::Horizontal(split) => direction.is_left_right() && !split.is_locked(),
+            // Separators next to collapsed leaves can't be moved.
+            Node::Vertical(split) => {
+                !direction.is_left_right()
+                    && !split.is_locked()
+                    && !tree[parent.left()].is_collapsed()
+                    && !tree[parent.right()].is_collapsed()
+            }
+            _ => false,
+        };
+        if along_axis {
+            if child.is_left() == matches!(direction, Split::Right | Split::Below) {
+                return Some(parent);
+            }
+            opposite.get_or_insert(parent);
+        }
+        child = parent;
+    }
+    opposite
+}
@@ -0,0 +1,108 @@
+use egui::{Rect, Ui};
+
+use crate::{
+    dock_area::state::{LayoutTransition, State},
+    DockArea, Node, NodeIndex, SurfaceIndex, Tree,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Moves the leaves of a surface from where they were shown before its layout changed to the
+    /// rectangles computed for them, see [`Style::animation`](crate::Style::animation).
+    ///
+    /// Must be called after the rectangles of the nodes are computed, and before the leaves are shown,
+    /// so that the tabs get the rectangles they're actually shown in.
+    ///
+    /// Returns `true` while the leaves are moving.
+    pub(super) fn animate_layout(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        surface_index: SurfaceIndex,
+    ) -> bool {
+        let animation = self.style.as_ref().unwrap().animation;
+        let tree = &self.dock_state[surface_index];
+        let layout = || {
+            tree.breadth_first_index_iter().filter_map(|node_index| {
+                let node = &tree[node_index];
+                Some((node_index, node.id()?, node.is_collapsed()))
+            })
+        };
+        let is_shown_leaf = |tree: &Tree<Tab>, node_index| {
+            tree[node_index].is_leaf() && !tree.is_auto_hidden(node_index)
+        };
+
+        let now = ui.input(|i| i.time);
+        let transition = state.layout_transitions.entry(surface_index).or_default();
+        // Compared and copied in place, so that the buffers of the transition are reused every frame.
+        if !transition.layout.iter().copied().eq(layout()) {
+            if animation.duration > 0.0 && !transition.layout.is_empty() {
+                let LayoutTransition { shown, from, .. } = &mut *transition;
+                from.clear();
+                from.extend(
+                    tree.breadth_first_index_iter()
+                        .filter(|&node_index| is_shown_leaf(tree, node_index))
+                        .filter_map(|node_index| {
+                            let node = &tree[node_index];
+                            let from = match shown.get(&node.id()?) {
+                                Some(&shown) => shown,
+                                None => grown_from(tree, node_index, node.rect()?)?,
+                            };
+                            Some((node.id()?, from))
+                        }),
+                );
+                transition.start_time = now;
+            }
+            transition.layout.clear();
+            transition.layout.extend(layout());
+        }
+
+        let progress = if animation.duration > 0.0 {
+            ((now - transition.start_time) as f32 / animation.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        if progress >= 1.0 {
+            transition.from.clear();
+        }
+        let t = animation.easing.function()(progress);
+
+        transition.shown.clear();
+        for node_index in self.dock_state[surface_index].breadth_first_index_iter() {
+            if !is_shown_leaf(&self.dock_state[surface_index], node_index) {
+                continue;
+            }
+            let Node::Leaf(leaf) = &mut self.dock_state[surface_index][node_index] else {
+                continue;
+            };
+            if let Some(from) = transition.from.get(&leaf.id) {
+                let target = leaf.rect;
+                leaf.rect = Rect::from_min_max(
+                    from.min + (target.min - from.min) * t,
+                    from.max + (target.max - from.max) * t,
+                );
+            }
+            transition.shown.insert(leaf.id, leaf.rect);
+        }
+
+        let animating = !transition.from.is_empty();
+        if animating {
+            ui.ctx().request_repaint();
+        }
+        animating
+    }
+}
+
+/// Returns the rectangle which a new leaf grows out of: a line at the outer edge of `rect`, on the side
+/// of its split which it's on. Returns `None` for the root leaf, which appears without moving.
+fn grown_from<Tab>(tree: &Tree<Tab>, node_index: NodeIndex, rect: Rect) -> Option<Rect> {
+    let parent = &tree[node_index.parent()?];
+    let mut from = rect;
+    match (parent, node_index.is_left()) {
+        (Node::Horizontal(_), true) => from.max.x = from.min.x,
+        (Node::Horizontal(_), false) => from.min.x = from.max.x,
+        (Node::Vertical(_), true) => from.max.y = from.min.y,
+        (Node::Vertical(_), false) => from.min.y = from.max.y,
+        _ => return None,
+    }
+    Some(from)
+}
@@ -1,20 +1,34 @@
 use egui::{
-    emath::TSTransform, epaint::TextShape, lerp, pos2, vec2, Align, Align2, Button, Color32,
-    CornerRadius, CursorIcon, Frame, Id, Key, LayerId, Layout, NumExt, Order, Popup,
-    PopupCloseBehavior, Rect, Response, ScrollArea, Sense, Shape, Stroke, StrokeKind, TextStyle,
-    Ui, UiBuilder, Vec2, WidgetText,
+    ahash::HashMap,
+    emath::TSTransform,
+    epaint::TextShape,
+    layers::ShapeIdx,
+    lerp, pos2,
+    text::{CCursor, CCursorRange, LayoutJob, LayoutSection, TextWrapping},
+    text_edit::TextEditState,
+    vec2, Align, Align2, Area, Button, Color32, CornerRadius, CursorIcon, Frame, Galley, Id, Key,
+    LayerId, Layout, NumExt, Order, Popup, PopupCloseBehavior, Pos2, Rect, Response, ScrollArea,
+    Sense, Shape, Spinner, Stroke, StrokeKind, TextEdit, Ui, UiBuilder, Vec2, WidgetText,
+};
+use std::{
+    cmp::Ordering,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
 };
-use std::ops::RangeInclusive;
 
+use super::accessibility;
 use crate::dock_area::tab_removal::{ForcedRemoval, TabRemoval};
 use crate::node::LeafNode;
+use crate::utils::profile_scope;
 use crate::{
     dock_area::{
-        drag_and_drop::{DragData, DragDropState, HoverData, TreeComponent},
-        state::State,
+        drag_and_drop::{DragData, DragDropState, DragSource, HoverData, TreeComponent},
+        state::{State, TabRename},
     },
-    utils::{fade_visuals, rect_set_size_centered, rect_stroke_box},
-    DockArea, Node, NodeIndex, Style, SurfaceIndex, TabAddAlign, TabIndex, TabStyle, TabViewer,
+    utils::{fade_tab_body_style, fade_visuals, rect_set_size_centered, rect_stroke_box},
+    AllowedSplits, DockArea, DragPreviewStyle, Node, NodeIndex, Split, Style, SurfaceIndex,
+    TabAddAlign, TabBarPosition, TabBodyStyle, TabIndex, TabInteractionStyle, TabShape, TabStyle,
+    TabViewer, TitleTruncation,
 };
 
 use crate::tab_viewer::OnCloseResponse;
@@ -28,6 +42,7 @@ impl<Tab> DockArea<'_, Tab> {
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
         fade_style: Option<(&Style, f32)>,
     ) {
+        profile_scope!("DockArea::show_leaf");
         assert!(self.dock_state[surface_index][node_index].is_leaf());
         let collapsed = self.dock_state[surface_index][node_index].is_collapsed();
 
@@ -44,19 +59,121 @@ impl<Tab> DockArea<'_, Tab> {
         ui.spacing_mut().item_spacing = Vec2::ZERO;
         ui.set_clip_rect(rect);
 
-        if self.dock_state[surface_index][node_index].tabs_count() == 0 {
+        if ui.rect_contains_pointer(rect) {
+            self.response.hovered_node = Some((surface_index, node_index));
+        }
+
+        let style = fade_style.map_or_else(|| self.style.as_ref().unwrap(), |(style, _)| style);
+        let tab_bar_height = style.tab_bar.height;
+        let default_position = style.tab_bar.position;
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        if leaf.is_empty() && !leaf.is_central() {
             return;
         }
-        let tabbar_rect = self.tab_bar(
-            ui,
-            state,
-            (surface_index, node_index),
-            tab_viewer,
-            fade_style.map(|(style, _)| style),
-            collapsed,
+        let position = leaf.tab_bar_position.unwrap_or(default_position);
+        let single_tab_bar_hidden = leaf.tabs.len() == 1
+            && leaf
+                .single_tab_bar_hidden
+                .unwrap_or(self.hide_single_tab_bars);
+        // A tab bar hidden by the leaf or the viewer isn't replaced by a drag handle either.
+        let tab_bar_visible = !leaf.tab_bar_hidden
+            && tab_viewer.tab_bar_visibility(&leaf.tabs, surface_index, node_index);
+        let tab_bar_shown = tab_bar_visible && !single_tab_bar_hidden;
+
+        let available = ui.available_rect_before_wrap();
+        let (tabbar_rect, body_rect) = if tab_bar_visible && single_tab_bar_hidden {
+            leaf.tab_rects.clear();
+            let handle_height = style.tab_bar.drag_handle_height;
+            self.single_tab_handle(
+                ui,
+                state,
+                (surface_index, node_index),
+                available,
+                position,
+                handle_height,
+                tab_viewer,
+                fade_style.map(|(style, _)| style),
+            )
+        } else if !tab_bar_shown {
+            leaf.tab_rects.clear();
+
+            // A hidden tab bar takes no space, so the body covers the whole leaf.
+            let tabbar_rect = Rect::from_min_size(available.min, vec2(available.width(), 0.0));
+            (tabbar_rect, available)
+        } else if position.is_vertical() {
+            let tabbar_rect = match position {
+                TabBarPosition::Left => {
+                    Rect::from_min_size(available.min, vec2(tab_bar_height, available.height()))
+                }
+                _ => Rect::from_min_max(
+                    pos2(available.max.x - tab_bar_height, available.min.y),
+                    available.max,
+                ),
+            };
+            let tabbar_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(tabbar_rect)
+                    .id_salt((node_index, "side_tab_bar")),
+            );
+            self.side_tab_bar(
+                tabbar_ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                position,
+                fade_style.map(|(style, _)| style),
+            );
+            let mut body_rect = available;
+            match position {
+                TabBarPosition::Left => body_rect.min.x = tabbar_rect.max.x,
+                _ => body_rect.max.x = tabbar_rect.min.x,
+            }
+            (tabbar_rect, body_rect)
+        } else {
+            // Tab bars at the bottom are laid out upwards, so that their scroll bar ends up above them.
+            let layout = match position {
+                TabBarPosition::Bottom => Layout::bottom_up(Align::Min),
+                _ => Layout::top_down(Align::Min),
+            };
+            let tabbar_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(available)
+                    .layout(layout.with_cross_justify(true))
+                    .id_salt((node_index, "tab_bar")),
+            );
+            let tabbar_rect = self.tab_bar(
+                tabbar_ui,
+                state,
+                (surface_index, node_index),
+                tab_viewer,
+                position,
+                fade_style.map(|(style, _)| style),
+                collapsed,
+            );
+            let mut body_rect = available;
+            match position {
+                TabBarPosition::Bottom => body_rect.max.y = tabbar_ui.min_rect().min.y,
+                _ => body_rect.min.y = tabbar_ui.min_rect().max.y,
+            }
+            (tabbar_rect, body_rect)
+        };
+
+        // A tab activated in the tab bar is told so before its body is shown.
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        Self::leaf_lifecycle(&mut state.tab_lifecycle, leaf, tab_viewer);
+
+        let body_ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(body_rect)
+                .layout(Layout::top_down_justified(Align::Min))
+                .id_salt((node_index, "tab_body")),
         );
         self.tab_body(
-            ui,
+            body_ui,
             state,
             (surface_index, node_index),
             tab_viewer,
@@ -66,6 +183,20 @@ impl<Tab> DockArea<'_, Tab> {
             collapsed,
         );
 
+        let tab_bar_area = match position {
+            _ if !tab_bar_shown => None,
+            TabBarPosition::Top => Some(Rect::from_min_max(
+                available.min,
+                pos2(available.max.x, body_rect.min.y),
+            )),
+            TabBarPosition::Bottom => Some(Rect::from_min_max(
+                pos2(available.min.x, body_rect.max.y),
+                available.max,
+            )),
+            _ => Some(tabbar_rect),
+        };
+        self.highlight_focus(ui, (surface_index, node_index), tab_bar_area, fade_style);
+
         let tabs = self.dock_state[surface_index][node_index]
             .tabs_mut()
             .expect("This node must be a leaf here");
@@ -81,21 +212,141 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
+    /// Shows the handle taking the place of the hidden tab bar of a leaf with a single tab, by which the tab can be
+    /// dragged like its title. Returns the rects of the handle and of the body of the leaf.
+    #[allow(clippy::too_many_arguments)]
+    fn single_tab_handle(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        available: Rect,
+        position: TabBarPosition,
+        height: f32,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+    ) -> (Rect, Rect) {
+        let height = height.clamp(0.0, available.height().min(available.width()));
+        let (handle_rect, body_rect) = match position {
+            TabBarPosition::Top => available.split_top_bottom_at_y(available.top() + height),
+            TabBarPosition::Bottom => {
+                let (body, handle) = available.split_top_bottom_at_y(available.bottom() - height);
+                (handle, body)
+            }
+            TabBarPosition::Left => available.split_left_right_at_x(available.left() + height),
+            TabBarPosition::Right => {
+                let (body, handle) = available.split_left_right_at_x(available.right() - height);
+                (handle, body)
+            }
+        };
+
+        let draggable = self.draggable_tabs
+            && self.dock_state[surface_index][node_index]
+                .tabs()
+                .is_some_and(|tabs| tabs.iter().all(|tab| tab_viewer.is_draggable(tab)));
+        let sense = if draggable {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let id = self
+            .id
+            .with((surface_index, node_index, "single_tab_handle"));
+        let mut response = ui.interact(handle_rect, id, sense);
+        if draggable && ui.ctx().dragged_id().is_none() {
+            response = response.on_hover_cursor(CursorIcon::Grab);
+        }
+
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let fill = if response.hovered() || response.dragged() {
+            style.tab_bar.hline_color
+        } else {
+            style.tab_bar.bg_fill
+        };
+        ui.painter()
+            .rect_filled(handle_rect, style.tab_bar.corner_radius, fill);
+
+        if response.clicked() {
+            self.new_focused = Some((surface_index, node_index));
+        }
+        if draggable && response.dragged() && ui.input(|i| i.pointer.is_decidedly_dragging()) {
+            self.drag_leaf(
+                ui,
+                state,
+                (surface_index, node_index),
+                DragSource::Tab(surface_index, node_index, TabIndex(0)),
+                tab_viewer,
+            );
+        }
+        (handle_rect, body_rect)
+    }
+
+    /// Makes the focused leaf stand out from the others, see [`Style::focus_highlight`].
+    fn highlight_focus(
+        &self,
+        ui: &Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_bar_area: Option<Rect>,
+        fade_style: Option<(&Style, f32)>,
+    ) {
+        let style = fade_style.map_or_else(|| self.style.as_ref().unwrap(), |(style, _)| style);
+        let highlight = style.focus_highlight;
+        let Some(focused) = self.dock_state.focused_leaf() else {
+            return;
+        };
+
+        if focused == (surface_index, node_index) {
+            if let Some(tab_bar_area) = tab_bar_area {
+                ui.painter()
+                    .rect_filled(tab_bar_area, CornerRadius::ZERO, highlight.tab_bar_tint);
+            }
+            ui.painter().rect_stroke(
+                ui.max_rect(),
+                CornerRadius::ZERO,
+                highlight.outline,
+                StrokeKind::Inside,
+            );
+        } else if let Some(tab_bar_area) = tab_bar_area {
+            let dimming = highlight.unfocused_tab_bar_dimming.clamp(0.0, 1.0);
+            ui.painter().rect_filled(
+                tab_bar_area,
+                style.tab_bar.corner_radius,
+                style.tab_bar.bg_fill.gamma_multiply(dimming),
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn tab_bar(
         &mut self,
         ui: &mut Ui,
         state: &mut State,
         (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        position: TabBarPosition,
         fade_style: Option<&Style>,
         collapsed: bool,
     ) -> Rect {
+        profile_scope!("DockArea::tab_bar");
         assert!(self.dock_state[surface_index][node_index].is_leaf());
 
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+
+        // The empty space of the tab bar drags the whole leaf, except in windows where it moves the window.
+        let leaf_draggable = surface_index.is_main()
+            && self.is_leaf_draggable(surface_index, node_index, tab_viewer);
+        let sense = if leaf_draggable {
+            Sense::drag()
+        } else {
+            Sense::hover()
+        };
         let (tabbar_outer_rect, tabbar_response) = ui.allocate_exact_size(
             vec2(ui.available_width(), style.tab_bar.height),
-            Sense::hover(),
+            if self.tab_bar_context_menus {
+                sense.union(Sense::click())
+            } else {
+                sense
+            },
         );
         ui.painter().rect_filled(
             tabbar_outer_rect,
@@ -113,49 +364,117 @@ impl<Tab> DockArea<'_, Tab> {
 
         // Reserve space for the buttons at the ends of the tab bar.
 
-        if self.show_add_buttons {
-            available_width -= Style::TAB_ADD_BUTTON_SIZE;
+        let show_add_button =
+            self.show_add_buttons && tab_viewer.show_add_button(surface_index, node_index);
+        if show_add_button {
+            available_width -= style.tab_bar.add_button.width;
         }
 
         if self.show_leaf_close_all_buttons {
             available_width -= Style::TAB_CLOSE_ALL_BUTTON_SIZE;
         }
 
+        // Only the splits allowed by both the dock area and the leaf get a button.
+        let split_buttons: &[Split] = if self.show_leaf_split_buttons {
+            match self.allowed_splits & self.dock_state.allowed_splits(surface_index, node_index) {
+                AllowedSplits::All => &[Split::Right, Split::Below],
+                AllowedSplits::LeftRightOnly => &[Split::Right],
+                AllowedSplits::TopBottomOnly => &[Split::Below],
+                AllowedSplits::None => &[],
+            }
+        } else {
+            &[]
+        };
+        let split_buttons_width = split_buttons.len() as f32 * Style::TAB_SPLIT_BUTTON_SIZE;
+        available_width -= split_buttons_width;
+
         if self.show_leaf_collapse_buttons {
             available_width -= Style::TAB_COLLAPSE_BUTTON_SIZE;
         }
 
+        // Custom widgets placed between the tabs and the buttons at the ends of the tab bar.
+        let tabs_left = tabbar_outer_rect.left()
+            + if self.show_leaf_collapse_buttons {
+                Style::TAB_COLLAPSE_BUTTON_SIZE
+            } else {
+                0.0
+            };
+        let tabs_right = tabs_left + available_width.at_least(0.0);
+        let trailing_right = tabbar_outer_rect.right()
+            - if show_add_button && style.buttons.add_tab_align == TabAddAlign::Right {
+                style.tab_bar.add_button.width
+            } else {
+                0.0
+            }
+            - if self.show_leaf_close_all_buttons {
+                Style::TAB_CLOSE_ALL_BUTTON_SIZE
+            } else {
+                0.0
+            }
+            - split_buttons_width;
+        // Everything is laid out from left to right, and mirrored if the tab bar is laid out from right to left.
+        let right_to_left = style.tab_bar.right_to_left;
+        let mirror = |rect: Rect| mirror_rect(rect, tabbar_outer_rect, right_to_left);
+        let (forward, backward) = if right_to_left {
+            (
+                Layout::right_to_left(Align::Center),
+                Layout::left_to_right(Align::Center),
+            )
+        } else {
+            (
+                Layout::left_to_right(Align::Center),
+                Layout::right_to_left(Align::Center),
+            )
+        };
+        let leading_width = Self::tab_bar_side_ui(
+            ui,
+            mirror(Rect::from_x_y_ranges(
+                tabs_left..=tabs_right,
+                tabbar_outer_rect.y_range(),
+            )),
+            forward,
+            "leading",
+            |ui| tab_viewer.tab_bar_leading_ui(ui, surface_index, node_index),
+        );
+        available_width -= leading_width;
+        let trailing_width = Self::tab_bar_side_ui(
+            ui,
+            mirror(Rect::from_x_y_ranges(
+                (trailing_right - available_width.at_least(0.0))..=trailing_right,
+                tabbar_outer_rect.y_range(),
+            )),
+            backward,
+            "trailing",
+            |ui| tab_viewer.tab_bar_trailing_ui(ui, surface_index, node_index),
+        );
+        available_width -= trailing_width;
+
         let actual_width = {
             let leaf = self.dock_state[surface_index][node_index]
                 .get_leaf_mut()
                 .expect("This node must be a leaf");
 
-            let tabbar_inner_rect = Rect::from_min_size(
-                (tabbar_outer_rect.min - pos2(-leaf.scroll, 0.0)
-                    + vec2(
-                        if self.show_leaf_collapse_buttons {
-                            Style::TAB_COLLAPSE_BUTTON_SIZE
-                        } else {
-                            0.0
-                        },
-                        0.0,
-                    ))
-                .to_pos2(),
+            // Scrolling moves the tabs towards the start of the tab bar, which is mirrored as well.
+            let tabbar_inner_rect = mirror(Rect::from_min_size(
+                pos2(
+                    tabs_left + leading_width + leaf.scroll,
+                    tabbar_outer_rect.top(),
+                ),
                 vec2(tabbar_outer_rect.width(), tabbar_outer_rect.height()),
-            );
+            ));
 
             let tabs_ui = &mut ui.new_child(
                 UiBuilder::new()
                     .max_rect(tabbar_inner_rect)
-                    .layout(Layout::left_to_right(Align::Center))
+                    .layout(forward)
                     .id_salt("tabs"),
             );
 
             let mut clip_rect = tabbar_outer_rect;
             clip_rect.set_width(available_width);
-            if self.show_leaf_collapse_buttons {
-                clip_rect = clip_rect.translate(vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0));
-            }
+            clip_rect = mirror(
+                clip_rect.translate(vec2(tabs_left + leading_width - clip_rect.left(), 0.0)),
+            );
             tabs_ui.set_clip_rect(clip_rect);
 
             // Desired size for tabs in "expanded" mode.
@@ -170,6 +489,7 @@ impl<Tab> DockArea<'_, Tab> {
                 (surface_index, node_index),
                 tab_viewer,
                 tabbar_outer_rect,
+                position,
                 prefered_width,
                 fade_style,
             );
@@ -178,24 +498,32 @@ impl<Tab> DockArea<'_, Tab> {
             let px = ui.ctx().pixels_per_point().recip();
             let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
 
+            let hline = if right_to_left {
+                tabbar_outer_rect.left()..=tabs_ui.min_rect().left().max(clip_rect.left())
+            } else {
+                tabs_ui.min_rect().right().min(clip_rect.right())..=tabbar_outer_rect.right()
+            };
             ui.painter().hline(
-                tabs_ui.min_rect().right().min(clip_rect.right())..=tabbar_outer_rect.right(),
-                tabbar_outer_rect.bottom() - px,
+                hline,
+                body_edge(tabbar_outer_rect, position, px),
                 (px, style.tab_bar.hline_color),
             );
 
             // Add button at the ends of the tab bar.
-            if self.show_add_buttons {
+            let close_all_width = if self.show_leaf_close_all_buttons {
+                Style::TAB_CLOSE_ALL_BUTTON_SIZE
+            } else {
+                0.0
+            };
+            if show_add_button {
                 let offset = match style.buttons.add_tab_align {
                     TabAddAlign::Left => {
                         (clip_rect.width() - tabs_ui.min_rect().width()).at_least(0.0)
+                            + trailing_width
                     }
                     TabAddAlign::Right => 0.0,
-                } + if self.show_leaf_close_all_buttons {
-                    Style::TAB_CLOSE_ALL_BUTTON_SIZE
-                } else {
-                    0.0
-                };
+                } + close_all_width
+                    + split_buttons_width;
                 self.tab_plus(
                     ui,
                     surface_index,
@@ -233,6 +561,19 @@ impl<Tab> DockArea<'_, Tab> {
                 )
             }
 
+            for (i, &split) in split_buttons.iter().enumerate() {
+                let offset = close_all_width
+                    + (split_buttons.len() - 1 - i) as f32 * Style::TAB_SPLIT_BUTTON_SIZE;
+                self.tab_split(
+                    ui,
+                    (surface_index, node_index),
+                    split,
+                    tabbar_outer_rect,
+                    offset,
+                    fade_style,
+                );
+            }
+
             if self.show_leaf_collapse_buttons {
                 self.tab_collapse(
                     ui,
@@ -247,6 +588,12 @@ impl<Tab> DockArea<'_, Tab> {
             tabs_ui.min_rect().width()
         };
 
+        self.scroll_to_switch(
+            ui,
+            (surface_index, node_index),
+            tabbar_response.contains_pointer(),
+            fade_style,
+        );
         self.tab_bar_scroll(
             ui,
             state,
@@ -258,9 +605,209 @@ impl<Tab> DockArea<'_, Tab> {
             fade_style,
         );
 
+        if leaf_draggable
+            && tabbar_response.dragged()
+            && ui.input(|i| i.pointer.is_decidedly_dragging())
+        {
+            self.drag_leaf(
+                ui,
+                state,
+                (surface_index, node_index),
+                DragSource::Node(surface_index, node_index),
+                tab_viewer,
+            );
+        }
+
+        if self.tab_bar_context_menus {
+            tabbar_response.context_menu(|ui| {
+                tab_viewer.tab_bar_context_menu(ui, surface_index, node_index);
+            });
+        }
+
         tabbar_outer_rect
     }
 
+    /// Whether a leaf can be dragged by the empty space of its tab bar, which needs all of its tabs to be draggable.
+    fn is_leaf_draggable(
+        &self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        self.draggable_tabs
+            && self.dock_state[surface_index][node_index]
+                .tabs()
+                .is_some_and(|tabs| {
+                    !tabs.is_empty() && tabs.iter().all(|tab| tab_viewer.is_draggable(tab))
+                })
+    }
+
+    /// Starts dragging a whole leaf, or `src` within it, once it's dragged far enough, and shows the titles of all
+    /// its tabs next to the pointer.
+    fn drag_leaf(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        src: DragSource,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
+        let Some(pointer_pos) = ui.ctx().pointer_interact_pos() else {
+            return;
+        };
+        let start = *state.drag_start.get_or_insert(pointer_pos);
+        let delta = pointer_pos - start;
+        if delta.x.abs() <= 30.0 && delta.y.abs() <= 6.0 {
+            return;
+        }
+
+        let preview_style = self.style.as_ref().unwrap().drag_preview;
+        let bounds = self.drag_preview_bounds(ui.ctx());
+        let Node::Leaf(leaf) = &mut self.dock_state[surface_index][node_index] else {
+            unreachable!("This node must be a leaf")
+        };
+        let titles: Vec<WidgetText> = leaf
+            .tabs
+            .iter_mut()
+            .map(|tab| tab_viewer.title(tab))
+            .collect();
+        let position = pointer_pos + preview_style.cursor_offset.unwrap_or_default();
+        Area::new(
+            self.id
+                .with((surface_index, node_index, "leaf_drag_preview")),
+        )
+        .order(Order::Tooltip)
+        .fixed_pos(position)
+        .constrain_to(bounds)
+        .interactable(false)
+        .show(ui.ctx(), |ui| {
+            ui.multiply_opacity(preview_style.opacity);
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (index, title) in titles.into_iter().enumerate() {
+                        if index != 0 {
+                            ui.separator();
+                        }
+                        ui.label(title);
+                    }
+                });
+            });
+        });
+
+        let rect = leaf.rect;
+        ui.memory_mut(|mem| {
+            mem.data.insert_temp(
+                self.id.with("drag_data"),
+                Some(DragData {
+                    src: Some(src),
+                    rect,
+                }),
+            );
+        });
+    }
+
+    /// Shows custom widgets in the tab bar within `rect`, returning the width they take up.
+    pub(super) fn tab_bar_side_ui(
+        ui: &mut Ui,
+        rect: Rect,
+        layout: Layout,
+        id_salt: &str,
+        add_contents: impl FnOnce(&mut Ui),
+    ) -> f32 {
+        if rect.width() <= 0.0 {
+            return 0.0;
+        }
+        let side_ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect)
+                .layout(layout)
+                .id_salt(id_salt),
+        );
+        side_ui.set_clip_rect(rect.intersect(ui.clip_rect()));
+        side_ui.spacing_mut().item_spacing = ui.ctx().style().spacing.item_spacing;
+        add_contents(side_ui);
+        side_ui.min_rect().width().at_most(rect.width())
+    }
+
+    /// Shows a tooltip for a tab button, after `delay` seconds if it's set.
+    pub(super) fn tab_tooltip(
+        response: Response,
+        delay: Option<f32>,
+        add_contents: impl FnOnce(&mut Ui),
+    ) -> Response {
+        let Some(delay) = delay else {
+            return response.on_hover_ui(add_contents);
+        };
+
+        // Hold the tooltip back until the pointer has rested on the tab for `delay` seconds, without
+        // touching the context's style, whose delay still applies on top of this one.
+        let resting_for = response.ctx.input(|i| {
+            i.time_since_last_scroll()
+                .min(i.pointer.time_since_last_movement())
+                .min(i.pointer.time_since_last_click())
+        });
+        if response.hovered() && !response.is_tooltip_open() && resting_for < delay {
+            response.ctx.request_repaint_after_secs(delay - resting_for);
+            return response;
+        }
+        response.on_hover_ui(add_contents)
+    }
+
+    /// Widths of the gaps opened before and after a tab, showing where a tab dragged within the same
+    /// tab bar would be inserted.
+    ///
+    /// A dragged tab takes the place of the hovered one, so the gap opens after the hovered tab when
+    /// dragging to the right and before it when dragging to the left.
+    fn reorder_gaps(
+        &self,
+        ui: &Ui,
+        state: &State,
+        id: Id,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        spacing: f32,
+        fade: Option<&Style>,
+    ) -> (f32, f32) {
+        let dragged_from = match &state.dnd {
+            Some(DragDropState {
+                drag:
+                    DragData {
+                        src: Some(DragSource::Tab(src_surface, src_node, src_tab)),
+                        ..
+                    },
+                hover:
+                    HoverData {
+                        dst: TreeComponent::Tab(dst_surface, dst_node, dst_tab),
+                        ..
+                    },
+                ..
+            }) if (*src_surface, *src_node) == (surface_index, node_index)
+                && (*dst_surface, *dst_node) == (surface_index, node_index)
+                && *dst_tab == tab_index =>
+            {
+                Some(*src_tab)
+            }
+            _ => None,
+        };
+
+        let animation = fade
+            .unwrap_or_else(|| self.style.as_ref().unwrap())
+            .tab_bar
+            .reorder_animation;
+        let gap = |side: &str, open: bool| {
+            ui.ctx().animate_bool_with_time_and_easing(
+                id.with(("reorder_gap", side)),
+                open,
+                animation.duration,
+                animation.easing.function(),
+            ) * (state.dragged_tab_width + spacing)
+        };
+        (
+            gap("before", dragged_from.is_some_and(|src| src > tab_index)),
+            gap("after", dragged_from.is_some_and(|src| src < tab_index)),
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn tabs(
         &mut self,
@@ -269,54 +816,123 @@ impl<Tab> DockArea<'_, Tab> {
         (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &mut impl TabViewer<Tab = Tab>,
         tabbar_outer_rect: Rect,
+        position: TabBarPosition,
         preferred_width: Option<f32>,
         fade: Option<&Style>,
     ) {
         assert!(self.dock_state[surface_index][node_index].is_leaf());
 
         let focused = self.dock_state.focused_leaf();
-        let tabs_len = {
-            let tabs = self.dock_state[surface_index][node_index]
-                .tabs()
+        let (tabs_len, active_tab) = {
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf_mut()
                 .expect("This node must be a leaf here");
-            tabs.len()
+            // The rects of the previous frame are swapped into the scratch buffer, whose allocation is reused.
+            std::mem::swap(&mut leaf.tab_rects, &mut state.scratch.tab_rects);
+            leaf.tab_rects.clear();
+            leaf.tab_rects.resize(leaf.tabs.len(), Rect::NOTHING);
+            (leaf.tabs.len(), leaf.active)
         };
 
-        for tab_index in 0..tabs_len {
-            let id = self
-                .id
-                .with((surface_index, "surface"))
-                .with((node_index, "node"))
-                .with((tab_index, "tab"));
-            let tab_index = TabIndex(tab_index);
+        // Overlapping tabs have their backgrounds painted before any of the titles, the line below the tabs and
+        // the active tab coming last, so that the active tab is drawn on top of its neighbours.
+        let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let overlap = browser_tab_slant(style, position).unwrap_or(0.0);
+        let mut background_slots = std::mem::take(&mut state.scratch.background_slots);
+        background_slots.clear();
+        if overlap > 0.0 {
+            background_slots.extend((0..=tabs_len).map(|_| tabs_ui.painter().add(Shape::Noop)));
+        }
+        // Each tab is painted over the one before it, except for the active tab which is painted over both of
+        // its neighbours.
+        let browser_layer = |tab_index: TabIndex| {
+            let slot = match tab_index.0.cmp(&active_tab.0) {
+                Ordering::Less => tab_index.0,
+                Ordering::Equal => tabs_len,
+                Ordering::Greater => tab_index.0 - 1,
+            };
+            BrowserTabLayer {
+                background_slot: background_slots.get(slot).copied(),
+                covered: [
+                    tab_index.0 > 0 && active_tab.0 == tab_index.0 - 1,
+                    tab_index != active_tab && tab_index.0 + 1 < tabs_len,
+                ],
+            }
+        };
+
+        if self.refocus_leaf == Some((surface_index, node_index)) {
+            let id = self.tab_title_id((surface_index, node_index, active_tab));
+            tabs_ui.memory_mut(|mem| mem.request_focus(id));
+        }
+
+        for tab_index in (0..tabs_len).map(TabIndex) {
+            let id = self.tab_title_id((surface_index, node_index, tab_index));
+            let draggable = self.draggable_tabs
+                && self.dock_state[surface_index][node_index]
+                    .tabs()
+                    .is_some_and(|tabs| tab_viewer.is_draggable(&tabs[tab_index.0]));
             let is_being_dragged = tabs_ui.ctx().is_being_dragged(id)
                 && tabs_ui.input(|i| i.pointer.is_decidedly_dragging())
-                && self.draggable_tabs;
+                && draggable;
 
             if is_being_dragged {
                 tabs_ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
             }
 
-            let (is_active, label, tab_style, closeable) = {
+            // A tab whose drag was cancelled slides back to its place.
+            let revert_offset = state
+                .drag_revert
+                .filter(|revert| revert.id == id && !is_being_dragged)
+                .and_then(|revert| {
+                    let animation = self.style.as_ref().unwrap().animation;
+                    let elapsed = (tabs_ui.input(|i| i.time) - revert.start_time) as f32;
+                    (elapsed < animation.duration).then(|| {
+                        revert.offset
+                            * (1.0 - animation.easing.function()(elapsed / animation.duration))
+                    })
+                });
+
+            let (is_active, label, tab_style, closeable, loading, accessible_name, tabs_count) = {
                 let leaf = self.dock_state[surface_index][node_index]
                     .get_leaf_mut()
                     .expect("This node must be a leaf");
                 let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
                 let tab_style = tab_viewer.tab_style_override(&leaf.tabs[tab_index.0], &style.tab);
                 (
-                    leaf.active == tab_index || is_being_dragged,
+                    leaf.active == tab_index || is_being_dragged || leaf.is_tab_selected(tab_index),
                     tab_viewer.title(&mut leaf.tabs[tab_index.0]),
-                    tab_style.unwrap_or(style.tab.clone()),
+                    tab_style.unwrap_or_else(|| style.tab.clone()),
                     tab_viewer.is_closeable(&leaf.tabs[tab_index.0]),
+                    tab_viewer.is_loading(&leaf.tabs[tab_index.0]),
+                    accessibility::tab_name(tab_viewer, &mut leaf.tabs[tab_index.0]),
+                    leaf.tabs.len(),
                 )
             };
 
             let show_close_button = self.show_close_buttons && closeable;
 
             let (response, title_id) = if is_being_dragged {
+                let previous_rect = state.scratch.tab_rects.get(tab_index.0).copied();
+                let preview_style = fade
+                    .unwrap_or_else(|| self.style.as_ref().unwrap())
+                    .drag_preview;
+                let custom_preview = self.custom_drag_preview(
+                    tabs_ui,
+                    state,
+                    id,
+                    (surface_index, node_index, tab_index),
+                    previous_rect,
+                    preview_style,
+                    tab_viewer,
+                );
+
                 let layer_id = LayerId::new(Order::Tooltip, id);
                 let response = tabs_ui
                     .scope_builder(UiBuilder::new().layer_id(layer_id), |ui| {
+                        ui.multiply_opacity(preview_style.opacity);
+                        if custom_preview.is_some() {
+                            ui.set_invisible();
+                        }
                         self.tab_title(
                             ui,
                             &tab_style,
@@ -325,9 +941,13 @@ impl<Tab> DockArea<'_, Tab> {
                             is_active && Some((surface_index, node_index)) == focused,
                             is_active,
                             is_being_dragged,
+                            draggable,
+                            position,
                             preferred_width,
                             show_close_button,
+                            loading,
                             fade,
+                            BrowserTabLayer::default(),
                         )
                     })
                     .response;
@@ -335,26 +955,29 @@ impl<Tab> DockArea<'_, Tab> {
 
                 let response =
                     tabs_ui.interact(response.rect, id.with("dragged"), Sense::click_and_drag());
+                state.dragged_tab_width = response.rect.width();
+                // Kept for the next frame, where a custom preview is placed relative to where the tab was grabbed.
+                if let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut() {
+                    if let Some(rect) = leaf.tab_rects.get_mut(tab_index.0) {
+                        *rect = response.rect.intersect(tabs_ui.clip_rect());
+                    }
+                }
 
                 if let Some(pointer_pos) = tabs_ui.ctx().pointer_interact_pos() {
                     let start = *state.drag_start.get_or_insert(pointer_pos);
                     let delta = pointer_pos - start;
                     if delta.x.abs() > 30.0 || delta.y.abs() > 6.0 {
+                        let offset = match preview_style.cursor_offset {
+                            Some(offset) => pointer_pos + offset - response.rect.min,
+                            None => delta,
+                        };
                         tabs_ui
                             .ctx()
-                            .transform_layer_shapes(layer_id, TSTransform::new(delta, 1.0));
-
-                        tabs_ui.memory_mut(|mem| {
-                            mem.data.insert_temp(
-                                self.id.with("drag_data"),
-                                Some(DragData {
-                                    src: TreeComponent::Tab(surface_index, node_index, tab_index),
-                                    rect: self.dock_state[surface_index][node_index]
-                                        .rect()
-                                        .unwrap(),
-                                }),
-                            );
-                        });
+                            .transform_layer_shapes(layer_id, TSTransform::new(offset, 1.0));
+                        // A custom preview is kept inside of the screen, so it may not be where the title would be.
+                        state.dragged_tab_offset = custom_preview
+                            .map_or(offset, |preview| preview.min - response.rect.min);
+                        self.start_tab_drag(tabs_ui, (surface_index, node_index, tab_index));
                     }
                 }
 
@@ -362,30 +985,166 @@ impl<Tab> DockArea<'_, Tab> {
             } else {
                 if tab_index.0 != 0 {
                     tabs_ui.allocate_space(vec2(tab_style.spacing, 0.0));
+                    if overlap > 0.0 {
+                        tabs_ui.add_space(-overlap);
+                    }
                 }
-                let (mut response, close_response) = self.tab_title(
+                let (gap_before, gap_after) = self.reorder_gaps(
                     tabs_ui,
-                    &tab_style,
+                    state,
                     id,
-                    label,
-                    is_active && Some((surface_index, node_index)) == focused,
-                    is_active,
-                    is_being_dragged,
-                    preferred_width,
-                    show_close_button,
+                    (surface_index, node_index, tab_index),
+                    tab_style.spacing,
                     fade,
                 );
+                if gap_before > 0.0 {
+                    tabs_ui.allocate_space(vec2(gap_before, 0.0));
+                }
+                let is_renaming =
+                    self.is_renaming(state, (surface_index, node_index, tab_index), tab_viewer);
+                let (mut response, close_response) = if is_renaming {
+                    let response = self.tab_rename(
+                        tabs_ui,
+                        state,
+                        id,
+                        (surface_index, node_index, tab_index),
+                        tab_viewer,
+                    );
+                    (response, None)
+                } else {
+                    let show_title = |ui: &mut Ui| {
+                        self.tab_title(
+                            ui,
+                            &tab_style,
+                            id,
+                            label,
+                            is_active && Some((surface_index, node_index)) == focused,
+                            is_active,
+                            is_being_dragged,
+                            draggable,
+                            position,
+                            preferred_width,
+                            show_close_button,
+                            loading,
+                            fade,
+                            browser_layer(tab_index),
+                        )
+                    };
+                    let (response, close_response) = match revert_offset {
+                        Some(offset) => {
+                            // Shown above the other tabs until it's back in its place.
+                            let layer_id = LayerId::new(Order::Tooltip, id);
+                            let title = tabs_ui
+                                .scope_builder(UiBuilder::new().layer_id(layer_id), show_title)
+                                .inner;
+                            tabs_ui
+                                .ctx()
+                                .transform_layer_shapes(layer_id, TSTransform::new(offset, 1.0));
+                            tabs_ui.ctx().request_repaint();
+                            title
+                        }
+                        None => show_title(tabs_ui),
+                    };
+                    let translations = &self.dock_state.translations.accessibility;
+                    accessibility::describe_tab(
+                        &response,
+                        translations,
+                        &accessible_name,
+                        tab_index.0,
+                        tabs_count,
+                        is_active,
+                    );
+                    if let Some(close_response) = &close_response {
+                        accessibility::describe_close_button(
+                            close_response,
+                            translations,
+                            &accessible_name,
+                        );
+                    }
+                    (response, close_response)
+                };
                 let title_id = response.id;
-                let close_clicked = close_response.is_some_and(|res| res.clicked());
-                let is_lonely_tab = self.dock_state[surface_index].num_tabs() == 1;
+                self.scroll_into_view(
+                    tabs_ui,
+                    (surface_index, node_index, tab_index),
+                    response.rect,
+                    position,
+                );
+                let visible_rect = response.rect.intersect(tabs_ui.clip_rect());
+                if let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut() {
+                    if let Some(rect) = leaf.tab_rects.get_mut(tab_index.0) {
+                        *rect = visible_rect;
+                    }
+                }
+                if visible_rect.is_positive() {
+                    self.response
+                        .set_tab_rect((surface_index, node_index, tab_index), |rects| {
+                            rects.button = Some(visible_rect);
+                        });
+                }
+                if gap_after > 0.0 {
+                    tabs_ui.allocate_space(vec2(gap_after, 0.0));
+                }
+
+                if self.tab_renaming && !is_renaming && response.double_clicked() {
+                    let tab = &mut self.dock_state[surface_index][node_index]
+                        .tabs_mut()
+                        .expect("This node must be a leaf")[tab_index.0];
+                    let text = tab_viewer.title(tab).text().to_owned();
+
+                    // Select the whole title, so that typing replaces it.
+                    let edit_id = id.with("rename");
+                    let mut edit_state =
+                        TextEditState::load(tabs_ui.ctx(), edit_id).unwrap_or_default();
+                    edit_state.cursor.set_char_range(Some(CCursorRange::two(
+                        CCursor::new(0),
+                        CCursor::new(text.chars().count()),
+                    )));
+                    edit_state.store(tabs_ui.ctx(), edit_id);
+                    tabs_ui.memory_mut(|mem| mem.request_focus(edit_id));
+
+                    state.renaming = Some(TabRename {
+                        location: (surface_index, node_index, tab_index),
+                        tab_id: tab_viewer.id(tab),
+                        text,
+                    });
+                } else if self.double_click_to_float
+                    && !self.tab_renaming
+                    && response.double_clicked()
+                    && tab_viewer.allowed_in_windows(
+                        &mut self.dock_state[surface_index][node_index]
+                            .tabs_mut()
+                            .expect("This node must be a leaf")[tab_index.0],
+                    )
+                {
+                    self.to_float.extend(self.dock_state.tab_id((
+                        surface_index,
+                        node_index,
+                        tab_index,
+                    )));
+                }
+                let close_clicked = close_response.as_ref().is_some_and(|res| res.clicked());
+                // Keyboard users keep their place: the tab taking the place of a closed one gets the focus.
+                if close_clicked
+                    && (response.has_focus() || close_response.is_some_and(|res| res.has_focus()))
+                {
+                    state.refocus_leaf = Some((surface_index, node_index));
+                }
+                let is_lonely_tab = self.dock_state[surface_index].num_tabs() == 1;
 
                 if self.show_tab_name_on_hover {
                     let tabs = self.dock_state[surface_index][node_index]
                         .tabs_mut()
                         .expect("This node must be a leaf");
                     let tab = &mut tabs[tab_index.0];
-                    response = response.on_hover_ui(|ui| {
-                        ui.label(tab_viewer.title(tab));
+                    let preview = (self.tab_previews && !is_active)
+                        .then(|| state.previews.get(tab_viewer.id(tab)))
+                        .flatten();
+                    response = Self::tab_tooltip(response, tab_style.tooltip_delay, |ui| {
+                        tab_viewer.tab_tooltip(ui, tab);
+                        if let Some(preview) = preview {
+                            ui.image(preview);
+                        }
                     });
                 }
 
@@ -394,6 +1153,14 @@ impl<Tab> DockArea<'_, Tab> {
                         Button::new(&self.dock_state.translations.tab_context_menu.eject_button);
                     let close_button =
                         Button::new(&self.dock_state.translations.tab_context_menu.close_button);
+                    let translations = &self.dock_state.translations.tab_context_menu;
+                    let auto_hide_button = Button::new(
+                        if self.dock_state[surface_index].is_auto_hidden(node_index) {
+                            &translations.dock_button
+                        } else {
+                            &translations.auto_hide_button
+                        },
+                    );
 
                     response.context_menu(|ui| {
                         let leaf = self.dock_state[surface_index][node_index]
@@ -411,38 +1178,51 @@ impl<Tab> DockArea<'_, Tab> {
                         }
                         if show_close_button && ui.add(close_button).clicked() {
                             match tab_viewer.on_close(tab) {
+                                // `on_close` has already been asked, so it must not be asked again.
                                 OnCloseResponse::Close => self.to_remove.push(TabRemoval::Tab(
                                     surface_index,
                                     node_index,
                                     tab_index,
-                                    ForcedRemoval(false),
+                                    ForcedRemoval(true),
                                 )),
                                 OnCloseResponse::Focus => {
                                     leaf.active = tab_index;
                                     self.new_focused = Some((surface_index, node_index));
                                 }
                                 OnCloseResponse::Ignore => (),
+                                OnCloseResponse::Defer => {
+                                    let tab_id = tab_viewer.id(tab);
+                                    self.dock_state.defer_close(tab_id);
+                                }
                             }
                             ui.close();
                         }
+                        // The root leaf has no sibling which could take its space.
+                        if node_index != NodeIndex::root() && ui.add(auto_hide_button).clicked() {
+                            let leaf = self.dock_state[surface_index][node_index]
+                                .get_leaf_mut()
+                                .expect("This node must be a leaf");
+                            leaf.set_auto_hidden(!leaf.is_auto_hidden());
+                            ui.close();
+                        }
                     });
                 }
 
                 if close_clicked {
-                    self.to_remove.push(TabRemoval::Tab(
-                        surface_index,
-                        node_index,
-                        tab_index,
-                        ForcedRemoval(false),
-                    ));
+                    self.close_with_selection(surface_index, node_index, tab_index);
                 }
 
                 if let Some(pos) = state.last_hover_pos {
                     // Use response.rect.contains instead of
                     // response.hovered as the dragged tab covers
-                    // the underlying tab
-                    if state.drag_start.is_some() && response.rect.contains(pos) {
-                        self.tab_hover_rect = Some((response.rect, tab_index));
+                    // the underlying tab. The gaps opened next to
+                    // the tab still count as hovering it.
+                    let hover_rect = Rect::from_x_y_ranges(
+                        response.rect.min.x - gap_before..=response.rect.max.x + gap_after,
+                        response.rect.y_range(),
+                    );
+                    if state.drag_start.is_some() && hover_rect.contains(pos) {
+                        self.tab_hover_rect = Some((hover_rect, tab_index));
                     }
                 }
 
@@ -453,40 +1233,215 @@ impl<Tab> DockArea<'_, Tab> {
             let leaf = self.dock_state[surface_index][node_index]
                 .get_leaf_mut()
                 .unwrap();
-            let tab = &mut leaf.tabs[tab_index.0];
             let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
-            let tab_style = tab_viewer.tab_style_override(tab, &style.tab);
+            let tab_style = tab_viewer.tab_style_override(&leaf.tabs[tab_index.0], &style.tab);
             let tab_style = tab_style.as_ref().unwrap_or(&style.tab);
 
-            if !is_active || tab_style.hline_below_active_tab_name {
+            if overlap == 0.0 && (!is_active || tab_style.hline_below_active_tab_name) {
                 let px = tabs_ui.ctx().pixels_per_point().recip();
                 tabs_ui.painter().hline(
                     response.rect.x_range(),
-                    tabbar_outer_rect.bottom() - px,
+                    body_edge(tabbar_outer_rect, position, px),
                     (px, style.tab_bar.hline_color),
                 );
             }
 
-            if response.clicked()
-                || (tabs_ui.memory(|m| m.has_focus(title_id))
-                    && tabs_ui.input(|i| i.key_pressed(Key::Enter) || i.key_pressed(Key::Space)))
+            if response.clicked() {
+                self.click_tab(tabs_ui, (surface_index, node_index, tab_index));
+            } else if tabs_ui.memory(|m| m.has_focus(title_id))
+                && tabs_ui.input(|i| i.key_pressed(Key::Enter) || i.key_pressed(Key::Space))
             {
                 leaf.active = tab_index;
                 self.new_focused = Some((surface_index, node_index));
+                self.response.clicked_tab = Some((surface_index, node_index, tab_index));
             }
 
+            let tab = &mut self.dock_state[surface_index][node_index]
+                .tabs_mut()
+                .expect("This node must be a leaf")[tab_index.0];
             tab_viewer.on_tab_button(tab, &response);
 
             if self.show_close_buttons && tab_viewer.is_closeable(tab) && response.middle_clicked()
             {
-                self.to_remove.push(TabRemoval::Tab(
-                    surface_index,
-                    node_index,
-                    tab_index,
-                    ForcedRemoval(false),
-                ));
+                self.close_with_selection(surface_index, node_index, tab_index);
+            }
+        }
+
+        // A single line runs below the overlapping tabs, which the active tab covers unless told otherwise.
+        if overlap > 0.0 {
+            let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
+            let px = tabs_ui.ctx().pixels_per_point().recip();
+            let hline = Shape::hline(
+                tabs_ui.min_rect().x_range(),
+                body_edge(tabbar_outer_rect, position, px),
+                (px, style.tab_bar.hline_color),
+            );
+            if style.tab.hline_below_active_tab_name {
+                tabs_ui.painter().add(hline);
+            } else if let Some(slot) = tabs_len.checked_sub(1).map(|slot| background_slots[slot]) {
+                tabs_ui.painter().set(slot, hline);
+            }
+        }
+        state.scratch.background_slots = background_slots;
+    }
+
+    /// The ID of the button of the tab at `location`, which is also given the keyboard focus.
+    pub(super) fn tab_title_id(
+        &self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) -> Id {
+        self.id
+            .with((surface_index, "surface"))
+            .with((node_index, "node"))
+            .with((tab_index, "tab"))
+    }
+
+    /// Selects the clicked tab at `location` and makes it active, extending or toggling the selection of its
+    /// leaf while shift or command is held.
+    pub(super) fn click_tab(
+        &mut self,
+        ui: &Ui,
+        location @ (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        let modifiers = ui.input(|i| i.modifiers);
+        if modifiers.command {
+            if leaf.selected_tabs().is_empty() {
+                leaf.set_tab_selected(leaf.active, true);
+            }
+            leaf.set_tab_selected(tab_index, !leaf.is_tab_selected(tab_index));
+        } else if modifiers.shift {
+            let (from, to) = (
+                leaf.active.0.min(tab_index.0),
+                leaf.active.0.max(tab_index.0),
+            );
+            leaf.clear_selection();
+            for index in from..=to {
+                leaf.set_tab_selected(index, true);
+            }
+        } else {
+            leaf.clear_selection();
+        }
+        // Unselecting a tab leaves the active tab as it was.
+        if !modifiers.command || leaf.is_tab_selected(tab_index) {
+            leaf.active = tab_index;
+        }
+        self.new_focused = Some((surface_index, node_index));
+        self.response.clicked_tab = Some(location);
+    }
+
+    /// Marks the tab at `location` as the one being dragged, so that it can be dropped somewhere else.
+    pub(super) fn start_tab_drag(
+        &self,
+        ui: &Ui,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        let rect = self.dock_state[surface_index][node_index]
+            .rect()
+            .expect("This node must be a leaf");
+        ui.memory_mut(|mem| {
+            mem.data.insert_temp(
+                self.id.with("drag_data"),
+                Some(DragData {
+                    src: Some(DragSource::Tab(surface_index, node_index, tab_index)),
+                    rect,
+                }),
+            );
+        });
+    }
+
+    /// Closes the tab at `tab_index`, along with the other selected tabs of its leaf if it is selected.
+    pub(super) fn close_with_selection(
+        &mut self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        tab_index: TabIndex,
+    ) {
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf()
+            .expect("This node must be a leaf");
+        let tabs = if leaf.is_tab_selected(tab_index) {
+            leaf.selected_tabs()
+        } else {
+            vec![tab_index]
+        };
+        // Removals are applied in reverse, so the tabs are removed from the last one and the indices stay valid.
+        for tab_index in tabs {
+            self.to_remove.push(TabRemoval::Tab(
+                surface_index,
+                node_index,
+                tab_index,
+                ForcedRemoval(false),
+            ));
+        }
+    }
+
+    /// Whether the tab at `location` is currently being renamed.
+    ///
+    /// Stops renaming if the tab at `location` is no longer the one which was being renamed.
+    fn is_renaming(
+        &mut self,
+        state: &mut State,
+        location @ (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        let Some(rename) = state.renaming.as_ref() else {
+            return false;
+        };
+        if !self.tab_renaming {
+            state.renaming = None;
+            return false;
+        }
+        if rename.location != location {
+            return false;
+        }
+        let tab = &mut self.dock_state[surface_index][node_index]
+            .tabs_mut()
+            .expect("This node must be a leaf")[tab_index.0];
+        if tab_viewer.id(tab) != rename.tab_id {
+            state.renaming = None;
+            return false;
+        }
+        true
+    }
+
+    /// Draws the text field used to rename a tab.
+    ///
+    /// The new name is committed when the text field loses focus, unless it was cancelled with escape.
+    /// The text field stays open if [`TabViewer::on_rename`] rejects the new name.
+    fn tab_rename(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        id: Id,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Response {
+        let rename = state.renaming.as_mut().expect("A tab must be renamed here");
+        let response = ui.add(
+            TextEdit::singleline(&mut rename.text)
+                .id(id.with("rename"))
+                .desired_width(Style::TAB_RENAME_MIN_WIDTH)
+                .clip_text(false),
+        );
+
+        if response.lost_focus() {
+            let rename = state.renaming.take().expect("A tab must be renamed here");
+            if !ui.input(|i| i.key_pressed(Key::Escape)) {
+                let tab = &mut self.dock_state[surface_index][node_index]
+                    .tabs_mut()
+                    .expect("This node must be a leaf")[tab_index.0];
+                if !tab_viewer.on_rename(tab, rename.text.clone()) {
+                    // A rejected name is kept in the text field, so that it can be corrected.
+                    state.renaming = Some(rename);
+                    response.request_focus();
+                }
             }
         }
+
+        response
     }
 
     /// Draws the tab add button.
@@ -501,9 +1456,16 @@ impl<Tab> DockArea<'_, Tab> {
         offset: f32,
         fade_style: Option<&Style>,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.right_top() - vec2(Style::TAB_ADD_BUTTON_SIZE + offset, 0.0),
-            tabbar_outer_rect.right_bottom() - vec2(offset, 2.0),
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let button_style = style.tab_bar.add_button;
+        let right_to_left = style.tab_bar.right_to_left;
+        let rect = mirror_rect(
+            Rect::from_min_max(
+                tabbar_outer_rect.right_top() - vec2(button_style.width + offset, 0.0),
+                tabbar_outer_rect.right_bottom() - vec2(offset, 2.0),
+            ),
+            tabbar_outer_rect,
+            right_to_left,
         );
 
         let ui = &mut ui.new_child(
@@ -517,7 +1479,6 @@ impl<Tab> DockArea<'_, Tab> {
 
         response = response.on_hover_cursor(CursorIcon::PointingHand);
 
-        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
         let color = if response.hovered() || response.has_focus() {
             ui.painter()
                 .rect_filled(rect, CornerRadius::ZERO, style.buttons.add_tab_bg_fill);
@@ -528,20 +1489,24 @@ impl<Tab> DockArea<'_, Tab> {
 
         let mut plus_rect = rect;
 
-        rect_set_size_centered(&mut plus_rect, Vec2::splat(Style::TAB_ADD_PLUS_SIZE));
+        rect_set_size_centered(&mut plus_rect, Vec2::splat(button_style.plus_size));
 
         ui.painter().line_segment(
             [plus_rect.center_top(), plus_rect.center_bottom()],
-            Stroke::new(1.0, color),
+            Stroke::new(button_style.plus_stroke_width, color),
         );
         ui.painter().line_segment(
             [plus_rect.right_center(), plus_rect.left_center()],
-            Stroke::new(1.0, color),
+            Stroke::new(button_style.plus_stroke_width, color),
         );
 
-        // Draw button left border.
+        // Draw button border on the side of the tabs.
         ui.painter().vline(
-            rect.left(),
+            if right_to_left {
+                rect.right()
+            } else {
+                rect.left()
+            },
             rect.y_range(),
             Stroke::new(
                 ui.ctx().pixels_per_point().recip(),
@@ -564,6 +1529,86 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
+    /// Draws a button which splits the leaf in the direction of `split` when clicked.
+    fn tab_split(
+        &mut self,
+        ui: &mut Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        split: Split,
+        tabbar_outer_rect: Rect,
+        offset: f32,
+        fade_style: Option<&Style>,
+    ) {
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let right_to_left = style.tab_bar.right_to_left;
+        let rect = mirror_rect(
+            Rect::from_min_max(
+                tabbar_outer_rect.right_top() - vec2(Style::TAB_SPLIT_BUTTON_SIZE + offset, 0.0),
+                tabbar_outer_rect.right_bottom() - vec2(offset, 2.0),
+            ),
+            tabbar_outer_rect,
+            right_to_left,
+        );
+
+        let ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect)
+                .layout(Layout::left_to_right(Align::Center))
+                .id_salt((node_index, "tab_split", split)),
+        );
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click());
+        let translations = &self.dock_state.translations.leaf;
+        let response = response
+            .on_hover_cursor(CursorIcon::PointingHand)
+            .on_hover_text(match split {
+                Split::Below => &translations.split_below_button,
+                _ => &translations.split_right_button,
+            });
+
+        let color = if response.hovered() || response.has_focus() {
+            ui.painter()
+                .rect_filled(rect, CornerRadius::ZERO, style.buttons.split_leaf_bg_fill);
+            style.buttons.split_leaf_active_color
+        } else {
+            style.buttons.split_leaf_color
+        };
+
+        // Draw a box divided in the direction of the split.
+        let mut icon_rect = rect;
+        rect_set_size_centered(&mut icon_rect, Vec2::splat(Style::TAB_SPLIT_ICON_SIZE));
+        let stroke = Stroke::new(1.0, color);
+        ui.painter()
+            .rect_stroke(icon_rect, CornerRadius::ZERO, stroke, StrokeKind::Inside);
+        let divider = if split.is_top_bottom() {
+            [icon_rect.left_center(), icon_rect.right_center()]
+        } else {
+            [icon_rect.center_top(), icon_rect.center_bottom()]
+        };
+        ui.painter().line_segment(divider, stroke);
+
+        // Draw button border on the side of the tabs.
+        ui.painter().vline(
+            if right_to_left {
+                rect.right()
+            } else {
+                rect.left()
+            },
+            rect.y_range(),
+            Stroke::new(
+                ui.ctx().pixels_per_point().recip(),
+                style.buttons.split_leaf_border_color,
+            ),
+        );
+
+        if response.clicked() {
+            let node_id = self.dock_state[surface_index][node_index]
+                .id()
+                .expect("This node must be a leaf");
+            self.to_split.push((node_id, split));
+        }
+    }
+
     /// Draws the close all button.
     #[allow(clippy::too_many_arguments)]
     #[allow(unused_assignments)]
@@ -577,9 +1622,16 @@ impl<Tab> DockArea<'_, Tab> {
         disabled: bool,
         close_window_disabled: bool,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.right_top() - vec2(Style::TAB_CLOSE_ALL_BUTTON_SIZE, 0.0),
-            tabbar_outer_rect.right_bottom() - vec2(0.0, 2.0),
+        let rect = mirror_rect(
+            Rect::from_min_max(
+                tabbar_outer_rect.right_top() - vec2(Style::TAB_CLOSE_ALL_BUTTON_SIZE, 0.0),
+                tabbar_outer_rect.right_bottom() - vec2(0.0, 2.0),
+            ),
+            tabbar_outer_rect,
+            fade_style
+                .unwrap_or_else(|| self.style.as_ref().unwrap())
+                .tab_bar
+                .right_to_left,
         );
 
         let ui = &mut ui.new_child(
@@ -714,9 +1766,15 @@ impl<Tab> DockArea<'_, Tab> {
         fade_style: Option<&Style>,
         collapsed: bool,
     ) {
-        let rect = Rect::from_min_max(
-            tabbar_outer_rect.left_top(),
-            tabbar_outer_rect.left_bottom() + vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0),
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let right_to_left = style.tab_bar.right_to_left;
+        let rect = mirror_rect(
+            Rect::from_min_max(
+                tabbar_outer_rect.left_top(),
+                tabbar_outer_rect.left_bottom() + vec2(Style::TAB_COLLAPSE_BUTTON_SIZE, 0.0),
+            ),
+            tabbar_outer_rect,
+            right_to_left,
         );
 
         let ui = &mut ui.new_child(
@@ -730,8 +1788,6 @@ impl<Tab> DockArea<'_, Tab> {
 
         response = response.on_hover_cursor(CursorIcon::PointingHand);
 
-        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
-
         // Whether we're on "secondary button mode" due to modifier keys
         let on_secondary_button = self.is_on_secondary_button(surface_index, ui, &response);
 
@@ -754,12 +1810,16 @@ impl<Tab> DockArea<'_, Tab> {
             Self::draw_chevron_down(ui, style, color, arrow_rect);
         } else {
             // Draw arrow.
-            Self::draw_arrow(collapsed, ui, color, arrow_rect);
+            Self::draw_arrow(collapsed, right_to_left, ui, color, arrow_rect);
         }
 
-        // Draw button right border.
+        // Draw button border on the side of the tabs.
         ui.painter().vline(
-            rect.right(),
+            if right_to_left {
+                rect.left()
+            } else {
+                rect.right()
+            },
             rect.y_range(),
             Stroke::new(
                 ui.ctx().pixels_per_point().recip(),
@@ -834,7 +1894,11 @@ impl<Tab> DockArea<'_, Tab> {
             && (response.hovered() || response.has_focus() || response.is_pointer_button_down_on())
     }
 
-    fn draw_close_window_symbol(ui: &mut Ui, stroke_color: Color32, close_all_rect: Rect) {
+    pub(super) fn draw_close_window_symbol(
+        ui: &mut Ui,
+        stroke_color: Color32,
+        close_all_rect: Rect,
+    ) {
         ui.painter().add(Shape::line(
             vec![
                 close_all_rect
@@ -859,9 +1923,22 @@ impl<Tab> DockArea<'_, Tab> {
         );
     }
 
-    fn draw_arrow(collapsed: bool, ui: &mut Ui, color: Color32, arrow_rect: Rect) {
+    fn draw_arrow(
+        collapsed: bool,
+        right_to_left: bool,
+        ui: &mut Ui,
+        color: Color32,
+        arrow_rect: Rect,
+    ) {
         ui.painter().add(Shape::convex_polygon(
-            if collapsed {
+            if collapsed && right_to_left {
+                // Arrow pointing leftwards, towards the tabs.
+                vec![
+                    arrow_rect.right_top(),
+                    arrow_rect.right_bottom(),
+                    arrow_rect.left_center(),
+                ]
+            } else if collapsed {
                 // Arrow pointing rightwards.
                 vec![
                     arrow_rect.left_top(),
@@ -940,12 +2017,55 @@ impl<Tab> DockArea<'_, Tab> {
         }
     }
 
+    /// Shows the preview of [`TabViewer::drag_preview_ui`] for a dragged tab.
+    ///
+    /// `grabbed_rect` is where the tab was shown in its tab bar the frame before.
+    /// Returns where the preview was shown if the [`TabViewer`] showed one, in which case the title of the tab is hidden.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn custom_drag_preview(
+        &mut self,
+        ui: &Ui,
+        state: &State,
+        id: Id,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        grabbed_rect: Option<Rect>,
+        preview_style: DragPreviewStyle,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Option<Rect> {
+        let pointer_pos = ui.ctx().pointer_interact_pos()?;
+        let position = match preview_style.cursor_offset {
+            Some(offset) => pointer_pos + offset,
+            None => {
+                let grabbed = grabbed_rect
+                    .filter(|rect| rect.is_positive())
+                    .map_or(pointer_pos, |rect| rect.min);
+                grabbed + (pointer_pos - state.drag_start.unwrap_or(pointer_pos))
+            }
+        };
+
+        let bounds = self.drag_preview_bounds(ui.ctx());
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        let tab = &mut leaf.tabs[tab_index.0];
+        let preview = Area::new(id.with("drag_preview"))
+            .order(Order::Tooltip)
+            .fixed_pos(position)
+            .constrain_to(bounds)
+            .interactable(false)
+            .show(ui.ctx(), |ui| {
+                ui.multiply_opacity(preview_style.opacity);
+                tab_viewer.drag_preview_ui(ui, tab)
+            });
+        preview.inner.then_some(preview.response.rect)
+    }
+
     /// * `active` means "the tab that is opened in the parent panel".
     /// * `focused` means "the tab that was last interacted with".
     ///
     /// Returns the main button response plus the response of the close button, if any.
     #[allow(clippy::too_many_arguments)]
-    fn tab_title(
+    pub(super) fn tab_title(
         &mut self,
         ui: &mut Ui,
         tab_style: &TabStyle,
@@ -954,33 +2074,82 @@ impl<Tab> DockArea<'_, Tab> {
         focused: bool,
         active: bool,
         is_being_dragged: bool,
+        draggable: bool,
+        position: TabBarPosition,
         preferred_width: Option<f32>,
         show_close_button: bool,
+        loading: bool,
         fade: Option<&Style>,
+        browser_layer: BrowserTabLayer,
     ) -> (Response, Option<Response>) {
         let style = fade.unwrap_or_else(|| self.style.as_ref().unwrap());
-        let galley = label.into_galley(ui, None, f32::INFINITY, TextStyle::Button);
-        let x_spacing = 8.0;
-        let text_width = galley.size().x + 2.0 * x_spacing;
+        let slant = browser_tab_slant(style, position);
+        let galley = tab_style.title_galley(ui, label, None);
+        let x_spacing = 8.0 + slant.unwrap_or(0.0);
+        // Loading tabs have a spinner as tall as their title before it.
+        let spinner_size = (loading && style.loading.title_spinner).then(|| galley.size().y);
+        let spinner_width = spinner_size.map_or(0.0, |size| size + ui.spacing().icon_spacing);
         let close_button_size = if show_close_button {
             Style::TAB_CLOSE_BUTTON_SIZE.min(style.tab_bar.height)
         } else {
             0.0
         };
+        let galley = match tab_style.maximum_width {
+            Some(maximum_width) => truncate_title(
+                ui,
+                galley,
+                maximum_width - spinner_width - 2.0 * x_spacing - close_button_size,
+                tab_style.title_truncation,
+            ),
+            None => galley,
+        };
+        let text_width = galley.size().x + spinner_width + 2.0 * x_spacing;
 
         // Compute total width of the tab bar.
         let minimum_width = tab_style
             .minimum_width
             .unwrap_or(0.0)
             .at_least(text_width + close_button_size);
-        let tab_width = preferred_width.unwrap_or(0.0).at_least(minimum_width);
+        let mut tab_width = preferred_width.unwrap_or(0.0).at_least(minimum_width);
+        if let Some(maximum_width) = tab_style.maximum_width {
+            // A title cut down to its ellipsis still has to fit.
+            tab_width = tab_width.at_most(maximum_width.at_least(text_width + close_button_size));
+        }
 
         let (_, tab_rect) = ui.allocate_space(vec2(tab_width, ui.available_height()));
-        let mut response = ui.interact(tab_rect, id, Sense::click_and_drag());
-        if ui.ctx().dragged_id().is_none() && self.draggable_tabs {
+        // The slanted sides of neighbouring tabs cross halfway, which is where one tab ends and the next begins.
+        let interact_rect = tab_rect.shrink2(vec2(slant.unwrap_or(0.0) / 2.0, 0.0));
+        let mut response = match slant {
+            Some(slant) => {
+                // Only the part of the tab which isn't painted over by its neighbours is hit, as the rects of
+                // neighbouring tabs overlap. The tab keeps its rect up to where its slanted sides cross the ones of
+                // its neighbours.
+                let hit = ui.input(|i| i.pointer.hover_pos()).is_some_and(|pos| {
+                    browser_tab_contains(
+                        tab_rect,
+                        slant,
+                        tab_style.spacing,
+                        position == TabBarPosition::Bottom,
+                        browser_layer.covered,
+                        pos,
+                    )
+                });
+                let hit_rect = if hit || ui.ctx().dragged_id() == Some(id) {
+                    tab_rect
+                } else {
+                    Rect::NOTHING
+                };
+                let mut response = ui.interact(hit_rect, id, Sense::click_and_drag());
+                response.rect = interact_rect;
+                response
+            }
+            None => ui.interact(interact_rect, id, Sense::click_and_drag()),
+        };
+        if ui.ctx().dragged_id().is_none() && draggable {
             response = response.on_hover_cursor(CursorIcon::Grab);
         }
 
+        let focus_ring = tab_style.focus_ring;
         let tab_style = if focused || is_being_dragged {
             if response.has_focus() {
                 &tab_style.focused_with_kb_focus
@@ -1001,84 +2170,219 @@ impl<Tab> DockArea<'_, Tab> {
             &tab_style.inactive
         };
 
-        // Draw the full tab first and then the stroke on top to avoid the stroke
-        // mixing with the background color.
-        ui.painter()
-            .rect_filled(tab_rect, tab_style.corner_radius, tab_style.bg_fill);
+        // Tabs of tab bars below the body are upside down.
+        let corner_radius = match position {
+            TabBarPosition::Bottom => CornerRadius {
+                nw: tab_style.corner_radius.sw,
+                ne: tab_style.corner_radius.se,
+                sw: tab_style.corner_radius.nw,
+                se: tab_style.corner_radius.ne,
+            },
+            _ => tab_style.corner_radius,
+        };
+
+        if let Some(slant) = slant {
+            let points = browser_tab_points(
+                tab_rect,
+                slant,
+                tab_style.corner_radius,
+                position == TabBarPosition::Bottom,
+            );
+            // The side towards the body is left open, so that the tab connects with it.
+            let shape = Shape::Vec(vec![
+                Shape::convex_polygon(points.clone(), tab_style.bg_fill, Stroke::NONE),
+                Shape::line(points, Stroke::new(1.0, tab_style.outline_color)),
+            ]);
+            match browser_layer.background_slot {
+                Some(slot) => ui.painter().set(slot, shape),
+                None => {
+                    ui.painter().add(shape);
+                }
+            }
+        } else {
+            Self::paint_tab_frame(ui, tab_rect, corner_radius, tab_style);
+        }
         let stroke_rect = rect_stroke_box(tab_rect, 1.0);
-        ui.painter().rect_stroke(
-            stroke_rect,
-            tab_style.corner_radius,
-            Stroke::new(1.0, tab_style.outline_color),
-            StrokeKind::Inside,
-        );
-        if !is_being_dragged {
+        if !is_being_dragged && slant.is_none() {
             // Make the tab name area connect with the tab ui area.
             ui.painter().hline(
                 RangeInclusive::new(
                     stroke_rect.min.x + f32::max(tab_style.corner_radius.sw.into(), 1.5),
                     stroke_rect.max.x - f32::max(tab_style.corner_radius.se.into(), 1.5),
                 ),
-                stroke_rect.bottom(),
+                match position {
+                    TabBarPosition::Bottom => stroke_rect.top(),
+                    _ => stroke_rect.bottom(),
+                },
                 Stroke::new(2.0, tab_style.bg_fill),
             );
         }
 
-        let mut text_rect = tab_rect;
-        text_rect.set_width(text_rect.width() - close_button_size);
-        let text_pos = {
-            let pos = Align2::CENTER_CENTER.pos_in_rect(&text_rect.shrink2(vec2(x_spacing, 0.0)));
-            pos - galley.size() / 2.0
+        // The close button comes after the title, and the spinner before it, in the direction of the tab bar.
+        let right_to_left = style.tab_bar.right_to_left;
+        // Browser tabs keep them between their slanted sides, which their neighbours are painted over.
+        let inner_rect = tab_rect.shrink2(vec2(slant.unwrap_or(0.0), 0.0));
+        let mut text_rect = inner_rect;
+        if right_to_left {
+            text_rect.set_left(text_rect.left() + close_button_size);
+        } else {
+            text_rect.set_width(text_rect.width() - close_button_size);
+        }
+        let content_pos = {
+            let pos = Align2::CENTER_CENTER.pos_in_rect(&text_rect);
+            pos - (galley.size() + vec2(spinner_width, 0.0)) / 2.0
         };
+        let (spinner_x, text_x) = if right_to_left {
+            let content_width = galley.size().x + spinner_width;
+            (content_width - spinner_size.unwrap_or(0.0), 0.0)
+        } else {
+            (0.0, spinner_width)
+        };
+        if let Some(size) = spinner_size {
+            Spinner::new()
+                .size(size)
+                .color(tab_style.text_color)
+                .paint_at(
+                    ui,
+                    Rect::from_min_size(content_pos + vec2(spinner_x, 0.0), Vec2::splat(size)),
+                );
+        }
+        let text_pos = content_pos + vec2(text_x, 0.0);
 
         ui.painter()
             .add(TextShape::new(text_pos, galley, tab_style.text_color));
+        if response.has_focus() {
+            ui.painter()
+                .rect_stroke(interact_rect, corner_radius, focus_ring, StrokeKind::Inside);
+        }
 
         let close_response = show_close_button.then(|| {
-            let mut close_button_rect = tab_rect;
-            close_button_rect.set_left(text_rect.right());
+            let mut close_button_rect = inner_rect;
+            if right_to_left {
+                close_button_rect.set_right(text_rect.left());
+            } else {
+                close_button_rect.set_left(text_rect.right());
+            }
             close_button_rect =
                 Rect::from_center_size(close_button_rect.center(), Vec2::splat(close_button_size));
 
-            let close_response = ui
-                .interact(close_button_rect, id.with("close-button"), Sense::click())
-                .on_hover_cursor(CursorIcon::PointingHand);
-
-            let color = if close_response.hovered() || close_response.has_focus() {
-                style.buttons.close_tab_active_color
+            let mut corner_radius = tab_style.corner_radius;
+            if right_to_left {
+                corner_radius.ne = 0;
+                corner_radius.se = 0;
             } else {
-                style.buttons.close_tab_color
-            };
-
-            if close_response.hovered() || close_response.has_focus() {
-                let mut corner_radius = tab_style.corner_radius;
                 corner_radius.nw = 0;
                 corner_radius.sw = 0;
-
-                ui.painter().rect_filled(
-                    close_button_rect,
-                    corner_radius,
-                    style.buttons.close_tab_bg_fill,
-                );
             }
-
-            let mut x_rect = close_button_rect;
-            rect_set_size_centered(&mut x_rect, Vec2::splat(Style::TAB_CLOSE_X_SIZE));
-            ui.painter().line_segment(
-                [x_rect.left_top(), x_rect.right_bottom()],
-                Stroke::new(1.0, color),
-            );
-            ui.painter().line_segment(
-                [x_rect.right_top(), x_rect.left_bottom()],
-                Stroke::new(1.0, color),
-            );
-
-            close_response
+            Self::tab_close_button(ui, id, close_button_rect, corner_radius, focus_ring, style)
         });
 
         (response, close_response)
     }
 
+    /// Paints the background of a tab at `rect` and its outline.
+    pub(super) fn paint_tab_frame(
+        ui: &Ui,
+        rect: Rect,
+        corner_radius: CornerRadius,
+        tab_style: &TabInteractionStyle,
+    ) {
+        // Draw the full tab first and then the stroke on top to avoid the stroke
+        // mixing with the background color.
+        ui.painter()
+            .rect_filled(rect, corner_radius, tab_style.bg_fill);
+        ui.painter().rect_stroke(
+            rect_stroke_box(rect, 1.0),
+            corner_radius,
+            Stroke::new(1.0, tab_style.outline_color),
+            StrokeKind::Inside,
+        );
+    }
+
+    /// Shows the close button of the tab with the given `id` at `rect`, with its background rounded by
+    /// `corner_radius` while hovered.
+    pub(super) fn tab_close_button(
+        ui: &Ui,
+        id: Id,
+        rect: Rect,
+        corner_radius: CornerRadius,
+        focus_ring: Stroke,
+        style: &Style,
+    ) -> Response {
+        let close_response = ui
+            .interact(rect, id.with("close-button"), Sense::click())
+            .on_hover_cursor(CursorIcon::PointingHand);
+
+        let color = if close_response.hovered() || close_response.has_focus() {
+            style.buttons.close_tab_active_color
+        } else {
+            style.buttons.close_tab_color
+        };
+
+        if close_response.hovered() || close_response.has_focus() {
+            ui.painter()
+                .rect_filled(rect, corner_radius, style.buttons.close_tab_bg_fill);
+        }
+
+        let mut x_rect = rect;
+        rect_set_size_centered(&mut x_rect, Vec2::splat(Style::TAB_CLOSE_X_SIZE));
+        ui.painter().line_segment(
+            [x_rect.left_top(), x_rect.right_bottom()],
+            Stroke::new(1.0, color),
+        );
+        ui.painter().line_segment(
+            [x_rect.right_top(), x_rect.left_bottom()],
+            Stroke::new(1.0, color),
+        );
+        if close_response.has_focus() {
+            ui.painter()
+                .rect_stroke(rect, CornerRadius::ZERO, focus_ring, StrokeKind::Inside);
+        }
+
+        close_response
+    }
+
+    /// Scrolls the tab bar so that the tab at `rect` is in view, if it was requested to be.
+    pub(super) fn scroll_into_view(
+        &mut self,
+        tabs_ui: &Ui,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        rect: Rect,
+        position: TabBarPosition,
+    ) {
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        if leaf.scroll_target != Some(tab_index) {
+            return;
+        }
+        leaf.scroll_target = None;
+
+        // The new scroll amount is clamped to the overflow of the tab bar when it's shown next frame.
+        // Right-to-left tab bars are scrolled the same in their mirrored coordinates.
+        let visible = tabs_ui.clip_rect();
+        let (start, end, visible_start, visible_end) = if position.is_vertical() {
+            (rect.top(), rect.bottom(), visible.top(), visible.bottom())
+        } else if self.style.as_ref().unwrap().tab_bar.right_to_left {
+            (
+                -rect.right(),
+                -rect.left(),
+                -visible.right(),
+                -visible.left(),
+            )
+        } else {
+            (rect.left(), rect.right(), visible.left(), visible.right())
+        };
+        if end > visible_end {
+            leaf.scroll -= (end - visible_end).min(start - visible_start);
+        } else if start < visible_start {
+            leaf.scroll += visible_start - start;
+        } else {
+            return;
+        }
+        tabs_ui.ctx().request_repaint();
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn tab_bar_scroll(
         &mut self,
@@ -1112,7 +2416,11 @@ impl<Tab> DockArea<'_, Tab> {
 
                 // Compute scroll bar handle position and size.
                 let overflow_ratio = actual_width / available_width;
-                let scroll_ratio = -leaf.scroll / overflow;
+                let scroll_ratio = if style.tab_bar.right_to_left {
+                    1.0 + leaf.scroll / overflow
+                } else {
+                    -leaf.scroll / overflow
+                };
 
                 let scroll_bar_handle_size = overflow_ratio.recip() * scroll_bar_rect.width();
                 let scroll_bar_handle_start = lerp(
@@ -1134,8 +2442,12 @@ impl<Tab> DockArea<'_, Tab> {
                 let points_to_scroll_coefficient =
                     overflow / (scroll_bar_rect.width() - scroll_bar_handle_size);
 
-                leaf.scroll -=
-                    scroll_bar_handle_response.drag_delta().x * points_to_scroll_coefficient;
+                let drag_delta = scroll_bar_handle_response.drag_delta().x;
+                if style.tab_bar.right_to_left {
+                    leaf.scroll += drag_delta * points_to_scroll_coefficient;
+                } else {
+                    leaf.scroll -= drag_delta * points_to_scroll_coefficient;
+                }
 
                 if let Some(pos) = state.last_hover_pos {
                     if scroll_bar_rect.contains(pos) {
@@ -1168,6 +2480,74 @@ impl<Tab> DockArea<'_, Tab> {
         leaf.scroll = leaf.scroll.clamp(-overflow, 0.0);
     }
 
+    /// Switches the active tab of the leaf while the mouse wheel is scrolled over its `hovered` tab bar, see
+    /// [`TabBarStyle::scroll_to_switch`](crate::TabBarStyle::scroll_to_switch).
+    ///
+    /// The scrolling is used up, so that the tab bar and whatever contains the [`DockArea`] don't scroll as well.
+    pub(super) fn scroll_to_switch(
+        &mut self,
+        ui: &Ui,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        hovered: bool,
+        fade_style: Option<&Style>,
+    ) {
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let Some(scroll_to_switch) = style.tab_bar.scroll_to_switch else {
+            return;
+        };
+        if !hovered {
+            return;
+        }
+        let delta = ui.ctx().input_mut(|i| {
+            let delta = i.raw_scroll_delta;
+            i.raw_scroll_delta = Vec2::ZERO;
+            i.smooth_scroll_delta = Vec2::ZERO;
+            delta.x + delta.y
+        });
+        if !delta.is_finite() {
+            return;
+        }
+
+        // Touchpads scroll a little at a time, so the scrolling adds up until it's worth a line.
+        let id = self
+            .id
+            .with((surface_index, node_index, "scroll_to_switch"));
+        let line = ui
+            .ctx()
+            .options(|o| o.input_options.line_scroll_speed)
+            .at_least(1.0);
+        let mut scrolled = ui.data(|d| d.get_temp::<f32>(id)).unwrap_or(0.0) + delta;
+        let lines = (scrolled / line).trunc();
+        scrolled -= lines * line;
+        ui.data_mut(|d| d.insert_temp(id, scrolled));
+        if lines == 0.0 {
+            return;
+        }
+        // Scrolling down gives a negative delta.
+        let steps = if scroll_to_switch.reversed {
+            lines as isize
+        } else {
+            -lines as isize
+        };
+
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        let len = leaf.tabs.len() as isize;
+        if len == 0 {
+            return;
+        }
+        let index = (leaf.active.0 as isize).saturating_add(steps);
+        let index = if scroll_to_switch.wrap_around {
+            index.rem_euclid(len)
+        } else {
+            index.clamp(0, len - 1)
+        };
+        leaf.clear_selection();
+        leaf.active = TabIndex(index as usize);
+        self.new_focused = Some((surface_index, node_index));
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn tab_body(
         &mut self,
@@ -1180,6 +2560,7 @@ impl<Tab> DockArea<'_, Tab> {
         fade: Option<(&Style, f32)>,
         collapsed: bool,
     ) {
+        profile_scope!("DockArea::tab_body");
         let (body_rect, _body_response) =
             ui.allocate_exact_size(ui.available_size_before_wrap(), Sense::hover());
 
@@ -1187,6 +2568,7 @@ impl<Tab> DockArea<'_, Tab> {
             .get_leaf_mut()
             .expect("This node must be a leaf");
         let LeafNode {
+            id: leaf_id,
             rect,
             viewport,
             tabs,
@@ -1195,6 +2577,14 @@ impl<Tab> DockArea<'_, Tab> {
         } = leaf;
         if !collapsed {
             if let Some(tab) = tabs.get_mut(active.0) {
+                self.response
+                    .visible_tabs
+                    .push((surface_index, node_index, *active));
+                self.response
+                    .set_tab_rect((surface_index, node_index, *active), |rects| {
+                        rects.body = Some(body_rect);
+                    });
+
                 if *viewport != body_rect {
                     *viewport = body_rect;
                     tab_viewer.on_rect_changed(tab);
@@ -1215,12 +2605,28 @@ impl<Tab> DockArea<'_, Tab> {
                 let tabs_styles = tab_viewer.tab_style_override(tab, &style.tab);
 
                 let tabs_style = tabs_styles.as_ref().unwrap_or(&style.tab);
+                let body_frame =
+                    tab_viewer
+                        .tab_body_frame(surface_index, node_index)
+                        .map(|frame| {
+                            let mut body_style = TabBodyStyle {
+                                inner_margin: frame.inner_margin,
+                                stroke: frame.stroke,
+                                corner_radius: frame.corner_radius,
+                                bg_fill: frame.fill,
+                            };
+                            if fade_factor != 1.0 {
+                                fade_tab_body_style(&mut body_style, fade_factor);
+                            }
+                            body_style
+                        });
+                let body_style = body_frame.as_ref().unwrap_or(&tabs_style.tab_body);
 
                 if tab_viewer.clear_background(tab) {
                     ui.painter().rect_filled(
                         body_rect,
-                        tabs_style.tab_body.corner_radius,
-                        tabs_style.tab_body.bg_fill,
+                        body_style.corner_radius,
+                        body_style.bg_fill,
                     );
                 }
 
@@ -1244,30 +2650,91 @@ impl<Tab> DockArea<'_, Tab> {
                 // Offset the background rectangle up to hide the top border behind the clip rect.
                 // To avoid anti-aliasing lines when the stroke width is not divisible by two, we
                 // need to calculate the effective anti-aliased stroke width.
-                let effective_stroke_width = (tabs_style.tab_body.stroke.width / 2.0).ceil() * 2.0;
+                let effective_stroke_width = (body_style.stroke.width / 2.0).ceil() * 2.0;
                 let tab_body_rect = Rect::from_min_max(
                     ui.clip_rect().min - vec2(0.0, effective_stroke_width),
                     ui.clip_rect().max,
                 );
                 ui.painter().rect_stroke(
-                    rect_stroke_box(tab_body_rect, tabs_style.tab_body.stroke.width),
-                    tabs_style.tab_body.corner_radius,
-                    tabs_style.tab_body.stroke,
+                    rect_stroke_box(tab_body_rect, body_style.stroke.width),
+                    body_style.corner_radius,
+                    body_style.stroke,
                     StrokeKind::Inside,
                 );
 
-                ScrollArea::new(tab_viewer.scroll_bars(tab)).show(ui, |ui| {
-                    Frame::new()
-                        .inner_margin(tabs_style.tab_body.inner_margin)
-                        .show(ui, |ui| {
-                            if fade_factor != 1.0 {
-                                fade_visuals(ui.visuals_mut(), fade_factor);
-                            }
-                            let available_rect = ui.available_rect_before_wrap();
-                            ui.expand_to_include_rect(available_rect);
-                            tab_viewer.ui(ui, tab);
-                        });
-                });
+                if tab_viewer.is_loading(tab) {
+                    let loading = style.loading;
+                    let spinner_rect = Rect::from_center_size(
+                        body_rect.center(),
+                        Vec2::splat(loading.body_spinner_size),
+                    );
+                    Spinner::new()
+                        .size(loading.body_spinner_size)
+                        .color(loading.body_spinner_color)
+                        .paint_at(ui, spinner_rect);
+                } else {
+                    let scroll_bars = tab_viewer.scroll_bars(tab);
+                    let mut body = |ui: &mut Ui| {
+                        Frame::new()
+                            .inner_margin(body_style.inner_margin)
+                            .show(ui, |ui| {
+                                if fade_factor != 1.0 {
+                                    fade_visuals(ui.visuals_mut(), fade_factor);
+                                }
+                                let available_rect = ui.available_rect_before_wrap();
+                                ui.expand_to_include_rect(available_rect);
+                                tab_viewer.ui(ui, tab);
+                            });
+                    };
+                    // The id of the scroll area comes from the one of the tab, so each tab keeps its own offset.
+                    if self.tab_scroll_areas {
+                        ScrollArea::new(scroll_bars).show(ui, body);
+                    } else {
+                        body(ui);
+                    }
+                }
+            } else if tabs.is_empty() {
+                // Only central leaves are shown without tabs.
+                *viewport = body_rect;
+                if ui.input(|i| i.pointer.any_click())
+                    && state
+                        .last_hover_pos
+                        .is_some_and(|pos| body_rect.contains(pos))
+                {
+                    self.new_focused = Some((surface_index, node_index));
+                }
+
+                let (style, fade_factor) =
+                    fade.unwrap_or_else(|| (self.style.as_ref().unwrap(), 1.0));
+                ui.painter().rect_filled(
+                    body_rect,
+                    style.tab.tab_body.corner_radius,
+                    style.tab.tab_body.bg_fill,
+                );
+                let ui = &mut ui.new_child(
+                    UiBuilder::new()
+                        .max_rect(body_rect)
+                        .id_salt(("empty_ui", *leaf_id)),
+                );
+                ui.spacing_mut().item_spacing = spacing;
+
+                // Interacted with before the content, so that the widgets of the content take precedence.
+                let body_response = self
+                    .empty_leaf_context_menus
+                    .then(|| ui.interact(body_rect, ui.id().with("empty_body"), Sense::click()));
+                Frame::new()
+                    .inner_margin(style.tab.tab_body.inner_margin)
+                    .show(ui, |ui| {
+                        if fade_factor != 1.0 {
+                            fade_visuals(ui.visuals_mut(), fade_factor);
+                        }
+                        tab_viewer.empty_ui(ui, surface_index, node_index);
+                    });
+                if let Some(body_response) = body_response {
+                    body_response.context_menu(|ui| {
+                        tab_viewer.empty_context_menu(ui, surface_index, node_index);
+                    });
+                }
             }
         }
 
@@ -1278,23 +2745,8 @@ impl<Tab> DockArea<'_, Tab> {
 
             // if the dragged tab isn't allowed in a window,
             // it's unnecessary to change the hover state
-            let is_dragged_valid = match &state.dnd {
-                Some(DragDropState {
-                    drag: DragData { src, .. },
-                    ..
-                }) => match *src {
-                    TreeComponent::Tab(d_surf, d_node, d_tab) => {
-                        if let Node::Leaf(leaf) = &mut self.dock_state[d_surf][d_node] {
-                            tab_viewer.allowed_in_windows(&mut leaf.tabs[d_tab.0])
-                                || surface_index == SurfaceIndex::main()
-                        } else {
-                            true
-                        }
-                    }
-                    _ => unreachable!("collections of nodes can't be dragged (yet)"),
-                },
-                _ => true,
-            };
+            let is_dragged_valid =
+                self.is_drag_allowed_on(state, (surface_index, node_index), tab_viewer);
 
             // Use rect.contains instead of response.hovered as the dragged tab covers
             // the underlying responses.
@@ -1316,10 +2768,418 @@ impl<Tab> DockArea<'_, Tab> {
                 ui.memory_mut(|mem| {
                     mem.data.insert_temp(
                         self.id.with("hover_data"),
-                        Some(HoverData { rect, dst, tab }),
+                        Some(HoverData {
+                            rect,
+                            dst,
+                            tab,
+                            edge: None,
+                        }),
                     );
                 });
             }
         }
     }
 }
+
+/// Mirrors `rect` across the vertical center line of `within` if `right_to_left`, which turns a rect laid out from
+/// left to right into the one laid out from right to left.
+fn mirror_rect(rect: Rect, within: Rect, right_to_left: bool) -> Rect {
+    if !right_to_left {
+        return rect;
+    }
+    let mirror = |x: f32| within.left() + within.right() - x;
+    Rect::from_x_y_ranges(mirror(rect.right())..=mirror(rect.left()), rect.y_range())
+}
+
+/// Titles cut short by [`truncate_title`] in the current and the last frame, so that they aren't laid out again
+/// every frame.
+#[derive(Clone, Default)]
+struct TruncatedTitles {
+    frame: u64,
+    current: HashMap<Id, Arc<Galley>>,
+    last: HashMap<Id, Arc<Galley>>,
+}
+
+/// Cuts `galley` short with an ellipsis where `truncation` says, so that it's at most `max_width` wide.
+fn truncate_title(
+    ui: &Ui,
+    galley: Arc<Galley>,
+    max_width: f32,
+    truncation: TitleTruncation,
+) -> Arc<Galley> {
+    if galley.size().x <= max_width {
+        return galley;
+    }
+    let key = Id::new((&*galley.job, max_width.to_bits(), truncation));
+    let cache_id = Id::new("egui_dock::truncated_titles");
+    let frame = ui.ctx().cumulative_frame_nr();
+    let cached = ui.data_mut(|data| {
+        let cache = data.get_temp_mut_or_default::<TruncatedTitles>(cache_id);
+        if cache.frame != frame {
+            // Only the titles shown in the last frame are kept.
+            cache.frame = frame;
+            cache.last = std::mem::take(&mut cache.current);
+        }
+        let cached = cache
+            .current
+            .get(&key)
+            .cloned()
+            .or_else(|| cache.last.remove(&key));
+        if let Some(galley) = &cached {
+            cache.current.insert(key, galley.clone());
+        }
+        cached
+    });
+    if let Some(galley) = cached {
+        return galley;
+    }
+
+    let mut job = (*galley.job).clone();
+    let truncated = match truncation {
+        TitleTruncation::End => {
+            job.wrap = TextWrapping::truncate_at_width(max_width.at_least(0.0));
+            ui.fonts(|fonts| fonts.layout_job(job))
+        }
+        TitleTruncation::Middle => {
+            let boundaries: Vec<usize> = job
+                .text
+                .char_indices()
+                .map(|(index, _)| index)
+                .chain(std::iter::once(job.text.len()))
+                .collect();
+            let chars = boundaries.len() - 1;
+            let layout = |kept: usize| {
+                let (head, tail) = (kept.div_ceil(2), kept / 2);
+                let job = elide_middle(&job, boundaries[head], boundaries[chars - tail]);
+                ui.fonts(|fonts| fonts.layout_job(job))
+            };
+
+            // Find the most characters which fit around the ellipsis.
+            let (mut fitting, mut too_many) = (0, chars);
+            while too_many - fitting > 1 {
+                let kept = (fitting + too_many) / 2;
+                if layout(kept).size().x <= max_width {
+                    fitting = kept;
+                } else {
+                    too_many = kept;
+                }
+            }
+            layout(fitting)
+        }
+    };
+    ui.data_mut(|data| {
+        data.get_temp_mut_or_default::<TruncatedTitles>(cache_id)
+            .current
+            .insert(key, truncated.clone());
+    });
+    truncated
+}
+
+/// Replaces the text of `job` between the byte offsets `head` and `tail` with an ellipsis, keeping the format of
+/// every section around it. The ellipsis takes the format of the text it replaces the start of.
+fn elide_middle(job: &LayoutJob, head: usize, tail: usize) -> LayoutJob {
+    let mut elided = job.clone();
+    elided.text.clear();
+    elided.sections.clear();
+    let append = |elided: &mut LayoutJob, range: Range<usize>, section: &LayoutSection| {
+        if range.is_empty() {
+            return;
+        }
+        let leading_space = if range.start == section.byte_range.start {
+            section.leading_space
+        } else {
+            0.0
+        };
+        elided.append(&job.text[range], leading_space, section.format.clone());
+    };
+    for section in &job.sections {
+        append(
+            &mut elided,
+            section.byte_range.start..section.byte_range.end.min(head),
+            section,
+        );
+    }
+    let cut = job
+        .sections
+        .iter()
+        .find(|section| section.byte_range.contains(&head))
+        .or(job.sections.last());
+    if let Some(section) = cut {
+        elided.append("…", 0.0, section.format.clone());
+    }
+    for section in &job.sections {
+        append(
+            &mut elided,
+            section.byte_range.start.max(tail)..section.byte_range.end,
+            section,
+        );
+    }
+    elided
+}
+
+/// The height of the line along the edge of a tab bar at `position` which faces the tab body,
+/// `px` away from the edge.
+fn body_edge(tabbar_rect: Rect, position: TabBarPosition, px: f32) -> f32 {
+    match position {
+        TabBarPosition::Bottom => tabbar_rect.top() + px,
+        _ => tabbar_rect.bottom() - px,
+    }
+}
+
+/// Width of the slanted sides of tabs shaped like the ones of web browsers, or `None` if they're rectangular.
+///
+/// Tab bars on the left or right of their leaves always have rectangular tabs.
+fn browser_tab_slant(style: &Style, position: TabBarPosition) -> Option<f32> {
+    match style.tab.shape {
+        TabShape::Browser { .. } if !position.is_vertical() => Some(style.tab.shape.overlap()),
+        _ => None,
+    }
+}
+
+/// How a tab shaped like the ones of web browsers is layered with its neighbours, whose slanted sides overlap it.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct BrowserTabLayer {
+    /// The shape the background of the tab is painted into, so that it's below the tabs painted over it.
+    pub background_slot: Option<ShapeIdx>,
+
+    /// Whether the tabs before and after the tab are painted over it.
+    pub covered: [bool; 2],
+}
+
+/// Whether `pos` is on the part of a tab shaped like the ones of web browsers at `rect` which isn't painted over by
+/// its neighbours, `spacing` away from it, as told by `covered`. See [`browser_tab_points`].
+fn browser_tab_contains(
+    rect: Rect,
+    slant: f32,
+    spacing: f32,
+    flipped: bool,
+    [covered_before, covered_after]: [bool; 2],
+    pos: Pos2,
+) -> bool {
+    if !rect.contains(pos) {
+        return false;
+    }
+    // How far up the slanted sides `pos` is, from 0 along the body to 1 at the other edge.
+    let height = if flipped {
+        pos.y - rect.top()
+    } else {
+        rect.bottom() - pos.y
+    } / rect.height().at_least(f32::EPSILON);
+    let inset = slant * height;
+    // The sides of the neighbours slant the other way.
+    let within = pos.x >= rect.left() + inset && pos.x <= rect.right() - inset;
+    let under_before = covered_before && pos.x <= rect.left() - spacing + slant - inset;
+    let under_after = covered_after && pos.x >= rect.right() + spacing - slant + inset;
+    within && !under_before && !under_after
+}
+
+/// Outline of a tab shaped like the ones of web browsers, from the bottom left corner to the bottom right one,
+/// or from the top ones for tabs below their bodies (`flipped`).
+fn browser_tab_points(
+    rect: Rect,
+    slant: f32,
+    corner_radius: CornerRadius,
+    flipped: bool,
+) -> Vec<Pos2> {
+    let flip = |pos: Pos2| {
+        if flipped {
+            pos2(pos.x, rect.top() + rect.bottom() - pos.y)
+        } else {
+            pos
+        }
+    };
+    let corners = [
+        rect.left_bottom(),
+        pos2(rect.left() + slant, rect.top()),
+        pos2(rect.right() - slant, rect.top()),
+        rect.right_bottom(),
+    ];
+    let mut points = vec![corners[0]];
+    for (index, radius) in [(1, corner_radius.nw), (2, corner_radius.ne)] {
+        let (before, corner, after) = (corners[index - 1], corners[index], corners[index + 1]);
+        // The radius can't be larger than half of either side meeting at the corner.
+        let radius = f32::from(radius)
+            .min(corner.distance(before) / 2.0)
+            .min(corner.distance(after) / 2.0);
+        if radius <= 0.0 {
+            points.push(corner);
+            continue;
+        }
+        let start = corner + (before - corner).normalized() * radius;
+        let end = corner + (after - corner).normalized() * radius;
+        const STEPS: usize = 6;
+        points.extend((0..=STEPS).map(|step| {
+            let t = step as f32 / STEPS as f32;
+            let (a, b) = (start.lerp(corner, t), corner.lerp(end, t));
+            a.lerp(b, t)
+        }));
+    }
+    points.push(corners[3]);
+    points.into_iter().map(flip).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use egui::{text::TextFormat, CentralPanel, Context, FontId, RawInput};
+
+    use super::*;
+
+    /// Runs `test` with a [`Ui`] whose fonts are loaded.
+    fn with_ui(test: impl Fn(&mut Ui)) {
+        let ctx = Context::default();
+        let _ = ctx.run(RawInput::default(), |_| {});
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| test(ui));
+        });
+    }
+
+    fn layout(ui: &Ui, sections: &[(&str, Color32)]) -> Arc<Galley> {
+        let mut job = LayoutJob::default();
+        for &(text, color) in sections {
+            job.append(
+                text,
+                0.0,
+                TextFormat::simple(FontId::monospace(12.0), color),
+            );
+        }
+        ui.fonts(|fonts| fonts.layout_job(job))
+    }
+
+    #[test]
+    fn titles_are_truncated_at_the_end() {
+        with_ui(|ui| {
+            let galley = layout(ui, &[("abcdefghijklmnopqrstuvwxyz", Color32::WHITE)]);
+            let max_width = galley.size().x / 2.0;
+
+            let truncated = truncate_title(ui, galley.clone(), max_width, TitleTruncation::End);
+            assert!(truncated.elided);
+            assert!(truncated.size().x <= max_width);
+
+            // Titles which fit are kept the way they are.
+            let kept = truncate_title(ui, galley.clone(), 1000.0, TitleTruncation::End);
+            assert!(Arc::ptr_eq(&kept, &galley));
+        });
+    }
+
+    #[test]
+    fn titles_are_truncated_in_the_middle_keeping_their_formats() {
+        with_ui(|ui| {
+            let galley = layout(
+                ui,
+                &[
+                    ("src/widgets/", Color32::GRAY),
+                    ("dock_area/show/", Color32::WHITE),
+                    ("leaf.rs", Color32::RED),
+                ],
+            );
+            let max_width = galley.size().x / 2.0;
+
+            let truncated = truncate_title(ui, galley, max_width, TitleTruncation::Middle);
+            let text = truncated.job.text.as_str();
+            assert!(truncated.size().x <= max_width);
+            assert!(text.starts_with("src/"), "{text}");
+            assert!(text.ends_with(".rs"), "{text}");
+            assert!(text.contains('…'), "{text}");
+            let colors: Vec<Color32> = truncated
+                .job
+                .sections
+                .iter()
+                .map(|section| section.format.color)
+                .collect();
+            assert_eq!(colors.first(), Some(&Color32::GRAY));
+            assert_eq!(colors.last(), Some(&Color32::RED));
+        });
+    }
+
+    #[test]
+    fn browser_tabs_contain_what_their_neighbours_dont_cover() {
+        let rect = Rect::from_min_size(pos2(100.0, 0.0), vec2(100.0, 20.0));
+        let contains = |covered, pos| browser_tab_contains(rect, 10.0, 0.0, false, covered, pos);
+
+        // The slanted sides narrow the tab towards its top.
+        assert!(contains([false; 2], pos2(102.0, 19.0)));
+        assert!(!contains([false; 2], pos2(102.0, 1.0)));
+        assert!(contains([false; 2], pos2(150.0, 1.0)));
+        assert!(!contains([false; 2], pos2(250.0, 10.0)));
+
+        // The bottom of its sides is below the tabs painted over it.
+        assert!(!contains([false, true], pos2(198.0, 19.0)));
+        assert!(contains([true, false], pos2(198.0, 19.0)));
+        assert!(!contains([true, false], pos2(102.0, 19.0)));
+
+        // Tabs below their bodies are upside down.
+        assert!(browser_tab_contains(
+            rect,
+            10.0,
+            0.0,
+            true,
+            [false; 2],
+            pos2(102.0, 1.0)
+        ));
+    }
+
+    /// Shows its own drag preview if `custom` is set.
+    #[cfg(feature = "testing")]
+    struct PreviewViewer {
+        custom: bool,
+    }
+
+    #[cfg(feature = "testing")]
+    impl TabViewer for PreviewViewer {
+        type Tab = String;
+
+        fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+            tab.as_str().into()
+        }
+
+        fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+            ui.label(tab.as_str());
+        }
+
+        fn drag_preview_ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) -> bool {
+            if self.custom {
+                ui.label(format!("Moving {tab}"));
+            }
+            self.custom
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn dragged_tabs_remember_where_their_preview_is() {
+        use crate::{testing::DockHarness, DockState};
+
+        for (position, custom) in [
+            (None, false),
+            (None, true),
+            (Some(TabBarPosition::Left), false),
+            (Some(TabBarPosition::Left), true),
+        ] {
+            let mut dock_state = DockState::new(vec!["A".to_owned(), "B".to_owned()]);
+            dock_state.main_surface_mut()[NodeIndex::root()]
+                .get_leaf_mut()
+                .unwrap()
+                .set_tab_bar_position(position);
+            let mut harness = DockHarness::new(dock_state, PreviewViewer { custom });
+            let rect = harness
+                .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)))
+                .unwrap();
+            let from = rect.left_top() + vec2(10.0, 10.0);
+            let to = from + vec2(200.0, 150.0);
+            harness.drag_and_hold(from, to);
+
+            // The preview keeps the tab where it was grabbed, which is where the drag started.
+            let state = harness
+                .ctx
+                .data_mut(|d| d.get_temp::<State>(Id::new("egui_dock::DockArea")))
+                .unwrap();
+            let grabbed = state.drag_start.unwrap();
+            assert_eq!(
+                state.dragged_tab_offset,
+                to - grabbed,
+                "{position:?}, {custom}"
+            );
+            harness.release();
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use egui::{Sense, Ui};
+use egui::{Sense, Ui, UiBuilder};
 
 use crate::{
     dock_area::{
         drag_and_drop::{HoverData, TreeComponent},
         state::State,
     },
-    DockArea, SurfaceIndex, TabViewer,
+    DockArea, NodeIndex, SurfaceIndex, TabViewer,
 };
 
 impl<Tab> DockArea<'_, Tab> {
@@ -20,7 +20,9 @@ impl<Tab> DockArea<'_, Tab> {
         if self.dock_state.main_surface().is_empty() {
             let rect = ui.available_rect_before_wrap();
             let response = ui.allocate_rect(rect, Sense::hover());
-            if response.contains_pointer() {
+            if response.contains_pointer()
+                && self.is_drag_allowed_on(state, (surf_index, NodeIndex::root()), tab_viewer)
+            {
                 ui.memory_mut(|mem| {
                     mem.data.insert_temp(
                         self.id.with("hover_data"),
@@ -28,10 +30,25 @@ impl<Tab> DockArea<'_, Tab> {
                             rect,
                             dst: TreeComponent::Surface(surf_index),
                             tab: None,
+                            edge: None,
                         }),
                     );
                 });
             }
+
+            let tabs = tab_viewer.on_empty_main_surface(
+                &mut ui.new_child(
+                    UiBuilder::new()
+                        .max_rect(rect)
+                        .id_salt("empty_main_surface"),
+                ),
+            );
+            if !tabs.is_empty() {
+                for tab in tabs {
+                    self.dock_state.main_surface_mut().push_to_first_leaf(tab);
+                }
+                self.new_focused = Some((surf_index, NodeIndex::root()));
+            }
             return;
         }
 
@@ -1,23 +1,40 @@
 use egui::{
-    CentralPanel, Color32, Context, CornerRadius, CursorIcon, EventFilter, Frame, Key, Pos2, Rect,
-    Sense, StrokeKind, Ui, Vec2,
+    ahash::{HashMap, HashSet},
+    lerp, CentralPanel, Color32, Context, CornerRadius, CursorIcon, Direction, EventFilter, Frame,
+    Id, Key, Pos2, Rect, Sense, StrokeKind, Ui, UiBuilder, Vec2,
 };
 
 use duplicate::duplicate;
 use paste::paste;
 
-use super::{drag_and_drop::TreeComponent, state::State, tab_removal::TabRemoval};
+use super::{
+    drag_and_drop::{
+        make_overlay_painter, DragData, DragDropState, DragSource, HoverData, TreeComponent,
+    },
+    nested,
+    state::{DragRevert, State},
+    tab_removal::TabRemoval,
+};
 use crate::dock_area::tab_removal::ForcedRemoval;
 use crate::tab_viewer::OnCloseResponse;
 use crate::{
-    utils::{expand_to_pixel, fade_dock_style, map_to_pixel},
-    AllowedSplits, DockArea, Node, NodeIndex, OverlayType, Style, SurfaceIndex, TabDestination,
-    TabViewer,
+    utils::{fade_dock_style, map_to_pixel, profile_scope},
+    AllowedSplits, BridgedTab, DockArea, DockEvent, DockResponse, ExternalPayload, LeafNode,
+    NestedDrags, Node, NodeIndex, OverlayType, SeparatorSnap, Split, Style, SurfaceIndex,
+    TabDestination, TabId, TabIndex, TabInsert, TabViewer,
 };
 
+mod accessibility;
+mod auto_hide;
+mod keyboard_resize;
+mod layout_animation;
 mod leaf;
 mod main_surface;
+mod overview;
+mod side_tab_bar;
+mod tab_switcher;
 mod window_surface;
+mod window_taskbar;
 
 impl<Tab> DockArea<'_, Tab> {
     /// Show the `DockArea` at the top level.
@@ -46,64 +63,186 @@ impl<Tab> DockArea<'_, Tab> {
     ///
     /// So you can't use the [`CentralPanel::show`] when using `DockArea`'s one.
     ///
+    /// Returns a [`DockResponse`] describing what the user did with the dock this frame.
+    ///
     /// See also [`show_inside`](Self::show_inside).
     #[inline]
-    pub fn show(self, ctx: &Context, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+    pub fn show(self, ctx: &Context, tab_viewer: &mut impl TabViewer<Tab = Tab>) -> DockResponse {
         CentralPanel::default()
             .frame(
                 Frame::central_panel(&ctx.style())
                     .inner_margin(0.)
                     .fill(Color32::TRANSPARENT),
             )
-            .show(ctx, |ui| {
-                self.show_inside(ui, tab_viewer);
-            });
+            .show(ctx, |ui| self.show_inside(ui, tab_viewer))
+            .inner
     }
 
     /// Shows the docking hierarchy inside a [`Ui`].
     ///
+    /// Returns a [`DockResponse`] describing what the user did with the dock this frame.
+    ///
     /// See also [`show`](Self::show).
-    pub fn show_inside(mut self, ui: &mut Ui, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
-        self.style
+    pub fn show_inside(
+        mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> DockResponse {
+        profile_scope!("DockArea::show_inside");
+        let style = self
+            .style
             .get_or_insert(Style::from_egui(ui.style().as_ref()));
-        self.window_bounds.get_or_insert(ui.ctx().screen_rect());
+        if ui.layout().main_dir() == Direction::RightToLeft {
+            style.tab_bar.right_to_left = true;
+        }
+        let bounds = if self.clip_to_parent {
+            ui.available_rect_before_wrap().intersect(ui.clip_rect())
+        } else {
+            ui.ctx().screen_rect()
+        };
+        self.window_bounds.get_or_insert(bounds);
+        self.native_windows &= !ui.ctx().embed_viewports()
+            && !ui.ctx().data(|d| {
+                d.get_temp(self.id.with("native_windows_unsupported"))
+                    .unwrap_or(false)
+            });
 
+        let (id, depth) = nested::enter(ui, self.id);
+        self.id = id;
         let mut state = State::load(ui.ctx(), self.id);
+        self.refocus_leaf = state.refocus_leaf.take();
+        if self.tab_previews {
+            state.previews.receive(ui.ctx(), self.id);
+        }
+        self.dock_state.sync_tab_ids();
+        self.dock_state.sync_window_order();
+        for tab_id in nested::take_accepted(ui.ctx(), self.id) {
+            if let Some(location) = self.dock_state.find_by_id(tab_id) {
+                self.dock_state.remove_tab(location);
+            }
+        }
+        if self.nested_drags == NestedDrags::Bridged {
+            nested::register_bridged(ui.ctx(), self.id, depth, ui.available_rect_before_wrap());
+        }
+        self.resize_focused_leaf(ui);
+        let mut surfaces_before = std::mem::take(&mut state.scratch.surfaces);
+        self.dock_state
+            .valid_surface_indices_into(&mut surfaces_before);
+        let focused_before = self.focused_tab(tab_viewer);
+        self.close_resolved_tabs(tab_viewer);
 
         // Delay hover position one frame. On touch screens hover_pos() is None when any_released()
         if !ui.input(|i| i.pointer.any_released()) {
             state.last_hover_pos = ui.input(|i| i.pointer.hover_pos());
         }
 
-        let (drag_data, hover_data) = ui.memory_mut(|mem| {
-            (
-                mem.data.remove_temp(self.id.with("drag_data")).flatten(),
-                mem.data.remove_temp(self.id.with("hover_data")).flatten(),
-            )
-        });
+        let (mut drag_data, mut hover_data): (Option<DragData>, Option<HoverData>) =
+            ui.memory_mut(|mem| {
+                (
+                    mem.data.remove_temp(self.id.with("drag_data")).flatten(),
+                    mem.data.remove_temp(self.id.with("hover_data")).flatten(),
+                )
+            });
+
+        // Bridged dock areas nested in this one take care of the drops onto them.
+        if state
+            .last_hover_pos
+            .is_some_and(|pos| nested::is_over_nested_bridged(ui.ctx(), self.id, depth, pos))
+        {
+            hover_data = None;
+        }
+
+        let animation = self.style.as_ref().unwrap().animation;
+        if state.drag_revert.is_some_and(|revert| {
+            ui.input(|i| i.time) - revert.start_time >= f64::from(animation.duration)
+        }) {
+            state.drag_revert = None;
+        }
+        if drag_data.as_ref().is_some_and(|drag| drag.src.is_some())
+            && self
+                .keybindings
+                .cancel_drag
+                .is_some_and(|shortcut| ui.input_mut(|i| i.consume_shortcut(&shortcut)))
+        {
+            self.cancel_drag(ui, &mut state, drag_data.as_ref().unwrap());
+            drag_data = None;
+            hover_data = None;
+        }
+
+        if let Some(DragData {
+            src: Some(DragSource::Tab(surface, node, tab)),
+            ..
+        }) = drag_data
+        {
+            self.response.dragged_tab = Some((surface, node, tab));
+            if self.nested_drags == NestedDrags::Bridged {
+                self.bridge_drag((surface, node, tab), tab_viewer, ui.ctx());
+            }
+        }
+
+        // Payloads dragged in from outside of the dock are handled like dragged tabs.
+        let drag_data = if drag_data.is_none() && self.is_external_drag_active(ui) {
+            if let Some(pointer) = state.last_hover_pos {
+                state.drag_start.get_or_insert(pointer);
+            }
+            hover_data.as_ref().map(|hover| DragData {
+                src: None,
+                rect: hover.rect,
+            })
+        } else {
+            if state.dnd.as_ref().is_some_and(|dnd| dnd.drag.src.is_none()) {
+                // The external payload was cancelled or dragged away.
+                state.reset_drag();
+            }
+            drag_data
+        };
+
+        if self.native_windows && hover_data.is_none() {
+            self.detach_outside_drop(ui, &state, &drag_data, tab_viewer);
+        }
 
         if let (Some(source), Some(hover)) = (drag_data, hover_data) {
             let style = self.style.as_ref().unwrap();
             state.set_drag_and_drop(source, hover, ui.ctx(), style);
             let tab_dst = self.show_drag_drop_overlay(ui, &mut state, tab_viewer);
-            if ui.input(|i| i.pointer.primary_released()) {
-                if let Some(destination) = tab_dst {
-                    let source = {
-                        match state.dnd.as_ref().unwrap().drag.src {
-                            TreeComponent::Tab(src_surf, src_node, src_tab) => {
-                                (src_surf, src_node, src_tab)
+            self.response.drop_destination = tab_dst;
+            if let Some(destination) = tab_dst {
+                match state.dnd.as_ref().unwrap().drag.src {
+                    Some(DragSource::Tab(src_surf, src_node, src_tab)) => {
+                        if ui.input(|i| i.pointer.primary_released()) {
+                            self.unselect_undraggable_tabs(src_surf, src_node, tab_viewer);
+                            self.dock_state.move_tab_with_selection(
+                                (src_surf, src_node, src_tab),
+                                destination,
+                            );
+                            if let TabDestination::Node(_, _, TabInsert::Split(_)) = destination {
+                                // The leaf created by the split is focused by `move_tab`.
+                                if let Some((surface, node)) = self.dock_state.focused_leaf() {
+                                    self.response
+                                        .events
+                                        .push(DockEvent::NodeSplit(surface, node));
+                                }
                             }
-                            _ => todo!(
-                                "collections of tabs, like nodes and surfaces can't be docked (yet)"
-                            ),
                         }
-                    };
-                    self.dock_state.move_tab(source, destination);
+                    }
+                    Some(DragSource::Node(src_surf, src_node)) => {
+                        if ui.input(|i| i.pointer.primary_released()) {
+                            self.dock_state.move_leaf((src_surf, src_node), destination);
+                            if let TabDestination::Node(_, _, TabInsert::Split(_)) = destination {
+                                if let Some((surface, node)) = self.dock_state.focused_leaf() {
+                                    self.response
+                                        .events
+                                        .push(DockEvent::NodeSplit(surface, node));
+                                }
+                            }
+                        }
+                    }
+                    None => self.drop_external_payload(ui, destination, tab_viewer),
                 }
             }
         }
 
-        if ui.input(|i| i.pointer.primary_released()) {
+        if ui.input(|i| i.pointer.primary_released() || !i.raw.dropped_files.is_empty()) {
             state.reset_drag();
         }
 
@@ -118,28 +257,52 @@ impl<Tab> DockArea<'_, Tab> {
             })
         };
 
-        for &surface_index in self.dock_state.valid_surface_indices().iter() {
-            self.show_surface_inside(
-                surface_index,
-                ui,
-                tab_viewer,
-                &mut state,
-                fade_style.as_ref().map(|(style, factor)| {
-                    (style, *factor, fade_surface.unwrap_or(SurfaceIndex::main()))
-                }),
-            );
+        // Tabs are told they're open or active before they're shown, so that they can load what they show.
+        self.tab_lifecycle(&mut state, tab_viewer);
+
+        // Shown first, so that it takes the keys it uses before the tabs get them.
+        self.show_tab_switcher(ui, tab_viewer, &mut state);
+        if self.dock_state.is_overview_open() {
+            self.show_overview(ui, tab_viewer, &state);
+        } else {
+            for surface_index in (0..self.dock_state.surfaces_count()).map(SurfaceIndex) {
+                if !self.dock_state.is_surface_valid(surface_index) {
+                    continue;
+                }
+                self.show_surface_inside(
+                    surface_index,
+                    ui,
+                    tab_viewer,
+                    &mut state,
+                    fade_style.as_ref().map(|(style, factor)| {
+                        (style, *factor, fade_surface.unwrap_or(SurfaceIndex::main()))
+                    }),
+                );
+            }
+        }
+
+        self.stack_windows(ui.ctx());
+        self.update_hidden_tabs(tab_viewer);
+        if self.tab_previews {
+            self.update_tab_previews(ui, &mut state, tab_viewer);
         }
 
         for removal in self.to_remove.drain(..).rev() {
             match removal {
                 TabRemoval::Tab(surface, node, tab, ForcedRemoval(is_forced)) => {
                     if is_forced {
-                        self.dock_state.remove_tab((surface, node, tab));
+                        self.dock_state.close_tab((surface, node, tab));
+                        self.response
+                            .events
+                            .push(DockEvent::TabClosed(surface, node, tab));
                     } else {
                         let leaf = &mut self.dock_state[surface][node].get_leaf_mut().unwrap();
                         match tab_viewer.on_close(&mut leaf.tabs[tab.0]) {
                             OnCloseResponse::Close => {
-                                self.dock_state.remove_tab((surface, node, tab));
+                                self.dock_state.close_tab((surface, node, tab));
+                                self.response
+                                    .events
+                                    .push(DockEvent::TabClosed(surface, node, tab));
                             }
                             OnCloseResponse::Focus => {
                                 leaf.active = tab;
@@ -148,35 +311,77 @@ impl<Tab> DockArea<'_, Tab> {
                             OnCloseResponse::Ignore => {
                                 // no-op
                             }
+                            OnCloseResponse::Defer => {
+                                let tab_id = tab_viewer.id(&mut leaf.tabs[tab.0]);
+                                self.dock_state.defer_close(tab_id);
+                            }
                         }
                     }
                 }
                 TabRemoval::Node(surface, node) => {
                     let mut all_tabs_are_closable = true;
+                    let mut deferred = Vec::new();
                     for tab in self.dock_state[surface][node].iter_tabs_mut() {
                         if !(tab_viewer.is_closeable(tab)
-                            && matches!(tab_viewer.on_close(tab), OnCloseResponse::Close))
+                            && Self::ask_to_close(tab_viewer, tab, &mut deferred))
                         {
                             all_tabs_are_closable = false;
                         }
                     }
+                    for tab_id in deferred {
+                        self.dock_state.defer_close(tab_id);
+                    }
                     if all_tabs_are_closable {
-                        self.dock_state.remove_leaf((surface, node));
+                        let tabs_count = self.dock_state[surface][node].tabs_count();
+                        self.response.events.extend(
+                            (0..tabs_count)
+                                .map(|tab| DockEvent::TabClosed(surface, node, TabIndex(tab))),
+                        );
+                        self.dock_state.close_leaf((surface, node));
                     }
                 }
                 TabRemoval::Window(surface) => {
+                    if !tab_viewer.on_window_close(surface) {
+                        continue;
+                    }
                     let mut all_tabs_are_closable = true;
+                    let mut deferred = Vec::new();
                     for node in self.dock_state[surface].iter_mut() {
                         for tab in node.iter_tabs_mut() {
                             if !(tab_viewer.is_closeable(tab)
-                                && matches!(tab_viewer.on_close(tab), OnCloseResponse::Close))
+                                && Self::ask_to_close(tab_viewer, tab, &mut deferred))
                             {
                                 all_tabs_are_closable = false;
                             }
                         }
                     }
+                    for tab_id in deferred {
+                        self.dock_state.defer_close(tab_id);
+                    }
                     if all_tabs_are_closable {
-                        self.dock_state.remove_surface(surface);
+                        let tree = &self.dock_state[surface];
+                        for node in tree.breadth_first_index_iter() {
+                            self.response.events.extend(
+                                (0..tree[node].tabs_count())
+                                    .map(|tab| DockEvent::TabClosed(surface, node, TabIndex(tab))),
+                            );
+                        }
+                        // The leaves are closed from the last one, so that the first one is reopened first.
+                        while let Some(node) =
+                            self.dock_state.get_surface(surface).and_then(|surface| {
+                                let tree = surface.node_tree()?;
+                                tree.breadth_first_index_iter()
+                                    .filter(|&node| {
+                                        tree[node].get_leaf().is_some_and(|leaf| !leaf.is_central())
+                                    })
+                                    .last()
+                            })
+                        {
+                            self.dock_state.close_leaf((surface, node));
+                        }
+                        if self.dock_state.is_surface_valid(surface) {
+                            self.dock_state.remove_surface(surface);
+                        }
                     }
                 }
             }
@@ -199,7 +404,80 @@ impl<Tab> DockArea<'_, Tab> {
             self.dock_state.set_focused_node_and_surface(focused);
         }
 
+        // A leaf closed along with its last tab hands the keyboard focus over to the focused leaf.
+        if let Some((surface, node)) = state.refocus_leaf {
+            let has_tabs = self
+                .dock_state
+                .get_surface(surface)
+                .and_then(|surface| surface.node_tree())
+                .is_some_and(|tree| node.0 < tree.len() && tree[node].tabs_count() > 0);
+            if !has_tabs {
+                state.refocus_leaf = self.dock_state.focused_leaf();
+            }
+        }
+
+        // The tab is found by its ID, as removing tabs above may have moved it around.
+        if let Some((surface_index, node_index, tab_index)) = std::mem::take(&mut self.to_float)
+            .pop()
+            .and_then(|tab_id| self.dock_state.find_by_id(tab_id))
+        {
+            let restorable = self
+                .dock_state
+                .get_window_state(surface_index)
+                .is_some_and(|window_state| window_state.restore_location().is_some());
+            if restorable {
+                self.dock_state.redock_window(surface_index);
+            } else if let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf() {
+                let body_rect = leaf.viewport;
+                self.dock_state
+                    .float_tab((surface_index, node_index, tab_index), body_rect);
+            }
+        }
+
+        // The leaves are found by their IDs, as removing tabs above may have moved them around.
+        for (node_id, split) in std::mem::take(&mut self.to_split) {
+            let Some((surface_index, node_index)) = self.dock_state.find_node_by_id(node_id) else {
+                continue;
+            };
+            let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut() else {
+                continue;
+            };
+            let active = leaf.active;
+            let Some(tab) = leaf.tabs.get_mut(active.0) else {
+                continue;
+            };
+            let behavior = tab_viewer.on_split(tab);
+            if let Some(new_leaf) =
+                self.dock_state
+                    .split_tab((surface_index, node_index, active), split, behavior)
+            {
+                self.response
+                    .events
+                    .push(DockEvent::NodeSplit(surface_index, new_leaf));
+            }
+        }
+
+        self.window_events(&surfaces_before);
+        state.scratch.surfaces = surfaces_before;
+        let focused_after = self.focused_tab(tab_viewer);
+        if let Some((location, id)) = focused_after {
+            if focused_before.is_none_or(|(_, id_before)| id_before != id) {
+                let (surface, node, tab) = location;
+                self.response
+                    .events
+                    .push(DockEvent::TabFocused(surface, node, tab));
+            }
+        }
+
         state.store(ui.ctx(), self.id);
+
+        // Nested dock areas may still drop the payload, so only the outermost one cancels it.
+        if depth == 0 && ui.input(|i| i.pointer.primary_released()) {
+            ExternalPayload::cancel_drag(ui.ctx());
+        }
+        nested::exit(ui.ctx());
+
+        self.response
     }
 
     /// Returns some when windows are fading, and what surface index is being hovered over
@@ -223,22 +501,425 @@ impl<Tab> DockArea<'_, Tab> {
         })
     }
 
-    /// Resolve where a dragged tab would land given it's dropped this frame, returns `None` when the resulting drop is an invalid move.
-    fn show_drag_drop_overlay(
+    /// Whether a tab dragged from `drag_start` to `pointer` may be torn off into a window, see [`DockArea::tear_off`].
+    fn can_tear_off(&self, ui: &Ui, drag_start: Option<Pos2>, pointer: Pos2) -> bool {
+        self.tear_off.allowed
+            && ui.input(|i| i.modifiers.contains(self.tear_off.modifiers))
+            && drag_start.is_none_or(|start| start.distance(pointer) >= self.tear_off.min_distance)
+    }
+
+    /// Moves a tab dragged outside of the main viewport into a native window of its own once it's released.
+    fn detach_outside_drop(
+        &mut self,
+        ui: &Ui,
+        state: &State,
+        drag_data: &Option<DragData>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(DragData {
+            src: Some(DragSource::Tab(surface, node, tab)),
+            rect,
+        }) = *drag_data
+        else {
+            return;
+        };
+        let Some(pointer) = ui
+            .input(|i| i.pointer.latest_pos())
+            .filter(|pos| !ui.ctx().screen_rect().contains(*pos))
+        else {
+            return;
+        };
+        let Node::Leaf(leaf) = &mut self.dock_state[surface][node] else {
+            unreachable!("tab drags can only come from leaf nodes")
+        };
+        if !tab_viewer.allowed_in_windows(&mut leaf.tabs[tab.0])
+            || !self.can_tear_off(ui, state.drag_start, pointer)
+        {
+            return;
+        }
+
+        let destination = TabDestination::Window(Rect::from_min_size(pointer, rect.size()));
+        self.response.drop_destination = Some(destination);
+        if ui.input(|i| i.pointer.primary_released()) {
+            self.unselect_undraggable_tabs(surface, node, tab_viewer);
+            self.dock_state
+                .move_tab_with_selection((surface, node, tab), destination);
+        }
+    }
+
+    /// Unselects the tabs of a leaf which can't be dragged, so that they stay behind when the selection is moved.
+    fn unselect_undraggable_tabs(
+        &mut self,
+        surface_index: SurfaceIndex,
+        node_index: NodeIndex,
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut() else {
+            return;
+        };
+        for tab_index in leaf.selected_tabs() {
+            if !tab_viewer.is_draggable(&leaf.tabs[tab_index.0]) {
+                leaf.set_tab_selected(tab_index, false);
+            }
+        }
+    }
+
+    /// Calls [`TabViewer::on_hidden_update`] for every tab which wasn't shown this frame.
+    fn update_hidden_tabs(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        let visible_tabs = self.visible_tab_ids();
+        for (_, leaf) in self.dock_state.iter_leaves_mut() {
+            leaf.sync_tab_ids();
+            for (tab, tab_id) in leaf.tabs.iter_mut().zip(&leaf.tab_ids) {
+                if !visible_tabs.contains(tab_id) {
+                    tab_viewer.on_hidden_update(tab);
+                }
+            }
+        }
+    }
+
+    /// The IDs of the tabs in [`DockResponse::visible_tabs`], to look them up without going through the list.
+    fn visible_tab_ids(&self) -> HashSet<TabId> {
+        self.response
+            .visible_tabs
+            .iter()
+            .filter_map(|&location| self.dock_state.tab_id(location))
+            .collect()
+    }
+
+    /// Calls the lifecycle hooks of the tabs which are new, or became or stopped being active, since they were last
+    /// shown, and forgets the tabs which are gone.
+    fn tab_lifecycle(&mut self, state: &mut State, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        let mut tab_ids = HashSet::default();
+        for (_, leaf) in self.dock_state.iter_leaves_mut() {
+            Self::leaf_lifecycle(&mut state.tab_lifecycle, leaf, tab_viewer);
+            tab_ids.extend(leaf.tab_ids.iter().copied());
+        }
+        state
+            .tab_lifecycle
+            .retain(|tab_id, _| tab_ids.contains(tab_id));
+    }
+
+    /// Calls the lifecycle hooks of the tabs of `leaf` which are new, or became or stopped being active, since
+    /// they were last recorded in `lifecycle`.
+    pub(super) fn leaf_lifecycle(
+        lifecycle: &mut HashMap<TabId, bool>,
+        leaf: &mut LeafNode<Tab>,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        // Tabs may have been pushed onto the leaf since the start of the frame, e.g. when dropping them.
+        leaf.sync_tab_ids();
+        let active = leaf.active;
+        for (tab_index, (tab, &tab_id)) in leaf.tabs.iter_mut().zip(&leaf.tab_ids).enumerate() {
+            let is_active = tab_index == active.0;
+            match lifecycle.insert(tab_id, is_active) {
+                None => {
+                    tab_viewer.on_open(tab);
+                    if is_active {
+                        tab_viewer.on_activate(tab);
+                    }
+                }
+                Some(was_active) if was_active != is_active => {
+                    if is_active {
+                        tab_viewer.on_activate(tab);
+                    } else {
+                        tab_viewer.on_deactivate(tab);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Requests snapshots of the tabs shown this frame and hands out the previews of all tabs.
+    fn update_tab_previews(
         &mut self,
         ui: &Ui,
         state: &mut State,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let mut shown = Vec::new();
+        let visible_tabs = self.visible_tab_ids();
+        let mut tab_ids = HashSet::default();
+        for &surface_index in self.dock_state.valid_surface_indices().iter() {
+            for node_index in self.dock_state[surface_index].breadth_first_index_iter() {
+                let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut() else {
+                    continue;
+                };
+                leaf.sync_tab_ids();
+                let tabs = leaf.tabs.iter_mut().zip(&leaf.tab_ids).enumerate();
+                for (tab_index, (tab, dock_tab_id)) in tabs {
+                    let location = (surface_index, node_index, TabIndex(tab_index));
+                    let tab_id = tab_viewer.id(tab);
+                    tab_ids.insert(tab_id);
+                    if let Some(preview) = state.previews.get(tab_id) {
+                        self.response.tab_previews.push((location, preview));
+                    }
+                    // Native windows aren't part of the screenshot of this viewport.
+                    if visible_tabs.contains(dock_tab_id)
+                        && (surface_index.is_main() || !self.native_windows)
+                    {
+                        shown.push((tab_id, leaf.viewport));
+                    }
+                }
+            }
+        }
+        state.previews.request(ui.ctx(), self.id, shown);
+        state.previews.retain(&tab_ids);
+    }
+
+    /// Focuses the tab at `location` and its leaf.
+    fn focus_tab(
+        &mut self,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+    ) {
+        self.dock_state
+            .set_focused_node_and_surface((surface_index, node_index));
+        self.dock_state
+            .set_active_tab((surface_index, node_index, tab_index));
+    }
+
+    /// Calls [`TabViewer::on_close`] for a tab which is closed along with its leaf or window.
+    ///
+    /// Returns `true` if the tab can be closed, and collects the ID of the tab into `deferred`
+    /// if its closing was deferred.
+    fn ask_to_close(
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        tab: &mut Tab,
+        deferred: &mut Vec<Id>,
+    ) -> bool {
+        match tab_viewer.on_close(tab) {
+            OnCloseResponse::Close => true,
+            OnCloseResponse::Focus | OnCloseResponse::Ignore => false,
+            OnCloseResponse::Defer => {
+                deferred.push(tab_viewer.id(tab));
+                false
+            }
+        }
+    }
+
+    /// Closes the tabs whose deferred close requests were resolved with [`DockState::resolve_close`](crate::DockState::resolve_close).
+    fn close_resolved_tabs(&mut self, tab_viewer: &mut impl TabViewer<Tab = Tab>) {
+        for tab_id in self.dock_state.take_resolved_closes() {
+            // Each closed tab may shift the others around, so they're looked up one at a time.
+            let mut location = None;
+            'search: for &surface_index in self.dock_state.valid_surface_indices().iter() {
+                for node_index in self.dock_state[surface_index].breadth_first_index_iter() {
+                    let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut()
+                    else {
+                        continue;
+                    };
+                    if let Some(tab_index) = leaf
+                        .tabs
+                        .iter_mut()
+                        .position(|tab| tab_viewer.id(tab) == tab_id)
+                    {
+                        location = Some((surface_index, node_index, TabIndex(tab_index)));
+                        break 'search;
+                    }
+                }
+            }
+            if let Some((surface, node, tab)) = location {
+                self.dock_state.close_tab((surface, node, tab));
+                self.response
+                    .events
+                    .push(DockEvent::TabClosed(surface, node, tab));
+            }
+        }
+    }
+
+    /// Returns the location and ID of the active tab of the focused leaf, if any.
+    fn focused_tab(
+        &mut self,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Option<((SurfaceIndex, NodeIndex, TabIndex), Id)> {
+        let location @ (surface_index, node_index, tab_index) = self.dock_state.focused_tab()?;
+        let tab = &mut self.dock_state[surface_index][node_index].tabs_mut()?[tab_index.0];
+        Some((location, tab_viewer.id(tab)))
+    }
+
+    /// Records which windows were created or removed since `surfaces_before` was taken.
+    fn window_events(&mut self, surfaces_before: &[SurfaceIndex]) {
+        for &surface in surfaces_before {
+            if !self.dock_state.is_surface_valid(surface) {
+                self.response.events.push(DockEvent::WindowClosed(surface));
+            }
+        }
+        for surface in (0..self.dock_state.surfaces_count()).map(SurfaceIndex) {
+            if self.dock_state.is_surface_valid(surface) && !surfaces_before.contains(&surface) {
+                self.response.events.push(DockEvent::WindowCreated(surface));
+            }
+        }
+    }
+
+    /// Offers the tab being dragged to the other dock areas as a [`BridgedTab`].
+    fn bridge_drag(
+        &mut self,
+        (surface, node, tab): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        ctx: &Context,
+    ) {
+        let Some(leaf) = self.dock_state[surface][node].get_leaf_mut() else {
+            return;
+        };
+        let Some(tab_id) = leaf.tab_id(tab) else {
+            return;
+        };
+        let title = tab_viewer.title(&mut leaf.tabs[tab.0]).text().to_string();
+        ExternalPayload::start_drag(
+            ctx,
+            BridgedTab {
+                dock_area: self.id,
+                tab: tab_id,
+                title,
+            },
+        );
+    }
+
+    /// Returns `true` if an [`ExternalPayload`] is being dragged over the dock.
+    fn is_external_drag_active(&self, ui: &Ui) -> bool {
+        let dragging_custom = ExternalPayload::dragged(ui.ctx()).is_some()
+            && ui.input(|i| i.pointer.any_down() || i.pointer.any_released());
+        let dragging_files = self.accept_dropped_files
+            && ui.input(|i| !i.raw.hovered_files.is_empty() || !i.raw.dropped_files.is_empty());
+        dragging_custom || dragging_files
+    }
+
+    /// Lets the [`TabViewer`] create a tab at `destination` if an [`ExternalPayload`] was dropped this frame.
+    fn drop_external_payload(
+        &mut self,
+        ui: &Ui,
+        destination: TabDestination,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        let dropped_files = ui.input(|i| i.raw.dropped_files.clone());
+        let payload = if self.accept_dropped_files && !dropped_files.is_empty() {
+            ExternalPayload::Files(dropped_files)
+        } else if ui.input(|i| i.pointer.primary_released()) {
+            match ExternalPayload::dragged(ui.ctx()) {
+                Some(payload) => ExternalPayload::Custom(payload),
+                None => return,
+            }
+        } else {
+            return;
+        };
+        // Windows are shrunk to the size of their preview, like windows detached from the main surface.
+        let destination = match destination {
+            TabDestination::Window(rect) => {
+                TabDestination::Window(Rect::from_min_size(rect.min, rect.size() * 0.8))
+            }
+            destination => destination,
+        };
+        if let Some(tab) = tab_viewer.on_external_drop(&payload, destination) {
+            if let Some(bridged) = payload.downcast_ref::<BridgedTab>() {
+                nested::accept(ui.ctx(), bridged.clone());
+            }
+            let (surface, node) = self.dock_state.insert_tab(tab, destination);
+            if let TabDestination::Node(_, _, TabInsert::Split(_)) = destination {
+                self.response
+                    .events
+                    .push(DockEvent::NodeSplit(surface, node));
+            }
+        }
+    }
+
+    /// Returns `false` if the tab being dragged can't be dropped onto `node_index` of `surface_index`,
+    /// because it's not allowed in windows or in that node, or because the surface is shown in a native window.
+    fn is_drag_allowed_on(
+        &mut self,
+        state: &State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
         tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) -> bool {
+        if (self.native_windows && !surface_index.is_main())
+            || self.is_blocked_by_modal(surface_index)
+        {
+            return false;
+        }
+        match &state.dnd {
+            Some(DragDropState {
+                drag: DragData { src: Some(src), .. },
+                ..
+            }) => match *src {
+                DragSource::Tab(d_surf, d_node, d_tab) => {
+                    if let Node::Leaf(leaf) = &mut self.dock_state[d_surf][d_node] {
+                        let tab = &mut leaf.tabs[d_tab.0];
+                        (tab_viewer.allowed_in_windows(tab)
+                            || surface_index == SurfaceIndex::main())
+                            && ((d_surf, d_node) == (surface_index, node_index)
+                                || tab_viewer.allowed_in_node(tab, surface_index, node_index))
+                    } else {
+                        true
+                    }
+                }
+                DragSource::Node(d_surf, d_node) => {
+                    // Dropping a leaf onto itself doesn't move it anywhere.
+                    (d_surf, d_node) != (surface_index, node_index)
+                        && self.dock_state[d_surf][d_node]
+                            .get_leaf_mut()
+                            .is_some_and(|leaf| {
+                                leaf.tabs.iter_mut().all(|tab| {
+                                    (tab_viewer.allowed_in_windows(tab)
+                                        || surface_index == SurfaceIndex::main())
+                                        && tab_viewer.allowed_in_node(
+                                            tab,
+                                            surface_index,
+                                            node_index,
+                                        )
+                                })
+                            })
+                }
+            },
+            _ => true,
+        }
+    }
+
+    /// Whether a [modal](crate::WindowState::set_modal) window other than the surface is open.
+    pub(super) fn is_blocked_by_modal(&self, surface_index: SurfaceIndex) -> bool {
+        self.dock_state
+            .modal_window()
+            .is_some_and(|modal| modal != surface_index)
+    }
+
+    /// Stops the drag of `drag` until the pointer is released, and slides the dragged tab back to its place.
+    fn cancel_drag(&self, ui: &Ui, state: &mut State, drag: &DragData) {
+        if let (Some(DragSource::Tab(..)), Some(id)) = (&drag.src, ui.ctx().dragged_id()) {
+            state.drag_revert = Some(DragRevert {
+                id,
+                offset: state.dragged_tab_offset,
+                start_time: ui.input(|i| i.time),
+            });
+        }
+        ui.ctx().stop_dragging();
+        state.reset_drag();
+    }
+
+    /// The rect drag previews are kept inside of, which is the rect windows are kept inside of if the
+    /// [`DockArea`] is clipped to its parent.
+    pub(super) fn drag_preview_bounds(&self, ctx: &Context) -> Rect {
+        if self.clip_to_parent {
+            self.window_bounds.unwrap()
+        } else {
+            ctx.screen_rect()
+        }
+    }
+
+    /// Resolve where a dragged tab would land given it's dropped this frame, returns `None` when the resulting drop is an invalid move.
+    fn show_drag_drop_overlay(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
     ) -> Option<TabDestination> {
+        profile_scope!("DockArea::show_drag_drop_overlay");
         let drag_state = state.dnd.as_mut().unwrap();
         let style = self.style.as_ref().unwrap();
 
         let deserted_node = {
             match (
-                drag_state.drag.src.node_address(),
+                drag_state.drag.src.as_ref().map(|src| src.node_address()),
                 drag_state.hover.dst.node_address(),
             ) {
-                ((src_surf, Some(src_node)), (dst_surf, Some(dst_node))) => {
+                (Some((src_surf, src_node)), (dst_surf, Some(dst_node))) => {
                     src_surf == dst_surf
                         && src_node == dst_node
                         && self.dock_state[src_surf][src_node].tabs_count() == 1
@@ -253,24 +934,47 @@ impl<Tab> DockArea<'_, Tab> {
         } else {
             AllowedSplits::All
         };
-        let allowed_splits = self.allowed_splits & restricted_splits;
+        let node_splits = match drag_state.hover.dst {
+            TreeComponent::Node(surface, node) => self.dock_state.allowed_splits(surface, node),
+            _ => AllowedSplits::All,
+        };
+        let allowed_splits = self.allowed_splits & restricted_splits & node_splits;
 
         let allowed_in_window = match drag_state.drag.src {
-            TreeComponent::Tab(surface, node, tab) => {
+            Some(DragSource::Tab(surface, node, tab)) => {
                 let Node::Leaf(leaf) = &mut self.dock_state[surface][node] else {
                     unreachable!("tab drags can only come from leaf nodes")
                 };
                 tab_viewer.allowed_in_windows(&mut leaf.tabs[tab.0])
             }
-            _ => todo!("collections of tabs, like nodes or surfaces, can't be dragged! (yet)"),
+            Some(DragSource::Node(surface, node)) => self.dock_state[surface][node]
+                .get_leaf_mut()
+                .is_some_and(|leaf| {
+                    leaf.tabs
+                        .iter_mut()
+                        .all(|tab| tab_viewer.allowed_in_windows(tab))
+                }),
+            None => true,
         };
 
         if let Some(pointer) = state.last_hover_pos {
             drag_state.pointer = pointer;
         }
+        let allowed_in_window =
+            allowed_in_window && self.can_tear_off(ui, state.drag_start, drag_state.pointer);
 
         let window_bounds = self.window_bounds.unwrap();
-        match (style.overlay.overlay_type, drag_state.is_on_title_bar()) {
+
+        // The overlay is painted on a layer of its own, clipped to the dock if it's kept inside its parent.
+        let mut ui = ui.new_child(UiBuilder::new().id_salt("overlay"));
+        ui.set_clip_rect(if self.clip_to_parent {
+            ui.max_rect().intersect(ui.clip_rect())
+        } else {
+            ui.ctx().screen_rect()
+        });
+        let ui = &ui;
+        let mut overlay = match (style.overlay.overlay_type, drag_state.is_on_title_bar()) {
+            _ if drag_state.hover.edge.is_some() => Some(drag_state.resolve_edge(ui, style)),
             (OverlayType::HighlightedAreas, _) | (_, true) => drag_state.resolve_traditional(
                 ui,
                 style,
@@ -278,14 +982,81 @@ impl<Tab> DockArea<'_, Tab> {
                 allowed_in_window,
                 window_bounds,
             ),
-            (OverlayType::Widgets, false) => drag_state.resolve_icon_based(
+            (OverlayType::Widgets, false) => Some(drag_state.resolve_icon_based(
                 ui,
                 style,
                 allowed_splits,
                 allowed_in_window,
                 window_bounds,
-            ),
+            )),
+        }?;
+        if style.overlay.layout_preview {
+            let preview = overlay.destination.and_then(|destination| {
+                self.preview_layout(drag_state.drag.src.as_ref(), destination, ui.ctx())
+            });
+            if let Some((leaves, target)) = preview {
+                overlay.leaf_previews = leaves;
+                overlay.drop_rect = Some(target);
+            }
+        }
+        if !tab_viewer.drop_overlay_ui(&make_overlay_painter(ui), &overlay) {
+            overlay.paint(ui, style);
+        }
+        accessibility::describe_drop_buttons(
+            ui,
+            &self.dock_state.translations.accessibility,
+            &overlay.buttons,
+        );
+        overlay.destination
+    }
+
+    /// Lays out the surface of `destination` as it would be if the dragged `src` was dropped there, without changing
+    /// the [`DockState`]. Returns the rects of its leaves, and the rect of the leaf the dragged tabs go to.
+    fn preview_layout(
+        &self,
+        src: Option<&DragSource>,
+        destination: TabDestination,
+        ctx: &Context,
+    ) -> Option<(Vec<Rect>, Rect)> {
+        let TabDestination::Node(surface, _, _) = destination else {
+            return None;
+        };
+        let max_rect = self.dock_state[surface].root_node()?.rect()?;
+        let mut preview = self.dock_state.map_tabs(|_| ());
+        match src {
+            Some(&DragSource::Tab(src_surface, src_node, src_tab)) => {
+                preview.move_tab_with_selection((src_surface, src_node, src_tab), destination);
+            }
+            Some(&DragSource::Node(src_surface, src_node)) => {
+                preview.move_leaf((src_surface, src_node), destination);
+            }
+            _ => {
+                preview.insert_tab((), destination);
+            }
+        }
+        // Moving tabs focuses the leaf they end up in.
+        let (target_surface, target_node) = preview.focused_leaf()?;
+        if target_surface != surface {
+            return None;
         }
+
+        let style = self.style.as_ref().unwrap();
+        let tree = &mut preview[surface];
+        tree[NodeIndex::root()].set_rect(max_rect);
+        for node_index in tree.breadth_first_index_iter() {
+            if tree[node_index].is_parent() {
+                tree.compute_rect_sizes(node_index, max_rect, style, ctx.pixels_per_point());
+                tree.expand_over_auto_hidden(node_index);
+            }
+        }
+        let leaves = tree
+            .breadth_first_index_iter()
+            .filter(|&node_index| node_index != target_node && !tree.is_auto_hidden(node_index))
+            .filter_map(|node_index| tree[node_index].get_leaf())
+            .filter(|leaf| !leaf.is_empty() || leaf.is_central())
+            .map(|leaf| leaf.rect())
+            .collect();
+        Some((leaves, tree[target_node].rect()?))
     }
 
     /// Show a single surface of a [`DockState`].
@@ -297,11 +1068,53 @@ impl<Tab> DockArea<'_, Tab> {
         state: &mut State,
         fade_style: Option<(&Style, f32, SurfaceIndex)>,
     ) {
+        // Surfaces with a style of their own are shown with it in place of the `DockArea`'s style.
+        let surface_style = self.dock_state.surface_style(surf_index).cloned();
+        let surface_fade_style = surface_style.as_ref().zip(fade_style).map(
+            |(surface_style, (_, factor, hovered_surface))| {
+                let mut style = surface_style.clone();
+                fade_dock_style(&mut style, factor);
+                (style, factor, hovered_surface)
+            },
+        );
+        let fade_style = match &surface_fade_style {
+            Some((style, factor, hovered_surface)) => Some((style, *factor, *hovered_surface)),
+            None => fade_style,
+        };
+        let dock_area_style = surface_style.map(|style| self.style.replace(style));
+
         if surf_index.is_main() {
-            self.show_root_surface_inside(ui, tab_viewer, state);
+            let taskbar_windows = self.taskbar_windows();
+            if !taskbar_windows.is_empty() {
+                // The taskbar takes the bottom of the main surface.
+                let height = self.style.as_ref().unwrap().window_taskbar.height;
+                let rect = ui.available_rect_before_wrap();
+                let (main_rect, taskbar_rect) =
+                    rect.split_top_bottom_at_y((rect.bottom() - height).max(rect.top()));
+                let blocked = self.is_blocked_by_modal(surf_index);
+                ui.scope_builder(UiBuilder::new().max_rect(main_rect), |ui| {
+                    if blocked {
+                        ui.disable();
+                    }
+                    self.show_root_surface_inside(ui, tab_viewer, state);
+                });
+                ui.allocate_rect(taskbar_rect, Sense::hover());
+                self.show_window_taskbar(ui, taskbar_rect, &taskbar_windows, tab_viewer);
+            } else if self.is_blocked_by_modal(surf_index) {
+                ui.scope(|ui| {
+                    ui.disable();
+                    self.show_root_surface_inside(ui, tab_viewer, state);
+                });
+            } else {
+                self.show_root_surface_inside(ui, tab_viewer, state);
+            }
         } else {
             self.show_window_surface(ui, surf_index, tab_viewer, state, fade_style);
         }
+
+        if let Some(style) = dock_area_style {
+            self.style = style;
+        }
     }
 
     fn render_nodes(
@@ -312,29 +1125,108 @@ impl<Tab> DockArea<'_, Tab> {
         surf_index: SurfaceIndex,
         fade_style: Option<(&Style, f32)>,
     ) {
+        profile_scope!("DockArea::render_nodes");
         // First compute all rect sizes in the node graph.
         let max_rect = self.allocate_area_for_root_node(ui, surf_index);
+        let strips = self.reserve_auto_hide_strips(surf_index, max_rect);
+        let max_rect = self.dock_state[surf_index][NodeIndex::root()]
+            .rect()
+            .unwrap_or(max_rect);
         for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
             if self.dock_state[surf_index][node_index].is_parent() {
-                self.compute_rect_sizes(ui, (surf_index, node_index), max_rect);
+                let style = self.style.as_ref().unwrap();
+                let tree = &mut self.dock_state[surf_index];
+                tree.compute_rect_sizes(node_index, max_rect, style, ui.ctx().pixels_per_point());
+                tree.expand_over_auto_hidden(node_index);
             }
         }
+        let animating = self.animate_layout(ui, state, surf_index);
 
         // Then, draw the bodies of each leaves.
         for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
-            if self.dock_state[surf_index][node_index].is_leaf() {
+            if self.dock_state[surf_index][node_index].is_leaf()
+                && !self.dock_state[surf_index].is_auto_hidden(node_index)
+            {
                 self.show_leaf(ui, state, (surf_index, node_index), tab_viewer, fade_style);
             }
         }
 
         // Finally, draw separators so that their "interaction zone" is above
         // bodies (see `SeparatorStyle::extra_interact_width`).
-        let fade_style = fade_style.map(|(style, _)| style);
+        // While the leaves move, separators would be out of place, so they're hidden.
+        let faded_style = fade_style.map(|(style, _)| style);
         for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
-            if self.dock_state[surf_index][node_index].is_parent() {
-                self.show_separator(ui, (surf_index, node_index), fade_style);
+            let tree = &self.dock_state[surf_index];
+            if !animating
+                && tree[node_index].is_parent()
+                && !tree.is_auto_hidden(node_index.left())
+                && !tree.is_auto_hidden(node_index.right())
+            {
+                self.show_separator(ui, (surf_index, node_index), faded_style);
             }
         }
+
+        self.hover_surface_edges(ui, state, surf_index, tab_viewer);
+
+        if !strips.is_empty() {
+            self.show_auto_hide_strips(ui, state, surf_index, &strips, tab_viewer, faded_style);
+        }
+        self.show_auto_hide_flyout(ui, state, surf_index, &strips, tab_viewer, fade_style);
+    }
+
+    /// Makes the bands along the outer edges of a surface drop targets splitting the whole surface, see
+    /// [`DockArea::surface_edge_splits`].
+    fn hover_surface_edges(
+        &mut self,
+        ui: &Ui,
+        state: &State,
+        surf_index: SurfaceIndex,
+        tab_viewer: &impl TabViewer<Tab = Tab>,
+    ) {
+        let Some(pointer) = state.last_hover_pos else {
+            return;
+        };
+        let root = NodeIndex::root();
+        // Splitting the surface of a single leaf is the same as splitting the leaf.
+        if !self.surface_edge_splits
+            || state.drag_start.is_none()
+            || !self.dock_state[surf_index][root].is_parent()
+        {
+            return;
+        }
+        let Some(rect) = self.dock_state[surf_index][root].rect() else {
+            return;
+        };
+        let width = self.style.as_ref().unwrap().overlay.feel.edge_width;
+        if !rect.contains(pointer) || rect.shrink(width).contains(pointer) {
+            return;
+        }
+
+        let distances = [
+            (Split::Above, pointer.y - rect.top()),
+            (Split::Below, rect.bottom() - pointer.y),
+            (Split::Left, pointer.x - rect.left()),
+            (Split::Right, rect.right() - pointer.x),
+        ];
+        let edge = distances
+            .into_iter()
+            .filter(|(split, distance)| *distance <= width && self.allowed_splits.allows(*split))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(split, _)| split);
+        if edge.is_none() || !self.is_drag_allowed_on(state, (surf_index, root), tab_viewer) {
+            return;
+        }
+        ui.memory_mut(|mem| {
+            mem.data.insert_temp(
+                self.id.with("hover_data"),
+                Some(HoverData {
+                    rect,
+                    dst: TreeComponent::Node(surf_index, root),
+                    tab: None,
+                    edge,
+                }),
+            );
+        });
     }
 
     fn allocate_area_for_root_node(&mut self, ui: &mut Ui, surface: SurfaceIndex) -> Rect {
@@ -364,113 +1256,6 @@ impl<Tab> DockArea<'_, Tab> {
         rect
     }
 
-    fn compute_rect_sizes(
-        &mut self,
-        ui: &Ui,
-        (surface_index, node_index): (SurfaceIndex, NodeIndex),
-        max_rect: Rect,
-    ) {
-        assert!(self.dock_state[surface_index][node_index].is_parent());
-
-        let style = self.style.as_ref().unwrap();
-        let pixels_per_point = ui.ctx().pixels_per_point();
-
-        let left_collapsed_count =
-            self.dock_state[surface_index][node_index.left()].collapsed_leaf_count();
-        let right_collapsed_count =
-            self.dock_state[surface_index][node_index.right()].collapsed_leaf_count();
-        let left_collapsed = self.dock_state[surface_index][node_index.left()].is_collapsed();
-        let right_collapsed = self.dock_state[surface_index][node_index.right()].is_collapsed();
-
-        if left_collapsed || right_collapsed {
-            if let Node::Vertical(split) = &mut self.dock_state[surface_index][node_index] {
-                let rect = split.rect();
-                debug_assert!(!rect.any_nan() && rect.is_finite());
-                let rect = expand_to_pixel(rect, pixels_per_point);
-
-                if left_collapsed {
-                    // EITHER only left collapsed OR left and right both collapsed
-                    let border_y =
-                        rect.min.y + (left_collapsed_count as f32) * style.tab_bar.height;
-                    let left_separator_border = map_to_pixel(
-                        border_y - style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let right_separator_border = map_to_pixel(
-                        border_y + style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let left = rect
-                        .intersect(Rect::everything_above(left_separator_border))
-                        .intersect(max_rect);
-                    let right = rect
-                        .intersect(Rect::everything_below(right_separator_border))
-                        .intersect(max_rect);
-                    self.dock_state[surface_index][node_index.left()].set_rect(left);
-                    self.dock_state[surface_index][node_index.right()].set_rect(right);
-                } else {
-                    // Only right collapsed
-                    let border_y =
-                        rect.max.y - (right_collapsed_count as f32) * style.tab_bar.height;
-                    let left_separator_border = map_to_pixel(
-                        border_y - style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let right_separator_border = map_to_pixel(
-                        border_y + style.separator.width * 0.5,
-                        pixels_per_point,
-                        f32::round,
-                    );
-                    let left = rect
-                        .intersect(Rect::everything_above(left_separator_border))
-                        .intersect(max_rect);
-                    let right = rect
-                        .intersect(Rect::everything_below(right_separator_border))
-                        .intersect(max_rect);
-                    self.dock_state[surface_index][node_index.left()].set_rect(left);
-                    self.dock_state[surface_index][node_index.right()].set_rect(right);
-                }
-                return;
-            }
-        }
-
-        duplicate! {
-            [
-                orientation   dim_point  dim_size  left_of    right_of;
-                [Horizontal]  [x]        [width]   [left_of]  [right_of];
-                [Vertical]    [y]        [height]  [above]    [below];
-            ]
-            if let Node::orientation(split) = &mut self.dock_state[surface_index][node_index] {
-                let rect = split.rect;
-                debug_assert!(!rect.any_nan() && rect.is_finite());
-                let rect = expand_to_pixel(rect, pixels_per_point);
-
-                let midpoint = rect.min.dim_point + rect.dim_size() * split.fraction;
-                let left_separator_border = map_to_pixel(
-                    midpoint - style.separator.width * 0.5,
-                    pixels_per_point,
-                    f32::round
-                );
-                let right_separator_border = map_to_pixel(
-                    midpoint + style.separator.width * 0.5,
-                    pixels_per_point,
-                    f32::round
-                );
-
-                paste! {
-                    let left = rect.intersect(Rect::[<everything_ left_of>](left_separator_border)).intersect(max_rect);
-                    let right = rect.intersect(Rect::[<everything_ right_of>](right_separator_border)).intersect(max_rect);
-                }
-
-                self.dock_state[surface_index][node_index.left()].set_rect(left);
-                self.dock_state[surface_index][node_index.right()].set_rect(right);
-            }
-        }
-    }
-
     fn show_separator(
         &mut self,
         ui: &mut Ui,
@@ -489,18 +1274,23 @@ impl<Tab> DockArea<'_, Tab> {
 
         let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
         let pixels_per_point = ui.ctx().pixels_per_point();
+        let mut separator_response = None;
+        let min_size_limits = self.dock_state[surface_index]
+            .min_size_fraction_limits(node_index, style.separator.width);
+        // The separator stays between the children, which are laid out with the fraction fitted to their min sizes.
+        let fitted_fraction = self.dock_state[surface_index].fitted_fraction(node_index, style);
 
         duplicate! {
             [
-                orientation   dim_point  dim_size;
-                [Horizontal]  [x]        [width];
-                [Vertical]    [y]        [height];
+                orientation   dim_point  dim_size  separator_name;
+                [Horizontal]  [x]        [width]   [horizontal_separator];
+                [Vertical]    [y]        [height]  [vertical_separator];
             ]
             if let Node::orientation(split) = &mut self.dock_state[surface_index][node_index] {
                 let rect = split.rect;
                 let mut separator = rect;
 
-                let midpoint = rect.min.dim_point + rect.dim_size() * split.fraction;
+                let midpoint = rect.min.dim_point + rect.dim_size() * fitted_fraction;
                 separator.min.dim_point = midpoint - style.separator.width * 0.5;
                 separator.max.dim_point = midpoint + style.separator.width * 0.5;
 
@@ -508,8 +1298,13 @@ impl<Tab> DockArea<'_, Tab> {
                 expand.dim_point += style.separator.extra_interact_width / 2.0;
                 let interact_rect = separator.expand2(expand);
 
-                let response = ui.allocate_rect(interact_rect, Sense::click_and_drag())
-                    .on_hover_and_drag_cursor(paste!{ CursorIcon::[<Resize orientation>]});
+                // Locked separators only take up their space, without any interaction.
+                let response = if split.locked {
+                    ui.allocate_rect(interact_rect, Sense::hover())
+                } else {
+                    ui.allocate_rect(interact_rect, Sense::click_and_drag())
+                        .on_hover_and_drag_cursor(paste!{ CursorIcon::[<Resize orientation>]})
+                };
 
                 let should_respond_to_arrow_keys = ui.input(|i| i.modifiers.command || i.modifiers.shift);
 
@@ -524,15 +1319,19 @@ impl<Tab> DockArea<'_, Tab> {
                     }));
                 }
 
+                let step = match style.separator.snap {
+                    SeparatorSnap::Grid(grid) if grid > 0.0 => grid,
+                    _ => 16.,
+                };
                 let arrow_key_offset = if response.has_focus() && should_respond_to_arrow_keys {
                     if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
-                        Some(egui::vec2(0., -16.))
+                        Some(egui::vec2(0., -step))
                     } else if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
-                        Some(egui::vec2(0., 16.))
+                        Some(egui::vec2(0., step))
                     } else if ui.input(|i| i.key_pressed(Key::ArrowLeft)) {
-                        Some(egui::vec2(-16., 0.))
+                        Some(egui::vec2(-step, 0.))
                     } else if ui.input(|i| i.key_pressed(Key::ArrowRight)) {
-                        Some(egui::vec2(16., 0.))
+                        Some(egui::vec2(step, 0.))
                     } else {
                         None
                     }
@@ -540,21 +1339,28 @@ impl<Tab> DockArea<'_, Tab> {
                     None
                 };
 
-                let midpoint = rect.min.dim_point + rect.dim_size() * split.fraction;
+                // Thin separators grow while they're hovered, the interaction area stays the same.
+                let active = !split.locked && (response.hovered() || response.dragged());
+                let width = lerp(
+                    style.separator.width..=style.separator.hover_width.max(style.separator.width),
+                    ui.ctx().animate_bool_responsive(response.id, active),
+                );
+
+                let midpoint = rect.min.dim_point + rect.dim_size() * fitted_fraction;
                 separator.min.dim_point = map_to_pixel(
-                    midpoint - style.separator.width * 0.5,
+                    midpoint - width * 0.5,
                     pixels_per_point,
                     f32::round,
                 );
                 separator.max.dim_point = map_to_pixel(
-                    midpoint + style.separator.width * 0.5,
+                    midpoint + width * 0.5,
                     pixels_per_point,
                     f32::round,
                 );
 
                 let color = if response.dragged() {
                     style.separator.color_dragged
-                } else if response.hovered() || response.has_focus() {
+                } else if !split.locked && (response.hovered() || response.has_focus()) {
                     style.separator.color_hovered
                 } else {
                     style.separator.color_idle
@@ -573,17 +1379,75 @@ impl<Tab> DockArea<'_, Tab> {
                         || (delta < 0. && dim_point < midpoint && dim_point > rect.min.dim_point)
                     {
                         let range = rect.max.dim_point - rect.min.dim_point;
-                        let min = (style.separator.extra / range).min(1.0);
-                        let max = 1.0 - min;
-                        let (min, max) = (min.min(max), max.max(min));
-                        split.fraction = (split.fraction + delta / range).clamp(min, max);
+                        let (min, max) = fraction_limits(style.separator.extra, range);
+
+                        // Snapped separators follow the pointer, as adding up the drag deltas would
+                        // keep them stuck to the position they snapped to.
+                        let fraction = match (&style.separator.snap, response.interact_pointer_pos()) {
+                            (SeparatorSnap::None, _) | (_, None) => fitted_fraction + delta / range,
+                            (_, Some(pos)) => (pos.dim_point - rect.min.dim_point) / range,
+                        };
+                        split.fraction = clamp_to_min_sizes(
+                            style.separator.snap
+                                .snap(fraction, range, style.separator.snap_distance)
+                                .clamp(min, max),
+                            min_size_limits,
+                        );
                     }
                 }
 
-                if response.double_clicked() {
-                    split.fraction = 0.5;
+                if response.double_clicked() && style.separator.double_click_reset {
+                    split.fraction = split.default_fraction;
+                }
+
+                let (fraction, locked) = (split.fraction, split.locked);
+                accessibility::describe_separator(
+                    &response,
+                    &self.dock_state.translations.accessibility.separator_name,
+                    fraction,
+                    locked,
+                );
+                separator_response = Some(response);
+            }
+        }
+
+        if let Some(response) = separator_response {
+            let mut distribute_evenly = false;
+            let mut flip_orientation = false;
+            response.context_menu(|ui| {
+                let translations = &self.dock_state.translations.separator;
+                if ui.button(&translations.distribute_evenly_button).clicked() {
+                    distribute_evenly = true;
+                    ui.close();
+                }
+                if ui.button(&translations.flip_orientation_button).clicked() {
+                    flip_orientation = true;
+                    ui.close();
                 }
+            });
+            if distribute_evenly {
+                self.dock_state[surface_index].distribute_evenly(node_index);
+            }
+            if flip_orientation {
+                self.dock_state[surface_index].rotate_split(node_index);
             }
         }
     }
 }
+
+/// Returns the smallest and largest fractions a split which is `range` points long can be resized to,
+/// see [`SeparatorStyle::extra`](crate::SeparatorStyle::extra).
+fn fraction_limits(extra: f32, range: f32) -> (f32, f32) {
+    let min = (extra / range).min(1.0);
+    let max = 1.0 - min;
+    (min.min(max), max.max(min))
+}
+
+/// Keeps `fraction` within the limits given by
+/// [`Tree::min_size_fraction_limits`](crate::Tree::min_size_fraction_limits), if the children of the split fit them.
+fn clamp_to_min_sizes(fraction: f32, limits: Option<(f32, f32)>) -> f32 {
+    match limits {
+        Some((min, max)) => fraction.clamp(min, max),
+        None => fraction,
+    }
+}
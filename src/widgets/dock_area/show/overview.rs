@@ -0,0 +1,161 @@
+use egui::{
+    pos2, vec2, Align2, Color32, Key, Rect, ScrollArea, Sense, StrokeKind, TextStyle, TextWrapMode,
+    Ui, UiBuilder,
+};
+
+use crate::utils::profile_scope;
+use crate::{
+    dock_area::state::State, DockArea, NodeIndex, Style, SurfaceIndex, TabIndex, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Shows every tab as a card in a grid instead of the layout.
+    ///
+    /// See [`DockState::set_overview_open`](crate::DockState::set_overview_open).
+    pub(super) fn show_overview(
+        &mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &State,
+    ) {
+        profile_scope!("DockArea::show_overview");
+        let style = self.style.as_ref().unwrap();
+        let rect = ui.available_rect_before_wrap();
+        ui.painter().rect_filled(
+            rect,
+            style.main_surface_border_rounding,
+            style.tab.tab_body.bg_fill,
+        );
+
+        let mut tabs = Vec::new();
+        for &surface_index in self.dock_state.valid_surface_indices().iter() {
+            for node_index in self.dock_state[surface_index].breadth_first_index_iter() {
+                if let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf() {
+                    for tab_index in 0..leaf.tabs.len() {
+                        tabs.push((surface_index, node_index, TabIndex(tab_index)));
+                    }
+                }
+            }
+        }
+        let focused = self
+            .dock_state
+            .focused_leaf()
+            .and_then(|(surface_index, node_index)| {
+                let leaf = self.dock_state[surface_index][node_index].get_leaf()?;
+                Some((surface_index, node_index, leaf.active))
+            });
+
+        let mut clicked = None;
+        let mut cards_ui = ui.new_child(UiBuilder::new().max_rect(rect));
+        ScrollArea::vertical()
+            .auto_shrink(false)
+            .show(&mut cards_ui, |ui| {
+                let spacing = Style::OVERVIEW_CARD_SPACING;
+                ui.spacing_mut().item_spacing = vec2(spacing, spacing);
+                ui.add_space(spacing);
+                ui.horizontal_wrapped(|ui| {
+                    ui.add_space(spacing);
+                    for &location in &tabs {
+                        if self.overview_card(
+                            ui,
+                            tab_viewer,
+                            state,
+                            location,
+                            focused == Some(location),
+                        ) {
+                            clicked = Some(location);
+                        }
+                    }
+                });
+                ui.add_space(spacing);
+            });
+        ui.advance_cursor_after_rect(rect);
+
+        if let Some(location) = clicked {
+            self.focus_tab(location);
+            self.dock_state.set_overview_open(false);
+            self.response.clicked_tab = Some(location);
+        } else if ui.input(|i| i.key_pressed(Key::Escape)) {
+            self.dock_state.set_overview_open(false);
+        }
+    }
+
+    /// Shows the card of a single tab, made of its preview, if any, and its title.
+    ///
+    /// Returns `true` if the card was clicked.
+    fn overview_card(
+        &mut self,
+        ui: &mut Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &State,
+        (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        is_focused: bool,
+    ) -> bool {
+        let style = self.style.as_ref().unwrap();
+        let tab = &mut self.dock_state[surface_index][node_index]
+            .tabs_mut()
+            .expect("This node must be a leaf")[tab_index.0];
+        let tab_id = tab_viewer.id(tab);
+        let title = tab_viewer.title(tab);
+
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(Style::OVERVIEW_CARD_WIDTH, Style::OVERVIEW_CARD_HEIGHT),
+            Sense::click(),
+        );
+        let tab_style = if response.hovered() {
+            &style.tab.hovered
+        } else if is_focused {
+            &style.tab.focused
+        } else {
+            &style.tab.inactive
+        };
+        let painter = ui.painter();
+        painter.rect(
+            rect,
+            tab_style.corner_radius,
+            tab_style.bg_fill,
+            (1.0, tab_style.outline_color),
+            StrokeKind::Inside,
+        );
+
+        let galley = title.into_galley(
+            ui,
+            Some(TextWrapMode::Truncate),
+            rect.width() - 2.0 * Style::OVERVIEW_CARD_PADDING,
+            TextStyle::Button,
+        );
+        let title_rect = Rect::from_min_max(
+            pos2(
+                rect.min.x,
+                rect.max.y - galley.size().y - 2.0 * Style::OVERVIEW_CARD_PADDING,
+            ),
+            rect.max,
+        );
+        let text_pos = Align2::CENTER_CENTER
+            .align_size_within_rect(galley.size(), title_rect)
+            .min;
+        painter.galley(text_pos, galley, tab_style.text_color);
+
+        let preview_rect = Rect::from_min_max(rect.min, pos2(rect.max.x, title_rect.min.y))
+            .shrink(Style::OVERVIEW_CARD_PADDING);
+        painter.rect_filled(preview_rect, 0.0, style.tab.tab_body.bg_fill);
+        if let Some(preview) = self
+            .tab_previews
+            .then(|| state.previews.get(tab_id))
+            .flatten()
+        {
+            // Fit the preview into the card, keeping its aspect ratio.
+            let scale = (preview_rect.size() / preview.size).min_elem();
+            let image_rect =
+                Align2::CENTER_CENTER.align_size_within_rect(preview.size * scale, preview_rect);
+            painter.image(
+                preview.id,
+                image_rect,
+                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                Color32::WHITE,
+            );
+        }
+
+        response.clicked()
+    }
+}
@@ -0,0 +1,311 @@
+use std::f32::consts::FRAC_PI_2;
+
+use egui::{
+    epaint::TextShape, pos2, vec2, Align, Area, CornerRadius, CursorIcon, Layout, NumExt, Order,
+    Rect, Sense, StrokeKind, TextWrapMode, Ui, Vec2,
+};
+
+use super::{accessibility, leaf::BrowserTabLayer};
+use crate::utils::profile_scope;
+use crate::{
+    dock_area::state::State, DockArea, NodeIndex, Style, SurfaceIndex, TabBarPosition, TabIndex,
+    TabStyle, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Shows the tab bar of a leaf whose [`TabBarPosition`] is on its left or right, filling `ui`.
+    ///
+    /// Titles are read from top to bottom, like in the auto-hide strips. Unlike the tab bars above and below
+    /// leaves, it only shows the tabs, which can be clicked, dragged, closed and have a context menu. Tabs which
+    /// don't fit are scrolled to with the mouse wheel.
+    pub(super) fn side_tab_bar(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        position: TabBarPosition,
+        fade_style: Option<&Style>,
+    ) {
+        profile_scope!("DockArea::side_tab_bar");
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let rect = ui.max_rect();
+        ui.painter()
+            .rect_filled(rect, CornerRadius::ZERO, style.tab_bar.bg_fill);
+        let px = ui.ctx().pixels_per_point().recip();
+        let body_edge = match position {
+            TabBarPosition::Left => rect.right() - px,
+            _ => rect.left() + px,
+        };
+        ui.painter()
+            .vline(body_edge, rect.y_range(), (px, style.tab_bar.hline_color));
+
+        // Interacted with before the tabs, so that only the space around them opens the menu.
+        let tabbar_response = ui.interact(
+            rect,
+            self.id.with((surface_index, node_index, "side_tab_bar")),
+            Sense::click(),
+        );
+        // Tabs which don't fit are scrolled out of the tab bar, and mustn't be drawn over the leaves around it.
+        ui.set_clip_rect(rect.intersect(ui.clip_rect()));
+
+        let focused = self.dock_state.focused_leaf();
+        let (tabs_len, scroll) = {
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf_mut()
+                .expect("This node must be a leaf");
+            leaf.tab_rects.clear();
+            leaf.tab_rects.resize(leaf.tabs.len(), Rect::NOTHING);
+            (leaf.tabs.len(), leaf.scroll)
+        };
+        let x_spacing = 8.0;
+        let mut cursor = rect.top() + scroll;
+
+        for tab_index in (0..tabs_len).map(TabIndex) {
+            let id = self.tab_title_id((surface_index, node_index, tab_index));
+            let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf_mut()
+                .expect("This node must be a leaf");
+            let is_active = leaf.active == tab_index || leaf.is_tab_selected(tab_index);
+            let tab = &mut leaf.tabs[tab_index.0];
+            let tab_style = tab_viewer
+                .tab_style_override(tab, &style.tab)
+                .unwrap_or_else(|| style.tab.clone());
+            let draggable = self.draggable_tabs && tab_viewer.is_draggable(tab);
+            let show_close_button = self.show_close_buttons && tab_viewer.is_closeable(tab);
+            let title = tab_viewer.title(tab);
+            let accessible_name = accessibility::tab_name(tab_viewer, tab);
+            let galley = tab_style.title_galley(ui, title, Some(TextWrapMode::Extend));
+
+            // The close button comes after the title, at the bottom of the tab.
+            let close_button_size = if show_close_button {
+                Style::TAB_CLOSE_BUTTON_SIZE.min(rect.width())
+            } else {
+                0.0
+            };
+            let length = galley.size().x + 2.0 * x_spacing + close_button_size;
+            let tab_rect = Rect::from_x_y_ranges(rect.x_range(), cursor..=cursor + length);
+            cursor += length + tab_style.spacing;
+
+            let mut response = ui.interact(tab_rect, id, Sense::click_and_drag());
+            if draggable && ui.ctx().dragged_id().is_none() {
+                response = response.on_hover_cursor(CursorIcon::Grab);
+            }
+            accessibility::describe_tab(
+                &response,
+                &self.dock_state.translations.accessibility,
+                &accessible_name,
+                tab_index.0,
+                tabs_len,
+                is_active,
+            );
+            let interaction = if is_active && focused == Some((surface_index, node_index)) {
+                &tab_style.focused
+            } else if is_active {
+                &tab_style.active
+            } else if response.hovered() {
+                &tab_style.hovered
+            } else {
+                &tab_style.inactive
+            };
+
+            Self::paint_tab_frame(ui, tab_rect, interaction.corner_radius, interaction);
+            let text_pos = pos2(
+                tab_rect.center().x + galley.size().y / 2.0,
+                tab_rect.min.y + x_spacing,
+            );
+            ui.painter().add(
+                TextShape::new(text_pos, galley, interaction.text_color).with_angle(FRAC_PI_2),
+            );
+            if response.has_focus() {
+                ui.painter().rect_stroke(
+                    tab_rect,
+                    interaction.corner_radius,
+                    tab_style.focus_ring,
+                    StrokeKind::Inside,
+                );
+            }
+
+            let close_response = show_close_button.then(|| {
+                let close_button_rect = Rect::from_center_size(
+                    pos2(
+                        tab_rect.center().x,
+                        tab_rect.bottom() - close_button_size / 2.0,
+                    ),
+                    Vec2::splat(close_button_size),
+                );
+                let mut corner_radius = interaction.corner_radius;
+                corner_radius.nw = 0;
+                corner_radius.ne = 0;
+                let close_response = Self::tab_close_button(
+                    ui,
+                    id,
+                    close_button_rect,
+                    corner_radius,
+                    tab_style.focus_ring,
+                    style,
+                );
+                accessibility::describe_close_button(
+                    &close_response,
+                    &self.dock_state.translations.accessibility,
+                    &accessible_name,
+                );
+                close_response
+            });
+
+            self.scroll_into_view(
+                ui,
+                (surface_index, node_index, tab_index),
+                tab_rect,
+                position,
+            );
+            let visible_rect = tab_rect.intersect(ui.clip_rect());
+            let leaf = self.dock_state[surface_index][node_index]
+                .get_leaf_mut()
+                .expect("This node must be a leaf");
+            leaf.tab_rects[tab_index.0] = visible_rect;
+            if visible_rect.is_positive() {
+                self.response
+                    .set_tab_rect((surface_index, node_index, tab_index), |rects| {
+                        rects.button = Some(visible_rect);
+                    });
+            }
+
+            let tab = &mut leaf.tabs[tab_index.0];
+            if self.show_tab_name_on_hover {
+                response = Self::tab_tooltip(response, tab_style.tooltip_delay, |ui| {
+                    tab_viewer.tab_tooltip(ui, tab);
+                });
+            }
+            if self.tab_context_menus {
+                response.context_menu(|ui| {
+                    tab_viewer.context_menu(ui, tab, surface_index, node_index);
+                });
+            }
+            tab_viewer.on_tab_button(tab, &response);
+
+            let close_clicked = close_response.is_some_and(|response| response.clicked());
+            if close_clicked || (show_close_button && response.middle_clicked()) {
+                self.close_with_selection(surface_index, node_index, tab_index);
+            } else if response.clicked() {
+                self.click_tab(ui, (surface_index, node_index, tab_index));
+            }
+
+            if draggable && response.dragged() && ui.input(|i| i.pointer.is_decidedly_dragging()) {
+                self.drag_side_tab(
+                    ui,
+                    state,
+                    (surface_index, node_index, tab_index),
+                    tab_rect,
+                    &tab_style,
+                    tab_viewer,
+                );
+            }
+        }
+
+        let hovered = ui.rect_contains_pointer(rect);
+        self.scroll_to_switch(ui, (surface_index, node_index), hovered, fade_style);
+
+        let tabs_length = cursor - scroll - rect.top();
+        let overflow = (tabs_length - rect.height()).at_least(0.0);
+        let leaf = self.dock_state[surface_index][node_index]
+            .get_leaf_mut()
+            .expect("This node must be a leaf");
+        if hovered {
+            leaf.scroll += ui.input(|i| i.smooth_scroll_delta.y + i.smooth_scroll_delta.x);
+        }
+        leaf.scroll = leaf.scroll.clamp(-overflow, 0.0);
+
+        if self.tab_bar_context_menus {
+            tabbar_response.context_menu(|ui| {
+                tab_viewer.tab_bar_context_menu(ui, surface_index, node_index);
+            });
+        }
+    }
+
+    /// Starts dragging a tab of a side tab bar once it's dragged far enough, and shows it next to the pointer
+    /// like the tabs of the other tab bars.
+    fn drag_side_tab(
+        &mut self,
+        ui: &Ui,
+        state: &mut State,
+        location @ (surface_index, node_index, tab_index): (SurfaceIndex, NodeIndex, TabIndex),
+        tab_rect: Rect,
+        tab_style: &TabStyle,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
+        let Some(pointer_pos) = ui.ctx().pointer_interact_pos() else {
+            return;
+        };
+        let start = *state.drag_start.get_or_insert(pointer_pos);
+        let delta = pointer_pos - start;
+        if delta.x.abs() <= 6.0 && delta.y.abs() <= 30.0 {
+            return;
+        }
+
+        let id = self.tab_title_id(location);
+        let preview_style = self.style.as_ref().unwrap().drag_preview;
+        let custom_preview = self.custom_drag_preview(
+            ui,
+            state,
+            id,
+            location,
+            Some(tab_rect),
+            preview_style,
+            tab_viewer,
+        );
+        let preview = custom_preview.unwrap_or_else(|| {
+            let (label, closeable, loading) = {
+                let tab = &mut self.dock_state[surface_index][node_index]
+                    .tabs_mut()
+                    .expect("This node must be a leaf")[tab_index.0];
+                (
+                    tab_viewer.title(tab),
+                    tab_viewer.is_closeable(tab),
+                    tab_viewer.is_loading(tab),
+                )
+            };
+            let height = self.style.as_ref().unwrap().tab_bar.height;
+            let position = preview_style
+                .cursor_offset
+                .map_or(tab_rect.min + delta, |offset| pointer_pos + offset);
+            let area = Area::new(id.with("drag_preview"))
+                .order(Order::Tooltip)
+                .fixed_pos(position)
+                .constrain_to(self.drag_preview_bounds(ui.ctx()))
+                .interactable(false)
+                .show(ui.ctx(), |ui| {
+                    ui.multiply_opacity(preview_style.opacity);
+                    // The dragged tab is shown the same as the tabs above leaves, which it becomes when dropped there.
+                    ui.allocate_ui_with_layout(
+                        vec2(ui.available_width(), height),
+                        Layout::left_to_right(Align::Center),
+                        |ui| {
+                            self.tab_title(
+                                ui,
+                                tab_style,
+                                id.with("drag_preview"),
+                                label,
+                                true,
+                                true,
+                                true,
+                                true,
+                                TabBarPosition::Top,
+                                None,
+                                self.show_close_buttons && closeable,
+                                loading,
+                                None,
+                                BrowserTabLayer::default(),
+                            )
+                        },
+                    );
+                });
+            area.response.rect
+        });
+        state.dragged_tab_offset = preview.min - tab_rect.min;
+
+        self.start_tab_drag(ui, location);
+    }
+}
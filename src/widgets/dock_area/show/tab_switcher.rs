@@ -0,0 +1,157 @@
+use egui::{vec2, Area, Button, Frame, Key, Modifiers, Order, Pos2, ScrollArea, TextEdit, Ui};
+
+use crate::utils::profile_scope;
+use crate::{
+    dock_area::state::{State, TabSwitcherState},
+    DockArea, Style, TabIndex, TabViewer,
+};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Shows the tab switcher at the top of `ui` if it's open,
+    /// see [`DockState::open_tab_switcher`](crate::DockState::open_tab_switcher).
+    pub(super) fn show_tab_switcher(
+        &mut self,
+        ui: &Ui,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+    ) {
+        profile_scope!("DockArea::show_tab_switcher");
+        if !self.dock_state.is_tab_switcher_open() {
+            state.tab_switcher = None;
+            return;
+        }
+        let switcher = state
+            .tab_switcher
+            .get_or_insert_with(TabSwitcherState::default);
+
+        let mut matches = Vec::new();
+        for &surface_index in self.dock_state.valid_surface_indices().iter() {
+            for node_index in self.dock_state[surface_index].breadth_first_index_iter() {
+                let Some(leaf) = self.dock_state[surface_index][node_index].get_leaf_mut() else {
+                    continue;
+                };
+                for (tab_index, tab) in leaf.tabs.iter_mut().enumerate() {
+                    let title = tab_viewer.title(tab);
+                    if let Some(score) = fuzzy_score(&switcher.query, title.text()) {
+                        let location = (surface_index, node_index, TabIndex(tab_index));
+                        matches.push((score, location, title));
+                    }
+                }
+            }
+        }
+        // Stable, so that equally good matches keep the order of the layout.
+        matches.sort_by_key(|&(score, _, _)| std::cmp::Reverse(score));
+
+        let (up, down, enter, escape) = ui.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        if up {
+            switcher.selected = switcher.selected.saturating_sub(1);
+        }
+        if down {
+            switcher.selected += 1;
+        }
+        switcher.selected = switcher.selected.min(matches.len().saturating_sub(1));
+
+        let translations = &self.dock_state.translations.tab_switcher;
+        let width = Style::TAB_SWITCHER_WIDTH.min(ui.max_rect().width());
+        let position = Pos2::new(
+            ui.max_rect().center().x - width / 2.0,
+            ui.max_rect().top() + Style::TAB_SWITCHER_MARGIN,
+        );
+        let mut chosen = enter
+            .then(|| {
+                matches
+                    .get(switcher.selected)
+                    .map(|&(_, location, _)| location)
+            })
+            .flatten();
+        let area_response = Area::new(self.id.with("tab_switcher"))
+            .order(Order::Foreground)
+            .fixed_pos(position)
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(width);
+                    let query_response = ui.add(
+                        TextEdit::singleline(&mut switcher.query)
+                            .hint_text(&translations.search_hint)
+                            .desired_width(f32::INFINITY),
+                    );
+                    query_response.request_focus();
+                    if query_response.changed() {
+                        switcher.selected = 0;
+                    }
+
+                    if matches.is_empty() {
+                        ui.weak(&translations.no_matches);
+                    }
+                    ScrollArea::vertical()
+                        .max_height(Style::TAB_SWITCHER_MAX_HEIGHT)
+                        .show(ui, |ui| {
+                            ui.spacing_mut().item_spacing = vec2(0.0, 0.0);
+                            for (index, (_, location, title)) in matches.into_iter().enumerate() {
+                                let is_selected = index == switcher.selected;
+                                let response = ui.add_sized(
+                                    vec2(ui.available_width(), ui.spacing().interact_size.y),
+                                    Button::selectable(is_selected, title),
+                                );
+                                if is_selected && (up || down) {
+                                    response.scroll_to_me(None);
+                                }
+                                if response.clicked() {
+                                    chosen = Some(location);
+                                }
+                            }
+                        });
+                });
+            })
+            .response;
+
+        if let Some(location) = chosen {
+            self.focus_tab(location);
+            self.dock_state.close_tab_switcher();
+        } else if escape || area_response.clicked_elsewhere() {
+            self.dock_state.close_tab_switcher();
+        }
+    }
+}
+
+/// Scores how well `title` matches `query`, or returns `None` if it doesn't contain all characters of `query`
+/// in the same order.
+///
+/// Case is ignored. Matches of consecutive characters and at the start of words score higher.
+fn fuzzy_score(query: &str, title: &str) -> Option<i32> {
+    let mut query = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut first_match = None;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+    for (position, c) in title.chars().enumerate() {
+        let Some(&wanted) = query.peek() else {
+            break;
+        };
+        let matched = c.to_lowercase().eq(std::iter::once(wanted));
+        if matched {
+            query.next();
+            first_match.get_or_insert(position);
+            score += 1;
+            if previous_matched {
+                score += 4;
+            }
+            if previous.is_none_or(|previous| !previous.is_alphanumeric()) {
+                score += 3;
+            }
+        }
+        previous = Some(c);
+        previous_matched = matched;
+    }
+
+    // Matches starting further into the title are slightly worse.
+    let late_start = first_match.unwrap_or(0).min(3) as i32;
+    query.peek().is_none().then_some(score - late_start)
+}
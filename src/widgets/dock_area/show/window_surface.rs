@@ -1,12 +1,18 @@
 use egui::{
-    vec2, Align, Color32, CornerRadius, CursorIcon, Frame, Layout, Rect, Response, RichText, Sense,
-    Shape, Stroke, Ui, UiBuilder, Vec2, WidgetText,
+    lerp, vec2, Align, CentralPanel, Color32, Context, CornerRadius, CursorIcon, Frame, Id, Label,
+    LayerId, Layout, Rect, Response, RichText, Sense, Shape, Stroke, Ui, UiBuilder, Vec2,
+    ViewportClass, ViewportCommand, ViewportId, WidgetText,
 };
 
+use crate::utils::profile_scope;
 use crate::{
-    dock_area::{state::State, tab_removal::TabRemoval},
+    dock_area::{
+        drag_and_drop::{HoverData, TreeComponent},
+        state::State,
+        tab_removal::TabRemoval,
+    },
     utils::{fade_visuals, rect_set_size_centered},
-    DockArea, NodeIndex, Style, SurfaceIndex, TabViewer,
+    ButtonsStyle, DockArea, NodeIndex, Style, Surface, SurfaceIndex, TabViewer,
 };
 
 impl<Tab> DockArea<'_, Tab> {
@@ -18,15 +24,36 @@ impl<Tab> DockArea<'_, Tab> {
         state: &mut State,
         fade_style: Option<(&Style, f32, SurfaceIndex)>,
     ) {
+        profile_scope!("DockArea::show_window_surface");
+        if let Some(window_state) = self.dock_state.get_window_state_mut(surf_index) {
+            let hidden = self.window_taskbar && window_state.is_in_taskbar();
+            window_state.set_hidden_in_taskbar(hidden);
+            if hidden {
+                return;
+            }
+        }
+        if self.native_windows {
+            self.show_native_window_surface(
+                ui,
+                surf_index,
+                tab_viewer,
+                state,
+                fade_style.map(|(style, factor, _)| (style, factor)),
+            );
+            return;
+        }
+
         // Construct egui window
-        let id = format!("window {surf_index:?}").into();
+        let id = self.window_id(surf_index);
         let bounds = self.window_bounds.unwrap();
         let open = true;
+        let blocked = self.is_blocked_by_modal(surf_index);
         let window = self
             .dock_state
             .get_window_state_mut(surf_index)
             .unwrap()
-            .create_window(id, bounds);
+            .create_window(id, bounds)
+            .enabled(!blocked);
 
         // Calculate fading of the window (if any)
         let (fade_factor, fade_style) = match fade_style {
@@ -41,22 +68,7 @@ impl<Tab> DockArea<'_, Tab> {
         };
 
         // Get galley of currently selected node as a window title
-        let title = {
-            let node_id = self.dock_state[surf_index]
-                .focused_leaf()
-                .unwrap_or_else(|| {
-                    for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
-                        if self.dock_state[surf_index][node_index].is_leaf() {
-                            return node_index;
-                        }
-                    }
-                    unreachable!("a window surface should never be empty")
-                });
-            let leaf = self.dock_state[surf_index][node_id].get_leaf_mut().unwrap();
-            tab_viewer
-                .title(&mut leaf.tabs[leaf.active.0])
-                .color(ui.visuals().widgets.noninteractive.fg_stroke.color)
-        };
+        let (title_node, title) = self.window_title(ui, surf_index, tab_viewer);
 
         // Iterate through every node in dock_state[surf_index], and sum up the number of tabs in them
         let mut tab_count = 0;
@@ -66,28 +78,54 @@ impl<Tab> DockArea<'_, Tab> {
             }
         }
 
+        // Fade the windows which aren't focused, animating as they lose and gain focus.
+        let focus_highlight = self.style.as_ref().unwrap().focus_highlight;
+        let focused =
+            self.dock_state.focused_leaf().map(|(surface, _)| surface) == Some(surf_index);
+        let unfocused = ui.ctx().animate_bool_with_time_and_easing(
+            self.id.with((surf_index, "window_fade")),
+            !focused,
+            focus_highlight.window_fade_animation.duration,
+            focus_highlight.window_fade_animation.easing.function(),
+        );
+        let opacity = self
+            .dock_state
+            .get_window_state(surf_index)
+            .unwrap()
+            .opacity()
+            * lerp(1.0..=focus_highlight.unfocused_window_opacity, unfocused);
+
         // Fade window frame (if necessary)
-        let mut frame = Frame::window(ui.style());
-        if fade_factor != 1.0 {
-            frame.fill = frame.fill.linear_multiply(fade_factor);
-            frame.stroke.color = frame.stroke.color.linear_multiply(fade_factor);
-            frame.shadow.color = frame.shadow.color.linear_multiply(fade_factor);
+        let mut frame = tab_viewer
+            .window_frame(surf_index)
+            .unwrap_or_else(|| Frame::window(ui.style()));
+        let frame_factor = fade_factor * opacity;
+        if frame_factor != 1.0 {
+            frame.fill = frame.fill.linear_multiply(frame_factor);
+            frame.stroke.color = frame.stroke.color.linear_multiply(frame_factor);
+            frame.shadow.color = frame.shadow.color.linear_multiply(frame_factor);
         }
 
         let tab_bar_height = self.style.as_ref().unwrap().tab_bar.height;
+        let title_bar_height = if self.show_window_title_bars {
+            tab_bar_height
+        } else {
+            0.0
+        };
         let minimized = self
             .dock_state
             .get_window_state(surf_index)
             .unwrap()
             .is_minimized();
-        if minimized {
+        let response = if minimized {
             let height = tab_bar_height;
             window
                 .resizable([true, false])
                 .max_height(height)
                 .min_height(height)
         } else if self.dock_state[surf_index].is_collapsed() {
-            let height = self.dock_state[surf_index].collapsed_leaf_count() as f32 * tab_bar_height;
+            let height = self.dock_state[surf_index].collapsed_leaf_count() as f32 * tab_bar_height
+                + title_bar_height;
             window
                 .resizable([true, false])
                 .max_height(height)
@@ -101,53 +139,414 @@ impl<Tab> DockArea<'_, Tab> {
             if fade_factor != 1.0 {
                 fade_visuals(ui.visuals_mut(), fade_factor);
             }
+            ui.multiply_opacity(opacity);
             if minimized {
                 self.minimized_body(
                     ui,
-                    surf_index,
+                    state,
+                    (surf_index, title_node),
+                    tab_viewer,
                     fade_style.map(|(style, _)| style),
                     title,
                     tab_count,
                 )
             } else {
+                if self.show_window_title_bars {
+                    self.window_title_bar(
+                        ui,
+                        surf_index,
+                        tab_viewer,
+                        fade_style.map(|(style, _)| style),
+                        title,
+                    );
+                }
                 self.render_nodes(ui, tab_viewer, state, surf_index, fade_style);
             }
         });
 
+        if let Some(response) = response {
+            // Keep the window above the container the dock is embedded in, e.g. an `egui::Window`.
+            let parent_layer = ui.layer_id();
+            if parent_layer.order == response.response.layer_id.order {
+                ui.ctx()
+                    .set_sublayer(parent_layer, response.response.layer_id);
+            }
+
+            if let Some(window_state) = self.dock_state.get_window_state_mut(surf_index) {
+                window_state.update(response.response.rect, response.response.dragged());
+            }
+
+            // egui raises a window when it's pressed anywhere, including its tabs.
+            let press = ui.input(|i| i.pointer.any_pressed().then(|| i.pointer.press_origin()));
+            let pressed_on_window = press
+                .flatten()
+                .is_some_and(|pos| ui.ctx().layer_id_at(pos) == Some(response.response.layer_id));
+            if pressed_on_window {
+                self.dock_state.raise_window(surf_index);
+            }
+        }
+
         if !open {
             self.to_remove.push(TabRemoval::Window(surf_index));
         }
     }
 
-    fn minimized_body(
+    /// Shows a window surface in a native window of its own.
+    fn show_native_window_surface(
+        &mut self,
+        ui: &Ui,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        state: &mut State,
+        fade_style: Option<(&Style, f32)>,
+    ) {
+        let (_, title) = self.window_title(ui, surf_index, tab_viewer);
+        let origin = ui
+            .ctx()
+            .input(|i| i.viewport().inner_rect)
+            .map_or(Vec2::ZERO, |rect| rect.min.to_vec2());
+        let builder = self
+            .dock_state
+            .get_window_state(surf_index)
+            .unwrap()
+            .create_viewport(title.text().to_owned(), origin);
+        let viewport_id = ViewportId::from_hash_of(self.id.with(surf_index));
+        let blocked = self.is_blocked_by_modal(surf_index);
+
+        // Dragging tabs across viewports isn't supported.
+        let draggable_tabs = std::mem::replace(&mut self.draggable_tabs, false);
+        let embedded = ui
+            .ctx()
+            .show_viewport_immediate(viewport_id, builder, |ctx, class| {
+                if class == ViewportClass::Embedded {
+                    return true;
+                }
+
+                CentralPanel::default()
+                    .frame(Frame::central_panel(&ctx.style()).inner_margin(0.0))
+                    .show(ctx, |ui| {
+                        if blocked {
+                            ui.disable();
+                        }
+                        if let Some((_, fade_factor)) = fade_style {
+                            fade_visuals(ui.visuals_mut(), fade_factor);
+                        }
+                        self.render_nodes(ui, tab_viewer, state, surf_index, fade_style);
+                    });
+
+                let (inner_rect, pixels_per_point, close_requested) = ctx.input(|i| {
+                    let viewport = i.viewport();
+                    (
+                        viewport.inner_rect,
+                        viewport.native_pixels_per_point,
+                        viewport.close_requested(),
+                    )
+                });
+                if close_requested {
+                    // The window is closed by removing its surface, which the tabs may refuse.
+                    ctx.send_viewport_cmd(ViewportCommand::CancelClose);
+                    self.to_remove.push(TabRemoval::Window(surf_index));
+                }
+
+                // Windows focused by the windowing system are the front-most ones.
+                let focused = ctx.input(|i| i.viewport().focused == Some(true));
+                let focus_id = self.id.with((surf_index, "native_window_focused"));
+                if focused && !ctx.data(|d| d.get_temp(focus_id).unwrap_or(false)) {
+                    self.dock_state.raise_window(surf_index);
+                }
+                ctx.data_mut(|d| d.insert_temp(focus_id, focused));
+
+                let window_state = self.dock_state.get_window_state_mut(surf_index).unwrap();
+                if window_state.is_minimized() {
+                    ctx.send_viewport_cmd(ViewportCommand::Minimized(true));
+                    window_state.toggle_minimized();
+                }
+                window_state.viewport_shown(
+                    inner_rect.map(|rect| rect.translate(-origin)),
+                    pixels_per_point,
+                );
+                false
+            });
+        self.draggable_tabs = draggable_tabs;
+
+        // The backend doesn't support multiple viewports after all, so fall back to regular windows.
+        if embedded {
+            ui.ctx()
+                .data_mut(|d| d.insert_temp(self.id.with("native_windows_unsupported"), true));
+            self.native_windows = false;
+            self.show_window_surface(ui, surf_index, tab_viewer, state, None);
+        }
+    }
+
+    /// Stacks the windows in the order of [`DockState::window_order`](crate::DockState::window_order) if it changed
+    /// since they were last stacked.
+    ///
+    /// egui keeps the relative order of windows raised in the same frame, so one window is raised per frame: the
+    /// first one of the order which isn't above all the ones behind it.
+    ///
+    /// Native windows are stacked by the windowing system, so only the window brought to the front by
+    /// [`DockState::bring_window_to_front`](crate::DockState::bring_window_to_front) is focused.
+    pub(super) fn stack_windows(&mut self, ctx: &Context) {
+        let raised = self.dock_state.take_raised_window();
+        if self.native_windows {
+            if let Some(raised) = raised {
+                ctx.send_viewport_cmd_to(
+                    ViewportId::from_hash_of(self.id.with(raised)),
+                    ViewportCommand::Focus,
+                );
+            }
+            self.dock_state.window_order_stacked();
+            return;
+        }
+
+        let Some(order) = self.dock_state.unstacked_window_order() else {
+            return;
+        };
+        let layers: Vec<LayerId> = order
+            .iter()
+            .filter_map(
+                |&surface_index| match self.dock_state.get_surface(surface_index)? {
+                    Surface::Window(_, window_state) => Some(LayerId::new(
+                        window_state.order(),
+                        self.window_id(surface_index),
+                    )),
+                    _ => None,
+                },
+            )
+            .collect();
+        let stacked: Vec<LayerId> = ctx.memory(|mem| {
+            mem.layer_ids()
+                .filter(|layer_id| layers.contains(layer_id))
+                .collect()
+        });
+        let mut below = None;
+        let unstacked = layers.iter().find(|layer_id| {
+            let position = stacked.iter().position(|stacked| stacked == *layer_id);
+            let is_stacked = position.is_some() && position > below;
+            below = position;
+            !is_stacked
+        });
+        match unstacked {
+            Some(&layer_id) => {
+                ctx.move_to_top(layer_id);
+                ctx.request_repaint();
+            }
+            None => self.dock_state.window_order_stacked(),
+        }
+    }
+
+    /// Returns the leaf shown in the title of a window, and the title itself.
+    pub(super) fn window_title(
+        &mut self,
+        ui: &Ui,
+        surf_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> (NodeIndex, WidgetText) {
+        let node_id = self.dock_state[surf_index]
+            .focused_leaf()
+            .unwrap_or_else(|| {
+                for node_index in self.dock_state[surf_index].breadth_first_index_iter() {
+                    if self.dock_state[surf_index][node_index].is_leaf() {
+                        return node_index;
+                    }
+                }
+                unreachable!("a window surface should never be empty")
+            });
+        let leaf = self.dock_state[surf_index][node_id].get_leaf_mut().unwrap();
+        // Central leaves may have no tabs.
+        let title = tab_viewer
+            .window_title(surf_index)
+            .or_else(|| {
+                leaf.tabs
+                    .get_mut(leaf.active.0)
+                    .map(|tab| tab_viewer.title(tab))
+            })
+            .unwrap_or_else(|| WidgetText::from(""))
+            .color(ui.visuals().widgets.noninteractive.fg_stroke.color);
+        (node_id, title)
+    }
+
+    /// Draws the title bar of a window, with its title and the widgets of
+    /// [`TabViewer::window_title_bar_ui`] at its end.
+    fn window_title_bar(
         &mut self,
         ui: &mut Ui,
         surface_index: SurfaceIndex,
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
         fade_style: Option<&Style>,
         title: WidgetText,
-        tab_count: usize,
     ) {
-        ui.horizontal(|ui| {
-            let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
-            let (tabbar_outer_rect, _) = ui.allocate_exact_size(
-                vec2(Style::TAB_EXPAND_BUTTON_SIZE, style.tab_bar.height),
-                Sense::hover(),
-            );
-            ui.painter().rect_filled(
-                tabbar_outer_rect,
-                style.tab_bar.corner_radius,
-                style.tab_bar.bg_fill,
+        let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+        let (rect, _) = ui.allocate_exact_size(
+            vec2(ui.available_width(), style.tab_bar.height),
+            Sense::hover(),
+        );
+        ui.painter()
+            .rect_filled(rect, style.tab_bar.corner_radius, style.tab_bar.bg_fill);
+        let px = ui.ctx().pixels_per_point().recip();
+        ui.painter().hline(
+            rect.x_range(),
+            rect.bottom() - px * 0.5,
+            (px, style.tab_bar.hline_color),
+        );
+
+        let rect = rect - style.tab_bar.inner_margin;
+        let close_button = self.show_window_close_buttons.then(|| {
+            // Disabled unless all of the tabs of the window can be closed.
+            !self.dock_state[surface_index].iter_mut().all(|node| {
+                node.get_leaf_mut()
+                    .is_none_or(|leaf| leaf.tabs.iter_mut().all(|tab| tab_viewer.is_closeable(tab)))
+            })
+        });
+        let minimize_button = self
+            .window_taskbar
+            .then(|| self.dock_state.translations.leaf.minimize_button.clone());
+        let mut close = false;
+        let mut minimize = false;
+        let trailing_width = Self::tab_bar_side_ui(
+            ui,
+            rect,
+            Layout::right_to_left(Align::Center),
+            "window_title_bar",
+            |ui| {
+                if let Some(disabled) = close_button {
+                    close = Self::window_close_button(ui, disabled, &style.buttons);
+                }
+                if let Some(tooltip) = &minimize_button {
+                    minimize = Self::window_minimize_button(ui, &style.buttons, tooltip);
+                }
+                tab_viewer.window_title_bar_ui(ui, surface_index);
+            },
+        );
+        if close {
+            self.to_remove.push(TabRemoval::Window(surface_index));
+        }
+        if minimize {
+            self.window_toggle_minimized(surface_index);
+        }
+        let title_rect = Rect::from_x_y_ranges(
+            (rect.left() + ui.spacing().item_spacing.x)..=(rect.right() - trailing_width),
+            rect.y_range(),
+        );
+        if title_rect.width() > 0.0 {
+            let title_ui = &mut ui.new_child(
+                UiBuilder::new()
+                    .max_rect(title_rect)
+                    .layout(Layout::left_to_right(Align::Center)),
             );
-            self.window_expand(ui, surface_index, tabbar_outer_rect, fade_style);
-            ui.label(title);
-            if tab_count > 1 {
-                ui.label(
-                    RichText::new(format!("+{}", tab_count - 1))
-                        .color(ui.visuals().weak_text_color()),
+            title_ui.set_clip_rect(title_rect.intersect(ui.clip_rect()));
+            title_ui.add(Label::new(title).truncate().selectable(false));
+        }
+    }
+
+    /// Draws the button closing a window in its title bar, and returns `true` if it was clicked.
+    fn window_close_button(ui: &mut Ui, disabled: bool, buttons: &ButtonsStyle) -> bool {
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(Style::TAB_CLOSE_ALL_BUTTON_SIZE, ui.available_height()),
+            if disabled {
+                Sense::hover()
+            } else {
+                Sense::click()
+            },
+        );
+        let color = if disabled {
+            buttons.close_all_tabs_disabled_color
+        } else if response.hovered() || response.has_focus() {
+            ui.painter()
+                .rect_filled(rect, CornerRadius::ZERO, buttons.close_all_tabs_bg_fill);
+            buttons.close_all_tabs_active_color
+        } else {
+            buttons.close_all_tabs_color
+        };
+        let mut symbol_rect = rect;
+        rect_set_size_centered(&mut symbol_rect, Vec2::splat(Style::TAB_CLOSE_ALL_SIZE));
+        Self::draw_close_window_symbol(ui, color, symbol_rect);
+        response.clicked()
+    }
+
+    /// Draws the button minimizing a window into the taskbar in its title bar, and returns `true` if it was clicked.
+    fn window_minimize_button(ui: &mut Ui, buttons: &ButtonsStyle, tooltip: &str) -> bool {
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(Style::TAB_CLOSE_ALL_BUTTON_SIZE, ui.available_height()),
+            Sense::click(),
+        );
+        let color = if response.hovered() || response.has_focus() {
+            ui.painter()
+                .rect_filled(rect, CornerRadius::ZERO, buttons.minimize_window_bg_fill);
+            buttons.minimize_window_active_color
+        } else {
+            buttons.minimize_window_color
+        };
+        let mut symbol_rect = rect;
+        rect_set_size_centered(&mut symbol_rect, Vec2::splat(Style::TAB_CLOSE_ALL_SIZE));
+        ui.painter().hline(
+            symbol_rect.x_range(),
+            symbol_rect.bottom(),
+            Stroke::new(1.0, color),
+        );
+        response.on_hover_text(tooltip).clicked()
+    }
+
+    /// Draws the title strip of a minimized window.
+    ///
+    /// Tabs dropped onto the strip are appended to the leaf of `node_index`, which is the one whose title is shown.
+    #[allow(clippy::too_many_arguments)]
+    fn minimized_body(
+        &mut self,
+        ui: &mut Ui,
+        state: &State,
+        (surface_index, node_index): (SurfaceIndex, NodeIndex),
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+        fade_style: Option<&Style>,
+        title: WidgetText,
+        tab_count: usize,
+    ) {
+        let rect = ui
+            .horizontal(|ui| {
+                let style = fade_style.unwrap_or_else(|| self.style.as_ref().unwrap());
+                let (tabbar_outer_rect, _) = ui.allocate_exact_size(
+                    vec2(Style::TAB_EXPAND_BUTTON_SIZE, style.tab_bar.height),
+                    Sense::hover(),
+                );
+                ui.painter().rect_filled(
+                    tabbar_outer_rect,
+                    style.tab_bar.corner_radius,
+                    style.tab_bar.bg_fill,
                 );
+                self.window_expand(ui, surface_index, tabbar_outer_rect, fade_style);
+                ui.label(title);
+                if tab_count > 1 {
+                    ui.label(
+                        RichText::new(format!("+{}", tab_count - 1))
+                            .color(ui.visuals().weak_text_color()),
+                    );
+                }
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    tab_viewer.window_title_bar_ui(ui, surface_index);
+                });
+                ui.allocate_space(ui.available_size());
+            })
+            .response
+            .rect;
+
+        if let Some(pointer) = state.last_hover_pos {
+            if state.drag_start.is_some()
+                && rect.contains(pointer)
+                && self.is_drag_allowed_on(state, (surface_index, node_index), tab_viewer)
+            {
+                ui.memory_mut(|mem| {
+                    mem.data.insert_temp(
+                        self.id.with("hover_data"),
+                        Some(HoverData {
+                            rect,
+                            dst: TreeComponent::Node(surface_index, node_index),
+                            tab: Some(rect),
+                            edge: None,
+                        }),
+                    );
+                });
             }
-            ui.allocate_space(ui.available_size());
-        });
+        }
     }
 
     /// Draws the expand window button.
@@ -258,6 +657,12 @@ impl<Tab> DockArea<'_, Tab> {
             .get_window_state(surf_index)
             .unwrap()
             .is_minimized();
+        if self.window_taskbar && !minimized {
+            if let Some(window_state) = self.dock_state.get_window_state_mut(surf_index) {
+                window_state.set_in_taskbar(true);
+            }
+            return;
+        }
         let surface = &mut self.dock_state[surf_index];
 
         if surface.root_node().is_some_and(|node| node.is_collapsed()) {
@@ -285,4 +690,9 @@ impl<Tab> DockArea<'_, Tab> {
             }
         }
     }
+
+    /// The [`Id`] of the [`egui::Window`] a window surface is shown in, unique to this [`DockArea`].
+    fn window_id(&self, surface_index: SurfaceIndex) -> Id {
+        self.id.with(("window", surface_index))
+    }
 }
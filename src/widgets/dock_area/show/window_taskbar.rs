@@ -0,0 +1,99 @@
+use egui::{
+    pos2, scroll_area::ScrollBarVisibility, vec2, Align, CursorIcon, Layout, Rect, ScrollArea,
+    Sense, TextStyle, TextWrapMode, Ui, UiBuilder,
+};
+
+use crate::{utils::profile_scope, DockArea, Surface, SurfaceIndex, TabViewer};
+
+impl<Tab> DockArea<'_, Tab> {
+    /// Returns the windows minimized into the taskbar, from the oldest to the newest surface. Empty if the
+    /// [`DockArea`] has no taskbar.
+    pub(super) fn taskbar_windows(&self) -> Vec<SurfaceIndex> {
+        if !self.window_taskbar {
+            return Vec::new();
+        }
+        self.dock_state
+            .iter_surfaces()
+            .enumerate()
+            .filter_map(|(index, surface)| match surface {
+                Surface::Window(_, state) if state.is_in_taskbar() => Some(SurfaceIndex(index)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Draws the taskbar in `rect`, listing the `windows` minimized into it by title. Clicking a title restores its
+    /// window and brings it to the front. Titles which don't fit are scrolled to with the mouse wheel.
+    pub(super) fn show_window_taskbar(
+        &mut self,
+        ui: &mut Ui,
+        rect: Rect,
+        windows: &[SurfaceIndex],
+        tab_viewer: &mut impl TabViewer<Tab = Tab>,
+    ) {
+        profile_scope!("DockArea::show_window_taskbar");
+        let style = self.style.as_ref().unwrap().window_taskbar;
+        ui.painter().rect_filled(rect, 0.0, style.bg_fill);
+        let px = ui.ctx().pixels_per_point().recip();
+        ui.painter().hline(
+            rect.x_range(),
+            rect.top() + px * 0.5,
+            (px, style.hline_color),
+        );
+
+        let padding = ui.spacing().button_padding.x;
+        let item_spacing = ui.spacing().item_spacing.x;
+        let taskbar_ui = &mut ui.new_child(
+            UiBuilder::new()
+                .max_rect(rect.shrink2(vec2(item_spacing, 0.0)))
+                .layout(Layout::left_to_right(Align::Center)),
+        );
+        // Like the tab bars, the taskbar is scrolled with the mouse wheel too.
+        taskbar_ui.style_mut().always_scroll_the_only_direction = true;
+        ScrollArea::horizontal()
+            .id_salt(self.id.with("window_taskbar"))
+            .scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden)
+            .show(taskbar_ui, |ui| {
+                for &surface_index in windows {
+                    let (_, title) = self.window_title(ui, surface_index, tab_viewer);
+                    // Titles longer than the whole taskbar are cut short.
+                    let galley = title.into_galley(
+                        ui,
+                        Some(TextWrapMode::Truncate),
+                        rect.width() - 2.0 * (padding + item_spacing),
+                        TextStyle::Button,
+                    );
+                    let (_, item_rect) =
+                        ui.allocate_space(vec2(galley.size().x + 2.0 * padding, rect.height()));
+                    let response = ui
+                        .interact(
+                            item_rect,
+                            self.id.with((surface_index, "window_taskbar")),
+                            Sense::click(),
+                        )
+                        .on_hover_cursor(CursorIcon::PointingHand);
+                    let color = if response.hovered() || response.has_focus() {
+                        style.label_active_color
+                    } else {
+                        style.label_color
+                    };
+                    ui.painter().galley_with_override_text_color(
+                        pos2(
+                            item_rect.left() + padding,
+                            item_rect.center().y - galley.size().y / 2.0,
+                        ),
+                        galley,
+                        color,
+                    );
+                    if response.clicked() {
+                        if let Some(window_state) =
+                            self.dock_state.get_window_state_mut(surface_index)
+                        {
+                            window_state.set_in_taskbar(false);
+                        }
+                        self.dock_state.raise_window(surface_index);
+                    }
+                }
+            });
+    }
+}
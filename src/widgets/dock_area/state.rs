@@ -1,8 +1,70 @@
-use egui::{Context, Id, Pos2};
+use egui::{ahash::HashMap, layers::ShapeIdx, Context, Id, Pos2, Rect, Vec2};
 
-use crate::{Style, SurfaceIndex};
+use crate::{NodeId, NodeIndex, Style, SurfaceIndex, TabId, TabIndex};
 
-use super::drag_and_drop::{DragData, DragDropState, HoverData};
+use super::{
+    drag_and_drop::{DragData, DragDropState, HoverData},
+    tab_previews::TabPreviews,
+};
+
+/// A tab title which is currently being edited.
+#[derive(Clone, Debug)]
+pub(super) struct TabRename {
+    pub location: (SurfaceIndex, NodeIndex, TabIndex),
+
+    /// Used to detect whether the tab at `location` has changed since the renaming started.
+    pub tab_id: Id,
+    pub text: String,
+}
+
+/// The search of the tab switcher, see [`DockState::open_tab_switcher`](crate::DockState::open_tab_switcher).
+#[derive(Clone, Debug, Default)]
+pub(super) struct TabSwitcherState {
+    pub query: String,
+
+    /// Index of the highlighted tab among the ones matching `query`.
+    pub selected: usize,
+}
+
+/// An auto-hidden leaf slid out of its strip, see [`LeafNode::set_auto_hidden`](crate::LeafNode::set_auto_hidden).
+#[derive(Clone, Copy, Debug)]
+pub(super) struct AutoHideFlyout {
+    pub surface: SurfaceIndex,
+    pub node: NodeId,
+
+    /// Whether it was opened by a click, in which case it stays open until something else is clicked.
+    pub clicked: bool,
+
+    /// `false` while it slides back into its strip.
+    pub open: bool,
+}
+
+/// Leaves of a surface moving to their new places after its layout changed,
+/// see [`Style::animation`](crate::Style::animation).
+#[derive(Clone, Debug, Default)]
+pub(super) struct LayoutTransition {
+    /// The nodes of the surface and whether they were collapsed, the last time it was shown.
+    pub layout: Vec<(NodeIndex, NodeId, bool)>,
+
+    /// The rectangles the leaves were shown in the last time.
+    pub shown: HashMap<NodeId, Rect>,
+
+    /// The rectangles the leaves move from.
+    pub from: HashMap<NodeId, Rect>,
+    pub start_time: f64,
+}
+
+/// A dragged tab sliding back to its place after its drag was cancelled, see
+/// [`KeyBindings::cancel_drag`](crate::KeyBindings::cancel_drag).
+#[derive(Clone, Copy, Debug)]
+pub(super) struct DragRevert {
+    /// The [`Id`] of the tab title.
+    pub id: Id,
+
+    /// How far the tab was from its place when the drag was cancelled.
+    pub offset: Vec2,
+    pub start_time: f64,
+}
 
 #[derive(Clone, Debug, Default)]
 pub(super) struct State {
@@ -10,16 +72,63 @@ pub(super) struct State {
     pub last_hover_pos: Option<Pos2>,
     pub dnd: Option<DragDropState>,
     pub window_fade: Option<(f64, SurfaceIndex)>,
+    pub renaming: Option<TabRename>,
+
+    /// Width of the last dragged tab, used to size the gap showing where it would be inserted.
+    pub dragged_tab_width: f32,
+
+    /// How far the dragged tab is shown from its place.
+    pub dragged_tab_offset: Vec2,
+    pub drag_revert: Option<DragRevert>,
+
+    /// The leaf whose active tab gets the keyboard focus the next frame, after a tab with the focus was closed.
+    pub refocus_leaf: Option<(SurfaceIndex, NodeIndex)>,
+
+    pub previews: TabPreviews,
+    pub tab_switcher: Option<TabSwitcherState>,
+    pub auto_hide_flyout: Option<AutoHideFlyout>,
+    pub layout_transitions: HashMap<SurfaceIndex, LayoutTransition>,
+
+    /// The tabs the lifecycle hooks were last called for, and whether they were the active tab of their leaf.
+    pub tab_lifecycle: HashMap<TabId, bool>,
+
+    /// Buffers kept between frames so that showing the dock doesn't allocate them every time.
+    pub scratch: Scratch,
+}
+
+/// Buffers reused by every frame, whose contents are only meaningful within a frame.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Scratch {
+    /// The tab rects of the previous frame of the leaf whose tab bar is being shown.
+    pub tab_rects: Vec<Rect>,
+
+    /// The surfaces which were valid at the start of the frame.
+    pub surfaces: Vec<SurfaceIndex>,
+
+    /// The shapes the backgrounds of overlapping tabs are painted into, see [`TabShape::Browser`](crate::TabShape).
+    pub background_slots: Vec<ShapeIdx>,
 }
 
 impl State {
     #[inline(always)]
     pub(super) fn load(ctx: &Context, id: Id) -> Self {
-        ctx.data_mut(|d| d.get_temp(id)).unwrap_or(Self {
+        // Taken out of the memory instead of cloned, since it's stored back at the end of the frame.
+        ctx.data_mut(|d| d.remove_temp(id)).unwrap_or(Self {
             drag_start: None,
             last_hover_pos: None,
             dnd: None,
             window_fade: None,
+            renaming: None,
+            dragged_tab_width: 0.0,
+            dragged_tab_offset: Vec2::ZERO,
+            drag_revert: None,
+            refocus_leaf: None,
+            previews: TabPreviews::default(),
+            tab_switcher: None,
+            auto_hide_flyout: None,
+            layout_transitions: HashMap::default(),
+            tab_lifecycle: HashMap::default(),
+            scratch: Scratch::default(),
         })
     }
 
@@ -41,7 +150,8 @@ impl State {
         ctx: &Context,
         style: &Style,
     ) {
-        if !self.is_drag_drop_locked(ctx, style) {
+        // The edges of a surface are inside of its leaves, so they take priority over a leaf which was hovered.
+        if drop.edge.is_some() || !self.is_drag_drop_locked(ctx, style) {
             self.dnd = Some(DragDropState {
                 hover: drop,
                 drag,
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use egui::{
+    ahash::{HashMap, HashSet},
+    load::SizedTexture,
+    vec2, Color32, ColorImage, Context, Event, Id, Pos2, Rect, TextureHandle, TextureOptions,
+    UserData, ViewportCommand,
+};
+
+/// Largest width or height of a preview, in pixels.
+const TAB_PREVIEW_MAX_SIZE: f32 = 256.0;
+
+/// Time in seconds after which the preview of a shown tab is captured again.
+const TAB_PREVIEW_REFRESH_INTERVAL: f64 = 1.0;
+
+/// Downscaled snapshots of tabs, cut out of screenshots of the viewport the [`DockArea`](crate::DockArea) is shown in.
+#[derive(Clone, Default)]
+pub(super) struct TabPreviews {
+    /// Previews by tab ID, along with the time they were captured at.
+    previews: HashMap<Id, (TextureHandle, f64)>,
+
+    /// The time at which the last screenshot was requested, if it hasn't arrived yet.
+    requested_at: Option<f64>,
+}
+
+impl fmt::Debug for TabPreviews {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TabPreviews").finish_non_exhaustive()
+    }
+}
+
+/// Sent along with a screenshot request, so the tabs can be found in the screenshot once it arrives.
+struct PreviewRequest {
+    dock_area: Id,
+    pixels_per_point: f32,
+    tabs: Vec<(Id, Rect)>,
+}
+
+impl TabPreviews {
+    /// The preview of the tab with the given ID, if one was captured.
+    pub(super) fn get(&self, tab_id: Id) -> Option<SizedTexture> {
+        self.previews
+            .get(&tab_id)
+            .map(|(texture, _)| SizedTexture::from_handle(texture))
+    }
+
+    /// Updates previews from screenshots requested by the [`DockArea`](crate::DockArea) with the given `id`
+    /// which arrived this frame.
+    pub(super) fn receive(&mut self, ctx: &Context, dock_area: Id) {
+        let now = ctx.input(|i| i.time);
+        let screenshots = ctx.input(|i| {
+            i.raw
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::Screenshot {
+                        user_data, image, ..
+                    } => Some((user_data.clone(), image.clone())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        for (user_data, image) in screenshots {
+            let Some(request) = user_data
+                .data
+                .as_ref()
+                .and_then(|data| data.downcast_ref::<PreviewRequest>())
+                .filter(|request| request.dock_area == dock_area)
+            else {
+                continue;
+            };
+
+            self.requested_at = None;
+            for &(tab_id, rect) in &request.tabs {
+                let Some(preview) = downscale(&image, rect, request.pixels_per_point) else {
+                    continue;
+                };
+                match self.previews.get_mut(&tab_id) {
+                    Some((texture, captured_at)) => {
+                        texture.set(preview, TextureOptions::LINEAR);
+                        *captured_at = now;
+                    }
+                    None => {
+                        let texture = ctx.load_texture(
+                            format!("egui_dock tab preview {tab_id:?}"),
+                            preview,
+                            TextureOptions::LINEAR,
+                        );
+                        self.previews.insert(tab_id, (texture, now));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Requests a screenshot if any of the `shown` tabs, given by their ID and body rectangle,
+    /// has no recent preview.
+    pub(super) fn request(&mut self, ctx: &Context, dock_area: Id, shown: Vec<(Id, Rect)>) {
+        let now = ctx.input(|i| i.time);
+        if self
+            .requested_at
+            .is_some_and(|requested_at| now - requested_at < TAB_PREVIEW_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        let outdated = shown.iter().any(|(tab_id, _)| {
+            self.previews
+                .get(tab_id)
+                .is_none_or(|(_, captured_at)| now - captured_at >= TAB_PREVIEW_REFRESH_INTERVAL)
+        });
+        if outdated {
+            self.requested_at = Some(now);
+            ctx.send_viewport_cmd(ViewportCommand::Screenshot(UserData::new(PreviewRequest {
+                dock_area,
+                pixels_per_point: ctx.pixels_per_point(),
+                tabs: shown,
+            })));
+        }
+    }
+
+    /// Drops the previews of tabs which no longer exist.
+    pub(super) fn retain(&mut self, tab_ids: &HashSet<Id>) {
+        self.previews.retain(|tab_id, _| tab_ids.contains(tab_id));
+    }
+}
+
+/// Copies `rect` out of `image`, scaled down so that neither of its sides is larger than [`TAB_PREVIEW_MAX_SIZE`].
+///
+/// Returns `None` if `rect` doesn't overlap `image`.
+fn downscale(image: &ColorImage, rect: Rect, pixels_per_point: f32) -> Option<ColorImage> {
+    let image_rect = Rect::from_min_size(
+        Pos2::ZERO,
+        vec2(image.width() as f32, image.height() as f32),
+    );
+    let rect = Rect::from_min_max(
+        (rect.min.to_vec2() * pixels_per_point).to_pos2(),
+        (rect.max.to_vec2() * pixels_per_point).to_pos2(),
+    )
+    .intersect(image_rect);
+    let [min_x, min_y] = [rect.min.x as usize, rect.min.y as usize];
+    let [width, height] = [rect.width() as usize, rect.height() as usize];
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let scale = (TAB_PREVIEW_MAX_SIZE / width.max(height) as f32).min(1.0);
+    let size = [
+        ((width as f32 * scale) as usize).max(1),
+        ((height as f32 * scale) as usize).max(1),
+    ];
+
+    // Each pixel of the preview is the average of the pixels it covers in the screenshot.
+    let source_range = |index: usize, len: usize, scaled_len: usize, offset: usize| {
+        let start = index * len / scaled_len;
+        let end = ((index + 1) * len / scaled_len).max(start + 1);
+        offset + start..offset + end
+    };
+    let mut pixels = Vec::with_capacity(size[0] * size[1]);
+    for y in 0..size[1] {
+        let rows = source_range(y, height, size[1], min_y);
+        for x in 0..size[0] {
+            let columns = source_range(x, width, size[0], min_x);
+            let mut sum = [0_u32; 4];
+            let mut count = 0;
+            for row in rows.clone() {
+                for pixel in &image.pixels[row * image.width() + columns.start..][..columns.len()] {
+                    for (sum, channel) in sum.iter_mut().zip(pixel.to_array()) {
+                        *sum += channel as u32;
+                    }
+                    count += 1;
+                }
+            }
+            let [r, g, b, a] = sum.map(|sum| (sum / count) as u8);
+            pixels.push(Color32::from_rgba_premultiplied(r, g, b, a));
+        }
+    }
+    Some(ColorImage::new(size, pixels))
+}
@@ -0,0 +1,28 @@
+use egui::Modifiers;
+
+/// When a dragged tab may be torn off into a window of its own, see [`DockArea::tear_off`](crate::DockArea::tear_off).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TearOff {
+    /// Whether tabs can be torn off into windows at all. Dropping a tab anywhere else than a node then
+    /// leaves it where it was. By `Default` it's `true`.
+    pub allowed: bool,
+
+    /// How far, in points, the pointer has to be dragged from where the drag started before the tab can be
+    /// torn off. By `Default` it's `0.0`.
+    pub min_distance: f32,
+
+    /// Modifiers which have to be held down for the tab to be torn off, e.g. [`Modifiers::SHIFT`].
+    /// By `Default` it's [`Modifiers::NONE`], so no modifier is needed.
+    pub modifiers: Modifiers,
+}
+
+impl Default for TearOff {
+    fn default() -> Self {
+        Self {
+            allowed: true,
+            min_distance: 0.0,
+            modifiers: Modifiers::NONE,
+        }
+    }
+}
@@ -4,5 +4,8 @@ pub mod dock_area;
 /// Trait for tab-viewing types.
 pub mod tab_viewer;
 
-pub use dock_area::{AllowedSplits, DockArea};
+pub use dock_area::{
+    AllowedSplits, BridgedTab, DockArea, DockEvent, DockResponse, DropButton, DropOverlay,
+    ExternalPayload, KeyBindings, NestedDrags, TabRects, TearOff,
+};
 pub use tab_viewer::TabViewer;
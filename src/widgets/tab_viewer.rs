@@ -1,5 +1,5 @@
-use crate::{NodeIndex, SurfaceIndex, TabStyle};
-use egui::{Id, Ui, WidgetText};
+use crate::{DropOverlay, ExternalPayload, NodeIndex, SurfaceIndex, TabDestination, TabStyle};
+use egui::{Frame, Id, Painter, Ui, WidgetText};
 
 /// Defines how a tab should behave and be rendered inside a [`Tree`](crate::Tree).
 pub trait TabViewer {
@@ -12,6 +12,38 @@ pub trait TabViewer {
     /// Actual tab content.
     fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab);
 
+    /// Content of the body of a [central](crate::LeafNode::set_central) leaf while it has no tabs,
+    /// e.g. a placeholder with shortcuts for opening files.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// the leaf is. By default, nothing is shown.
+    fn empty_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
+    /// Content inside the context menu shown when the body of a [central](crate::LeafNode::set_central) leaf
+    /// without tabs is right-clicked, e.g. buttons opening files into it.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// the leaf is. This requires that
+    /// [`DockArea::empty_leaf_context_menus`](crate::DockArea::empty_leaf_context_menus) is set to `true`.
+    /// Widgets shown by [`TabViewer::empty_ui`] keep their own context menus.
+    fn empty_context_menu(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
+    /// Content shown in place of the main surface while it has no tabs, e.g. a welcome screen.
+    ///
+    /// Returns the tabs to put into the main surface, e.g. when a button of the welcome screen was clicked.
+    /// By default, nothing is shown and no tabs are returned.
+    fn on_empty_main_surface(&mut self, _ui: &mut Ui) -> Vec<Self::Tab> {
+        Vec::new()
+    }
+
+    /// Content of the preview following the pointer while `_tab` is dragged, e.g. a thumbnail of the tab.
+    ///
+    /// Returns `false` to show the title of the tab instead, which is done by default. The preview is
+    /// placed as specified by [`DragPreviewStyle`](crate::DragPreviewStyle).
+    fn drag_preview_ui(&mut self, _ui: &mut Ui, _tab: &mut Self::Tab) -> bool {
+        false
+    }
+
     /// Content inside the context menu shown when the tab is right-clicked.
     ///
     /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
@@ -25,6 +57,14 @@ pub trait TabViewer {
     ) {
     }
 
+    /// Content inside the context menu shown when the empty space of a tab bar is right-clicked, e.g. buttons
+    /// adding a tab to the leaf or closing all of its tabs.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// the tab bar belongs to. This requires that
+    /// [`DockArea::tab_bar_context_menus`](crate::DockArea::tab_bar_context_menus) is set to `true`.
+    fn tab_bar_context_menu(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
     /// Unique ID for this tab.
     ///
     /// If not implemented, uses tab title text as an ID source.
@@ -32,6 +72,25 @@ pub trait TabViewer {
         Id::new(self.title(tab).text())
     }
 
+    /// The name screen readers announce the tab's button with, e.g. "Console" in "Tab: Console, 2 of 5",
+    /// see [`AccessibilityTranslations`](crate::AccessibilityTranslations).
+    ///
+    /// Only called if the `accesskit` feature is enabled. By default, the text of the tab's
+    /// [`title`](Self::title) is used.
+    fn accessible_name(&mut self, tab: &mut Self::Tab) -> String {
+        self.title(tab).text().to_owned()
+    }
+
+    /// Content of the tooltip shown when hovering over the tab's button.
+    ///
+    /// This requires that [`DockArea::show_tab_name_on_hover`](crate::DockArea::show_tab_name_on_hover)
+    /// is set to `true`. The delay before it's shown is set by [`TabStyle::tooltip_delay`].
+    ///
+    /// By default, the [`title`](Self::title) of the tab is shown.
+    fn tab_tooltip(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(self.title(tab));
+    }
+
     /// Called after each tab button is shown, so you can add a tooltip, check for clicks, etc.
     fn on_tab_button(&mut self, _tab: &mut Self::Tab, _response: &egui::Response) {}
 
@@ -42,6 +101,35 @@ pub trait TabViewer {
         OnCloseResponse::Close
     }
 
+    /// This is called when the user closes the window showing `_surface`, with the close button in its title bar,
+    /// its close all tabs button, or the close button of its native window.
+    ///
+    /// Returns `false` to keep the window open. Otherwise, each of its tabs is closed with
+    /// [`on_close`](Self::on_close), and the window is closed if all of them are. By default, `true` is returned.
+    fn on_window_close(&mut self, _surface: SurfaceIndex) -> bool {
+        true
+    }
+
+    /// This is called when the user splits the leaf containing `_tab` using its split buttons,
+    /// where `_tab` is the active tab of the leaf.
+    ///
+    /// Returns a [`SplitBehavior`] which determines what is put into the new leaf.
+    ///
+    /// This requires that [`DockArea::show_leaf_split_buttons`](crate::DockArea::show_leaf_split_buttons)
+    /// is set to `true`. By default, the tab is moved into the new leaf.
+    fn on_split(&mut self, _tab: &mut Self::Tab) -> SplitBehavior<Self::Tab> {
+        SplitBehavior::Move
+    }
+
+    /// This is called when the user finishes renaming the `_tab` in place.
+    ///
+    /// Returns `true` if the tab was renamed to `_new_name`, `false` if the new name was rejected.
+    ///
+    /// This requires that [`DockArea::tab_renaming`](crate::DockArea::tab_renaming) is set to `true`.
+    fn on_rename(&mut self, _tab: &mut Self::Tab, _new_name: String) -> bool {
+        false
+    }
+
     /// Returns `true` if the user of your app should be able to close a given `_tab`.
     ///
     /// By default, `true` is always returned.
@@ -49,6 +137,16 @@ pub trait TabViewer {
         true
     }
 
+    /// Returns `true` if the user of your app should be able to drag a given `_tab`, e.g. to reorder it or
+    /// move it to another node. Tabs which can't be dragged stay behind when the other selected tabs of their
+    /// leaf are dragged.
+    ///
+    /// This is only called if [`DockArea::draggable_tabs`](crate::DockArea::draggable_tabs) is `true`.
+    /// By default, `true` is always returned.
+    fn is_draggable(&self, _tab: &Self::Tab) -> bool {
+        true
+    }
+
     /// Returns `true` if the user of your app should be able to close a given `_tab`.
     ///
     /// By default, `true` is always returned.
@@ -66,12 +164,73 @@ pub trait TabViewer {
         false
     }
 
+    /// This is called every frame for each tab whose [`ui`](Self::ui) isn't called, because it's not the active
+    /// tab of its leaf, or because its leaf or window is collapsed.
+    ///
+    /// Useful for keeping tabs alive while throttling expensive work they do when they're not visible.
+    /// See also [`DockResponse::visible_tabs`](crate::DockResponse::visible_tabs).
+    fn on_hidden_update(&mut self, _tab: &mut Self::Tab) {}
+
+    /// Returns `true` if the content of a tab is still loading, e.g. in the background.
+    ///
+    /// A spinner is shown in the body of loading tabs instead of calling [`ui`](Self::ui), as well as before their
+    /// title, see [`LoadingStyle`](crate::LoadingStyle). The [`DockArea`](crate::DockArea) keeps repainting while a
+    /// loading tab is shown, so this is checked again every frame.
+    fn is_loading(&self, _tab: &Self::Tab) -> bool {
+        false
+    }
+
+    /// This is called once for each tab the [`DockArea`](crate::DockArea) finds in the
+    /// [`DockState`](crate::DockState) which it didn't have the previous time it was shown, e.g. because it was just
+    /// inserted. All tabs are new the first time the [`DockArea`](crate::DockArea) is shown.
+    ///
+    /// Tabs keep their identity when they're moved around, so this isn't called again after they're dragged into
+    /// another leaf or window. Like [`on_activate`](Self::on_activate), it's called before the tab is first shown.
+    fn on_open(&mut self, _tab: &mut Self::Tab) {}
+
+    /// This is called when a tab becomes the active tab of its leaf, right after [`on_open`](Self::on_open) for new
+    /// tabs which are active.
+    ///
+    /// Useful for lazily loading resources which are only needed while the tab can be shown.
+    fn on_activate(&mut self, _tab: &mut Self::Tab) {}
+
+    /// This is called when a tab stops being the active tab of its leaf, but not when it's closed,
+    /// see [`on_close`](Self::on_close).
+    ///
+    /// Useful for unloading what was loaded in [`on_activate`](Self::on_activate).
+    fn on_deactivate(&mut self, _tab: &mut Self::Tab) {}
+
+    /// Returns `true` if the add button should be shown in the tab bar of a leaf.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// the tab bar belongs to. This requires that [`DockArea::show_add_buttons`](crate::DockArea::show_add_buttons)
+    /// is set to `true`.
+    ///
+    /// By default, `true` is always returned.
+    fn show_add_button(&mut self, _surface: SurfaceIndex, _node: NodeIndex) -> bool {
+        true
+    }
+
     /// This is called when the add button is pressed.
     ///
     /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and on which
     /// [`Node`](crate::Node) this particular add button was pressed.
     fn on_add(&mut self, _surface: SurfaceIndex, _node: NodeIndex) {}
 
+    /// This is called when a `_payload` dragged in from outside of the dock is dropped at `_destination`.
+    ///
+    /// Returns the tab to put at `_destination`, or `None` if the payload should be ignored.
+    /// See [`ExternalPayload`] for what can be dragged into the dock.
+    ///
+    /// By default, `None` is always returned.
+    fn on_external_drop(
+        &mut self,
+        _payload: &ExternalPayload,
+        _destination: TabDestination,
+    ) -> Option<Self::Tab> {
+        None
+    }
+
     /// Called when the rectangle of the tab content changes.
     ///
     /// This can happen when the window is resized, panels are docked or undocked,
@@ -82,17 +241,112 @@ pub trait TabViewer {
     /// available space.
     fn on_rect_changed(&mut self, _tab: &mut Self::Tab) {}
 
+    /// Custom widgets shown at the start of a tab bar, before its tabs.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// the tab bar belongs to. The space taken up by the widgets is reserved, and the tabs scroll if
+    /// they no longer fit.
+    fn tab_bar_leading_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
+    /// Custom widgets shown at the end of a tab bar, after its tabs and before its buttons.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// the tab bar belongs to. Widgets are laid out from right to left. The space taken up by the
+    /// widgets is reserved, and the tabs scroll if they no longer fit.
+    fn tab_bar_trailing_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
+
     /// Content of the popup under the add button. Useful for selecting what type of tab to add.
     ///
     /// This requires that [`DockArea::show_add_buttons`](crate::DockArea::show_add_buttons) and
     /// [`DockArea::show_add_popup`](crate::DockArea::show_add_popup) are set to `true`.
     fn add_popup(&mut self, _ui: &mut Ui, _surface: SurfaceIndex, _node: NodeIndex) {}
 
+    /// Draws the indicators showing where a dragged tab would be dropped, in place of the ones specified by
+    /// [`Style::overlay`](crate::Style::overlay).
+    ///
+    /// `_painter` paints above everything else, and `_overlay` describes what's hovered and where the tab would go.
+    /// Animated indicators can use the [`egui::Context`] of the painter to animate values and request repaints.
+    /// The buttons of [`OverlayType::Widgets`](crate::OverlayType::Widgets) react to the pointer whether they're
+    /// drawn or not.
+    ///
+    /// Returns `false` to draw the default indicators, which is done by default.
+    fn drop_overlay_ui(&mut self, _painter: &Painter, _overlay: &DropOverlay) -> bool {
+        false
+    }
+
     /// Sets custom style for given tab.
+    ///
+    /// This is consulted whenever the tab's title or body is drawn, e.g. to color tabs by the type of their file or
+    /// to highlight the ones with errors. Besides colors, the [`TabStyle`] sets the text style and italics of titles
+    /// which don't set their own, see [`TabStyle::title_text_style`] and [`TabStyle::title_italics`].
+    ///
+    /// ```rust
+    /// # use egui_dock::{TabStyle, TabViewer};
+    /// # use egui::{Color32, Ui, WidgetText};
+    /// struct Document {
+    ///     has_errors: bool,
+    /// }
+    ///
+    /// struct MyTabViewer;
+    ///
+    /// impl TabViewer for MyTabViewer {
+    ///     type Tab = Document;
+    ///
+    ///     fn title(&mut self, _tab: &mut Self::Tab) -> WidgetText {
+    ///         "Document".into()
+    ///     }
+    ///
+    ///     fn ui(&mut self, _ui: &mut Ui, _tab: &mut Self::Tab) {}
+    ///
+    ///     fn tab_style_override(&self, tab: &Self::Tab, global_style: &TabStyle) -> Option<TabStyle> {
+    ///         tab.has_errors.then(|| {
+    ///             let mut style = global_style.clone();
+    ///             style.active.text_color = Color32::RED;
+    ///             style.inactive.text_color = Color32::RED;
+    ///             style.title_italics = true;
+    ///             style
+    ///         })
+    ///     }
+    /// }
+    /// ```
     fn tab_style_override(&self, _tab: &Self::Tab, _global_style: &TabStyle) -> Option<TabStyle> {
         None
     }
 
+    /// Frame around the tab body of `_node` in `_surface`, used instead of the
+    /// [`TabBodyStyle`](crate::TabBodyStyle) of its tabs.
+    ///
+    /// The inner margin, fill, stroke and corner radius of the frame are used, the fill only if
+    /// [`TabViewer::clear_background`] returns `true` for the active tab. By default, `None` is returned.
+    fn tab_body_frame(&mut self, _surface: SurfaceIndex, _node: NodeIndex) -> Option<Frame> {
+        None
+    }
+
+    /// Frame of the window showing `_surface`, used instead of [`Frame::window`].
+    ///
+    /// The frame is faded like the rest of the window while a tab is dragged over another surface. Native windows
+    /// don't use it. By default, `None` is returned.
+    fn window_frame(&mut self, _surface: SurfaceIndex) -> Option<Frame> {
+        None
+    }
+
+    /// Title of the window showing `_surface`, used instead of the title of the active tab of its focused leaf.
+    ///
+    /// The title is shown in the title bar of the window, see
+    /// [`DockArea::show_window_title_bars`](crate::DockArea::show_window_title_bars), in its strip when it's
+    /// minimized, and by the system for native windows. By default, `None` is returned.
+    fn window_title(&mut self, _surface: SurfaceIndex) -> Option<WidgetText> {
+        None
+    }
+
+    /// Custom widgets shown at the end of the title bar of the window showing `_surface`, like buttons to dock it
+    /// back or pin it in place.
+    ///
+    /// Widgets are laid out from right to left. They're shown in the title bar of the window, see
+    /// [`DockArea::show_window_title_bars`](crate::DockArea::show_window_title_bars), and in its strip when it's
+    /// minimized.
+    fn window_title_bar_ui(&mut self, _ui: &mut Ui, _surface: SurfaceIndex) {}
+
     /// Specifies a tab's ability to be shown in a window.
     ///
     /// Returns `false` if this tab should never be turned into a window.
@@ -100,13 +354,43 @@ pub trait TabViewer {
         true
     }
 
+    /// Specifies a tab's ability to be dropped into, or split off next to, a given node.
+    ///
+    /// Returns `false` if this tab should never be moved into `_node` of `_surface`. No drop hints are shown
+    /// over such nodes while the tab is being dragged. The node the tab is dragged from is always allowed.
+    fn allowed_in_node(
+        &self,
+        _tab: &mut Self::Tab,
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+    ) -> bool {
+        true
+    }
+
     /// Whether the tab body will be cleared with the color specified in
     /// [`TabBarStyle::bg_fill`](crate::TabBarStyle::bg_fill).
     fn clear_background(&self, _tab: &Self::Tab) -> bool {
         true
     }
 
+    /// Returns `false` if the tab bar of the leaf containing `_tabs` should be hidden.
+    ///
+    /// `_surface` and `_node` specify which [`Surface`](crate::Surface) and [`Node`](crate::Node)
+    /// the tab bar belongs to. Tabs can still be dropped onto a leaf with a hidden tab bar.
+    ///
+    /// The tab bar is also hidden when [`LeafNode::set_tab_bar_hidden`](crate::LeafNode::set_tab_bar_hidden)
+    /// was used on the leaf. By default, `true` is always returned.
+    fn tab_bar_visibility(
+        &mut self,
+        _tabs: &[Self::Tab],
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+    ) -> bool {
+        true
+    }
+
     /// Returns `true` if the horizontal and vertical scroll bars will be shown for `tab`.
+    /// This requires that [`DockArea::tab_scroll_areas`](crate::DockArea::tab_scroll_areas) is set to `true`.
     ///
     /// By default, both scroll bars are shown.
     fn scroll_bars(&self, _tab: &Self::Tab) -> [bool; 2] {
@@ -123,4 +407,23 @@ pub enum OnCloseResponse {
     Focus,
     /// Ignores the close request.
     Ignore,
+    /// Keeps the tab open until the close request is resolved with [`DockState::resolve_close`],
+    /// e.g. once the user answered a "Save changes?" dialog shown over multiple frames.
+    ///
+    /// The tab is added to [`DockState::pending_closes`] until then.
+    ///
+    /// [`DockState::resolve_close`]: crate::DockState::resolve_close
+    /// [`DockState::pending_closes`]: crate::DockState::pending_closes
+    Defer,
+}
+
+/// Determines what happens to a tab when a user splits the leaf containing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SplitBehavior<Tab> {
+    /// Moves the tab into the new leaf. Leaves containing a single tab aren't split.
+    Move,
+    /// Puts the given tab into the new leaf, leaving the original tab where it is.
+    Clone(Tab),
+    /// Ignores the split request.
+    Veto,
 }
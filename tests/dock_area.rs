@@ -0,0 +1,520 @@
+//! Tests of how a [`DockArea`](egui_dock::DockArea) reacts to the user, driven by the headless
+//! [`DockHarness`].
+#![cfg(feature = "testing")]
+
+use egui::{pos2, vec2, Key, Modifiers, Ui, WidgetText};
+use egui_dock::{
+    testing::DockHarness, AllowedSplits, DockState, NodeIndex, Style, SurfaceIndex, TabBarPosition,
+    TabIndex, TabShape, TabViewer,
+};
+
+struct Viewer;
+
+impl TabViewer for Viewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        tab.as_str().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(tab.as_str());
+    }
+}
+
+fn tabs(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn fraction(dock_state: &DockState<String>, node_index: NodeIndex) -> f32 {
+    match &dock_state.main_surface()[node_index] {
+        egui_dock::Node::Horizontal(split) | egui_dock::Node::Vertical(split) => split.fraction,
+        _ => panic!("not a split"),
+    }
+}
+
+#[test]
+fn separators_are_dragged_where_min_sizes_put_them() {
+    let mut dock_state = DockState::new(tabs(&["Left"]));
+    let [left, _] =
+        dock_state
+            .main_surface_mut()
+            .split_right(NodeIndex::root(), 0.5, tabs(&["Right"]));
+    dock_state.main_surface_mut()[left]
+        .get_leaf_mut()
+        .unwrap()
+        .set_min_size(vec2(500.0, 0.0));
+    let mut harness = DockHarness::new(dock_state, Viewer);
+
+    // The left leaf pushes the separator past the middle of the split, without changing its fraction.
+    let separator = harness
+        .separator_pos(SurfaceIndex::main(), NodeIndex::root())
+        .unwrap();
+    assert!((separator.x - 500.0).abs() < 1.0);
+    assert_eq!(fraction(&harness.dock_state, NodeIndex::root()), 0.5);
+
+    harness.drag_separator(SurfaceIndex::main(), NodeIndex::root(), vec2(50.0, 0.0));
+    let fraction = fraction(&harness.dock_state, NodeIndex::root());
+    assert!((fraction - 0.6875).abs() < 0.01, "{fraction}");
+}
+
+fn side_tab_harness(names: &[&str]) -> DockHarness<String, Viewer> {
+    let mut dock_state = DockState::new(tabs(names));
+    dock_state.main_surface_mut()[NodeIndex::root()]
+        .get_leaf_mut()
+        .unwrap()
+        .set_tab_bar_position(Some(TabBarPosition::Left));
+    DockHarness::new(dock_state, Viewer).with_size(vec2(400.0, 300.0))
+}
+
+#[test]
+fn side_tabs_which_overflow_are_clipped_and_scrolled_to() {
+    let names: Vec<String> = (0..20).map(|i| format!("Tab {i}")).collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    let mut harness = side_tab_harness(&names);
+    let leaf_rect = harness
+        .leaf_rect(SurfaceIndex::main(), NodeIndex::root())
+        .unwrap();
+    let last = (SurfaceIndex::main(), NodeIndex::root(), TabIndex(19));
+
+    // Tabs below the leaf aren't shown, and the shown ones stay inside of it.
+    assert_eq!(harness.tab_rect(last), None);
+    let first = harness
+        .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)))
+        .unwrap();
+    assert!(leaf_rect.contains_rect(first));
+
+    harness.scroll(first.center(), vec2(0.0, -10_000.0));
+    let rect = harness.tab_rect(last).unwrap();
+    assert!(leaf_rect.contains_rect(rect), "{rect:?}");
+    harness.click(rect.center_top() + vec2(0.0, 10.0));
+    assert_eq!(
+        harness.dock_state.find_active_focused().unwrap().1,
+        &mut "Tab 19".to_owned()
+    );
+}
+
+#[test]
+fn side_tabs_are_selected_with_modifiers_and_closed_with_their_buttons() {
+    let mut harness = side_tab_harness(&["A", "B", "C", "D"]);
+    let leaf = |harness: &DockHarness<String, Viewer>| {
+        harness.dock_state.main_surface()[NodeIndex::root()]
+            .get_leaf()
+            .unwrap()
+            .selected_tabs()
+    };
+    // Clicks the title of a tab, which comes before its close button.
+    let click_title = |harness: &mut DockHarness<String, Viewer>, tab_index: usize| {
+        let rect = harness
+            .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(tab_index)))
+            .unwrap();
+        harness.click(rect.center_top() + vec2(0.0, 10.0));
+    };
+
+    harness.set_modifiers(Modifiers::SHIFT);
+    click_title(&mut harness, 2);
+    assert_eq!(leaf(&harness), [TabIndex(0), TabIndex(1), TabIndex(2)]);
+
+    harness.set_modifiers(Modifiers::COMMAND);
+    click_title(&mut harness, 1);
+    assert_eq!(leaf(&harness), [TabIndex(0), TabIndex(2)]);
+
+    // The close button is at the end of the tab, and closes it along with the other selected tabs.
+    harness.set_modifiers(Modifiers::NONE);
+    let rect = harness
+        .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(2)))
+        .unwrap();
+    harness.click(pos2(rect.center().x, rect.bottom() - 6.0));
+    let tabs: Vec<_> = harness
+        .dock_state
+        .iter_all_tabs()
+        .map(|(_, tab)| tab.as_str())
+        .collect();
+    assert_eq!(tabs, ["B", "D"]);
+}
+
+#[test]
+fn taskbar_titles_which_dont_fit_are_scrolled_to() {
+    let mut dock_state = DockState::new(tabs(&["Main"]));
+    let windows: Vec<SurfaceIndex> = (0..20)
+        .map(|i| {
+            let surface_index = dock_state.add_window(vec![format!("Window {i}")]);
+            dock_state
+                .get_window_state_mut(surface_index)
+                .unwrap()
+                .set_in_taskbar(true);
+            surface_index
+        })
+        .collect();
+    let mut harness = DockHarness::new(dock_state, Viewer)
+        .with_size(vec2(400.0, 300.0))
+        .with_dock_area(|dock_area| dock_area.window_taskbar(true));
+    let in_taskbar = |harness: &mut DockHarness<String, Viewer>, surface_index| {
+        harness
+            .dock_state
+            .get_window_state(surface_index)
+            .unwrap()
+            .is_in_taskbar()
+    };
+
+    // The titles at the end are reached by scrolling the taskbar to its end.
+    let taskbar_end = pos2(390.0, 290.0);
+    harness.scroll(taskbar_end, vec2(0.0, -10_000.0));
+    harness.click(taskbar_end);
+    assert!(!in_taskbar(&mut harness, windows[19]));
+    assert!(windows[..19]
+        .iter()
+        .all(|&surface_index| in_taskbar(&mut harness, surface_index)));
+}
+
+#[test]
+fn windows_in_the_taskbar_are_found_at_their_position_without_a_taskbar() {
+    let mut dock_state = DockState::new(tabs(&["Main"]));
+    let surface_index = dock_state.add_window(tabs(&["Window"]));
+    dock_state
+        .get_window_state_mut(surface_index)
+        .unwrap()
+        .set_position(pos2(100.0, 100.0))
+        .set_size(vec2(200.0, 200.0))
+        .set_in_taskbar(true);
+    let harness = DockHarness::new(dock_state, Viewer);
+    let pos = pos2(200.0, 200.0);
+    assert_eq!(
+        harness
+            .dock_state
+            .node_at_pos(pos)
+            .map(|(surface, _)| surface),
+        Some(surface_index)
+    );
+
+    let harness = harness.with_dock_area(|dock_area| dock_area.window_taskbar(true));
+    assert_eq!(
+        harness
+            .dock_state
+            .node_at_pos(pos)
+            .map(|(surface, _)| surface),
+        Some(SurfaceIndex::main())
+    );
+}
+
+/// Records the lifecycle hooks called for the tabs, and the tabs shown.
+#[derive(Default)]
+struct LifecycleViewer {
+    log: Vec<String>,
+}
+
+impl TabViewer for LifecycleViewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        tab.as_str().into()
+    }
+
+    fn ui(&mut self, _ui: &mut Ui, tab: &mut Self::Tab) {
+        self.log.push(format!("ui {tab}"));
+    }
+
+    fn on_open(&mut self, tab: &mut Self::Tab) {
+        self.log.push(format!("open {tab}"));
+    }
+
+    fn on_activate(&mut self, tab: &mut Self::Tab) {
+        self.log.push(format!("activate {tab}"));
+    }
+
+    fn on_deactivate(&mut self, tab: &mut Self::Tab) {
+        self.log.push(format!("deactivate {tab}"));
+    }
+
+    fn on_hidden_update(&mut self, tab: &mut Self::Tab) {
+        self.log.push(format!("hidden {tab}"));
+    }
+}
+
+#[test]
+fn only_hidden_tabs_are_updated_as_hidden() {
+    let mut dock_state = DockState::new(tabs(&["A", "B"]));
+    dock_state
+        .main_surface_mut()
+        .split_right(NodeIndex::root(), 0.5, tabs(&["C"]));
+    let mut harness = DockHarness::new(dock_state, LifecycleViewer::default());
+    harness.tab_viewer.log.clear();
+    harness.run();
+
+    let hidden: Vec<_> = harness
+        .tab_viewer
+        .log
+        .iter()
+        .filter(|entry| entry.starts_with("hidden"))
+        .collect();
+    assert_eq!(hidden, ["hidden B"]);
+}
+
+#[test]
+fn tabs_are_activated_before_they_are_shown() {
+    let mut harness = DockHarness::new(
+        DockState::new(tabs(&["A", "B"])),
+        LifecycleViewer::default(),
+    );
+    let position = |harness: &DockHarness<String, LifecycleViewer>, entry: &str| {
+        harness
+            .tab_viewer
+            .log
+            .iter()
+            .position(|logged| logged == entry)
+    };
+    assert_eq!(
+        harness.tab_viewer.log[..4],
+        ["open A", "activate A", "open B", "ui A"]
+    );
+
+    harness.tab_viewer.log.clear();
+    let rect = harness
+        .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)))
+        .unwrap();
+    harness.click(rect.left_center() + vec2(10.0, 0.0));
+    let activated = position(&harness, "activate B").unwrap();
+    assert!(position(&harness, "deactivate A").is_some());
+    assert!(activated < position(&harness, "ui B").unwrap());
+}
+
+/// Renames tabs to any name which isn't empty.
+struct RenameViewer;
+
+impl TabViewer for RenameViewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        tab.as_str().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(tab.as_str());
+    }
+
+    fn on_rename(&mut self, tab: &mut Self::Tab, new_name: String) -> bool {
+        if new_name.trim().is_empty() {
+            return false;
+        }
+        *tab = new_name;
+        true
+    }
+}
+
+#[test]
+fn rejected_tab_names_keep_the_tab_renamed() {
+    let mut harness = DockHarness::new(DockState::new(tabs(&["Notes"])), RenameViewer)
+        .with_dock_area(|dock_area| dock_area.tab_renaming(true));
+    let location = (SurfaceIndex::main(), NodeIndex::root(), TabIndex(0));
+    let rect = harness.tab_rect(location).unwrap();
+    harness.double_click(rect.left_center() + vec2(10.0, 0.0));
+
+    // The whole title is selected, so typing spaces replaces it with a name which is rejected.
+    harness.type_text("  ");
+    harness.press_key(Key::Enter);
+    assert_eq!(
+        harness.dock_state.main_surface()[NodeIndex::root()]
+            .tabs()
+            .unwrap()[0],
+        "Notes"
+    );
+
+    // The text field is still focused, so the name can be corrected.
+    harness.type_text("Todo");
+    harness.press_key(Key::Enter);
+    assert_eq!(
+        harness.dock_state.main_surface()[NodeIndex::root()]
+            .tabs()
+            .unwrap()[0],
+        "  Todo"
+    );
+}
+
+#[test]
+fn split_buttons_only_split_in_allowed_directions() {
+    let mut harness =
+        DockHarness::new(DockState::new(tabs(&["A", "B"])), Viewer).with_dock_area(|dock_area| {
+            dock_area
+                .show_leaf_split_buttons(true)
+                .show_leaf_close_all_buttons(false)
+                .allowed_splits(AllowedSplits::TopBottomOnly)
+        });
+    let rect = harness
+        .leaf_rect(SurfaceIndex::main(), NodeIndex::root())
+        .unwrap();
+    let tab = harness
+        .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)))
+        .unwrap();
+
+    // Only the button splitting the leaf below is shown, at the right end of the tab bar.
+    harness.click(pos2(rect.right() - 36.0, tab.center().y));
+    assert_eq!(harness.dock_state.main_surface().len(), 1);
+
+    harness.click(pos2(rect.right() - 12.0, tab.center().y));
+    assert!(harness.dock_state.main_surface()[NodeIndex::root()].is_vertical());
+}
+
+/// Closes the tab "A" in the frame in which another tab is double-clicked.
+#[derive(Default)]
+struct CloseOnDoubleClickViewer {
+    double_clicked: bool,
+}
+
+impl TabViewer for CloseOnDoubleClickViewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        tab.as_str().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(tab.as_str());
+    }
+
+    fn on_tab_button(&mut self, _tab: &mut Self::Tab, response: &egui::Response) {
+        self.double_clicked |= response.double_clicked();
+    }
+
+    fn force_close(&mut self, tab: &mut Self::Tab) -> bool {
+        self.double_clicked && tab == "A"
+    }
+}
+
+#[test]
+fn tabs_double_clicked_while_others_close_are_floated() {
+    let mut dock_state = DockState::new(tabs(&["B", "C"]));
+    dock_state
+        .main_surface_mut()
+        .split_right(NodeIndex::root(), 0.5, tabs(&["A"]));
+    let mut harness = DockHarness::new(dock_state, CloseOnDoubleClickViewer::default())
+        .with_dock_area(|dock_area| dock_area.double_click_to_float(true));
+    let rect = harness
+        .tab_rect((SurfaceIndex::main(), NodeIndex(1), TabIndex(1)))
+        .unwrap();
+    harness.double_click(rect.left_center() + vec2(10.0, 0.0));
+
+    // Closing "A" removes its leaf, which moves the leaf of "C" to the root.
+    assert_eq!(harness.dock_state.find_tab(&"A".to_owned()), None);
+    let (surface, _, _) = harness.dock_state.find_tab(&"C".to_owned()).unwrap();
+    assert_ne!(surface, SurfaceIndex::main());
+    assert!(harness.dock_state.find_tab(&"B".to_owned()).is_some());
+}
+
+#[test]
+fn browser_tabs_are_hit_where_they_are_painted() {
+    let mut dock_state = DockState::new(tabs(&["First", "Second"]));
+    dock_state.set_active_tab((SurfaceIndex::main(), NodeIndex::root(), TabIndex(1)));
+    let mut harness = DockHarness::new(dock_state, Viewer).with_dock_area(|dock_area| {
+        let mut style = Style::default();
+        style.tab.shape = TabShape::Browser { slant: 12.0 };
+        dock_area.style(style)
+    });
+    let first = harness
+        .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)))
+        .unwrap();
+    let active = |harness: &DockHarness<String, Viewer>| {
+        harness.dock_state.main_surface()[NodeIndex::root()]
+            .get_leaf()
+            .unwrap()
+            .active
+    };
+
+    // The active tab is painted over the bottom of the slanted side of the first one.
+    harness.click(first.right_bottom() - vec2(2.0, 3.0));
+    assert_eq!(active(&harness), TabIndex(1));
+
+    // Between the tops of the slanted sides, neither tab is hit.
+    harness.click(first.right_top() + vec2(0.0, 1.0));
+    assert_eq!(active(&harness), TabIndex(1));
+
+    harness.click(first.left_center() + vec2(20.0, 0.0));
+    assert_eq!(active(&harness), TabIndex(0));
+}
+
+/// Counts the frames in which the tooltip of a tab was shown.
+#[derive(Default)]
+struct TooltipViewer {
+    tooltips: usize,
+}
+
+impl TabViewer for TooltipViewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        tab.as_str().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        ui.label(tab.as_str());
+    }
+
+    fn tab_tooltip(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        self.tooltips += 1;
+        ui.label(tab.as_str());
+    }
+}
+
+#[test]
+fn tab_tooltips_wait_for_their_own_delay() {
+    let dock_state = DockState::new(tabs(&["First", "Second"]));
+    let mut harness =
+        DockHarness::new(dock_state, TooltipViewer::default()).with_dock_area(|dock_area| {
+            let mut style = Style::default();
+            style.tab.tooltip_delay = Some(0.5);
+            dock_area.style(style).show_tab_name_on_hover(true)
+        });
+    harness
+        .ctx
+        .style_mut(|style| style.interaction.tooltip_delay = 0.0);
+    let rect = harness
+        .tab_rect((SurfaceIndex::main(), NodeIndex::root(), TabIndex(0)))
+        .unwrap();
+    harness.hover(rect.left_center() + vec2(10.0, 0.0));
+    harness.run_frames(10);
+    assert_eq!(harness.tab_viewer.tooltips, 0);
+
+    harness.run_frames(30);
+    assert!(harness.tab_viewer.tooltips > 0);
+    assert_eq!(harness.ctx.style().interaction.tooltip_delay, 0.0);
+}
+
+/// Hides all tab bars, and remembers where the body of the last shown tab starts.
+#[derive(Default)]
+struct HiddenTabBarViewer {
+    body_top: Option<f32>,
+}
+
+impl TabViewer for HiddenTabBarViewer {
+    type Tab = String;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        tab.as_str().into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab) {
+        self.body_top = Some(ui.max_rect().top());
+        ui.label(tab.as_str());
+    }
+
+    fn tab_bar_visibility(
+        &mut self,
+        _tabs: &[Self::Tab],
+        _surface: SurfaceIndex,
+        _node: NodeIndex,
+    ) -> bool {
+        false
+    }
+}
+
+#[test]
+fn tab_bars_hidden_by_the_viewer_get_no_single_tab_handle() {
+    let body_top = |hide_single_tab_bars: bool| {
+        let harness = DockHarness::new(
+            DockState::new(tabs(&["Only"])),
+            HiddenTabBarViewer::default(),
+        )
+        .with_dock_area(move |dock_area| dock_area.hide_single_tab_bars(hide_single_tab_bars));
+        harness.tab_viewer.body_top.unwrap()
+    };
+    assert_eq!(body_top(true), body_top(false));
+}